@@ -15,8 +15,8 @@ pub mod stats;
 pub mod tcp_type;
 pub mod udp_mux;
 pub mod udp_network;
-pub mod url;
 pub mod use_candidate;
 pub mod util;
 
 pub use error::Error;
+pub use stun::url;
@@ -0,0 +1,222 @@
+use bytes::Bytes;
+
+use super::*;
+
+#[test]
+fn test_application_defined_unmarshal() {
+    let tests = vec![
+        (
+            "valid",
+            Bytes::from_static(&[
+                0x85, 0xcc, 0x00, 0x03, // v=2, p=0, subtype=5, APP, len=3
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                b'a', b'b', b'c', b'd', // name=abcd
+                0x01, 0x02, 0x03, 0x04, // data
+            ]),
+            ApplicationDefined {
+                sub_type: 5,
+                ssrc: 0x902f9e2e,
+                name: *b"abcd",
+                data: Bytes::from_static(&[0x01, 0x02, 0x03, 0x04]),
+            },
+            None,
+        ),
+        (
+            "no data",
+            Bytes::from_static(&[
+                0x80, 0xcc, 0x00, 0x02, // v=2, p=0, subtype=0, APP, len=2
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                b'a', b'b', b'c', b'd', // name=abcd
+            ]),
+            ApplicationDefined {
+                sub_type: 0,
+                ssrc: 0x902f9e2e,
+                name: *b"abcd",
+                data: Bytes::new(),
+            },
+            None,
+        ),
+        (
+            "padded",
+            Bytes::from_static(&[
+                0xa1, 0xcc, 0x00, 0x04, // v=2, p=1, subtype=1, APP, len=4
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                b'a', b'b', b'c', b'd', // name=abcd
+                0x01, 0x02, 0x03, 0x04, // data
+                0x00, 0x00, 0x00, 0x04, // padding
+            ]),
+            ApplicationDefined {
+                sub_type: 1,
+                ssrc: 0x902f9e2e,
+                name: *b"abcd",
+                data: Bytes::from_static(&[0x01, 0x02, 0x03, 0x04]),
+            },
+            None,
+        ),
+        (
+            "wrong padding",
+            Bytes::from_static(&[
+                0xa1, 0xcc, 0x00, 0x03, // v=2, p=1, subtype=1, APP, len=3
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                b'a', b'b', b'c', b'd', // name=abcd
+                0x00, 0x00, 0x00, 0x00, // padding
+            ]),
+            ApplicationDefined::default(),
+            Some(Error::WrongPadding),
+        ),
+        (
+            "short packet",
+            Bytes::from_static(&[
+                0x80, 0xcc, 0x00, 0x01, // v=2, p=0, subtype=0, APP, len=1
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+            ]),
+            ApplicationDefined::default(),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::ApplicationDefined,
+                offset: 0,
+                expected: 12,
+                got: 8,
+            }),
+        ),
+        (
+            "wrong type",
+            Bytes::from_static(&[
+                0x80, 0xcb, 0x00, 0x02, // v=2, p=0, count=0, BYE, len=2
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                b'a', b'b', b'c', b'd', // reason
+            ]),
+            ApplicationDefined::default(),
+            Some(Error::WrongType {
+                packet_type: PacketType::Goodbye,
+                count: 0,
+            }),
+        ),
+    ];
+
+    for (name, data, want, want_error) in tests {
+        let got = ApplicationDefined::unmarshal(&mut data.clone());
+
+        assert_eq!(
+            got.is_err(),
+            want_error.is_some(),
+            "Unmarshal {name}: err = {got:?}, want {want_error:?}"
+        );
+
+        if let Some(err) = want_error {
+            let got_err = got.err().unwrap();
+            assert_eq!(
+                err, got_err,
+                "Unmarshal {name}: err = {got_err:?}, want {err:?}",
+            );
+        } else {
+            let actual = got.unwrap();
+            assert_eq!(
+                actual, want,
+                "Unmarshal {name}: got {actual:?}, want {want:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_application_defined_roundtrip() {
+    let tests = vec![
+        (
+            "valid",
+            ApplicationDefined {
+                sub_type: 31,
+                ssrc: 0x902f9e2e,
+                name: *b"test",
+                data: Bytes::from_static(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]),
+            },
+            None,
+        ),
+        (
+            "subtype too large",
+            ApplicationDefined {
+                sub_type: 32,
+                ssrc: 0x902f9e2e,
+                name: *b"test",
+                data: Bytes::new(),
+            },
+            Some(Error::InvalidHeader),
+        ),
+        (
+            "unaligned data",
+            ApplicationDefined {
+                sub_type: 0,
+                ssrc: 0x902f9e2e,
+                name: *b"test",
+                data: Bytes::from_static(&[0x01, 0x02, 0x03]),
+            },
+            Some(Error::InvalidApplicationDataLength),
+        ),
+    ];
+
+    for (name, want, want_error) in tests {
+        let got = want.marshal();
+
+        assert_eq!(
+            got.is_ok(),
+            want_error.is_none(),
+            "Marshal {name}: err = {got:?}, want {want_error:?}"
+        );
+
+        if let Some(err) = want_error {
+            let got_err = got.err().unwrap();
+            assert_eq!(
+                err, got_err,
+                "Marshal {name}: err = {got_err:?}, want {err:?}",
+            );
+        } else {
+            let mut data = got.ok().unwrap();
+            let actual = ApplicationDefined::unmarshal(&mut data)
+                .unwrap_or_else(|_| panic!("Unmarshal {name}"));
+
+            assert_eq!(
+                actual, want,
+                "{name} round trip: got {actual:?}, want {want:?}"
+            )
+        }
+    }
+}
+
+#[test]
+fn test_application_defined_new() {
+    let app = ApplicationDefined::new(3, 0x902f9e2e, "sig1", vec![0u8; 8]).unwrap();
+    assert_eq!(app.name_str(), Some("sig1"));
+    assert_eq!(app.destination_ssrc(), vec![0x902f9e2e]);
+    assert_eq!(app.header().count, 3);
+    assert_eq!(app.header().length, 4);
+
+    let tests = vec![
+        (32, "sig1", 0, Error::InvalidHeader),
+        (0, "sig", 0, Error::InvalidApplicationName),
+        (0, "sig12", 0, Error::InvalidApplicationName),
+        (0, "sïg", 0, Error::InvalidApplicationName),
+        (0, "sig1", 6, Error::InvalidApplicationDataLength),
+    ];
+
+    for (sub_type, name, data_len, want_error) in tests {
+        let got = ApplicationDefined::new(sub_type, 1, name, vec![0u8; data_len]);
+        assert_eq!(
+            want_error,
+            got.err().unwrap(),
+            "new({sub_type}, {name}, {data_len})"
+        );
+    }
+}
+
+#[test]
+fn test_application_defined_dispatch() {
+    let app = ApplicationDefined::new(1, 0x902f9e2e, "sig1", vec![0xaa; 4]).unwrap();
+
+    let mut data = crate::packet::marshal(&[Box::new(app.clone())]).expect("marshal");
+    let packets = crate::packet::unmarshal(&mut data).expect("unmarshal");
+
+    assert_eq!(packets.len(), 1);
+    assert_eq!(
+        packets[0].as_any().downcast_ref::<ApplicationDefined>(),
+        Some(&app)
+    );
+}
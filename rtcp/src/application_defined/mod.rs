@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod application_defined_test;
+
+use std::any::Any;
+use std::fmt;
+
+use bytes::{Buf, BufMut, Bytes};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+use crate::header::*;
+use crate::packet::*;
+use crate::util::*;
+
+type Result<T> = std::result::Result<T, util::Error>;
+
+const APP_NAME_LENGTH: usize = 4;
+const APP_SUB_TYPE_MAX: u8 = (1 << 5) - 1;
+
+/// The ApplicationDefined (APP) packet is intended for experimental use as new
+/// applications and new features are developed, without requiring packet type
+/// value registration. See RFC 3550 section 6.7.
+///
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |V=2|P| subtype |   PT=APP=204  |             length            |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                           SSRC/CSRC                           |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                          name (ASCII)                         |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                   application-dependent data                ...
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApplicationDefined {
+    /// A 5-bit subtype, allowing a set of APP packets to be defined under one unique name
+    pub sub_type: u8,
+    /// The SSRC/CSRC of the sender of this packet
+    pub ssrc: u32,
+    /// A name chosen by the person defining the set of APP packets, made of 4 ASCII characters
+    pub name: [u8; APP_NAME_LENGTH],
+    /// Application-dependent data, which must be a multiple of 32 bits long
+    pub data: Bytes,
+}
+
+impl ApplicationDefined {
+    /// new creates an APP packet, returning an error if `sub_type` does not fit
+    /// in 5 bits, `name` is not made of exactly 4 ASCII characters or `data` is
+    /// not a multiple of 32 bits long.
+    pub fn new(sub_type: u8, ssrc: u32, name: &str, data: impl Into<Bytes>) -> Result<Self> {
+        if name.len() != APP_NAME_LENGTH || !name.is_ascii() {
+            return Err(Error::InvalidApplicationName.into());
+        }
+
+        let mut n = [0; APP_NAME_LENGTH];
+        n.copy_from_slice(name.as_bytes());
+
+        let p = ApplicationDefined {
+            sub_type,
+            ssrc,
+            name: n,
+            data: data.into(),
+        };
+        p.validate()?;
+
+        Ok(p)
+    }
+
+    /// validate checks that the packet can be marshaled.
+    pub fn validate(&self) -> Result<()> {
+        if self.sub_type > APP_SUB_TYPE_MAX {
+            return Err(Error::InvalidHeader.into());
+        }
+
+        if get_padding_size(self.data.len()) != 0 {
+            return Err(Error::InvalidApplicationDataLength.into());
+        }
+
+        Ok(())
+    }
+
+    /// name_str returns the name of this packet, if it is valid ASCII.
+    pub fn name_str(&self) -> Option<&str> {
+        if self.name.is_ascii() {
+            std::str::from_utf8(&self.name).ok()
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for ApplicationDefined {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ApplicationDefined {:x} {} subtype {} data {:?}",
+            self.ssrc,
+            String::from_utf8_lossy(&self.name),
+            self.sub_type,
+            self.data,
+        )
+    }
+}
+
+impl Packet for ApplicationDefined {
+    /// Header returns the Header associated with this packet.
+    fn header(&self) -> Header {
+        Header {
+            padding: get_padding_size(self.raw_size()) != 0,
+            count: self.sub_type,
+            packet_type: PacketType::ApplicationDefined,
+            length: ((self.marshal_size() / 4) - 1) as u16,
+        }
+    }
+
+    /// destination_ssrc returns an array of SSRC values that this packet refers to.
+    fn destination_ssrc(&self) -> Vec<u32> {
+        vec![self.ssrc]
+    }
+
+    fn raw_size(&self) -> usize {
+        HEADER_LENGTH + SSRC_LENGTH + APP_NAME_LENGTH + self.data.len()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+
+    fn equal(&self, other: &(dyn Packet + Send + Sync)) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<ApplicationDefined>()
+            .map_or(false, |a| self == a)
+    }
+
+    fn cloned(&self) -> Box<dyn Packet + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+impl MarshalSize for ApplicationDefined {
+    fn marshal_size(&self) -> usize {
+        let l = self.raw_size();
+        // align to 32-bit boundary
+        l + get_padding_size(l)
+    }
+}
+
+impl Marshal for ApplicationDefined {
+    /// marshal_to encodes the ApplicationDefined packet in binary
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize> {
+        self.validate()?;
+
+        if buf.remaining_mut() < self.marshal_size() {
+            return Err(Error::BufferTooShort.into());
+        }
+
+        let h = self.header();
+        let n = h.marshal_to(buf)?;
+        buf = &mut buf[n..];
+
+        buf.put_u32(self.ssrc);
+        buf.put_slice(&self.name);
+        buf.put(self.data.clone());
+
+        if h.padding {
+            put_padding(buf, self.raw_size());
+        }
+
+        Ok(self.marshal_size())
+    }
+}
+
+impl Unmarshal for ApplicationDefined {
+    /// Unmarshal decodes the ApplicationDefined packet from binary
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let raw_packet_len = raw_packet.remaining();
+        if raw_packet_len < (HEADER_LENGTH + SSRC_LENGTH + APP_NAME_LENGTH) {
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::ApplicationDefined,
+                offset: 0,
+                expected: HEADER_LENGTH + SSRC_LENGTH + APP_NAME_LENGTH,
+                got: raw_packet_len,
+            }
+            .into());
+        }
+
+        let h = Header::unmarshal(raw_packet)?;
+        if h.packet_type != PacketType::ApplicationDefined {
+            return Err(Error::WrongType {
+                packet_type: h.packet_type,
+                count: h.count,
+            }
+            .into());
+        }
+
+        let ssrc = raw_packet.get_u32();
+        let mut name = [0; APP_NAME_LENGTH];
+        raw_packet.copy_to_slice(&mut name);
+
+        let mut data = raw_packet.copy_to_bytes(raw_packet.remaining());
+        if h.padding {
+            // the last octet counts the padding octets, including itself
+            let padding = data.last().copied().unwrap_or(0) as usize;
+            if padding == 0 || padding > data.len() {
+                return Err(Error::WrongPadding.into());
+            }
+            data.truncate(data.len() - padding);
+        }
+
+        Ok(ApplicationDefined {
+            sub_type: h.count,
+            ssrc,
+            name,
+            data,
+        })
+    }
+}
@@ -150,11 +150,24 @@ fn test_valid_packet() {
             "goodbye",
             CompoundPacket(vec![
                 Box::<ReceiverReport>::default(),
-                Box::new(cname),
+                Box::new(cname.clone()),
                 Box::<Goodbye>::default(),
             ]),
             None,
         ),
+        (
+            "padded packet before last",
+            CompoundPacket(vec![
+                Box::<ReceiverReport>::default(),
+                Box::new(cname),
+                Box::new(Goodbye {
+                    sources: vec![1234],
+                    reason: Bytes::from_static(b"ok"),
+                }),
+                Box::<PictureLossIndication>::default(),
+            ]),
+            Some(Error::PaddingBeforeLastPacket),
+        ),
     ];
 
     for (name, packet, error) in tests {
@@ -327,3 +340,62 @@ fn test_compound_packet_roundtrip() {
         )
     }
 }
+
+#[test]
+fn test_compound_packet_split() {
+    let sr = SenderReport {
+        ssrc: 0x902f9e2e,
+        ..Default::default()
+    };
+    let sdes = SourceDescription::with_cname(0x902f9e2e, "cname");
+    let pli = PictureLossIndication {
+        sender_ssrc: 0x902f9e2e,
+        media_ssrc: 0xbc5e9a40,
+    };
+    let bye = Goodbye {
+        sources: vec![0x902f9e2e],
+        reason: Bytes::new(),
+    };
+
+    let mut packets: Vec<Box<dyn Packet + Send + Sync>> = vec![Box::new(sr), Box::new(sdes)];
+    for _ in 0..7 {
+        packets.push(Box::new(pli.clone()));
+    }
+    packets.push(Box::new(bye));
+    packets.push(Box::new(pli));
+    let compound = CompoundPacket(packets);
+
+    // SR and SDES take 44 octets, an empty RR and SDES 24 and each PLI 12
+    let datagrams = compound.split(80).expect("split");
+    let lengths: Vec<usize> = datagrams.iter().map(|d| d.0.len()).collect();
+    assert_eq!(lengths, vec![5, 6, 3, 3]);
+
+    let mut packet_count = 0;
+    for (i, d) in datagrams.iter().enumerate() {
+        let data = d
+            .marshal()
+            .unwrap_or_else(|_| panic!("marshal datagram {i}"));
+        assert!(data.len() <= 80, "datagram {i} is {} octets", data.len());
+
+        let c = CompoundPacket::unmarshal(&mut data.clone())
+            .unwrap_or_else(|_| panic!("unmarshal datagram {i}"));
+        assert_eq!(c.cname().unwrap(), Bytes::from_static(b"cname"));
+
+        if i > 0 {
+            let rr = c.0[0].as_any().downcast_ref::<ReceiverReport>().unwrap();
+            assert_eq!(rr.ssrc, 0x902f9e2e);
+        }
+        packet_count += c.0.len() - 2;
+    }
+    assert_eq!(packet_count, 9);
+    assert!(datagrams[2].0[2]
+        .as_any()
+        .downcast_ref::<Goodbye>()
+        .is_some());
+
+    let result = compound.split(40);
+    assert_eq!(Error::PacketTooLarge, result.unwrap_err());
+
+    let result = CompoundPacket(compound.0[2..].to_vec()).split(1500);
+    assert_eq!(Error::BadFirstPacket, result.unwrap_err());
+}
@@ -32,9 +32,33 @@ type Result<T> = std::result::Result<T, util::Error>;
 #[derive(Debug, Default, PartialEq, Clone)]
 pub struct CompoundPacket(pub Vec<Box<dyn Packet + Send + Sync>>);
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for CompoundPacket {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::packet_enum::serde_packets::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CompoundPacket {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::packet_enum::serde_packets::deserialize(deserializer).map(CompoundPacket)
+    }
+}
+
 impl fmt::Display for CompoundPacket {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{self:?}")
+        let mut out = format!("CompoundPacket of {} packets:\n", self.0.len());
+        for p in &self.0 {
+            out += format!("{p}\n").as_str();
+        }
+        write!(f, "{out}")
     }
 }
 
@@ -137,6 +161,15 @@ impl CompoundPacket {
             return Err(Error::BadFirstPacket.into());
         }
 
+        // Only the last packet of a compound packet may carry padding,
+        // since padding is only required to align the whole datagram.
+        if self.0[..self.0.len() - 1]
+            .iter()
+            .any(|pkt| pkt.header().padding)
+        {
+            return Err(Error::PaddingBeforeLastPacket.into());
+        }
+
         for pkt in &self.0[1..] {
             // If the number of RecetpionReports exceeds 31 additional ReceiverReports
             // can be included here.
@@ -192,4 +225,71 @@ impl CompoundPacket {
 
         Err(Error::MissingCname.into())
     }
+
+    /// split divides the packets of this compound into compound packets of at most
+    /// `mtu` octets each once marshaled, so that they can be sent as separate
+    /// datagrams.
+    ///
+    /// Every datagram starts with a report and the SourceDescription of this
+    /// compound: the first one with the original report, the following ones with
+    /// an empty ReceiverReport from the same SSRC. The other packets follow in
+    /// order, and a padded packet always ends its datagram. An error is returned
+    /// if this compound is not valid up to its CNAME, or if a packet does not fit
+    /// in a datagram along with the report and the SourceDescription.
+    pub fn split(&self, mtu: usize) -> Result<Vec<CompoundPacket>> {
+        let prefix_len = self
+            .0
+            .iter()
+            .position(|p| p.as_any().downcast_ref::<SourceDescription>().is_some())
+            .map_or(self.0.len(), |i| i + 1);
+        CompoundPacket(self.0[..prefix_len].to_vec()).validate()?;
+
+        let report = &self.0[0];
+        let sdes = &self.0[prefix_len - 1];
+        let ssrc = match report.as_any().downcast_ref::<SenderReport>() {
+            Some(sr) => sr.ssrc,
+            None => report
+                .as_any()
+                .downcast_ref::<ReceiverReport>()
+                .map_or(0, |rr| rr.ssrc),
+        };
+
+        let mut datagrams = vec![];
+        let mut packets = vec![report.clone(), sdes.clone()];
+        let mut size = report.marshal_size() + sdes.marshal_size();
+        let mut padded = false;
+        if size > mtu {
+            return Err(Error::PacketTooLarge.into());
+        }
+
+        // additional ReceiverReports before the SourceDescription may as well
+        // follow it, which lets them go to any datagram
+        for p in self.0[1..prefix_len - 1]
+            .iter()
+            .chain(&self.0[prefix_len..])
+        {
+            let n = p.marshal_size();
+            if size + n > mtu || padded {
+                datagrams.push(CompoundPacket(packets));
+
+                let rr = ReceiverReport {
+                    ssrc,
+                    ..Default::default()
+                };
+                size = rr.marshal_size() + sdes.marshal_size();
+                packets = vec![Box::new(rr), sdes.clone()];
+
+                if size + n > mtu {
+                    return Err(Error::PacketTooLarge.into());
+                }
+            }
+
+            packets.push(p.clone());
+            size += n;
+            padded = p.header().padding;
+        }
+        datagrams.push(CompoundPacket(packets));
+
+        Ok(datagrams)
+    }
 }
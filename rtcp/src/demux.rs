@@ -0,0 +1,145 @@
+//! Demultiplexing of RTP and RTCP packets sent over a single transport, as
+//! described in RFC 5761 section 4.
+//!
+//! The second octet of an RTCP packet carries its packet type, which is in the
+//! range 192 to 223 for the packet types in use. The same octet of an RTP packet
+//! carries the marker bit and the payload type, so RTP payload types 64 to 95
+//! cannot be told apart from RTCP and must not be used when multiplexing.
+
+use crate::error::{Error, Result};
+use crate::header::{HEADER_LENGTH, RTP_VERSION};
+
+/// The range of the RTCP packet types that may be multiplexed with RTP.
+const RTCP_PACKET_TYPES: std::ops::RangeInclusive<u8> = 192..=223;
+/// The range of the RTP payload types that conflict with RTCP packet types.
+const CONFLICTING_PAYLOAD_TYPES: std::ops::RangeInclusive<u8> = 64..=95;
+const RTP_HEADER_LENGTH: usize = 12;
+
+/// PacketKind is the protocol a multiplexed datagram belongs to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PacketKind {
+    Rtp,
+    Rtcp,
+    /// The datagram is neither a valid RTP nor RTCP packet.
+    Unknown,
+}
+
+/// is_rtcp returns true if `buf` looks like an RTCP packet rather than an RTP
+/// packet multiplexed on the same transport.
+pub fn is_rtcp(buf: &[u8]) -> bool {
+    buf.len() >= HEADER_LENGTH && buf[0] >> 6 == RTP_VERSION && RTCP_PACKET_TYPES.contains(&buf[1])
+}
+
+/// is_rtp returns true if `buf` looks like an RTP packet rather than an RTCP
+/// packet multiplexed on the same transport.
+pub fn is_rtp(buf: &[u8]) -> bool {
+    buf.len() >= RTP_HEADER_LENGTH && buf[0] >> 6 == RTP_VERSION && !is_rtcp(buf)
+}
+
+/// is_muxable_payload_type returns false for the RTP payload types that conflict
+/// with RTCP packet types, and thus cannot be used when multiplexing RTP and RTCP.
+pub fn is_muxable_payload_type(payload_type: u8) -> bool {
+    !CONFLICTING_PAYLOAD_TYPES.contains(&(payload_type & 0x7f))
+}
+
+/// Demuxer routes the datagrams received on a transport where RTP and RTCP are
+/// multiplexed. RTP packets are only recognized if their payload type is one of
+/// those negotiated for the session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Demuxer {
+    payload_types: Vec<u8>,
+}
+
+impl Demuxer {
+    /// new creates a Demuxer for a session using the given RTP payload types. It
+    /// returns an error if one of them conflicts with RTCP packet types.
+    pub fn new(payload_types: &[u8]) -> Result<Self> {
+        if payload_types
+            .iter()
+            .any(|&pt| pt > 127 || !is_muxable_payload_type(pt))
+        {
+            return Err(Error::WrongPayloadType);
+        }
+
+        Ok(Demuxer {
+            payload_types: payload_types.to_vec(),
+        })
+    }
+
+    /// demux returns the protocol the datagram `buf` belongs to.
+    pub fn demux(&self, buf: &[u8]) -> PacketKind {
+        if is_rtcp(buf) {
+            PacketKind::Rtcp
+        } else if is_rtp(buf) && self.payload_types.contains(&(buf[1] & 0x7f)) {
+            PacketKind::Rtp
+        } else {
+            PacketKind::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rtp(marker: bool, payload_type: u8) -> Vec<u8> {
+        let mut buf = vec![0u8; RTP_HEADER_LENGTH];
+        buf[0] = 0x80;
+        buf[1] = ((marker as u8) << 7) | payload_type;
+        buf
+    }
+
+    #[test]
+    fn test_is_rtcp() {
+        let tests = vec![
+            ("sender report", vec![0x80, 200, 0x00, 0x06], true),
+            ("receiver report", vec![0x81, 201, 0x00, 0x07], true),
+            ("extended report", vec![0x80, 207, 0x00, 0x02], true),
+            ("highest type", vec![0x80, 223, 0x00, 0x00], true),
+            ("above range", vec![0x80, 224, 0x00, 0x00], false),
+            ("below range", vec![0x80, 191, 0x00, 0x00], false),
+            ("bad version", vec![0x40, 200, 0x00, 0x06], false),
+            ("short", vec![0x80, 200], false),
+        ];
+
+        for (name, buf, want) in tests {
+            assert_eq!(is_rtcp(&buf), want, "{name}");
+        }
+
+        assert!(is_rtp(&rtp(false, 96)));
+        assert!(is_rtp(&rtp(true, 111)));
+        assert!(is_rtp(&rtp(false, 72)));
+        // with the marker bit, payload type 72 reads as a sender report
+        assert!(!is_rtp(&rtp(true, 72)));
+        assert!(is_rtcp(&rtp(true, 72)));
+        assert!(!is_rtp(&rtp(false, 96)[..4]));
+    }
+
+    #[test]
+    fn test_demuxer() {
+        for pt in [0, 63, 96, 127] {
+            assert!(is_muxable_payload_type(pt), "{pt}");
+        }
+        for pt in [64, 72, 95] {
+            assert!(!is_muxable_payload_type(pt), "{pt}");
+        }
+
+        assert_eq!(Demuxer::new(&[96, 72]), Err(Error::WrongPayloadType));
+        assert_eq!(Demuxer::new(&[128]), Err(Error::WrongPayloadType));
+
+        let demuxer = Demuxer::new(&[0, 96, 111]).unwrap();
+        assert_eq!(demuxer.demux(&rtp(false, 96)), PacketKind::Rtp);
+        assert_eq!(demuxer.demux(&rtp(true, 111)), PacketKind::Rtp);
+        assert_eq!(demuxer.demux(&rtp(true, 0)), PacketKind::Rtp);
+        assert_eq!(demuxer.demux(&rtp(false, 97)), PacketKind::Unknown);
+        assert_eq!(
+            demuxer.demux(&[0x81, 201, 0x00, 0x01, 0, 0, 0, 1]),
+            PacketKind::Rtcp
+        );
+        assert_eq!(
+            demuxer.demux(&[0x16, 0xfe, 0xfd, 0x00]),
+            PacketKind::Unknown
+        );
+        assert_eq!(demuxer.demux(&[]), PacketKind::Unknown);
+    }
+}
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::header::PacketType;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Error, Debug, PartialEq)]
@@ -28,6 +30,21 @@ pub enum Error {
     /// Packet was defined before CNAME.
     #[error("Feedback packet seen before CNAME")]
     PacketBeforeCname,
+    /// Padding was set on a packet other than the last one of a compound.
+    #[error("Only the last packet in compound may be padded")]
+    PaddingBeforeLastPacket,
+    /// Packets do not fit in a single RFC 4571 frame.
+    #[error("Packets exceed the maximum RFC 4571 frame length")]
+    FrameTooLong,
+    /// Packet does not fit in the MTU.
+    #[error("Packet does not fit in the MTU")]
+    PacketTooLarge,
+    /// Header count does not match the packet contents.
+    #[error("Header count does not match packet contents")]
+    CountMismatch,
+    /// Header length does not match the packet contents.
+    #[error("Header length does not match packet contents")]
+    LengthMismatch,
     /// Too many reports.
     #[error("Too many reports")]
     TooManyReports,
@@ -37,21 +54,40 @@ pub enum Error {
     /// Too many sources.
     #[error("too many sources")]
     TooManySources,
-    /// Packet received is too short.
-    #[error("Packet status chunk must be 2 bytes")]
-    PacketTooShort,
+    /// Packet received is too short: the part of a packet of packet_type
+    /// starting at offset needs the packet to be expected bytes long, but it
+    /// is only got bytes long.
+    #[error(
+        "{packet_type} packet too short: {expected} bytes needed for offset {offset}, got {got}"
+    )]
+    PacketTooShort {
+        packet_type: PacketType,
+        offset: usize,
+        expected: usize,
+        got: usize,
+    },
     /// Buffer is too short.
     #[error("Buffer too short to be written")]
     BufferTooShort,
-    /// Wrong packet type.
-    #[error("Wrong packet type")]
-    WrongType,
+    /// Wrong packet type: the header has the offending packet_type and count,
+    /// the feedback message type of feedback packets.
+    #[error("Wrong packet type {packet_type} with count {count}")]
+    WrongType { packet_type: PacketType, count: u8 },
     /// SDES received is too long.
     #[error("SDES must be < 255 octets long")]
     SdesTextTooLong,
     /// SDES type is missing.
     #[error("SDES item missing type")]
     SdesMissingType,
+    /// SDES PRIV item prefix is missing or does not fit in the item.
+    #[error("SDES PRIV item has an invalid prefix length")]
+    SdesInvalidPrivatePrefix,
+    /// APP name is not made of 4 ASCII characters.
+    #[error("APP name must be 4 ASCII characters")]
+    InvalidApplicationName,
+    /// APP data is not 32-bit aligned.
+    #[error("APP data must be a multiple of 32 bits long")]
+    InvalidApplicationDataLength,
     /// Reason is too long.
     #[error("Reason must be < 255 octets long")]
     ReasonTooLong,
@@ -67,6 +103,12 @@ pub enum Error {
     /// Wrong payload type.
     #[error("Wrong payload type")]
     WrongPayloadType,
+    /// SLI entry field does not fit in its width.
+    #[error("Invalid slice loss entry")]
+    InvalidSliEntry,
+    /// Layer index does not fit in its field.
+    #[error("Invalid layer index")]
+    InvalidLayerIndex,
     /// Header length is too small.
     #[error("Header length is too small")]
     HeaderTooSmall,
@@ -90,6 +132,8 @@ pub enum Error {
     PacketStatusChunkLength,
     #[error("Invalid bitrate")]
     InvalidBitrate,
+    #[error("Invalid measured overhead")]
+    InvalidOverhead,
     #[error("Wrong chunk type")]
     WrongChunkType,
     #[error("Struct contains unexpected member type")]
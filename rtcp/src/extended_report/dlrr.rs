@@ -4,6 +4,7 @@ const DLRR_REPORT_LENGTH: u16 = 12;
 
 /// DLRRReport encodes a single report inside a DLRRReportBlock.
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DLRRReport {
     pub ssrc: u32,
     pub last_rr: u32,
@@ -35,6 +36,7 @@ impl fmt::Display for DLRRReport {
 /// :                               ...                             :   2
 /// +=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+=+
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DLRRReportBlock {
     pub reports: Vec<DLRRReport>,
 }
@@ -122,14 +124,22 @@ impl Unmarshal for DLRRReportBlock {
         B: Buf,
     {
         if raw_packet.remaining() < XR_HEADER_LENGTH {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::PacketTooShort {
+                packet_type: PacketType::ExtendedReport,
+                offset: 0,
+                expected: XR_HEADER_LENGTH,
+                got: raw_packet.remaining(),
+            }
+            .into());
         }
 
         let xr_header = XRHeader::unmarshal(raw_packet)?;
         let block_length = xr_header.block_length * 4;
-        if block_length % DLRR_REPORT_LENGTH != 0 || raw_packet.remaining() < block_length as usize
-        {
-            return Err(error::Error::PacketTooShort.into());
+        if block_length % DLRR_REPORT_LENGTH != 0 {
+            return Err(error::Error::LengthMismatch.into());
+        }
+        if raw_packet.remaining() < block_length as usize {
+            return Err(block_too_short(block_length as usize, raw_packet.remaining()).into());
         }
 
         let mut offset = 0;
@@ -182,3 +182,82 @@ fn test_decode() -> Result<()> {
     assert_eq!(actual.to_string(), expected.to_string());
     Ok(())
 }
+
+#[test]
+fn test_typed_block_accessors() {
+    let packet = decoded_packet();
+
+    let rrt = packet
+        .receiver_reference_time()
+        .expect("missing receiver reference time block");
+    assert_eq!(rrt.ntp_timestamp, 0x0102030405060708);
+
+    let dlrr = packet.dlrr().expect("missing DLRR block");
+    assert_eq!(dlrr.reports.len(), 3);
+    assert_eq!(dlrr.reports[0].ssrc, 0x88888888);
+
+    assert_eq!(packet.blocks::<VoIPMetricsReportBlock>().count(), 1);
+    assert_eq!(packet.blocks::<UnknownReportBlock>().count(), 0);
+
+    let empty = ExtendedReport::default();
+    assert!(empty.receiver_reference_time().is_none());
+    assert!(empty.dlrr().is_none());
+}
+
+#[test]
+fn test_ttl_or_hop_limit_roundtrip() -> Result<()> {
+    for toh in [
+        TTLorHopLimitType::Missing,
+        TTLorHopLimitType::IPv4,
+        TTLorHopLimitType::IPv6,
+    ] {
+        let block = StatisticsSummaryReportBlock {
+            ttl_or_hop_limit: toh,
+            ..Default::default()
+        };
+        let mut data = block.marshal()?;
+        let decoded = StatisticsSummaryReportBlock::unmarshal(&mut data)?;
+        assert_eq!(decoded.ttl_or_hop_limit, toh);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_voip_metrics_helpers() -> Result<()> {
+    let mut block = VoIPMetricsReportBlock {
+        ssrc: 0x89ABCDEF,
+        ..Default::default()
+    };
+    block.set_loss_fraction(0.25);
+    block.set_discard_fraction(2.0);
+    block.set_mos_lq_score(Some(4.13));
+    block.set_mos_cq_score(None);
+    block.set_rx_config(
+        PacketLossConcealment::Enhanced,
+        JitterBufferAdaptive::Adaptive,
+        0x1F,
+    );
+
+    assert_eq!(block.loss_rate, 64);
+    assert_eq!(block.discard_rate, 255);
+    assert_eq!(block.mos_lq, 41);
+    assert_eq!(block.mos_cq, VM_METRIC_UNAVAILABLE);
+    assert_eq!(block.rx_config, 0xBF);
+
+    let mut data = block.marshal()?;
+    let decoded = VoIPMetricsReportBlock::unmarshal(&mut data)?;
+    assert_eq!(decoded, block);
+    assert_eq!(decoded.loss_fraction(), 0.25);
+    assert_eq!(decoded.mos_lq_score(), Some(4.1));
+    assert_eq!(decoded.mos_cq_score(), None);
+    assert_eq!(
+        decoded.packet_loss_concealment(),
+        PacketLossConcealment::Enhanced
+    );
+    assert_eq!(
+        decoded.jitter_buffer_adaptive(),
+        JitterBufferAdaptive::Adaptive
+    );
+    assert_eq!(decoded.jitter_buffer_rate(), 0x0F);
+    Ok(())
+}
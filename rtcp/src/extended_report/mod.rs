@@ -20,7 +20,9 @@ pub use rrt::ReceiverReferenceTimeReportBlock;
 pub use ssr::{StatisticsSummaryReportBlock, TTLorHopLimitType};
 pub use unknown::UnknownReportBlock;
 use util::marshal::{Marshal, MarshalSize, Unmarshal};
-pub use vm::VoIPMetricsReportBlock;
+pub use vm::{
+    JitterBufferAdaptive, PacketLossConcealment, VoIPMetricsReportBlock, VM_METRIC_UNAVAILABLE,
+};
 
 use crate::error;
 use crate::header::{Header, PacketType, HEADER_LENGTH, SSRC_LENGTH};
@@ -34,6 +36,7 @@ const XR_HEADER_LENGTH: usize = 4;
 /// BlockType specifies the type of report in a report block
 /// Extended Report block types from RFC 3611.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlockType {
     #[default]
     Unknown = 0,
@@ -90,6 +93,7 @@ pub type TypeSpecificField = u8;
 /// blocks, these values will not be accurate until the corresponding
 /// packet is marshaled.
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XRHeader {
     pub block_type: BlockType,
     pub type_specific: TypeSpecificField,
@@ -125,7 +129,13 @@ impl Unmarshal for XRHeader {
         B: Buf,
     {
         if raw_packet.remaining() < XR_HEADER_LENGTH {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::PacketTooShort {
+                packet_type: PacketType::ExtendedReport,
+                offset: 0,
+                expected: XR_HEADER_LENGTH,
+                got: raw_packet.remaining(),
+            }
+            .into());
         }
 
         let block_type: BlockType = raw_packet.get_u8().into();
@@ -139,6 +149,18 @@ impl Unmarshal for XRHeader {
         })
     }
 }
+
+/// block_too_short returns the error of a report block whose block_length
+/// bytes following its header exceed the remaining bytes of the packet. The
+/// offsets and lengths are those within the block.
+fn block_too_short(block_length: usize, remaining: usize) -> error::Error {
+    error::Error::PacketTooShort {
+        packet_type: PacketType::ExtendedReport,
+        offset: XR_HEADER_LENGTH,
+        expected: XR_HEADER_LENGTH + block_length,
+        got: XR_HEADER_LENGTH + remaining,
+    }
+}
 /// The ExtendedReport packet is an Implementation of RTCP Extended
 /// reports defined in RFC 3611. It is used to convey detailed
 /// information about an RTP stream. Each packet contains one or
@@ -155,14 +177,125 @@ impl Unmarshal for XRHeader {
 /// :                         report blocks                         :
 /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 #[derive(Debug, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExtendedReport {
     pub sender_ssrc: u32,
+    #[cfg_attr(feature = "serde", serde(with = "serde_report_blocks"))]
     pub reports: Vec<Box<dyn Packet + Send + Sync>>,
 }
 
 impl fmt::Display for ExtendedReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{self:?}")
+        let mut out = format!("ExtendedReport from {:x}\n", self.sender_ssrc);
+        for report in &self.reports {
+            out += format!("\t{report}\n").as_str();
+        }
+        write!(f, "{out}")
+    }
+}
+
+impl ExtendedReport {
+    /// blocks returns an iterator over all report blocks of type `T` in this packet.
+    pub fn blocks<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.reports
+            .iter()
+            .filter_map(|r| r.as_any().downcast_ref::<T>())
+    }
+
+    /// receiver_reference_time returns the first Receiver Reference Time
+    /// report block of this packet, if any.
+    pub fn receiver_reference_time(&self) -> Option<&ReceiverReferenceTimeReportBlock> {
+        self.blocks::<ReceiverReferenceTimeReportBlock>().next()
+    }
+
+    /// dlrr returns the first DLRR report block of this packet, if any.
+    pub fn dlrr(&self) -> Option<&DLRRReportBlock> {
+        self.blocks::<DLRRReportBlock>().next()
+    }
+}
+
+/// serde_report_blocks (de)serializes the boxed report blocks of an
+/// ExtendedReport through an internally tagged enum of the known block types.
+#[cfg(feature = "serde")]
+mod serde_report_blocks {
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    enum ReportBlock {
+        #[serde(rename = "RLE")]
+        Rle(RLEReportBlock),
+        PacketReceiptTimes(PacketReceiptTimesReportBlock),
+        ReceiverReferenceTime(ReceiverReferenceTimeReportBlock),
+        #[serde(rename = "DLRR")]
+        Dlrr(DLRRReportBlock),
+        StatisticsSummary(StatisticsSummaryReportBlock),
+        VoIPMetrics(VoIPMetricsReportBlock),
+        Unknown(UnknownReportBlock),
+    }
+
+    impl ReportBlock {
+        fn from_packet(r: &(dyn Packet + Send + Sync)) -> Option<Self> {
+            let r = r.as_any();
+            if let Some(b) = r.downcast_ref::<RLEReportBlock>() {
+                Some(ReportBlock::Rle(b.clone()))
+            } else if let Some(b) = r.downcast_ref::<PacketReceiptTimesReportBlock>() {
+                Some(ReportBlock::PacketReceiptTimes(b.clone()))
+            } else if let Some(b) = r.downcast_ref::<ReceiverReferenceTimeReportBlock>() {
+                Some(ReportBlock::ReceiverReferenceTime(b.clone()))
+            } else if let Some(b) = r.downcast_ref::<DLRRReportBlock>() {
+                Some(ReportBlock::Dlrr(b.clone()))
+            } else if let Some(b) = r.downcast_ref::<StatisticsSummaryReportBlock>() {
+                Some(ReportBlock::StatisticsSummary(b.clone()))
+            } else if let Some(b) = r.downcast_ref::<VoIPMetricsReportBlock>() {
+                Some(ReportBlock::VoIPMetrics(b.clone()))
+            } else {
+                r.downcast_ref::<UnknownReportBlock>()
+                    .map(|b| ReportBlock::Unknown(b.clone()))
+            }
+        }
+
+        fn into_packet(self) -> Box<dyn Packet + Send + Sync> {
+            match self {
+                ReportBlock::Rle(b) => Box::new(b),
+                ReportBlock::PacketReceiptTimes(b) => Box::new(b),
+                ReportBlock::ReceiverReferenceTime(b) => Box::new(b),
+                ReportBlock::Dlrr(b) => Box::new(b),
+                ReportBlock::StatisticsSummary(b) => Box::new(b),
+                ReportBlock::VoIPMetrics(b) => Box::new(b),
+                ReportBlock::Unknown(b) => Box::new(b),
+            }
+        }
+    }
+
+    pub(super) fn serialize<S>(
+        reports: &[Box<dyn Packet + Send + Sync>],
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        reports
+            .iter()
+            .map(|r| {
+                ReportBlock::from_packet(r.as_ref())
+                    .ok_or_else(|| S::Error::custom("unsupported report block type"))
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> std::result::Result<Vec<Box<dyn Packet + Send + Sync>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let reports = Vec::<ReportBlock>::deserialize(deserializer)?;
+        Ok(reports.into_iter().map(ReportBlock::into_packet).collect())
     }
 }
 
@@ -253,12 +386,22 @@ impl Unmarshal for ExtendedReport {
     {
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < (HEADER_LENGTH + SSRC_LENGTH) {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::PacketTooShort {
+                packet_type: PacketType::ExtendedReport,
+                offset: 0,
+                expected: HEADER_LENGTH + SSRC_LENGTH,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let header = Header::unmarshal(raw_packet)?;
         if header.packet_type != PacketType::ExtendedReport {
-            return Err(error::Error::WrongType.into());
+            return Err(error::Error::WrongType {
+                packet_type: header.packet_type,
+                count: header.count,
+            }
+            .into());
         }
 
         let sender_ssrc = raw_packet.get_u32();
@@ -267,7 +410,13 @@ impl Unmarshal for ExtendedReport {
         let mut reports = vec![];
         while raw_packet.remaining() > 0 {
             if offset + XR_HEADER_LENGTH > raw_packet_len {
-                return Err(error::Error::PacketTooShort.into());
+                return Err(error::Error::PacketTooShort {
+                    packet_type: PacketType::ExtendedReport,
+                    offset,
+                    expected: offset + XR_HEADER_LENGTH,
+                    got: raw_packet_len,
+                }
+                .into());
             }
 
             let block_type: BlockType = raw_packet.chunk()[0].into();
@@ -23,6 +23,7 @@ const PRT_REPORT_BLOCK_MIN_LENGTH: u16 = 8;
 /// |       Receipt time of packet (end_seq - 1) mod 65536          |
 /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PacketReceiptTimesReportBlock {
     //not included in marshal/unmarshal
     pub t: u8,
@@ -114,16 +115,24 @@ impl Unmarshal for PacketReceiptTimesReportBlock {
         B: Buf,
     {
         if raw_packet.remaining() < XR_HEADER_LENGTH {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::PacketTooShort {
+                packet_type: PacketType::ExtendedReport,
+                offset: 0,
+                expected: XR_HEADER_LENGTH,
+                got: raw_packet.remaining(),
+            }
+            .into());
         }
 
         let xr_header = XRHeader::unmarshal(raw_packet)?;
         let block_length = xr_header.block_length * 4;
         if block_length < PRT_REPORT_BLOCK_MIN_LENGTH
             || (block_length - PRT_REPORT_BLOCK_MIN_LENGTH) % 4 != 0
-            || raw_packet.remaining() < block_length as usize
         {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::LengthMismatch.into());
+        }
+        if raw_packet.remaining() < block_length as usize {
+            return Err(block_too_short(block_length as usize, raw_packet.remaining()).into());
         }
 
         let t = xr_header.type_specific & 0x0F;
@@ -4,6 +4,7 @@ const RLE_REPORT_BLOCK_MIN_LENGTH: u16 = 8;
 
 /// ChunkType enumerates the three kinds of chunks described in RFC 3611 section 4.1.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChunkType {
     RunLength = 0,
     BitVector = 1,
@@ -37,6 +38,7 @@ pub enum ChunkType {
 ///  |0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0|
 ///  +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Chunk(pub u16);
 
 impl fmt::Display for Chunk {
@@ -103,6 +105,7 @@ impl Chunk {
 /// |          chunk n-1            |             chunk n           |
 /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RLEReportBlock {
     //not included in marshal/unmarshal
     pub is_loss_rle: bool,
@@ -209,16 +212,24 @@ impl Unmarshal for RLEReportBlock {
         B: Buf,
     {
         if raw_packet.remaining() < XR_HEADER_LENGTH {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::PacketTooShort {
+                packet_type: PacketType::ExtendedReport,
+                offset: 0,
+                expected: XR_HEADER_LENGTH,
+                got: raw_packet.remaining(),
+            }
+            .into());
         }
 
         let xr_header = XRHeader::unmarshal(raw_packet)?;
         let block_length = xr_header.block_length * 4;
         if block_length < RLE_REPORT_BLOCK_MIN_LENGTH
             || (block_length - RLE_REPORT_BLOCK_MIN_LENGTH) % 2 != 0
-            || raw_packet.remaining() < block_length as usize
         {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::LengthMismatch.into());
+        }
+        if raw_packet.remaining() < block_length as usize {
+            return Err(block_too_short(block_length as usize, raw_packet.remaining()).into());
         }
 
         let is_loss_rle = xr_header.block_type == BlockType::LossRLE;
@@ -15,6 +15,7 @@ const RRT_REPORT_BLOCK_LENGTH: u16 = 8;
 /// |             NTP timestamp, least significant word             |
 /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReceiverReferenceTimeReportBlock {
     pub ntp_timestamp: u64,
 }
@@ -94,14 +95,22 @@ impl Unmarshal for ReceiverReferenceTimeReportBlock {
         B: Buf,
     {
         if raw_packet.remaining() < XR_HEADER_LENGTH {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::PacketTooShort {
+                packet_type: PacketType::ExtendedReport,
+                offset: 0,
+                expected: XR_HEADER_LENGTH,
+                got: raw_packet.remaining(),
+            }
+            .into());
         }
 
         let xr_header = XRHeader::unmarshal(raw_packet)?;
         let block_length = xr_header.block_length * 4;
-        if block_length != RRT_REPORT_BLOCK_LENGTH || raw_packet.remaining() < block_length as usize
-        {
-            return Err(error::Error::PacketTooShort.into());
+        if block_length != RRT_REPORT_BLOCK_LENGTH {
+            return Err(error::Error::LengthMismatch.into());
+        }
+        if raw_packet.remaining() < block_length as usize {
+            return Err(block_too_short(block_length as usize, raw_packet.remaining()).into());
         }
 
         let ntp_timestamp = raw_packet.get_u64();
@@ -29,6 +29,7 @@ const SSR_REPORT_BLOCK_LENGTH: u16 = 4 + 2 * 2 + 4 * 6 + 4;
 /// | min_ttl_or_hl | max_ttl_or_hl |mean_ttl_or_hl | dev_ttl_or_hl |
 /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatisticsSummaryReportBlock {
     //not included in marshal/unmarshal
     pub loss_reports: bool,
@@ -61,6 +62,7 @@ impl fmt::Display for StatisticsSummaryReportBlock {
 /// TTLorHopLimitType encodes values for the ToH field in
 /// a StatisticsSummaryReportBlock
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TTLorHopLimitType {
     #[default]
     Missing = 0,
@@ -72,7 +74,7 @@ impl From<u8> for TTLorHopLimitType {
     fn from(v: u8) -> Self {
         match v {
             1 => TTLorHopLimitType::IPv4,
-            2 => TTLorHopLimitType::IPv4,
+            2 => TTLorHopLimitType::IPv6,
             _ => TTLorHopLimitType::Missing,
         }
     }
@@ -182,14 +184,22 @@ impl Unmarshal for StatisticsSummaryReportBlock {
         B: Buf,
     {
         if raw_packet.remaining() < XR_HEADER_LENGTH {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::PacketTooShort {
+                packet_type: PacketType::ExtendedReport,
+                offset: 0,
+                expected: XR_HEADER_LENGTH,
+                got: raw_packet.remaining(),
+            }
+            .into());
         }
 
         let xr_header = XRHeader::unmarshal(raw_packet)?;
         let block_length = xr_header.block_length * 4;
-        if block_length != SSR_REPORT_BLOCK_LENGTH || raw_packet.remaining() < block_length as usize
-        {
-            return Err(error::Error::PacketTooShort.into());
+        if block_length != SSR_REPORT_BLOCK_LENGTH {
+            return Err(error::Error::LengthMismatch.into());
+        }
+        if raw_packet.remaining() < block_length as usize {
+            return Err(block_too_short(block_length as usize, raw_packet.remaining()).into());
         }
 
         let loss_reports = xr_header.type_specific & 0x80 != 0;
@@ -3,6 +3,7 @@ use super::*;
 /// UnknownReportBlock is used to store bytes for any report block
 /// that has an unknown Report Block Type.
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnknownReportBlock {
     pub bytes: Bytes,
 }
@@ -82,13 +83,19 @@ impl Unmarshal for UnknownReportBlock {
         B: Buf,
     {
         if raw_packet.remaining() < XR_HEADER_LENGTH {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::PacketTooShort {
+                packet_type: PacketType::ExtendedReport,
+                offset: 0,
+                expected: XR_HEADER_LENGTH,
+                got: raw_packet.remaining(),
+            }
+            .into());
         }
 
         let xr_header = XRHeader::unmarshal(raw_packet)?;
         let block_length = xr_header.block_length * 4;
         if raw_packet.remaining() < block_length as usize {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(block_too_short(block_length as usize, raw_packet.remaining()).into());
         }
 
         let bytes = raw_packet.copy_to_bytes(block_length as usize);
@@ -27,6 +27,7 @@ const VM_REPORT_BLOCK_LENGTH: u16 = 4 + 4 + 2 * 4 + 10 + 2 * 3;
 /// |          JB maximum           |          JB abs max           |
 /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VoIPMetricsReportBlock {
     pub ssrc: u32,
     pub loss_rate: u8,
@@ -58,6 +59,56 @@ impl fmt::Display for VoIPMetricsReportBlock {
     }
 }
 
+/// VM_METRIC_UNAVAILABLE is the value of the signal, noise, RERL, R factor
+/// and MOS fields of a VoIPMetricsReportBlock when the metric is unavailable.
+pub const VM_METRIC_UNAVAILABLE: u8 = 127;
+
+/// PacketLossConcealment encodes the PLC bits of the RX config
+/// field in a VoIPMetricsReportBlock, RFC 3611 section 4.7.6.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PacketLossConcealment {
+    #[default]
+    Unspecified = 0,
+    Disabled = 1,
+    Enhanced = 2,
+    Standard = 3,
+}
+
+impl From<u8> for PacketLossConcealment {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => PacketLossConcealment::Disabled,
+            2 => PacketLossConcealment::Enhanced,
+            3 => PacketLossConcealment::Standard,
+            _ => PacketLossConcealment::Unspecified,
+        }
+    }
+}
+
+/// JitterBufferAdaptive encodes the JBA bits of the RX config
+/// field in a VoIPMetricsReportBlock, RFC 3611 section 4.7.6.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JitterBufferAdaptive {
+    #[default]
+    Unknown = 0,
+    Reserved = 1,
+    NonAdaptive = 2,
+    Adaptive = 3,
+}
+
+impl From<u8> for JitterBufferAdaptive {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => JitterBufferAdaptive::Reserved,
+            2 => JitterBufferAdaptive::NonAdaptive,
+            3 => JitterBufferAdaptive::Adaptive,
+            _ => JitterBufferAdaptive::Unknown,
+        }
+    }
+}
+
 impl VoIPMetricsReportBlock {
     pub fn xr_header(&self) -> XRHeader {
         XRHeader {
@@ -66,6 +117,90 @@ impl VoIPMetricsReportBlock {
             block_length: (self.raw_size() / 4 - 1) as u16,
         }
     }
+
+    /// loss_fraction returns the loss rate as a fraction between 0 and 1.
+    pub fn loss_fraction(&self) -> f64 {
+        self.loss_rate as f64 / 256.0
+    }
+
+    /// set_loss_fraction encodes a fraction between 0 and 1 into the loss rate field.
+    pub fn set_loss_fraction(&mut self, fraction: f64) {
+        self.loss_rate = fraction_to_rate(fraction);
+    }
+
+    /// discard_fraction returns the discard rate as a fraction between 0 and 1.
+    pub fn discard_fraction(&self) -> f64 {
+        self.discard_rate as f64 / 256.0
+    }
+
+    /// set_discard_fraction encodes a fraction between 0 and 1 into the discard rate field.
+    pub fn set_discard_fraction(&mut self, fraction: f64) {
+        self.discard_rate = fraction_to_rate(fraction);
+    }
+
+    /// mos_lq_score returns the listening quality MOS (1.0 to 5.0), or None if unavailable.
+    pub fn mos_lq_score(&self) -> Option<f64> {
+        mos_to_score(self.mos_lq)
+    }
+
+    /// set_mos_lq_score encodes a listening quality MOS, None marks it unavailable.
+    pub fn set_mos_lq_score(&mut self, score: Option<f64>) {
+        self.mos_lq = score_to_mos(score);
+    }
+
+    /// mos_cq_score returns the conversational quality MOS (1.0 to 5.0), or None if unavailable.
+    pub fn mos_cq_score(&self) -> Option<f64> {
+        mos_to_score(self.mos_cq)
+    }
+
+    /// set_mos_cq_score encodes a conversational quality MOS, None marks it unavailable.
+    pub fn set_mos_cq_score(&mut self, score: Option<f64>) {
+        self.mos_cq = score_to_mos(score);
+    }
+
+    /// packet_loss_concealment returns the PLC bits of the RX config field.
+    pub fn packet_loss_concealment(&self) -> PacketLossConcealment {
+        (self.rx_config >> 6).into()
+    }
+
+    /// jitter_buffer_adaptive returns the JBA bits of the RX config field.
+    pub fn jitter_buffer_adaptive(&self) -> JitterBufferAdaptive {
+        ((self.rx_config >> 4) & 0x03).into()
+    }
+
+    /// jitter_buffer_rate returns the 4-bit JB rate of the RX config field.
+    pub fn jitter_buffer_rate(&self) -> u8 {
+        self.rx_config & 0x0F
+    }
+
+    /// set_rx_config packs PLC, JBA and the 4-bit JB rate into the RX config field.
+    pub fn set_rx_config(
+        &mut self,
+        plc: PacketLossConcealment,
+        jba: JitterBufferAdaptive,
+        jb_rate: u8,
+    ) {
+        self.rx_config = ((plc as u8) << 6) | ((jba as u8) << 4) | (jb_rate & 0x0F);
+    }
+}
+
+fn fraction_to_rate(fraction: f64) -> u8 {
+    (fraction * 256.0).clamp(0.0, 255.0) as u8
+}
+
+fn mos_to_score(mos: u8) -> Option<f64> {
+    if (10..=50).contains(&mos) {
+        Some(mos as f64 / 10.0)
+    } else {
+        None
+    }
+}
+
+fn score_to_mos(score: Option<f64>) -> u8 {
+    match score {
+        Some(s) => (s * 10.0).round().clamp(10.0, 50.0) as u8,
+        None => VM_METRIC_UNAVAILABLE,
+    }
 }
 
 impl Packet for VoIPMetricsReportBlock {
@@ -148,14 +283,22 @@ impl Unmarshal for VoIPMetricsReportBlock {
         B: Buf,
     {
         if raw_packet.remaining() < XR_HEADER_LENGTH {
-            return Err(error::Error::PacketTooShort.into());
+            return Err(error::Error::PacketTooShort {
+                packet_type: PacketType::ExtendedReport,
+                offset: 0,
+                expected: XR_HEADER_LENGTH,
+                got: raw_packet.remaining(),
+            }
+            .into());
         }
 
         let xr_header = XRHeader::unmarshal(raw_packet)?;
         let block_length = xr_header.block_length * 4;
-        if block_length != VM_REPORT_BLOCK_LENGTH || raw_packet.remaining() < block_length as usize
-        {
-            return Err(error::Error::PacketTooShort.into());
+        if block_length != VM_REPORT_BLOCK_LENGTH {
+            return Err(error::Error::LengthMismatch.into());
+        }
+        if raw_packet.remaining() < block_length as usize {
+            return Err(block_too_short(block_length as usize, raw_packet.remaining()).into());
         }
 
         let ssrc = raw_packet.get_u32();
@@ -27,7 +27,12 @@ fn test_goodbye_unmarshal() {
                 sources: vec![],
                 reason: Bytes::from_static(b""),
             },
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::Goodbye,
+                offset: 8,
+                expected: 13,
+                got: 12,
+            }),
         ),
         (
             "wrong type",
@@ -40,7 +45,10 @@ fn test_goodbye_unmarshal() {
                 sources: vec![],
                 reason: Bytes::from_static(b""),
             },
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::SourceDescription,
+                count: 1,
+            }),
         ),
         (
             "short reason",
@@ -66,7 +74,12 @@ fn test_goodbye_unmarshal() {
                 sources: vec![],
                 reason: Bytes::from_static(b""),
             },
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::Goodbye,
+                offset: 10,
+                expected: 12,
+                got: 10,
+            }),
         ),
         (
             "bad count in header",
@@ -78,7 +91,12 @@ fn test_goodbye_unmarshal() {
                 sources: vec![],
                 reason: Bytes::from_static(b""),
             },
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::Goodbye,
+                offset: 4,
+                expected: 12,
+                got: 8,
+            }),
         ),
         (
             "empty packet",
@@ -99,7 +117,12 @@ fn test_goodbye_unmarshal() {
                 sources: vec![],
                 reason: Bytes::from_static(b""),
             },
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::Unsupported,
+                offset: 0,
+                expected: 4,
+                got: 0,
+            }),
         ),
     ];
 
@@ -223,3 +246,51 @@ fn test_goodbye_round_trip() {
         }
     }
 }
+
+#[test]
+fn test_goodbye_new() {
+    let too_many_sources: Vec<u32> = (0..(1 << 5)).collect();
+    let too_long_reason = vec![b'x'; 1 << 8];
+
+    let tests = vec![
+        ("no reason", vec![1, 2], Bytes::new(), None),
+        (
+            "max sources and reason",
+            (0..31).collect(),
+            Bytes::from(vec![b'x'; 255]),
+            None,
+        ),
+        (
+            "too many sources",
+            too_many_sources,
+            Bytes::new(),
+            Some(Error::TooManySources),
+        ),
+        (
+            "reason too long",
+            vec![1],
+            Bytes::from(too_long_reason),
+            Some(Error::ReasonTooLong),
+        ),
+    ];
+
+    for (name, sources, reason, want_error) in tests {
+        let got = Goodbye::new(sources.clone(), reason.clone());
+
+        if let Some(err) = want_error {
+            let got_err = got.err().unwrap();
+            assert_eq!(err, got_err, "new {name}: err = {got_err:?}, want {err:?}");
+        } else {
+            let bye = got.unwrap_or_else(|_| panic!("new {name}"));
+            assert_eq!(bye.sources, sources, "new {name} sources");
+            assert_eq!(bye.reason, reason, "new {name} reason");
+
+            // anything new accepts must marshal to an aligned packet
+            let data = bye.marshal().unwrap_or_else(|_| panic!("Marshal {name}"));
+            assert_eq!(data.len() % 4, 0, "{name} padding");
+        }
+    }
+
+    let bye = Goodbye::new(vec![1], "camera malfunction").unwrap();
+    assert_eq!(bye.reason, Bytes::from_static(b"camera malfunction"));
+}
@@ -16,6 +16,7 @@ type Result<T> = std::result::Result<T, util::Error>;
 
 /// The Goodbye packet indicates that one or more sources are no longer active.
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Goodbye {
     /// The SSRC/CSRC identifiers that are no longer active
     pub sources: Vec<u32>,
@@ -23,13 +24,31 @@ pub struct Goodbye {
     pub reason: Bytes,
 }
 
+impl Goodbye {
+    /// new creates a Goodbye packet for up to 31 `sources`, returning an error
+    /// if the sources or the `reason` do not fit in the packet.
+    pub fn new(sources: Vec<u32>, reason: impl Into<Bytes>) -> Result<Self> {
+        let reason = reason.into();
+
+        if sources.len() > COUNT_MAX {
+            return Err(Error::TooManySources.into());
+        }
+
+        if reason.len() > SDES_MAX_OCTET_COUNT {
+            return Err(Error::ReasonTooLong.into());
+        }
+
+        Ok(Goodbye { sources, reason })
+    }
+}
+
 impl fmt::Display for Goodbye {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = "Goodbye:\n\tSources:\n".to_string();
         for s in &self.sources {
             out += format!("\t{}\n", *s).as_str();
         }
-        out += format!("\tReason: {:?}\n", self.reason).as_str();
+        out += format!("\tReason: {}\n", String::from_utf8_lossy(&self.reason)).as_str();
 
         write!(f, "{out}")
     }
@@ -155,17 +174,33 @@ impl Unmarshal for Goodbye {
 
         let header = Header::unmarshal(raw_packet)?;
         if header.packet_type != PacketType::Goodbye {
-            return Err(Error::WrongType.into());
+            return Err(Error::WrongType {
+                packet_type: header.packet_type,
+                count: header.count,
+            }
+            .into());
         }
 
         if get_padding_size(raw_packet_len) != 0 {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: header.packet_type,
+                offset: raw_packet_len,
+                expected: raw_packet_len + get_padding_size(raw_packet_len),
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let reason_offset = HEADER_LENGTH + header.count as usize * SSRC_LENGTH;
 
         if reason_offset > raw_packet_len {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: header.packet_type,
+                offset: HEADER_LENGTH,
+                expected: reason_offset,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let mut sources = Vec::with_capacity(header.count as usize);
@@ -178,7 +213,13 @@ impl Unmarshal for Goodbye {
             let reason_end = reason_offset + 1 + reason_len;
 
             if reason_end > raw_packet_len {
-                return Err(Error::PacketTooShort.into());
+                return Err(Error::PacketTooShort {
+                    packet_type: header.packet_type,
+                    offset: reason_offset,
+                    expected: reason_end,
+                    got: raw_packet_len,
+                }
+                .into());
             }
 
             raw_packet.copy_to_bytes(reason_len)
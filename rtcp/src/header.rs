@@ -5,7 +5,8 @@ use crate::error::Error;
 
 /// PacketType specifies the type of an RTCP packet
 /// RTCP packet types registered with IANA. See: https://www.iana.org/assignments/rtp-parameters/rtp-parameters.xhtml#rtp-parameters-4
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum PacketType {
     #[default]
@@ -14,7 +15,7 @@ pub enum PacketType {
     ReceiverReport = 201,            // RFC 3550, 6.4.2
     SourceDescription = 202,         // RFC 3550, 6.5
     Goodbye = 203,                   // RFC 3550, 6.6
-    ApplicationDefined = 204,        // RFC 3550, 6.7 (unimplemented)
+    ApplicationDefined = 204,        // RFC 3550, 6.7
     TransportSpecificFeedback = 205, // RFC 4585, 6051
     PayloadSpecificFeedback = 206,   // RFC 4585, 6.3
     ExtendedReport = 207,            // RFC 3611
@@ -26,9 +27,16 @@ pub const FORMAT_SLI: u8 = 2;
 pub const FORMAT_PLI: u8 = 1;
 /// Transport and Payload specific feedback messages overload the count field to act as a message type. those are listed here
 pub const FORMAT_FIR: u8 = 4;
+/// Transport and Payload specific feedback messages overload the count field to act as a message type. those are listed here.
+/// https://datatracker.ietf.org/doc/html/draft-ietf-avtext-lrr-07#section-3.1
+pub const FORMAT_LRR: u8 = 10;
 /// Transport and Payload specific feedback messages overload the count field to act as a message type. those are listed here
 pub const FORMAT_TLN: u8 = 1;
 /// Transport and Payload specific feedback messages overload the count field to act as a message type. those are listed here
+pub const FORMAT_TMMBR: u8 = 3;
+/// Transport and Payload specific feedback messages overload the count field to act as a message type. those are listed here
+pub const FORMAT_TMMBN: u8 = 4;
+/// Transport and Payload specific feedback messages overload the count field to act as a message type. those are listed here
 pub const FORMAT_RRR: u8 = 5;
 /// Transport and Payload specific feedback messages overload the count field to act as a message type. those are listed here
 pub const FORMAT_REMB: u8 = 15;
@@ -60,7 +68,7 @@ impl From<u8> for PacketType {
             201 => PacketType::ReceiverReport,            // RFC 3550, 6.4.2
             202 => PacketType::SourceDescription,         // RFC 3550, 6.5
             203 => PacketType::Goodbye,                   // RFC 3550, 6.6
-            204 => PacketType::ApplicationDefined,        // RFC 3550, 6.7 (unimplemented)
+            204 => PacketType::ApplicationDefined,        // RFC 3550, 6.7
             205 => PacketType::TransportSpecificFeedback, // RFC 4585, 6051
             206 => PacketType::PayloadSpecificFeedback,   // RFC 4585, 6.3
             207 => PacketType::ExtendedReport,            // RFC 3611
@@ -84,6 +92,7 @@ pub const SDES_MAX_OCTET_COUNT: usize = (1 << 8) - 1;
 
 /// A Header is the common header shared by all RTCP packets
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
     /// If the padding bit is set, this individual RTCP packet contains
     /// some additional padding octets at the end which are not part of
@@ -141,7 +150,13 @@ impl Unmarshal for Header {
         B: Buf,
     {
         if raw_packet.remaining() < HEADER_LENGTH {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::Unsupported,
+                offset: 0,
+                expected: HEADER_LENGTH,
+                got: raw_packet.remaining(),
+            }
+            .into());
         }
 
         /*
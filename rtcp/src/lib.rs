@@ -40,20 +40,38 @@
 //!     let pli_data = pkt.marshal().unwrap();
 //!     // ...
 //!```
+//!
+//! With the `arbitrary` feature enabled, all the packet types implement
+//! `arbitrary::Arbitrary` for use by fuzzers. Generated packets always marshal,
+//! and unmarshaling the result gives back the same packet.
 
+pub mod application_defined;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 pub mod compound_packet;
+pub mod demux;
 mod error;
 pub mod extended_report;
 pub mod goodbye;
 pub mod header;
+pub mod ntp;
 pub mod packet;
+pub mod packet_enum;
 pub mod payload_feedbacks;
 pub mod raw_packet;
+pub mod reader;
 pub mod receiver_report;
+pub mod receiver_stream;
 pub mod reception_report;
+pub mod rtt;
+pub mod scheduler;
 pub mod sender_report;
 pub mod source_description;
+pub mod stats;
 pub mod transport_feedbacks;
+pub mod unknown_feedback;
 mod util;
 
+pub use demux::{is_rtcp, Demuxer};
 pub use error::Error;
+pub use scheduler::Scheduler;
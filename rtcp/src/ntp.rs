@@ -0,0 +1,136 @@
+//! Conversions between wallclock time and the NTP timestamp formats used by RTCP.
+//!
+//! The 64-bit format carried in `SenderReport::ntp_time` counts seconds since
+//! 1 January 1900 in its upper 32 bits and fractions of a second in its lower
+//! 32 bits. The compact 32-bit format used by the LSR and DLSR fields of
+//! reception reports keeps the middle 32 bits, i.e. units of 1/65536 seconds.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Seconds between the NTP epoch (1900) and the unix epoch (1970).
+pub const NTP_UNIX_OFFSET: u64 = 0x83AA_7E80;
+
+const NTP_ERA_SECONDS: u64 = 1 << 32;
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// system_time_to_ntp converts a SystemTime to a 64-bit NTP timestamp. Times
+/// before the unix epoch map to the unix epoch, and times past 2036 wrap into
+/// the next NTP era as the format requires.
+pub fn system_time_to_ntp(t: SystemTime) -> u64 {
+    let d = t.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = (d.as_secs() + NTP_UNIX_OFFSET) % NTP_ERA_SECONDS;
+    let fraction = ((d.subsec_nanos() as u64) << 32) / NANOS_PER_SECOND;
+
+    (seconds << 32) | fraction
+}
+
+/// ntp_to_system_time converts a 64-bit NTP timestamp to a SystemTime.
+///
+/// Following RFC 4330 section 3, timestamps with the most significant bit of the
+/// seconds cleared are taken to be in the era starting in 2036.
+pub fn ntp_to_system_time(ntp_time: u64) -> SystemTime {
+    let mut seconds = ntp_time >> 32;
+    if seconds & 0x8000_0000 == 0 {
+        seconds += NTP_ERA_SECONDS;
+    }
+
+    let nanos = ((ntp_time & 0xFFFF_FFFF) * NANOS_PER_SECOND) >> 32;
+    let since_ntp_epoch = Duration::new(seconds, nanos as u32);
+    let offset = Duration::from_secs(NTP_UNIX_OFFSET);
+
+    match since_ntp_epoch.checked_sub(offset) {
+        Some(d) => UNIX_EPOCH + d,
+        None => UNIX_EPOCH - (offset - since_ntp_epoch),
+    }
+}
+
+/// to_compact_ntp returns the middle 32 bits of a 64-bit NTP timestamp, as used
+/// by the LSR field of reception reports.
+pub fn to_compact_ntp(ntp_time: u64) -> u32 {
+    (ntp_time >> 16) as u32
+}
+
+/// duration_to_compact_ntp converts a Duration to units of 1/65536 seconds, as used
+/// by the DLSR field of reception reports. Durations the field cannot hold saturate.
+pub fn duration_to_compact_ntp(d: Duration) -> u32 {
+    // clamped before the shift, which would overflow for huge durations
+    let seconds = d.as_secs().min(u64::from(u16::MAX) + 1);
+    let units = (seconds << 16) | (((d.subsec_nanos() as u64) << 16) / NANOS_PER_SECOND);
+    units.min(u32::MAX as u64) as u32
+}
+
+/// compact_ntp_to_duration converts units of 1/65536 seconds to a Duration.
+pub fn compact_ntp_to_duration(compact: u32) -> Duration {
+    let seconds = (compact >> 16) as u64;
+    let nanos = ((compact & 0xFFFF) as u64 * NANOS_PER_SECOND) >> 16;
+
+    Duration::new(seconds, nanos as u32)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ntp_system_time() {
+        let tests = vec![
+            (UNIX_EPOCH, 0x83AA_7E80_0000_0000),
+            (
+                UNIX_EPOCH + Duration::from_millis(1_500),
+                0x83AA_7E81_8000_0000,
+            ),
+            (
+                // 2036-02-07T06:28:16Z, the start of NTP era 1
+                UNIX_EPOCH + Duration::from_secs(NTP_ERA_SECONDS - NTP_UNIX_OFFSET),
+                0,
+            ),
+            (
+                UNIX_EPOCH + Duration::from_secs(NTP_ERA_SECONDS - NTP_UNIX_OFFSET + 1),
+                0x0000_0001_0000_0000,
+            ),
+        ];
+
+        for (t, want) in tests {
+            let ntp_time = system_time_to_ntp(t);
+            assert_eq!(ntp_time, want, "{t:?}");
+            assert_eq!(ntp_to_system_time(ntp_time), t, "{ntp_time:x}");
+        }
+
+        // the fraction keeps sub-microsecond precision
+        let t = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+        let back = ntp_to_system_time(system_time_to_ntp(t));
+        let diff = t.duration_since(back).unwrap_or_else(|e| e.duration());
+        assert!(diff < Duration::from_nanos(2), "{diff:?}");
+
+        // times before the unix epoch, within NTP era 0
+        assert_eq!(
+            ntp_to_system_time(0x8000_0000_0000_0000),
+            UNIX_EPOCH - Duration::from_secs(NTP_UNIX_OFFSET - 0x8000_0000)
+        );
+    }
+
+    #[test]
+    fn test_ntp_compact() {
+        assert_eq!(to_compact_ntp(0xda8b_d1fc_dddd_a05a), 0xd1fc_dddd);
+
+        let tests = vec![
+            (Duration::ZERO, 0),
+            (Duration::from_millis(500), 0x8000),
+            (Duration::from_millis(1_500), 0x0001_8000),
+            (Duration::from_secs(0xFFFF), 0xFFFF_0000),
+        ];
+
+        for (d, want) in tests {
+            assert_eq!(duration_to_compact_ntp(d), want, "{d:?}");
+            assert_eq!(compact_ntp_to_duration(want), d, "{want:x}");
+        }
+
+        for d in [
+            Duration::from_secs(1 << 16),
+            Duration::from_secs(1 << 48),
+            Duration::MAX,
+        ] {
+            assert_eq!(duration_to_compact_ntp(d), u32::MAX, "{d:?}");
+        }
+    }
+}
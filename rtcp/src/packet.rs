@@ -1,14 +1,18 @@
 use std::any::Any;
 use std::fmt;
 
-use bytes::{Buf, BufMut, Bytes, BytesMut};
+use bytes::{Buf, Bytes, BytesMut};
 use util::marshal::{Marshal, Unmarshal};
 
+use crate::application_defined::ApplicationDefined;
+use crate::compound_packet::CompoundPacket;
 use crate::error::{Error, Result};
 use crate::extended_report::ExtendedReport;
 use crate::goodbye::*;
 use crate::header::*;
+use crate::packet_enum::PacketEnum;
 use crate::payload_feedbacks::full_intra_request::*;
+use crate::payload_feedbacks::layer_refresh_request::*;
 use crate::payload_feedbacks::picture_loss_indication::*;
 use crate::payload_feedbacks::receiver_estimated_maximum_bitrate::*;
 use crate::payload_feedbacks::slice_loss_indication::*;
@@ -17,8 +21,11 @@ use crate::receiver_report::*;
 use crate::sender_report::*;
 use crate::source_description::*;
 use crate::transport_feedbacks::rapid_resynchronization_request::*;
+use crate::transport_feedbacks::temporary_maximum_media_stream_bitrate::*;
 use crate::transport_feedbacks::transport_layer_cc::*;
 use crate::transport_feedbacks::transport_layer_nack::*;
+use crate::unknown_feedback::UnknownFeedback;
+use crate::util::get_padding_size;
 
 /// Packet represents an RTCP packet, a protocol used for out-of-band statistics and
 /// control information for an RTP session
@@ -43,13 +50,79 @@ impl Clone for Box<dyn Packet + Send + Sync> {
     }
 }
 
-/// marshal takes an array of Packets and serializes them to a single buffer
+/// equal compares two packets. Packets of the same type are compared field by
+/// field; packets of different types, such as a RawPacket and the packet it
+/// would parse to, are equal if they marshal to the same bytes.
+pub fn equal(a: &(dyn Packet + Send + Sync), b: &(dyn Packet + Send + Sync)) -> bool {
+    if a.equal(b) {
+        return true;
+    }
+
+    match (a.marshal(), b.marshal()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// marshal takes an array of Packets and serializes them to a single buffer.
+/// The total size is computed upfront so the output is allocated only once.
 pub fn marshal(packets: &[Box<dyn Packet + Send + Sync>]) -> Result<Bytes> {
-    let mut out = BytesMut::new();
+    let l = packets.iter().map(|p| p.marshal_size()).sum();
+    let mut out = BytesMut::with_capacity(l);
+    out.resize(l, 0);
+
+    let mut offset = 0;
     for p in packets {
-        let data = p.marshal()?;
-        out.put(data);
+        offset += p.marshal_to(&mut out[offset..])?;
     }
+
+    Ok(out.freeze())
+}
+
+/// marshal_with_padding serializes packets like [`marshal`], then pads the last
+/// packet so that the whole datagram is a multiple of `block_size` octets, as
+/// some encryption profiles require. `block_size` must be a non-zero multiple
+/// of 4, and the resulting padding of the last packet must fit in 255 octets.
+pub fn marshal_with_padding(
+    packets: &[Box<dyn Packet + Send + Sync>],
+    block_size: usize,
+) -> Result<Bytes> {
+    if block_size == 0 || get_padding_size(block_size) != 0 {
+        return Err(Error::WrongPadding);
+    }
+
+    let l: usize = packets.iter().map(|p| p.marshal_size()).sum();
+    let extra = (block_size - l % block_size) % block_size;
+    let last = match packets.last() {
+        Some(last) if extra != 0 => last,
+        _ => return marshal(packets),
+    };
+
+    // the last packet may already carry padding of its own
+    let last_padding = last.marshal_size() - last.raw_size();
+    let padding = last_padding + extra;
+    if padding > u8::MAX as usize {
+        return Err(Error::WrongPadding);
+    }
+
+    let mut out = BytesMut::with_capacity(l + extra);
+    out.resize(l + extra, 0);
+
+    let mut offset = 0;
+    for p in packets {
+        offset += p.marshal_to(&mut out[offset..])?;
+    }
+
+    let start = offset - last.marshal_size();
+    let mut h = last.header();
+    h.padding = true;
+    h.length += (extra / 4) as u16;
+    h.marshal_to(&mut out[start..])?;
+
+    let end = l + extra;
+    out[end - padding..end - 1].fill(0);
+    out[end - 1] = padding as u8;
+
     Ok(out.freeze())
 }
 
@@ -79,9 +152,167 @@ where
     }
 }
 
+/// unmarshal_enum parses a datagram like [`unmarshal`], returning its packets as
+/// PacketEnums rather than boxed trait objects. This spares the allocation and
+/// dynamic dispatch of each packet, and lets callers match on the packet types.
+pub fn unmarshal_enum<B>(raw_data: &mut B) -> Result<Vec<PacketEnum>>
+where
+    B: Buf,
+{
+    let mut packets = vec![];
+
+    while raw_data.has_remaining() {
+        packets.push(packet_enum_unmarshaller(raw_data, ParseMode::Lenient)?);
+    }
+
+    if packets.is_empty() {
+        return Err(Error::InvalidHeader);
+    }
+
+    Ok(packets)
+}
+
+/// unmarshal_ref parses a datagram like [`unmarshal`] without copying its
+/// payload: variable-length fields of the returned packets, such as SDES text,
+/// Goodbye reasons or profile-specific extensions, are views into `raw_data`.
+pub fn unmarshal_ref(raw_data: &Bytes) -> Result<Vec<Box<dyn Packet + Send + Sync>>> {
+    unmarshal(&mut raw_data.clone())
+}
+
+/// RtcpMode selects which datagram layouts are emitted and accepted by
+/// [`marshal_with_mode`] and [`unmarshal_with_mode`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RtcpMode {
+    /// Every datagram must be a compound packet as described in RFC 3550:
+    /// a leading SenderReport or ReceiverReport followed by a CNAME.
+    #[default]
+    Compound,
+    /// Datagrams may also carry individual packets without a leading
+    /// SenderReport or ReceiverReport, as negotiated with `a=rtcp-rsize`.
+    /// See RFC 5506.
+    ReducedSize,
+}
+
+/// marshal_with_mode serializes packets like [`marshal`], but first checks that
+/// they form a datagram permitted in the given mode.
+pub fn marshal_with_mode(
+    packets: &[Box<dyn Packet + Send + Sync>],
+    mode: RtcpMode,
+) -> Result<Bytes> {
+    validate_mode(packets, mode)?;
+    marshal(packets)
+}
+
+/// unmarshal_with_mode parses a datagram like [`unmarshal`], but rejects it if
+/// it is not permitted in the given mode. In particular, reduced-size packets
+/// are only accepted in [`RtcpMode::ReducedSize`].
+pub fn unmarshal_with_mode<B>(
+    raw_data: &mut B,
+    mode: RtcpMode,
+) -> Result<Vec<Box<dyn Packet + Send + Sync>>>
+where
+    B: Buf,
+{
+    let packets = unmarshal(raw_data)?;
+    validate_mode(&packets, mode)?;
+    Ok(packets)
+}
+
+/// is_reduced_size returns true if the packets do not start with a
+/// SenderReport or ReceiverReport, and so can only be sent as reduced-size RTCP.
+pub fn is_reduced_size(packets: &[Box<dyn Packet + Send + Sync>]) -> bool {
+    match packets.first() {
+        Some(p) => {
+            p.as_any().downcast_ref::<SenderReport>().is_none()
+                && p.as_any().downcast_ref::<ReceiverReport>().is_none()
+        }
+        None => false,
+    }
+}
+
+fn validate_mode(packets: &[Box<dyn Packet + Send + Sync>], mode: RtcpMode) -> Result<()> {
+    match mode {
+        RtcpMode::Compound => CompoundPacket(packets.to_vec()).validate()?,
+        RtcpMode::ReducedSize => {
+            if packets.is_empty() {
+                return Err(Error::EmptyCompound);
+            }
+            // a datagram that does start with a report must still be a
+            // valid compound packet
+            if !is_reduced_size(packets) {
+                CompoundPacket(packets.to_vec()).validate()?;
+            } else if packets[..packets.len() - 1]
+                .iter()
+                .any(|p| p.header().padding)
+            {
+                return Err(Error::PaddingBeforeLastPacket);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// ParseMode selects how packets whose header disagrees with their contents
+/// are handled by [`unmarshal_with_parse_mode`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Packets are parsed as far as their contents allow: trailing octets are
+    /// ignored, packets of unknown types are returned as RawPacket and feedback
+    /// messages of unknown formats as UnknownFeedback.
+    #[default]
+    Lenient,
+    /// Packets are rejected unless their header count or format matches their
+    /// contents, e.g. the number of report blocks or BYE sources, and their
+    /// length matches the length of these contents.
+    Strict,
+}
+
+/// unmarshal_with_parse_mode parses a datagram like [`unmarshal`], validating
+/// the header of each packet against its contents in [`ParseMode::Strict`].
+pub fn unmarshal_with_parse_mode<B>(
+    raw_data: &mut B,
+    mode: ParseMode,
+) -> Result<Vec<Box<dyn Packet + Send + Sync>>>
+where
+    B: Buf,
+{
+    let mut packets = vec![];
+
+    while raw_data.has_remaining() {
+        let p = unmarshaller_with_parse_mode(raw_data, mode)?;
+        packets.push(p);
+    }
+
+    if packets.is_empty() {
+        return Err(Error::InvalidHeader);
+    }
+
+    Ok(packets)
+}
+
 /// unmarshaller is a factory which pulls the first RTCP packet from a bytestream,
 /// and returns it's parsed representation, and the amount of data that was processed.
 pub(crate) fn unmarshaller<B>(raw_data: &mut B) -> Result<Box<dyn Packet + Send + Sync>>
+where
+    B: Buf,
+{
+    unmarshaller_with_parse_mode(raw_data, ParseMode::Lenient)
+}
+
+fn unmarshaller_with_parse_mode<B>(
+    raw_data: &mut B,
+    mode: ParseMode,
+) -> Result<Box<dyn Packet + Send + Sync>>
+where
+    B: Buf,
+{
+    Ok(packet_enum_unmarshaller(raw_data, mode)?.into_packet())
+}
+
+/// packet_enum_unmarshaller pulls the first RTCP packet from a bytestream like
+/// [`unmarshaller`], returning it as a PacketEnum.
+fn packet_enum_unmarshaller<B>(raw_data: &mut B, mode: ParseMode) -> Result<PacketEnum>
 where
     B: Buf,
 {
@@ -89,37 +320,138 @@ where
 
     let length = (h.length as usize) * 4;
     if length > raw_data.remaining() {
-        return Err(Error::PacketTooShort);
+        return Err(Error::PacketTooShort {
+            packet_type: h.packet_type,
+            offset: HEADER_LENGTH,
+            expected: HEADER_LENGTH + length,
+            got: HEADER_LENGTH + raw_data.remaining(),
+        });
+    }
+
+    let mut body = raw_data.copy_to_bytes(length);
+    let mut h = h;
+    let original = h.clone();
+    let mut unpadded_length = HEADER_LENGTH + length;
+    if h.padding {
+        // the last octet of a padded packet counts the padding octets,
+        // including itself
+        let padding = body.last().copied().unwrap_or(0) as usize;
+        if padding == 0 || padding > length {
+            return Err(Error::WrongPadding);
+        }
+
+        // hand the packet over without its whole words of padding, so that
+        // they cannot be mistaken for payload. Padding within the last word
+        // is left for packets whose payload is not 32-bit aligned.
+        unpadded_length -= padding;
+        let remainder = padding % 4;
+        let stripped = padding - remainder;
+        body.truncate(length - stripped);
+        h.padding = remainder > 0;
+        h.length = ((length - stripped) / 4) as u16;
     }
 
-    let mut in_packet = h.marshal()?.chain(raw_data.take(length));
+    // the header is rebuilt on the stack, while the body stays a view of
+    // raw_data when it is backed by Bytes
+    let mut header = [0u8; HEADER_LENGTH];
+    h.marshal_to(&mut header)?;
+    let mut in_packet = (&header[..]).chain(body);
 
-    let p: Box<dyn Packet + Send + Sync> = match h.packet_type {
-        PacketType::SenderReport => Box::new(SenderReport::unmarshal(&mut in_packet)?),
-        PacketType::ReceiverReport => Box::new(ReceiverReport::unmarshal(&mut in_packet)?),
-        PacketType::SourceDescription => Box::new(SourceDescription::unmarshal(&mut in_packet)?),
-        PacketType::Goodbye => Box::new(Goodbye::unmarshal(&mut in_packet)?),
+    let p = match h.packet_type {
+        PacketType::SenderReport => {
+            PacketEnum::SenderReport(SenderReport::unmarshal(&mut in_packet)?)
+        }
+        PacketType::ReceiverReport => {
+            PacketEnum::ReceiverReport(ReceiverReport::unmarshal(&mut in_packet)?)
+        }
+        PacketType::SourceDescription => {
+            PacketEnum::SourceDescription(SourceDescription::unmarshal(&mut in_packet)?)
+        }
+        PacketType::Goodbye => PacketEnum::Goodbye(Goodbye::unmarshal(&mut in_packet)?),
+        PacketType::ApplicationDefined => {
+            PacketEnum::ApplicationDefined(ApplicationDefined::unmarshal(&mut in_packet)?)
+        }
 
         PacketType::TransportSpecificFeedback => match h.count {
-            FORMAT_TLN => Box::new(TransportLayerNack::unmarshal(&mut in_packet)?),
-            FORMAT_RRR => Box::new(RapidResynchronizationRequest::unmarshal(&mut in_packet)?),
-            FORMAT_TCC => Box::new(TransportLayerCc::unmarshal(&mut in_packet)?),
-            _ => Box::new(RawPacket::unmarshal(&mut in_packet)?),
+            FORMAT_TLN => {
+                PacketEnum::TransportLayerNack(TransportLayerNack::unmarshal(&mut in_packet)?)
+            }
+            FORMAT_RRR => PacketEnum::RapidResynchronizationRequest(
+                RapidResynchronizationRequest::unmarshal(&mut in_packet)?,
+            ),
+            FORMAT_TMMBR => PacketEnum::TemporaryMaximumMediaStreamBitrateRequest(
+                TemporaryMaximumMediaStreamBitrateRequest::unmarshal(&mut in_packet)?,
+            ),
+            FORMAT_TMMBN => PacketEnum::TemporaryMaximumMediaStreamBitrateNotification(
+                TemporaryMaximumMediaStreamBitrateNotification::unmarshal(&mut in_packet)?,
+            ),
+            FORMAT_TCC => {
+                PacketEnum::TransportLayerCc(TransportLayerCc::unmarshal(&mut in_packet)?)
+            }
+            _ => PacketEnum::UnknownFeedback(UnknownFeedback::unmarshal(&mut in_packet)?),
         },
         PacketType::PayloadSpecificFeedback => match h.count {
-            FORMAT_PLI => Box::new(PictureLossIndication::unmarshal(&mut in_packet)?),
-            FORMAT_SLI => Box::new(SliceLossIndication::unmarshal(&mut in_packet)?),
-            FORMAT_REMB => Box::new(ReceiverEstimatedMaximumBitrate::unmarshal(&mut in_packet)?),
-            FORMAT_FIR => Box::new(FullIntraRequest::unmarshal(&mut in_packet)?),
-            _ => Box::new(RawPacket::unmarshal(&mut in_packet)?),
+            FORMAT_PLI => {
+                PacketEnum::PictureLossIndication(PictureLossIndication::unmarshal(&mut in_packet)?)
+            }
+            FORMAT_SLI => {
+                PacketEnum::SliceLossIndication(SliceLossIndication::unmarshal(&mut in_packet)?)
+            }
+            FORMAT_REMB => PacketEnum::ReceiverEstimatedMaximumBitrate(
+                ReceiverEstimatedMaximumBitrate::unmarshal(&mut in_packet)?,
+            ),
+            FORMAT_FIR => {
+                PacketEnum::FullIntraRequest(FullIntraRequest::unmarshal(&mut in_packet)?)
+            }
+            FORMAT_LRR => {
+                PacketEnum::LayerRefreshRequest(LayerRefreshRequest::unmarshal(&mut in_packet)?)
+            }
+            _ => PacketEnum::UnknownFeedback(UnknownFeedback::unmarshal(&mut in_packet)?),
         },
-        PacketType::ExtendedReport => Box::new(ExtendedReport::unmarshal(&mut in_packet)?),
-        _ => Box::new(RawPacket::unmarshal(&mut in_packet)?),
+        PacketType::ExtendedReport => {
+            PacketEnum::ExtendedReport(ExtendedReport::unmarshal(&mut in_packet)?)
+        }
+        _ => PacketEnum::RawPacket(RawPacket::unmarshal(&mut in_packet)?),
     };
 
+    if mode == ParseMode::Strict {
+        validate_header(&original, unpadded_length, p.as_packet())?;
+    }
+
     Ok(p)
 }
 
+// validate_header checks the header a packet was parsed from against the
+// header and size of the parsed packet.
+fn validate_header(
+    h: &Header,
+    unpadded_length: usize,
+    p: &(dyn Packet + Send + Sync),
+) -> Result<()> {
+    if p.as_any().downcast_ref::<UnknownFeedback>().is_some() {
+        return Err(Error::WrongFeedbackType);
+    }
+    if p.as_any().downcast_ref::<RawPacket>().is_some() {
+        return Err(Error::WrongType {
+            packet_type: h.packet_type,
+            count: h.count,
+        });
+    }
+
+    if p.header().count != h.count {
+        return Err(Error::CountMismatch);
+    }
+
+    // packets carrying text are padded to a 32-bit boundary with null octets,
+    // which may or may not be signalled by the padding bit
+    let raw_size = p.raw_size();
+    if unpadded_length < raw_size || unpadded_length > raw_size + get_padding_size(raw_size) {
+        return Err(Error::LengthMismatch);
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use bytes::Bytes;
@@ -211,6 +543,412 @@ mod test {
         assert!(packet == expected, "Invalid packets");
     }
 
+    #[test]
+    fn test_packet_marshal_roundtrip() -> Result<()> {
+        let packets: Vec<Box<dyn Packet + Send + Sync>> = vec![
+            Box::new(ReceiverReport {
+                ssrc: 0x902f9e2e,
+                reports: vec![ReceptionReport {
+                    ssrc: 0xbc5e9a40,
+                    last_sequence_number: 0x46e1,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            Box::new(Goodbye {
+                sources: vec![0x902f9e2e],
+                reason: Bytes::from_static(b"FOO"),
+            }),
+            Box::new(PictureLossIndication {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0x902f9e2e,
+            }),
+        ];
+
+        let data = marshal(&packets)?;
+        let total: usize = packets.iter().map(|p| p.marshal_size()).sum();
+        assert_eq!(data.len(), total);
+
+        let decoded = unmarshal(&mut data.clone())?;
+        assert!(decoded == packets, "Invalid packets");
+
+        assert!(marshal(&[])?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_packet_reduced_size_mode() -> Result<()> {
+        let pli: Vec<Box<dyn Packet + Send + Sync>> = vec![Box::new(PictureLossIndication {
+            sender_ssrc: 0x902f9e2e,
+            media_ssrc: 0x902f9e2e,
+        })];
+        let compound: Vec<Box<dyn Packet + Send + Sync>> = vec![
+            Box::new(ReceiverReport {
+                ssrc: 0x902f9e2e,
+                ..Default::default()
+            }),
+            Box::new(SourceDescription {
+                chunks: vec![SourceDescriptionChunk {
+                    source: 0x902f9e2e,
+                    items: vec![SourceDescriptionItem {
+                        sdes_type: SdesType::SdesCname,
+                        text: Bytes::from_static(b"cname"),
+                    }],
+                }],
+            }),
+            pli[0].clone(),
+        ];
+
+        assert!(is_reduced_size(&pli));
+        assert!(!is_reduced_size(&compound));
+        assert!(!is_reduced_size(&[]));
+
+        // reduced-size datagrams are only emitted and accepted when negotiated
+        let data = marshal_with_mode(&pli, RtcpMode::ReducedSize)?;
+        assert_eq!(data, marshal(&pli)?);
+        let decoded = unmarshal_with_mode(&mut data.clone(), RtcpMode::ReducedSize)?;
+        assert!(decoded == pli, "Invalid packets");
+
+        let got = marshal_with_mode(&pli, RtcpMode::Compound);
+        assert!(
+            matches!(got, Err(Error::Util(ref e)) if Error::BadFirstPacket == *e),
+            "marshal_with_mode(Compound) err = {got:?}"
+        );
+        let got = unmarshal_with_mode(&mut data.clone(), RtcpMode::Compound);
+        assert!(
+            matches!(got, Err(Error::Util(ref e)) if Error::BadFirstPacket == *e),
+            "unmarshal_with_mode(Compound) err = {got:?}"
+        );
+
+        // full compound packets are valid in both modes
+        for mode in [RtcpMode::Compound, RtcpMode::ReducedSize] {
+            let data = marshal_with_mode(&compound, mode)?;
+            let decoded = unmarshal_with_mode(&mut data.clone(), mode)?;
+            assert!(decoded == compound, "Invalid packets in {mode:?}");
+        }
+
+        // a datagram starting with a report is still held to the compound rules
+        let got = marshal_with_mode(&compound[..1], RtcpMode::ReducedSize);
+        assert!(
+            matches!(got, Err(Error::Util(ref e)) if Error::MissingCname == *e),
+            "marshal_with_mode(ReducedSize) err = {got:?}"
+        );
+
+        assert_eq!(
+            marshal_with_mode(&[], RtcpMode::ReducedSize),
+            Err(Error::EmptyCompound)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_packet_marshal_with_padding() -> Result<()> {
+        let pli = PictureLossIndication {
+            sender_ssrc: 0x902f9e2e,
+            media_ssrc: 0x902f9e2e,
+        };
+        let feedback_last: Vec<Box<dyn Packet + Send + Sync>> =
+            vec![Box::<ReceiverReport>::default(), Box::new(pli.clone())];
+        let padded_last: Vec<Box<dyn Packet + Send + Sync>> = vec![
+            Box::<ReceiverReport>::default(),
+            Box::new(Goodbye {
+                sources: vec![0x902f9e2e],
+                reason: Bytes::new(),
+            }),
+        ];
+        let tests = vec![
+            ("feedback last", feedback_last.clone(), 16, 32),
+            ("already padded last", padded_last, 16, 32),
+            ("already aligned", feedback_last, 4, 20),
+        ];
+
+        for (name, packets, block_size, want_len) in tests {
+            let data = marshal_with_padding(&packets, block_size)?;
+            assert_eq!(data.len(), want_len, "{name} length");
+
+            let decoded = unmarshal(&mut data.clone())?;
+            assert!(decoded == packets, "{name} round trip: got {decoded:?}");
+        }
+
+        for block_size in [0, 6] {
+            let got = marshal_with_padding(&[], block_size);
+            assert_eq!(got, Err(Error::WrongPadding), "block size {block_size}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_packet_unmarshal_padding() -> Result<()> {
+        // a NACK padded with one word must not yield an extra NackPair
+        let mut data = Bytes::from_static(&[
+            // v=2, p=1, FMT=1, RTPFB, len=4
+            0xa1, 0xcd, 0x00, 0x04, // sender=0x902f9e2e
+            0x90, 0x2f, 0x9e, 0x2e, // media=0x902f9e2e
+            0x90, 0x2f, 0x9e, 0x2e, // pid=0xaaaa, blp=0
+            0xaa, 0xaa, 0x00, 0x00, // padding
+            0x00, 0x00, 0x00, 0x04,
+        ]);
+        let packets = unmarshal(&mut data)?;
+        let nack = packets[0]
+            .as_any()
+            .downcast_ref::<TransportLayerNack>()
+            .expect("TransportLayerNack");
+        assert_eq!(
+            nack.nacks,
+            vec![NackPair {
+                packet_id: 0xaaaa,
+                lost_packets: 0
+            }]
+        );
+
+        let tests = vec![
+            (
+                "zero padding count",
+                Bytes::from_static(&[
+                    // v=2, p=1, FMT=1, PSFB, len=3
+                    0xa1, 0xce, 0x00, 0x03, // sender=0x902f9e2e
+                    0x90, 0x2f, 0x9e, 0x2e, // media=0x902f9e2e
+                    0x90, 0x2f, 0x9e, 0x2e, // padding
+                    0x00, 0x00, 0x00, 0x00,
+                ]),
+            ),
+            (
+                "padding count exceeds packet",
+                Bytes::from_static(&[
+                    // v=2, p=1, FMT=1, PSFB, len=3
+                    0xa1, 0xce, 0x00, 0x03, // sender=0x902f9e2e
+                    0x90, 0x2f, 0x9e, 0x2e, // media=0x902f9e2e
+                    0x90, 0x2f, 0x9e, 0x2e, // padding
+                    0x00, 0x00, 0x00, 0x10,
+                ]),
+            ),
+        ];
+
+        for (name, mut data) in tests {
+            let got = unmarshal(&mut data);
+            assert!(
+                matches!(got, Err(Error::WrongPadding)),
+                "Unmarshal {name}: err = {got:?}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_packet_equal() -> Result<()> {
+        let pli = PictureLossIndication {
+            sender_ssrc: 0x902f9e2e,
+            media_ssrc: 0xbc5e9a40,
+        };
+        let raw = RawPacket(pli.marshal()?);
+        let other = PictureLossIndication {
+            media_ssrc: 0,
+            ..pli.clone()
+        };
+
+        assert!(equal(&pli, &pli.clone()));
+        assert!(equal(&pli, &raw));
+        assert!(equal(&raw, &pli));
+        assert!(!equal(&pli, &other));
+        assert!(!equal(&raw, &other));
+
+        // the trait objects themselves only compare packets of the same type
+        let a: Box<dyn Packet + Send + Sync> = Box::new(pli);
+        let b: Box<dyn Packet + Send + Sync> = Box::new(raw);
+        assert!(a != b);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_packet_unmarshal_parse_mode() -> Result<()> {
+        let packets: Vec<Box<dyn Packet + Send + Sync>> = vec![
+            Box::new(ReceiverReport {
+                ssrc: 0x902f9e2e,
+                reports: vec![ReceptionReport {
+                    ssrc: 0xbc5e9a40,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            Box::new(SourceDescription {
+                chunks: vec![SourceDescriptionChunk {
+                    source: 0x902f9e2e,
+                    items: vec![SourceDescriptionItem {
+                        sdes_type: SdesType::SdesCname,
+                        text: Bytes::from_static(b"{9c00eb92-1afb-9d49-a47d-91f64eee69f5}"),
+                    }],
+                }],
+            }),
+            Box::new(PictureLossIndication {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0xbc5e9a40,
+            }),
+            Box::new(Goodbye {
+                sources: vec![0x902f9e2e],
+                reason: Bytes::from_static(b"bye"),
+            }),
+        ];
+        let data = marshal(&packets)?;
+        let got = unmarshal_with_parse_mode(&mut data.clone(), ParseMode::Strict)?;
+        assert!(got == packets, "Invalid packets");
+
+        // a BYE whose reason is padded with null octets, without the padding bit
+        let mut data = Bytes::from_static(&[
+            0x81, 0xcb, 0x00, 0x02, // v=2, p=0, count=1, BYE, len=2
+            0x90, 0x2f, 0x9e, 0x2e, // source=0x902f9e2e
+            0x02, 0x6f, 0x6b, 0x00, // reason="ok"
+        ]);
+        unmarshal_with_parse_mode(&mut data, ParseMode::Strict)?;
+
+        let tests = vec![
+            (
+                "trailing octets",
+                Bytes::from_static(&[
+                    0x81, 0xcb, 0x00, 0x03, // v=2, p=0, count=1, BYE, len=3
+                    0x90, 0x2f, 0x9e, 0x2e, // source=0x902f9e2e
+                    0x00, 0x00, 0x00, 0x00, // reason=""
+                    0x00, 0x00, 0x00, 0x00, //
+                ]),
+                Error::LengthMismatch,
+            ),
+            (
+                "reserved bits set",
+                Bytes::from_static(&[
+                    0x81, 0xcf, 0x00, 0x01, // v=2, p=0, count=1, XR, len=1
+                    0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                ]),
+                Error::CountMismatch,
+            ),
+            (
+                "unknown format",
+                Bytes::from_static(&[
+                    0x89, 0xce, 0x00, 0x02, // v=2, p=0, FMT=9, PSFB, len=2
+                    0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                    0xbc, 0x5e, 0x9a, 0x40, // media=0xbc5e9a40
+                ]),
+                Error::WrongFeedbackType,
+            ),
+            (
+                "unknown packet type",
+                Bytes::from_static(&[
+                    0x80, 0xc3, 0x00, 0x01, // v=2, p=0, count=0, PT=195, len=1
+                    0x90, 0x2f, 0x9e, 0x2e, //
+                ]),
+                Error::WrongType {
+                    packet_type: PacketType::Unsupported,
+                    count: 0,
+                },
+            ),
+        ];
+
+        for (name, data, want) in tests {
+            unmarshal_with_parse_mode(&mut data.clone(), ParseMode::Lenient)
+                .unwrap_or_else(|e| panic!("Unmarshal {name} lenient: {e:?}"));
+
+            let got = unmarshal_with_parse_mode(&mut data.clone(), ParseMode::Strict);
+            assert_eq!(got.err(), Some(want), "Unmarshal {name} strict");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_packet_unmarshal_ref() -> Result<()> {
+        let packets: Vec<Box<dyn Packet + Send + Sync>> = vec![
+            Box::new(SourceDescription {
+                chunks: vec![SourceDescriptionChunk {
+                    source: 0x902f9e2e,
+                    items: vec![SourceDescriptionItem {
+                        sdes_type: SdesType::SdesCname,
+                        text: Bytes::from_static(b"{9c00eb92-1afb-9d49-a47d-91f64eee69f5}"),
+                    }],
+                }],
+            }),
+            Box::new(Goodbye {
+                sources: vec![0x902f9e2e],
+                reason: Bytes::from_static(b"FOO"),
+            }),
+        ];
+        let data = marshal(&packets)?;
+        let range = data.as_ptr_range();
+
+        let decoded = unmarshal_ref(&data)?;
+        assert!(decoded == packets, "Invalid packets");
+
+        let sdes = decoded[0]
+            .as_any()
+            .downcast_ref::<SourceDescription>()
+            .expect("SourceDescription");
+        let text = &sdes.chunks[0].items[0].text;
+        assert!(range.contains(&text.as_ptr()), "SDES text was copied");
+
+        let bye = decoded[1]
+            .as_any()
+            .downcast_ref::<Goodbye>()
+            .expect("Goodbye");
+        assert!(
+            range.contains(&bye.reason.as_ptr()),
+            "BYE reason was copied"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_packet_unmarshal_enum() -> Result<()> {
+        let packets: Vec<Box<dyn Packet + Send + Sync>> = vec![
+            Box::new(ReceiverReport {
+                ssrc: 0x902f9e2e,
+                ..Default::default()
+            }),
+            Box::new(SourceDescription::with_cname(0x902f9e2e, "cname")),
+            Box::new(PictureLossIndication {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0xbc5e9a40,
+            }),
+            Box::new(UnknownFeedback {
+                packet_type: PacketType::TransportSpecificFeedback,
+                format: 31,
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0xbc5e9a40,
+                fci: Bytes::new(),
+            }),
+        ];
+        let data = marshal(&packets)?;
+
+        let decoded = unmarshal_enum(&mut data.clone())?;
+        assert_eq!(decoded.len(), packets.len());
+        for (got, want) in decoded.iter().zip(&packets) {
+            assert!(got.as_packet().equal(want.as_ref()), "{got:?} != {want:?}");
+        }
+
+        match &decoded[2] {
+            PacketEnum::PictureLossIndication(pli) => assert_eq!(pli.media_ssrc, 0xbc5e9a40),
+            p => panic!("unexpected packet {p:?}"),
+        }
+
+        assert_eq!(
+            unmarshal_enum(&mut Bytes::new()).unwrap_err(),
+            Error::InvalidHeader
+        );
+        assert_eq!(
+            unmarshal_enum(&mut data.slice(..data.len() - 4)).unwrap_err(),
+            Error::PacketTooShort {
+                packet_type: PacketType::TransportSpecificFeedback,
+                offset: 4,
+                expected: 12,
+                got: 8
+            }
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_packet_unmarshal_empty() -> Result<()> {
         let result = unmarshal(&mut Bytes::new());
@@ -234,7 +972,12 @@ mod test {
 
         let result = unmarshal(&mut data);
         if let Err(got) = result {
-            let want = Error::PacketTooShort;
+            let want = Error::PacketTooShort {
+                packet_type: PacketType::Goodbye,
+                offset: HEADER_LENGTH,
+                expected: HEADER_LENGTH + 400,
+                got: HEADER_LENGTH,
+            };
             assert_eq!(
                 got, want,
                 "Unmarshal(invalid_header_length) err = {got}, want {want}"
@@ -0,0 +1,253 @@
+use crate::application_defined::ApplicationDefined;
+use crate::error::Result;
+use crate::extended_report::ExtendedReport;
+use crate::goodbye::Goodbye;
+use crate::packet::Packet;
+use crate::payload_feedbacks::full_intra_request::FullIntraRequest;
+use crate::payload_feedbacks::layer_refresh_request::LayerRefreshRequest;
+use crate::payload_feedbacks::picture_loss_indication::PictureLossIndication;
+use crate::payload_feedbacks::receiver_estimated_maximum_bitrate::ReceiverEstimatedMaximumBitrate;
+use crate::payload_feedbacks::slice_loss_indication::SliceLossIndication;
+use crate::raw_packet::RawPacket;
+use crate::receiver_report::ReceiverReport;
+use crate::sender_report::SenderReport;
+use crate::source_description::SourceDescription;
+use crate::transport_feedbacks::rapid_resynchronization_request::RapidResynchronizationRequest;
+use crate::transport_feedbacks::temporary_maximum_media_stream_bitrate::{
+    TemporaryMaximumMediaStreamBitrateNotification, TemporaryMaximumMediaStreamBitrateRequest,
+};
+use crate::transport_feedbacks::transport_layer_cc::TransportLayerCc;
+use crate::transport_feedbacks::transport_layer_nack::TransportLayerNack;
+use crate::unknown_feedback::UnknownFeedback;
+
+/// PacketEnum holds any of the concrete RTCP packet types of this crate, as
+/// returned by `unmarshal_enum`.
+///
+/// With the `serde` feature enabled it is serialized as a tagged representation,
+/// e.g. `{"type": "PictureLossIndication", "packet": {"sender_ssrc": 1, ...}}`,
+/// which can stand in for the boxed packets returned by `unmarshal`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "packet"))]
+pub enum PacketEnum {
+    SenderReport(SenderReport),
+    ReceiverReport(ReceiverReport),
+    SourceDescription(SourceDescription),
+    Goodbye(Goodbye),
+    ApplicationDefined(ApplicationDefined),
+    TransportLayerNack(TransportLayerNack),
+    RapidResynchronizationRequest(RapidResynchronizationRequest),
+    TemporaryMaximumMediaStreamBitrateRequest(TemporaryMaximumMediaStreamBitrateRequest),
+    TemporaryMaximumMediaStreamBitrateNotification(TemporaryMaximumMediaStreamBitrateNotification),
+    TransportLayerCc(TransportLayerCc),
+    PictureLossIndication(PictureLossIndication),
+    SliceLossIndication(SliceLossIndication),
+    ReceiverEstimatedMaximumBitrate(ReceiverEstimatedMaximumBitrate),
+    FullIntraRequest(FullIntraRequest),
+    LayerRefreshRequest(LayerRefreshRequest),
+    ExtendedReport(ExtendedReport),
+    UnknownFeedback(UnknownFeedback),
+    RawPacket(RawPacket),
+}
+
+macro_rules! packet_enum_conversions {
+    ($($variant:ident),*) => {
+        impl PacketEnum {
+            /// from_packet converts a boxed packet into its PacketEnum variant.
+            /// Packets of types unknown to this crate are marshaled into a RawPacket.
+            pub fn from_packet(packet: &(dyn Packet + Send + Sync)) -> Result<Self> {
+                $(
+                    if let Some(p) = packet.as_any().downcast_ref::<$variant>() {
+                        return Ok(PacketEnum::$variant(p.clone()));
+                    }
+                )*
+
+                Ok(PacketEnum::RawPacket(RawPacket(packet.marshal()?)))
+            }
+
+            /// into_packet boxes the contained packet.
+            pub fn into_packet(self) -> Box<dyn Packet + Send + Sync> {
+                match self {
+                    $(PacketEnum::$variant(p) => Box::new(p),)*
+                }
+            }
+
+            /// as_packet returns the contained packet as a trait object.
+            pub fn as_packet(&self) -> &(dyn Packet + Send + Sync) {
+                match self {
+                    $(PacketEnum::$variant(p) => p,)*
+                }
+            }
+        }
+
+        $(
+            impl From<$variant> for PacketEnum {
+                fn from(p: $variant) -> Self {
+                    PacketEnum::$variant(p)
+                }
+            }
+        )*
+    };
+}
+
+packet_enum_conversions!(
+    SenderReport,
+    ReceiverReport,
+    SourceDescription,
+    Goodbye,
+    ApplicationDefined,
+    TransportLayerNack,
+    RapidResynchronizationRequest,
+    TemporaryMaximumMediaStreamBitrateRequest,
+    TemporaryMaximumMediaStreamBitrateNotification,
+    TransportLayerCc,
+    PictureLossIndication,
+    SliceLossIndication,
+    ReceiverEstimatedMaximumBitrate,
+    FullIntraRequest,
+    LayerRefreshRequest,
+    ExtendedReport,
+    UnknownFeedback,
+    RawPacket
+);
+
+/// from_packets converts boxed packets, e.g. the result of `unmarshal`, into PacketEnums.
+pub fn from_packets(packets: &[Box<dyn Packet + Send + Sync>]) -> Result<Vec<PacketEnum>> {
+    packets
+        .iter()
+        .map(|p| PacketEnum::from_packet(p.as_ref()))
+        .collect()
+}
+
+/// into_packets boxes each of the given PacketEnums, e.g. to pass them to `marshal`.
+pub fn into_packets(packets: Vec<PacketEnum>) -> Vec<Box<dyn Packet + Send + Sync>> {
+    packets.into_iter().map(PacketEnum::into_packet).collect()
+}
+
+#[cfg(feature = "serde")]
+pub(crate) mod serde_packets {
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    pub(crate) fn serialize<S>(
+        packets: &[Box<dyn Packet + Send + Sync>],
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        from_packets(packets)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> std::result::Result<Vec<Box<dyn Packet + Send + Sync>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let packets = Vec::<PacketEnum>::deserialize(deserializer)?;
+        Ok(into_packets(packets))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::header::PacketType;
+    use crate::packet::{marshal, unmarshal};
+    use crate::source_description::{SdesType, SourceDescriptionChunk, SourceDescriptionItem};
+
+    fn packets() -> Vec<Box<dyn Packet + Send + Sync>> {
+        vec![
+            Box::new(ReceiverReport {
+                ssrc: 0x902f9e2e,
+                ..Default::default()
+            }),
+            Box::new(SourceDescription {
+                chunks: vec![SourceDescriptionChunk {
+                    source: 0x902f9e2e,
+                    items: vec![SourceDescriptionItem {
+                        sdes_type: SdesType::SdesCname,
+                        text: Bytes::from_static(b"cname"),
+                    }],
+                }],
+            }),
+            Box::new(PictureLossIndication {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0xbc5e9a40,
+            }),
+            Box::new(UnknownFeedback {
+                packet_type: PacketType::TransportSpecificFeedback,
+                format: 31,
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0xbc5e9a40,
+                fci: Bytes::from_static(&[0x01, 0x02, 0x03, 0x04]),
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_packet_enum_conversion() -> Result<()> {
+        let packets = packets();
+
+        let enums = from_packets(&packets)?;
+        assert_eq!(
+            enums[2],
+            PacketEnum::PictureLossIndication(PictureLossIndication {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0xbc5e9a40,
+            })
+        );
+        assert!(enums[1].as_packet().equal(packets[1].as_ref()));
+
+        let boxed = into_packets(enums);
+        assert!(boxed == packets, "Invalid packets");
+
+        let mut data = marshal(&packets)?;
+        let decoded = unmarshal(&mut data)?;
+        assert_eq!(from_packets(&decoded)?, from_packets(&packets)?);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_packet_enum_serde() -> Result<()> {
+        use crate::compound_packet::CompoundPacket;
+        use crate::extended_report::{DLRRReport, DLRRReportBlock};
+
+        let enums = from_packets(&packets())?;
+        let json = serde_json::to_string(&enums).unwrap();
+        assert!(json.contains(r#""type":"PictureLossIndication""#), "{json}");
+        let decoded: Vec<PacketEnum> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, enums);
+
+        let compound = CompoundPacket(packets());
+        let json = serde_json::to_string(&compound).unwrap();
+        let decoded: CompoundPacket = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, compound);
+
+        let xr = ExtendedReport {
+            sender_ssrc: 1,
+            reports: vec![Box::new(DLRRReportBlock {
+                reports: vec![DLRRReport {
+                    ssrc: 2,
+                    last_rr: 3,
+                    dlrr: 4,
+                }],
+            })],
+        };
+        let json = serde_json::to_string(&PacketEnum::from(xr.clone())).unwrap();
+        assert!(json.contains(r#""type":"DLRR""#), "{json}");
+        let decoded: PacketEnum = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, PacketEnum::ExtendedReport(xr));
+
+        Ok(())
+    }
+}
@@ -55,7 +55,12 @@ fn test_full_intra_request_unmarshal() {
             "packet too short",
             Bytes::from_static(&[0x00, 0x00, 0x00, 0x00]),
             FullIntraRequest::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                offset: 0,
+                expected: 8,
+                got: 4,
+            }),
         ),
         (
             "invalid header",
@@ -76,7 +81,10 @@ fn test_full_intra_request_unmarshal() {
                 0x42, 0x00, 0x00, 0x00, // Seqno=0x42
             ]),
             FullIntraRequest::default(),
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::ReceiverReport,
+                count: 4,
+            }),
         ),
         (
             "wrong fmt",
@@ -88,7 +96,10 @@ fn test_full_intra_request_unmarshal() {
                 0x42, 0x00, 0x00, 0x00, // Seqno=0x42
             ]),
             FullIntraRequest::default(),
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                count: 2,
+            }),
         ),
     ];
 
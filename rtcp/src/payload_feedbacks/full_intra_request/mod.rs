@@ -16,6 +16,7 @@ type Result<T> = std::result::Result<T, util::Error>;
 
 /// A FIREntry is a (ssrc, seqno) pair, as carried by FullIntraRequest.
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FirEntry {
     pub ssrc: u32,
     pub sequence_number: u8,
@@ -25,6 +26,7 @@ pub struct FirEntry {
 /// in a video stream.  See RFC 5104 Section 3.5.1.  This is not for loss
 /// recovery, which should use PictureLossIndication (PLI) instead.
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FullIntraRequest {
     pub sender_ssrc: u32,
     pub media_ssrc: u32,
@@ -128,17 +130,33 @@ impl Unmarshal for FullIntraRequest {
     {
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < (HEADER_LENGTH + SSRC_LENGTH) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                offset: 0,
+                expected: HEADER_LENGTH + SSRC_LENGTH,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let h = Header::unmarshal(raw_packet)?;
 
         if raw_packet_len < (HEADER_LENGTH + (4 * h.length) as usize) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: h.packet_type,
+                offset: HEADER_LENGTH,
+                expected: HEADER_LENGTH + (4 * h.length) as usize,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         if h.packet_type != PacketType::PayloadSpecificFeedback || h.count != FORMAT_FIR {
-            return Err(Error::WrongType.into());
+            return Err(Error::WrongType {
+                packet_type: h.packet_type,
+                count: h.count,
+            }
+            .into());
         }
 
         let sender_ssrc = raw_packet.get_u32();
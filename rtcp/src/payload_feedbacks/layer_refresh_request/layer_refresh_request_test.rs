@@ -0,0 +1,207 @@
+use bytes::Bytes;
+
+use super::*;
+
+#[test]
+fn test_layer_refresh_request_unmarshal() {
+    let tests = vec![
+        (
+            "valid",
+            Bytes::from_static(&[
+                0x8a, 0xce, 0x00, 0x05, // v=2, p=0, FMT=10, PSFB, len=5
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0x00, 0x00, 0x00, 0x00, // media=0
+                0x12, 0x34, 0x56, 0x78, // ssrc=0x12345678
+                0x42, 0xe0, 0x00, 0x00, // Seqno=0x42, C=1, PT=96
+                0x02, 0x01, 0x01, 0x00, // TTID=2, TLID=1, CTID=1, CLID=0
+            ]),
+            LayerRefreshRequest {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0,
+                lrr: vec![LrrEntry {
+                    ssrc: 0x12345678,
+                    sequence_number: 0x42,
+                    payload_type: 96,
+                    target_layer: LayerIndex {
+                        temporal_id: 2,
+                        layer_id: 1,
+                    },
+                    current_layer: Some(LayerIndex {
+                        temporal_id: 1,
+                        layer_id: 0,
+                    }),
+                }],
+            },
+            None,
+        ),
+        (
+            "without current layer",
+            Bytes::from_static(&[
+                0x8a, 0xce, 0x00, 0x05, // v=2, p=0, FMT=10, PSFB, len=5
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0x00, 0x00, 0x00, 0x00, // media=0
+                0x12, 0x34, 0x56, 0x78, // ssrc=0x12345678
+                0x07, 0x60, 0x00, 0x00, // Seqno=7, C=0, PT=96
+                0x00, 0x02, 0x00, 0x00, // TTID=0, TLID=2
+            ]),
+            LayerRefreshRequest {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0,
+                lrr: vec![LrrEntry {
+                    ssrc: 0x12345678,
+                    sequence_number: 7,
+                    payload_type: 96,
+                    target_layer: LayerIndex {
+                        temporal_id: 0,
+                        layer_id: 2,
+                    },
+                    current_layer: None,
+                }],
+            },
+            None,
+        ),
+        (
+            "short entry",
+            Bytes::from_static(&[
+                0x8a, 0xce, 0x00, 0x04, // v=2, p=0, FMT=10, PSFB, len=4
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0x00, 0x00, 0x00, 0x00, // media=0
+                0x12, 0x34, 0x56, 0x78, // ssrc=0x12345678
+                0x07, 0x60, 0x00, 0x00, // Seqno=7, C=0, PT=96
+            ]),
+            LayerRefreshRequest::default(),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                offset: 12,
+                expected: 24,
+                got: 20,
+            }),
+        ),
+        (
+            "wrong fmt",
+            Bytes::from_static(&[
+                0x84, 0xce, 0x00, 0x02, // v=2, p=0, FMT=4, PSFB, len=2
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0x00, 0x00, 0x00, 0x00, // media=0
+            ]),
+            LayerRefreshRequest::default(),
+            Some(Error::WrongType {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                count: 4,
+            }),
+        ),
+    ];
+
+    for (name, mut data, want, want_error) in tests {
+        let got = LayerRefreshRequest::unmarshal(&mut data);
+
+        assert_eq!(
+            got.is_err(),
+            want_error.is_some(),
+            "Unmarshal {name}: err = {got:?}, want {want_error:?}"
+        );
+
+        if let Some(err) = want_error {
+            let got_err = got.err().unwrap();
+            assert_eq!(
+                err, got_err,
+                "Unmarshal {name}: err = {got_err:?}, want {err:?}",
+            );
+        } else {
+            let actual = got.unwrap();
+            assert_eq!(
+                actual, want,
+                "Unmarshal {name}: got {actual:?}, want {want:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_layer_refresh_request_round_trip() {
+    let entry = LrrEntry {
+        ssrc: 3,
+        sequence_number: 42,
+        payload_type: 100,
+        target_layer: LayerIndex {
+            temporal_id: 7,
+            layer_id: 255,
+        },
+        current_layer: None,
+    };
+
+    let tests = vec![
+        (
+            "valid",
+            LayerRefreshRequest {
+                sender_ssrc: 1,
+                media_ssrc: 0,
+                lrr: vec![
+                    entry.clone(),
+                    LrrEntry {
+                        ssrc: 4,
+                        current_layer: Some(LayerIndex::default()),
+                        ..entry.clone()
+                    },
+                ],
+            },
+            None,
+        ),
+        (
+            "payload type too large",
+            LayerRefreshRequest {
+                sender_ssrc: 1,
+                media_ssrc: 0,
+                lrr: vec![LrrEntry {
+                    payload_type: 128,
+                    ..entry.clone()
+                }],
+            },
+            Some(Error::WrongPayloadType),
+        ),
+        (
+            "temporal id too large",
+            LayerRefreshRequest {
+                sender_ssrc: 1,
+                media_ssrc: 0,
+                lrr: vec![LrrEntry {
+                    current_layer: Some(LayerIndex {
+                        temporal_id: 8,
+                        layer_id: 0,
+                    }),
+                    ..entry
+                }],
+            },
+            Some(Error::InvalidLayerIndex),
+        ),
+    ];
+
+    for (name, want, want_error) in tests {
+        let got = want.marshal();
+
+        assert_eq!(
+            got.is_ok(),
+            want_error.is_none(),
+            "Marshal {name}: err = {got:?}, want {want_error:?}"
+        );
+
+        if let Some(err) = want_error {
+            let got_err = got.err().unwrap();
+            assert_eq!(
+                err, got_err,
+                "Marshal {name}: err = {got_err:?}, want {err:?}",
+            );
+        } else {
+            let mut data = got.ok().unwrap();
+            let packets =
+                crate::packet::unmarshal(&mut data).unwrap_or_else(|_| panic!("Unmarshal {name}"));
+            let actual = packets[0].as_any().downcast_ref::<LayerRefreshRequest>();
+
+            assert_eq!(
+                actual,
+                Some(&want),
+                "{name} round trip: got {actual:?}, want {want:?}"
+            )
+        }
+    }
+}
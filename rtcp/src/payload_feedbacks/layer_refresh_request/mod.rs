@@ -0,0 +1,280 @@
+#[cfg(test)]
+mod layer_refresh_request_test;
+
+use std::any::Any;
+use std::fmt;
+
+use bytes::{Buf, BufMut};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+use crate::header::*;
+use crate::packet::*;
+use crate::util::*;
+
+type Result<T> = std::result::Result<T, util::Error>;
+
+const LRR_OFFSET: usize = 8;
+const LRR_ENTRY_LENGTH: usize = 12;
+const TEMPORAL_ID_MAX: u8 = 7;
+const PAYLOAD_TYPE_MAX: u8 = 127;
+
+/// A LayerIndex identifies a layer of a scalable or simulcast stream by its
+/// temporal layer ID and its layer ID, whose meaning depends on the payload format.
+#[derive(Debug, PartialEq, Eq, Default, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayerIndex {
+    /// The 3-bit temporal layer ID
+    pub temporal_id: u8,
+    /// The layer ID, e.g. the spatial or quality layer
+    pub layer_id: u8,
+}
+
+/// A LrrEntry requests a refresh of one layer of the stream from an SSRC,
+/// as carried by LayerRefreshRequest.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LrrEntry {
+    pub ssrc: u32,
+    pub sequence_number: u8,
+    /// The RTP payload type for which the refresh is requested
+    pub payload_type: u8,
+    /// The layer the receiver wants to be refreshed
+    pub target_layer: LayerIndex,
+    /// The layer the receiver currently decodes, if it knows it
+    pub current_layer: Option<LayerIndex>,
+}
+
+/// The LayerRefreshRequest packet is used by a receiver of a scalable or
+/// simulcast stream to request a refresh of specific layers, without
+/// requesting a full refresh as FullIntraRequest would.
+/// See draft-ietf-avtext-lrr section 3.
+///
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                              SSRC                             |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// | Seq nr.       |C| Payload Type| Reserved                      |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// | RES     | TTID| TLID          | RES     | CTID| CLID          |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayerRefreshRequest {
+    pub sender_ssrc: u32,
+    pub media_ssrc: u32,
+    pub lrr: Vec<LrrEntry>,
+}
+
+impl fmt::Display for LayerRefreshRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = format!(
+            "LayerRefreshRequest {} {}",
+            self.sender_ssrc, self.media_ssrc
+        );
+        for e in &self.lrr {
+            out += format!(
+                " ({} {} pt {} target {}/{}",
+                e.ssrc,
+                e.sequence_number,
+                e.payload_type,
+                e.target_layer.temporal_id,
+                e.target_layer.layer_id
+            )
+            .as_str();
+            if let Some(c) = e.current_layer {
+                out += format!(" current {}/{}", c.temporal_id, c.layer_id).as_str();
+            }
+            out += ")";
+        }
+        write!(f, "{out}")
+    }
+}
+
+impl Packet for LayerRefreshRequest {
+    fn header(&self) -> Header {
+        Header {
+            padding: get_padding_size(self.raw_size()) != 0,
+            count: FORMAT_LRR,
+            packet_type: PacketType::PayloadSpecificFeedback,
+            length: ((self.marshal_size() / 4) - 1) as u16,
+        }
+    }
+
+    /// destination_ssrc returns an array of SSRC values that this packet refers to.
+    fn destination_ssrc(&self) -> Vec<u32> {
+        self.lrr.iter().map(|e| e.ssrc).collect()
+    }
+
+    fn raw_size(&self) -> usize {
+        HEADER_LENGTH + LRR_OFFSET + self.lrr.len() * LRR_ENTRY_LENGTH
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+
+    fn equal(&self, other: &(dyn Packet + Send + Sync)) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<LayerRefreshRequest>()
+            .map_or(false, |a| self == a)
+    }
+
+    fn cloned(&self) -> Box<dyn Packet + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+impl MarshalSize for LayerRefreshRequest {
+    fn marshal_size(&self) -> usize {
+        let l = self.raw_size();
+        // align to 32-bit boundary
+        l + get_padding_size(l)
+    }
+}
+
+impl Marshal for LayerRefreshRequest {
+    /// Marshal encodes the LayerRefreshRequest
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize> {
+        if buf.remaining_mut() < self.marshal_size() {
+            return Err(Error::BufferTooShort.into());
+        }
+
+        for e in &self.lrr {
+            if e.payload_type > PAYLOAD_TYPE_MAX {
+                return Err(Error::WrongPayloadType.into());
+            }
+            let mut layers = std::iter::once(e.target_layer).chain(e.current_layer);
+            if layers.any(|l| l.temporal_id > TEMPORAL_ID_MAX) {
+                return Err(Error::InvalidLayerIndex.into());
+            }
+        }
+
+        let h = self.header();
+        let n = h.marshal_to(buf)?;
+        buf = &mut buf[n..];
+
+        buf.put_u32(self.sender_ssrc);
+        buf.put_u32(self.media_ssrc);
+
+        for e in &self.lrr {
+            buf.put_u32(e.ssrc);
+            buf.put_u8(e.sequence_number);
+            let c = if e.current_layer.is_some() { 0x80 } else { 0 };
+            buf.put_u8(c | e.payload_type);
+            buf.put_u16(0);
+
+            let current = e.current_layer.unwrap_or_default();
+            buf.put_u8(e.target_layer.temporal_id);
+            buf.put_u8(e.target_layer.layer_id);
+            buf.put_u8(current.temporal_id);
+            buf.put_u8(current.layer_id);
+        }
+
+        if h.padding {
+            put_padding(buf, self.raw_size());
+        }
+
+        Ok(self.marshal_size())
+    }
+}
+
+impl Unmarshal for LayerRefreshRequest {
+    /// Unmarshal decodes the LayerRefreshRequest
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let raw_packet_len = raw_packet.remaining();
+        if raw_packet_len < (HEADER_LENGTH + LRR_OFFSET) {
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                offset: 0,
+                expected: HEADER_LENGTH + LRR_OFFSET,
+                got: raw_packet_len,
+            }
+            .into());
+        }
+
+        let h = Header::unmarshal(raw_packet)?;
+
+        let end = HEADER_LENGTH + (h.length as usize) * 4;
+        if raw_packet_len < end || end < HEADER_LENGTH + LRR_OFFSET {
+            return Err(Error::PacketTooShort {
+                packet_type: h.packet_type,
+                offset: HEADER_LENGTH,
+                expected: end.max(HEADER_LENGTH + LRR_OFFSET),
+                got: raw_packet_len.min(end),
+            }
+            .into());
+        }
+
+        if h.packet_type != PacketType::PayloadSpecificFeedback || h.count != FORMAT_LRR {
+            return Err(Error::WrongType {
+                packet_type: h.packet_type,
+                count: h.count,
+            }
+            .into());
+        }
+
+        let sender_ssrc = raw_packet.get_u32();
+        let media_ssrc = raw_packet.get_u32();
+
+        let mut i = HEADER_LENGTH + LRR_OFFSET;
+        let mut lrr = vec![];
+        while i < end {
+            if i + LRR_ENTRY_LENGTH > end {
+                return Err(Error::PacketTooShort {
+                    packet_type: h.packet_type,
+                    offset: i,
+                    expected: i + LRR_ENTRY_LENGTH,
+                    got: end,
+                }
+                .into());
+            }
+
+            let ssrc = raw_packet.get_u32();
+            let sequence_number = raw_packet.get_u8();
+            let b = raw_packet.get_u8();
+            raw_packet.get_u16();
+
+            let target_layer = LayerIndex {
+                temporal_id: raw_packet.get_u8() & TEMPORAL_ID_MAX,
+                layer_id: raw_packet.get_u8(),
+            };
+            let current_layer = LayerIndex {
+                temporal_id: raw_packet.get_u8() & TEMPORAL_ID_MAX,
+                layer_id: raw_packet.get_u8(),
+            };
+
+            lrr.push(LrrEntry {
+                ssrc,
+                sequence_number,
+                payload_type: b & PAYLOAD_TYPE_MAX,
+                target_layer,
+                current_layer: if b & 0x80 != 0 {
+                    Some(current_layer)
+                } else {
+                    None
+                },
+            });
+
+            i += LRR_ENTRY_LENGTH;
+        }
+
+        if
+        /*h.padding &&*/
+        raw_packet.has_remaining() {
+            raw_packet.advance(raw_packet.remaining());
+        }
+
+        Ok(LayerRefreshRequest {
+            sender_ssrc,
+            media_ssrc,
+            lrr,
+        })
+    }
+}
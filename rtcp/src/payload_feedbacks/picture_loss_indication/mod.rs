@@ -18,6 +18,7 @@ const PLI_LENGTH: usize = 2;
 
 /// The PictureLossIndication packet informs the encoder about the loss of an undefined amount of coded video data belonging to one or more pictures
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PictureLossIndication {
     /// SSRC of sender
     pub sender_ssrc: u32,
@@ -116,12 +117,22 @@ impl Unmarshal for PictureLossIndication {
     {
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < (HEADER_LENGTH + (SSRC_LENGTH * 2)) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                offset: 0,
+                expected: HEADER_LENGTH + (SSRC_LENGTH * 2),
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let h = Header::unmarshal(raw_packet)?;
         if h.packet_type != PacketType::PayloadSpecificFeedback || h.count != FORMAT_PLI {
-            return Err(Error::WrongType.into());
+            return Err(Error::WrongType {
+                packet_type: h.packet_type,
+                count: h.count,
+            }
+            .into());
         }
 
         let sender_ssrc = raw_packet.get_u32();
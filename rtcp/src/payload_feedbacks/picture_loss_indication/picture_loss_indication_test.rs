@@ -22,7 +22,12 @@ fn test_picture_loss_indication_unmarshal() {
             "packet too short",
             Bytes::from_static(&[0x81, 0xce, 0x00, 0x00]),
             PictureLossIndication::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                offset: 0,
+                expected: 12,
+                got: 4,
+            }),
         ),
         (
             "invalid header",
@@ -40,7 +45,10 @@ fn test_picture_loss_indication_unmarshal() {
                 0x4b, 0xc4, 0xfc, 0xb4, // ssrc=0x4bc4fcb4
             ]),
             PictureLossIndication::default(),
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::ReceiverReport,
+                count: 1,
+            }),
         ),
         (
             "wrong fmt",
@@ -50,7 +58,10 @@ fn test_picture_loss_indication_unmarshal() {
                 0x4b, 0xc4, 0xfc, 0xb4, // ssrc=0x4bc4fcb4
             ]),
             PictureLossIndication::default(),
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::ReceiverReport,
+                count: 2,
+            }),
         ),
     ];
 
@@ -17,6 +17,7 @@ type Result<T> = std::result::Result<T, util::Error>;
 /// ReceiverEstimatedMaximumBitrate contains the receiver's estimated maximum bitrate.
 /// see: https://tools.ietf.org/html/draft-alvestrand-rmcat-remb-03
 #[derive(Debug, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReceiverEstimatedMaximumBitrate {
     /// SSRC of sender
     pub sender_ssrc: u32,
@@ -30,10 +31,87 @@ pub struct ReceiverEstimatedMaximumBitrate {
 
 const REMB_OFFSET: usize = 16;
 
+const BITRATE_MAX: f32 = 2.417_842_4e24; //0x3FFFFp+63;
+const BITRATE_MANTISSA_BITS: u32 = 18;
+const BITRATE_EXP_MAX: u8 = (1 << 6) - 1;
+
 /// Keep a table of powers to units for fast conversion.
 const BIT_UNITS: [&str; 7] = ["b", "Kb", "Mb", "Gb", "Tb", "Pb", "Eb"];
 const UNIQUE_IDENTIFIER: [u8; 4] = [b'R', b'E', b'M', b'B'];
 
+impl ReceiverEstimatedMaximumBitrate {
+    /// new creates a REMB packet whose bitrate is rounded to the closest value
+    /// representable by the 6-bit exponent and 18-bit mantissa on the wire, so
+    /// that the packet marshals without any further loss. Bitrates above the
+    /// representable maximum are clamped to it.
+    pub fn new(sender_ssrc: u32, bitrate: f32, ssrcs: Vec<u32>) -> Result<Self> {
+        let (exp, mantissa) = Self::round_bitrate(bitrate)?;
+        Ok(ReceiverEstimatedMaximumBitrate {
+            sender_ssrc,
+            bitrate: Self::bitrate_from_exp_mantissa(exp, mantissa),
+            ssrcs,
+        })
+    }
+
+    /// effective_bitrate returns the bitrate that is actually sent on the wire
+    /// once `bitrate` is truncated to its exponent/mantissa representation.
+    pub fn effective_bitrate(&self) -> Result<f32> {
+        let (exp, mantissa) = Self::bitrate_exp_mantissa(self.bitrate)?;
+        Ok(Self::bitrate_from_exp_mantissa(exp, mantissa))
+    }
+
+    /// is_lossless reports whether `bitrate` marshals without truncation.
+    pub fn is_lossless(&self) -> bool {
+        matches!(self.effective_bitrate(), Ok(bitrate) if bitrate == self.bitrate)
+    }
+
+    /// bitrate_exp_mantissa splits a bitrate into the exponent and mantissa
+    /// written on the wire, rounding down as marshal does.
+    pub fn bitrate_exp_mantissa(bitrate: f32) -> Result<(u8, u32)> {
+        if bitrate.is_nan() || bitrate < 0.0 {
+            return Err(Error::InvalidBitrate.into());
+        }
+
+        let mut bitrate = bitrate.min(BITRATE_MAX);
+        let mut exp = 0;
+        while bitrate >= (1 << BITRATE_MANTISSA_BITS) as f32 {
+            bitrate /= 2.0;
+            exp += 1;
+        }
+
+        if exp > BITRATE_EXP_MAX {
+            return Err(Error::InvalidBitrate.into());
+        }
+
+        Ok((exp, bitrate.floor() as u32))
+    }
+
+    /// bitrate_from_exp_mantissa computes `mantissa * 2^exp`.
+    pub fn bitrate_from_exp_mantissa(exp: u8, mantissa: u32) -> f32 {
+        mantissa as f32 * 2f32.powi(exp as i32)
+    }
+
+    fn round_bitrate(bitrate: f32) -> Result<(u8, u32)> {
+        let (mut exp, mut mantissa) = Self::bitrate_exp_mantissa(bitrate)?;
+
+        if bitrate.min(BITRATE_MAX) - Self::bitrate_from_exp_mantissa(exp, mantissa)
+            >= 2f32.powi(exp as i32) / 2.0
+        {
+            mantissa += 1;
+            if mantissa == 1 << BITRATE_MANTISSA_BITS {
+                if exp == BITRATE_EXP_MAX {
+                    mantissa -= 1;
+                } else {
+                    mantissa >>= 1;
+                    exp += 1;
+                }
+            }
+        }
+
+        Ok((exp, mantissa))
+    }
+}
+
 /// String prints the REMB packet in a human-readable format.
 impl fmt::Display for ReceiverEstimatedMaximumBitrate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -104,8 +182,6 @@ impl MarshalSize for ReceiverEstimatedMaximumBitrate {
 impl Marshal for ReceiverEstimatedMaximumBitrate {
     /// Marshal serializes the packet and returns a byte slice.
     fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize> {
-        const BITRATE_MAX: f32 = 2.417_842_4e24; //0x3FFFFp+63;
-
         /*
             0                   1                   2                   3
             0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
@@ -141,31 +217,12 @@ impl Marshal for ReceiverEstimatedMaximumBitrate {
         // Write the length of the ssrcs to follow at the end
         buf.put_u8(self.ssrcs.len() as u8);
 
-        let mut exp = 0;
-        let mut bitrate = self.bitrate;
-        if bitrate >= BITRATE_MAX {
-            bitrate = BITRATE_MAX
-        }
-
-        if bitrate < 0.0 {
-            return Err(Error::InvalidBitrate.into());
-        }
-
-        while bitrate >= (1 << 18) as f32 {
-            bitrate /= 2.0;
-            exp += 1;
-        }
-
-        if exp >= (1 << 6) {
-            return Err(Error::InvalidBitrate.into());
-        }
-
-        let mantissa = bitrate.floor() as u32;
+        let (exp, mantissa) = Self::bitrate_exp_mantissa(self.bitrate)?;
 
         // We can't quite use the binary package because
         // a) it's a uint24 and b) the exponent is only 6-bits
         // Just trust me; this is big-endian encoding.
-        buf.put_u8((exp << 2) as u8 | (mantissa >> 16) as u8);
+        buf.put_u8((exp << 2) | (mantissa >> 16) as u8);
         buf.put_u8((mantissa >> 8) as u8);
         buf.put_u8(mantissa as u8);
 
@@ -192,7 +249,13 @@ impl Unmarshal for ReceiverEstimatedMaximumBitrate {
         let raw_packet_len = raw_packet.remaining();
         // 20 bytes is the size of the packet with no SSRCs
         if raw_packet_len < 20 {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                offset: 0,
+                expected: 20,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         const MANTISSA_MAX: u32 = 0x7FFFFF;
@@ -218,7 +281,11 @@ impl Unmarshal for ReceiverEstimatedMaximumBitrate {
 
         if header.packet_type != PacketType::PayloadSpecificFeedback || header.count != FORMAT_REMB
         {
-            return Err(Error::WrongType.into());
+            return Err(Error::WrongType {
+                packet_type: header.packet_type,
+                count: header.count,
+            }
+            .into());
         }
 
         let sender_ssrc = raw_packet.get_u32();
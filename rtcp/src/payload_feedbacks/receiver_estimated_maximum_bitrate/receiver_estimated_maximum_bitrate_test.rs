@@ -119,3 +119,56 @@ fn test_receiver_estimated_maximum_bitrate_overflow() {
     let packet = ReceiverEstimatedMaximumBitrate::unmarshal(&mut input).unwrap();
     assert_eq!(packet.bitrate, f32::from_bits(0x62800000));
 }
+
+#[test]
+fn test_receiver_estimated_maximum_bitrate_rounding() {
+    let tests = vec![
+        // (requested, rounded)
+        (1.0, 1.0),
+        (262143.0, 262143.0),
+        // 262144 = 2^18 needs exp=1, mantissa=131072
+        (262144.0, 262144.0),
+        // 262145 is 0.5 past 131072 * 2, rounds up to the next multiple of 2
+        (262145.0, 262146.0),
+        // 8927201 lies between 139487 * 64 and 139488 * 64, closer to the latter
+        (8927201.0, 8927232.0),
+        (8927190.0, 8927168.0),
+        // rounding up may carry into the exponent
+        (524287.0, 524288.0),
+        (f32::MAX, 2.417_842_4e24),
+    ];
+
+    for (bitrate, want) in tests {
+        let remb = ReceiverEstimatedMaximumBitrate::new(1, bitrate, vec![2]).unwrap();
+        assert_eq!(remb.bitrate, want, "new({bitrate})");
+        assert!(remb.is_lossless(), "new({bitrate}) is lossless");
+
+        let mut data = remb.marshal().unwrap();
+        let decoded = ReceiverEstimatedMaximumBitrate::unmarshal(&mut data).unwrap();
+        assert_eq!(decoded, remb, "new({bitrate}) round trip");
+    }
+
+    assert!(ReceiverEstimatedMaximumBitrate::new(1, -1.0, vec![]).is_err());
+    assert!(ReceiverEstimatedMaximumBitrate::new(1, f32::NAN, vec![]).is_err());
+}
+
+#[test]
+fn test_receiver_estimated_maximum_bitrate_effective_bitrate() {
+    let remb = ReceiverEstimatedMaximumBitrate {
+        sender_ssrc: 1,
+        bitrate: 8927199.0,
+        ssrcs: vec![],
+    };
+
+    // marshal truncates to 139487 * 2^6
+    assert_eq!(remb.effective_bitrate().unwrap(), 8927168.0);
+    assert!(!remb.is_lossless());
+    assert_eq!(
+        ReceiverEstimatedMaximumBitrate::bitrate_exp_mantissa(remb.bitrate).unwrap(),
+        (6, 139487)
+    );
+
+    let mut data = remb.marshal().unwrap();
+    let decoded = ReceiverEstimatedMaximumBitrate::unmarshal(&mut data).unwrap();
+    assert_eq!(decoded.bitrate, remb.effective_bitrate().unwrap());
+}
@@ -16,10 +16,14 @@ type Result<T> = std::result::Result<T, util::Error>;
 
 const SLI_LENGTH: usize = 2;
 const SLI_OFFSET: usize = 8;
+const SLI_FIRST_MAX: u16 = (1 << 13) - 1;
+const SLI_NUMBER_MAX: u16 = (1 << 13) - 1;
+const SLI_PICTURE_MAX: u8 = (1 << 6) - 1;
 
 /// SLIEntry represents a single entry to the SLI packet's
 /// list of lost slices.
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SliEntry {
     /// ID of first lost slice
     pub first: u16,
@@ -29,8 +33,36 @@ pub struct SliEntry {
     pub picture: u8,
 }
 
+impl SliEntry {
+    /// new creates an SliEntry, returning an error if `first` or `number` does not
+    /// fit in 13 bits, or `picture` in 6 bits.
+    pub fn new(first: u16, number: u16, picture: u8) -> Result<Self> {
+        let e = SliEntry {
+            first,
+            number,
+            picture,
+        };
+        e.validate()?;
+
+        Ok(e)
+    }
+
+    /// validate checks that the fields of the entry fit in their widths.
+    pub fn validate(&self) -> Result<()> {
+        if self.first > SLI_FIRST_MAX
+            || self.number > SLI_NUMBER_MAX
+            || self.picture > SLI_PICTURE_MAX
+        {
+            return Err(Error::InvalidSliEntry.into());
+        }
+
+        Ok(())
+    }
+}
+
 /// The SliceLossIndication packet informs the encoder about the loss of a picture slice
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SliceLossIndication {
     /// SSRC of sender
     pub sender_ssrc: u32,
@@ -40,13 +72,38 @@ pub struct SliceLossIndication {
     pub sli_entries: Vec<SliEntry>,
 }
 
+impl SliceLossIndication {
+    /// new creates a SliceLossIndication reporting the loss of `number` slices
+    /// starting at `first` in the picture `picture` of the media source.
+    pub fn new(
+        sender_ssrc: u32,
+        media_ssrc: u32,
+        first: u16,
+        number: u16,
+        picture: u8,
+    ) -> Result<Self> {
+        Ok(SliceLossIndication {
+            sender_ssrc,
+            media_ssrc,
+            sli_entries: vec![SliEntry::new(first, number, picture)?],
+        })
+    }
+}
+
 impl fmt::Display for SliceLossIndication {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "SliceLossIndication {:x} {:x} {:?}",
-            self.sender_ssrc, self.media_ssrc, self.sli_entries,
-        )
+        let mut out = format!(
+            "SliceLossIndication {:x} {:x}",
+            self.sender_ssrc, self.media_ssrc
+        );
+        for e in &self.sli_entries {
+            out += format!(
+                " (first {} number {} picture {})",
+                e.first, e.number, e.picture
+            )
+            .as_str();
+        }
+        write!(f, "{out}")
     }
 }
 
@@ -56,7 +113,7 @@ impl Packet for SliceLossIndication {
         Header {
             padding: get_padding_size(self.raw_size()) != 0,
             count: FORMAT_SLI,
-            packet_type: PacketType::TransportSpecificFeedback,
+            packet_type: PacketType::PayloadSpecificFeedback,
             length: ((self.marshal_size() / 4) - 1) as u16,
         }
     }
@@ -100,6 +157,9 @@ impl Marshal for SliceLossIndication {
         if (self.sli_entries.len() + SLI_LENGTH) as u8 > std::u8::MAX {
             return Err(Error::TooManyReports.into());
         }
+        for e in &self.sli_entries {
+            e.validate()?;
+        }
         if buf.remaining_mut() < self.marshal_size() {
             return Err(Error::BufferTooShort.into());
         }
@@ -136,17 +196,33 @@ impl Unmarshal for SliceLossIndication {
     {
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < (HEADER_LENGTH + SSRC_LENGTH) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                offset: 0,
+                expected: HEADER_LENGTH + SSRC_LENGTH,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let h = Header::unmarshal(raw_packet)?;
 
         if raw_packet_len < (HEADER_LENGTH + (4 * h.length as usize)) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: h.packet_type,
+                offset: HEADER_LENGTH,
+                expected: HEADER_LENGTH + (4 * h.length as usize),
+                got: raw_packet_len,
+            }
+            .into());
         }
 
-        if h.packet_type != PacketType::TransportSpecificFeedback || h.count != FORMAT_SLI {
-            return Err(Error::WrongType.into());
+        if h.packet_type != PacketType::PayloadSpecificFeedback || h.count != FORMAT_SLI {
+            return Err(Error::WrongType {
+                packet_type: h.packet_type,
+                count: h.count,
+            }
+            .into());
         }
 
         let sender_ssrc = raw_packet.get_u32();
@@ -8,7 +8,7 @@ fn test_slice_loss_indication_unmarshal() {
         (
             "valid",
             Bytes::from_static(&[
-                0x82u8, 0xcd, 0x0, 0x3, // SliceLossIndication
+                0x82u8, 0xce, 0x0, 0x3, // SliceLossIndication
                 0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
                 0x90, 0x2f, 0x9e, 0x2e, // media=0x902f9e2e
                 0x55, 0x50, 0x00, 0x2C, // nack 0xAAAA, 0x5555
@@ -27,12 +27,31 @@ fn test_slice_loss_indication_unmarshal() {
         (
             "short report",
             Bytes::from_static(&[
-                0x82, 0xcd, 0x0, 0x2, // ssrc=0x902f9e2e
+                0x82, 0xce, 0x0, 0x2, // ssrc=0x902f9e2e
                 0x90, 0x2f, 0x9e, 0x2e,
                 // report ends early
             ]),
             SliceLossIndication::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                offset: 4,
+                expected: 12,
+                got: 8,
+            }),
+        ),
+        (
+            "transport layer feedback",
+            Bytes::from_static(&[
+                0x82u8, 0xcd, 0x0, 0x3, // not payload-specific feedback
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0x90, 0x2f, 0x9e, 0x2e, // media=0x902f9e2e
+                0x55, 0x50, 0x00, 0x2C, // nack 0xAAAA, 0x5555
+            ]),
+            SliceLossIndication::default(),
+            Some(Error::WrongType {
+                packet_type: PacketType::TransportSpecificFeedback,
+                count: 2,
+            }),
         ),
         (
             "wrong type",
@@ -48,13 +67,21 @@ fn test_slice_loss_indication_unmarshal() {
                 0x0, 0x2, 0x4a, 0x79,
             ]),
             SliceLossIndication::default(),
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::SenderReport,
+                count: 1,
+            }),
         ),
         (
             "nil",
             Bytes::from_static(&[]),
             SliceLossIndication::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                offset: 0,
+                expected: 8,
+                got: 0,
+            }),
         ),
     ];
 
@@ -133,3 +160,43 @@ fn test_slice_loss_indication_roundtrip() {
         }
     }
 }
+
+#[test]
+fn test_slice_loss_indication_new() {
+    let sli = SliceLossIndication::new(0x902f9e2e, 0x902f9e2e, 0x1FFF, 0x1FFF, 0x3F).unwrap();
+    assert_eq!(
+        sli.sli_entries,
+        vec![SliEntry {
+            first: 0x1FFF,
+            number: 0x1FFF,
+            picture: 0x3F,
+        }]
+    );
+
+    let tests = vec![
+        ("first", 0x2000, 0, 0),
+        ("number", 0, 0x2000, 0),
+        ("picture", 0, 0, 0x40),
+    ];
+
+    for (name, first, number, picture) in tests {
+        let got = SliceLossIndication::new(1, 2, first, number, picture);
+        assert_eq!(Error::InvalidSliEntry, got.unwrap_err(), "new {name}");
+
+        // the fields are rejected rather than truncated on marshal too
+        let sli = SliceLossIndication {
+            sender_ssrc: 1,
+            media_ssrc: 2,
+            sli_entries: vec![SliEntry {
+                first,
+                number,
+                picture,
+            }],
+        };
+        assert_eq!(
+            Error::InvalidSliEntry,
+            sli.marshal().unwrap_err(),
+            "marshal {name}"
+        );
+    }
+}
@@ -12,6 +12,7 @@ use crate::util::*;
 /// RawPacket represents an unparsed RTCP packet. It's returned by Unmarshal when
 /// a packet with an unknown type is encountered.
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RawPacket(pub Bytes);
 
 impl fmt::Display for RawPacket {
@@ -83,7 +84,13 @@ impl Unmarshal for RawPacket {
     {
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < HEADER_LENGTH {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::Unsupported,
+                offset: 0,
+                expected: HEADER_LENGTH,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let h = Header::unmarshal(raw_packet)?;
@@ -117,7 +124,12 @@ mod test {
             (
                 "short header",
                 RawPacket(Bytes::from_static(&[0x80])),
-                Some(Error::PacketTooShort),
+                Some(Error::PacketTooShort {
+                    packet_type: PacketType::Unsupported,
+                    offset: 0,
+                    expected: HEADER_LENGTH,
+                    got: 1,
+                }),
             ),
             (
                 "invalid header",
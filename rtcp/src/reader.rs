@@ -0,0 +1,199 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::error::{Error, Result};
+use crate::packet::{marshal, unmarshal, Packet};
+
+/// Length of the RFC 4571 framing header preceding each packet.
+pub const FRAME_HEADER_LENGTH: usize = 2;
+/// Largest datagram that fits in an RFC 4571 frame.
+pub const FRAME_MAX_LENGTH: usize = u16::MAX as usize;
+
+/// RtcpReader parses RTCP packets from a stream framed as in RFC 4571, where each
+/// datagram is preceded by its length as a 16-bit unsigned integer in network byte
+/// order. This is how RTCP is carried over TCP or TLS, where a datagram may arrive
+/// split across several reads, or several datagrams in a single one.
+///
+///```nobuild
+///     let mut reader = RtcpReader::new();
+///     loop {
+///         let n = stream.read(&mut buf)?;
+///         reader.push(&buf[..n]);
+///         while let Some(packets) = reader.read_packets()? {
+///             // ...
+///         }
+///     }
+///```
+#[derive(Debug, Default)]
+pub struct RtcpReader {
+    buffer: BytesMut,
+}
+
+impl RtcpReader {
+    pub fn new() -> Self {
+        RtcpReader::default()
+    }
+
+    /// push appends data read from the stream to the buffered input.
+    pub fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// buffered returns the number of octets of input not yet consumed.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// read_packets returns the packets of the next complete datagram in the
+    /// buffered input, or None if more input is needed. Empty frames are skipped.
+    ///
+    /// A datagram which fails to parse is consumed before the error is returned,
+    /// so that reading can continue with the next one.
+    pub fn read_packets(&mut self) -> Result<Option<Vec<Box<dyn Packet + Send + Sync>>>> {
+        loop {
+            let length = match frame_length(&self.buffer) {
+                Some(length) => length,
+                None => return Ok(None),
+            };
+
+            self.buffer.advance(FRAME_HEADER_LENGTH);
+            let mut frame = self.buffer.split_to(length).freeze();
+            if !frame.is_empty() {
+                return unmarshal(&mut frame).map(Some);
+            }
+        }
+    }
+}
+
+/// unmarshal_frame parses the RFC 4571 frame at the start of `raw_data`. It returns
+/// the packets of the datagram it carries along with the number of octets consumed,
+/// or None if `raw_data` does not hold a complete frame yet.
+#[allow(clippy::type_complexity)]
+pub fn unmarshal_frame(
+    raw_data: &Bytes,
+) -> Result<Option<(Vec<Box<dyn Packet + Send + Sync>>, usize)>> {
+    let length = match frame_length(raw_data) {
+        Some(length) => length,
+        None => return Ok(None),
+    };
+
+    let consumed = FRAME_HEADER_LENGTH + length;
+    let mut frame = raw_data.slice(FRAME_HEADER_LENGTH..consumed);
+
+    Ok(Some((unmarshal(&mut frame)?, consumed)))
+}
+
+/// marshal_frame serializes packets into a datagram preceded by the RFC 4571
+/// framing header.
+pub fn marshal_frame(packets: &[Box<dyn Packet + Send + Sync>]) -> Result<Bytes> {
+    let datagram = marshal(packets)?;
+    if datagram.len() > FRAME_MAX_LENGTH {
+        return Err(Error::FrameTooLong);
+    }
+
+    let mut out = BytesMut::with_capacity(FRAME_HEADER_LENGTH + datagram.len());
+    out.put_u16(datagram.len() as u16);
+    out.put(datagram);
+
+    Ok(out.freeze())
+}
+
+// returns the length of the frame at the start of buf, if it is complete
+fn frame_length(buf: &[u8]) -> Option<usize> {
+    if buf.len() < FRAME_HEADER_LENGTH {
+        return None;
+    }
+
+    let length = u16::from_be_bytes([buf[0], buf[1]]) as usize;
+    if buf.len() < FRAME_HEADER_LENGTH + length {
+        return None;
+    }
+
+    Some(length)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::goodbye::Goodbye;
+    use crate::payload_feedbacks::picture_loss_indication::PictureLossIndication;
+
+    fn packets() -> Vec<Box<dyn Packet + Send + Sync>> {
+        vec![
+            Box::new(PictureLossIndication {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0xbc5e9a40,
+            }),
+            Box::new(Goodbye {
+                sources: vec![0x902f9e2e],
+                reason: Bytes::from_static(b"bye"),
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_rtcp_reader_fragmented() -> Result<()> {
+        let frame = marshal_frame(&packets())?;
+        let mut stream = BytesMut::new();
+        stream.extend_from_slice(&frame);
+        stream.extend_from_slice(&[0x00, 0x00]); // empty frame
+        stream.extend_from_slice(&frame);
+
+        // feed the stream one octet at a time
+        let mut reader = RtcpReader::new();
+        let mut got = vec![];
+        for b in stream.iter() {
+            reader.push(&[*b]);
+            while let Some(p) = reader.read_packets()? {
+                got.push(p);
+            }
+        }
+
+        assert_eq!(got.len(), 2);
+        for p in got {
+            assert!(p == packets(), "Invalid packets");
+        }
+        assert_eq!(reader.buffered(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rtcp_reader_invalid_frame() -> Result<()> {
+        let mut reader = RtcpReader::new();
+        reader.push(&[0x00, 0x04, 0x00, 0x00, 0x00, 0x00]); // not RTCP
+        reader.push(&marshal_frame(&packets())?);
+
+        match reader.read_packets() {
+            Err(Error::Util(e)) => assert_eq!(Error::BadVersion, e),
+            got => panic!("expected BadVersion, got {got:?}"),
+        }
+        let p = reader.read_packets()?.expect("second frame");
+        assert!(p == packets(), "Invalid packets");
+        assert_eq!(reader.read_packets()?.map(|p| p.len()), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unmarshal_frame() -> Result<()> {
+        let frame = marshal_frame(&packets())?;
+        assert_eq!(
+            &frame[..FRAME_HEADER_LENGTH],
+            &[0x00, (frame.len() - FRAME_HEADER_LENGTH) as u8]
+        );
+
+        let mut data = BytesMut::from(&frame[..]);
+        data.extend_from_slice(&[0x00, 0x08]);
+        let data = data.freeze();
+
+        let (p, consumed) = unmarshal_frame(&data)?.expect("complete frame");
+        assert!(p == packets(), "Invalid packets");
+        assert_eq!(consumed, frame.len());
+
+        let rest = data.slice(consumed..);
+        assert!(unmarshal_frame(&rest)?.is_none());
+        assert!(unmarshal_frame(&frame.slice(..frame.len() - 1))?.is_none());
+
+        Ok(())
+    }
+}
@@ -20,6 +20,7 @@ pub(super) const RR_REPORT_OFFSET: usize = RR_SSRC_OFFSET + SSRC_LENGTH;
 
 /// A ReceiverReport (RR) packet provides reception quality feedback for an RTP stream
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReceiverReport {
     /// The synchronization source identifier for the originator of this RR packet.
     pub ssrc: u32,
@@ -33,6 +34,48 @@ pub struct ReceiverReport {
     pub profile_extensions: Bytes,
 }
 
+impl ReceiverReport {
+    /// validate returns an error if the report blocks do not fit in the 5-bit
+    /// count field of the header.
+    pub fn validate(&self) -> Result<()> {
+        if self.reports.len() > COUNT_MAX {
+            return Err(Error::TooManyReports.into());
+        }
+
+        Ok(())
+    }
+
+    /// split returns this report as ReceiverReports of at most 31 report blocks
+    /// each, which RFC 3550 section 6.4.2 allows to follow one another in a
+    /// compound packet when more sources are heard. The profile extensions go
+    /// with the first one.
+    pub fn split(&self) -> Vec<ReceiverReport> {
+        let mut rrs = split_reports(self.ssrc, &self.reports);
+        rrs[0].profile_extensions = self.profile_extensions.clone();
+        rrs
+    }
+}
+
+/// split_reports returns ReceiverReports from `ssrc` carrying the given report
+/// blocks, 31 at most each, and at least one even without report blocks.
+pub(crate) fn split_reports(ssrc: u32, reports: &[ReceptionReport]) -> Vec<ReceiverReport> {
+    if reports.is_empty() {
+        return vec![ReceiverReport {
+            ssrc,
+            ..Default::default()
+        }];
+    }
+
+    reports
+        .chunks(COUNT_MAX)
+        .map(|reports| ReceiverReport {
+            ssrc,
+            reports: reports.to_vec(),
+            ..Default::default()
+        })
+        .collect()
+}
+
 impl fmt::Display for ReceiverReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = format!("ReceiverReport from {}\n", self.ssrc);
@@ -102,9 +145,7 @@ impl MarshalSize for ReceiverReport {
 impl Marshal for ReceiverReport {
     /// marshal_to encodes the packet in binary.
     fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize> {
-        if self.reports.len() > COUNT_MAX {
-            return Err(Error::TooManyReports.into());
-        }
+        self.validate()?;
 
         if buf.remaining_mut() < self.marshal_size() {
             return Err(Error::BufferTooShort.into());
@@ -194,12 +235,22 @@ impl Unmarshal for ReceiverReport {
          */
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < (HEADER_LENGTH + SSRC_LENGTH) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::ReceiverReport,
+                offset: 0,
+                expected: HEADER_LENGTH + SSRC_LENGTH,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let header = Header::unmarshal(raw_packet)?;
         if header.packet_type != PacketType::ReceiverReport {
-            return Err(Error::WrongType.into());
+            return Err(Error::WrongType {
+                packet_type: header.packet_type,
+                count: header.count,
+            }
+            .into());
         }
 
         let ssrc = raw_packet.get_u32();
@@ -208,7 +259,13 @@ impl Unmarshal for ReceiverReport {
         let mut reports = Vec::with_capacity(header.count as usize);
         for _ in 0..header.count {
             if offset + RECEPTION_REPORT_LENGTH > raw_packet_len {
-                return Err(Error::PacketTooShort.into());
+                return Err(Error::PacketTooShort {
+                    packet_type: PacketType::ReceiverReport,
+                    offset,
+                    expected: offset + RECEPTION_REPORT_LENGTH,
+                    got: raw_packet_len,
+                }
+                .into());
             }
             let reception_report = ReceptionReport::unmarshal(raw_packet)?;
             reports.push(reception_report);
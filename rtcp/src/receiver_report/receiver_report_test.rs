@@ -71,7 +71,12 @@ fn test_receiver_report_unmarshal() {
                       // report ends early
             ]),
             ReceiverReport::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::ReceiverReport,
+                offset: 8,
+                expected: 32,
+                got: 12,
+            }),
         ),
         (
             "wrong type",
@@ -87,7 +92,10 @@ fn test_receiver_report_unmarshal() {
                 0x0, 0x2, 0x4a, 0x79,
             ]),
             ReceiverReport::default(),
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::SenderReport,
+                count: 1,
+            }),
         ),
         (
             "bad count in header",
@@ -102,13 +110,23 @@ fn test_receiver_report_unmarshal() {
                 0x0, 0x2, 0x4a, 0x79, // delay=150137
             ]),
             ReceiverReport::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::ReceiverReport,
+                offset: 32,
+                expected: 56,
+                got: 32,
+            }),
         ),
         (
             "nil",
             Bytes::from_static(&[]),
             ReceiverReport::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::ReceiverReport,
+                offset: 0,
+                expected: 8,
+                got: 0,
+            }),
         ),
     ];
 
@@ -240,3 +258,37 @@ fn test_receiver_report_roundtrip() {
         }
     }
 }
+
+#[test]
+fn test_receiver_report_split() {
+    let reports: Vec<ReceptionReport> = (0..32)
+        .map(|ssrc| ReceptionReport {
+            ssrc,
+            ..Default::default()
+        })
+        .collect();
+    let rr = ReceiverReport {
+        ssrc: 0x902f9e2e,
+        reports: reports.clone(),
+        profile_extensions: Bytes::from_static(&[0x81, 0xca, 0x00, 0x00]),
+    };
+    assert_eq!(Error::TooManyReports, rr.validate().unwrap_err());
+
+    let rrs = rr.split();
+    assert_eq!(rrs.len(), 2);
+    assert_eq!(rrs[0].reports, reports[..31]);
+    assert_eq!(rrs[0].profile_extensions, rr.profile_extensions);
+    assert_eq!(rrs[1].reports, reports[31..]);
+    assert!(rrs[1].profile_extensions.is_empty());
+    for r in &rrs {
+        assert_eq!(r.ssrc, rr.ssrc);
+        assert!(r.validate().is_ok());
+    }
+
+    // a report without report blocks stays as it is
+    let empty = ReceiverReport {
+        ssrc: 0x902f9e2e,
+        ..Default::default()
+    };
+    assert_eq!(empty.split(), vec![empty]);
+}
@@ -0,0 +1,328 @@
+//! Reception statistics of an RTP stream, from which receivers build their
+//! reception reports. See RFC 3550 section 6.4.1 and appendix A.
+
+use std::time::Instant;
+
+use crate::receiver_report::ReceiverReport;
+use crate::reception_report::ReceptionReport;
+use crate::rtt::LastSenderReport;
+use crate::sender_report::SenderReport;
+
+const RTP_SEQ_MOD: u32 = 1 << 16;
+/// Largest forward jump of the sequence number still taken as packet loss.
+const MAX_DROPOUT: u16 = 3000;
+/// Largest backward jump of the sequence number still taken as reordering.
+const MAX_MISORDER: u16 = 100;
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// StreamStats tracks the packets received from an RTP source: the highest
+/// sequence number, the number of packets lost and the interarrival jitter,
+/// following the algorithms of RFC 3550 appendices A.1, A.3 and A.8.
+///
+/// The caller feeds it every RTP packet with [`StreamStats::update`] and every
+/// SenderReport of the source with [`StreamStats::on_sender_report`], and gets
+/// the report block to send with [`StreamStats::reception_report`]:
+///
+///```nobuild
+///     let mut stats = StreamStats::new(ssrc, 90000);
+///     stats.update(pkt.header.sequence_number, pkt.header.timestamp, Instant::now());
+///     // ...
+///     let rr = stats.receiver_report(receiver_ssrc, Instant::now());
+///```
+#[derive(Debug, Clone)]
+pub struct StreamStats {
+    ssrc: u32,
+    clock_rate: u32,
+
+    /// The time of the first packet, from which arrival times are measured.
+    epoch: Option<Instant>,
+    /// The highest sequence number seen.
+    max_seq: u16,
+    /// The count of sequence number cycles.
+    cycles: u32,
+    /// The extended sequence number of the first packet.
+    base_seq: u32,
+    /// The sequence number after a large jump, which restarts the sequence
+    /// numbering if the next packet carries it.
+    bad_seq: u32,
+    /// The count of packets received, including late and duplicate ones.
+    received: u32,
+    /// The packets expected at the last report.
+    expected_prior: u32,
+    /// The packets received at the last report.
+    received_prior: u32,
+
+    /// The relative transit time of the last packet, in timestamp units.
+    transit: Option<u32>,
+    /// The interarrival jitter estimate, in timestamp units.
+    jitter: f64,
+
+    last_sender_report: Option<LastSenderReport>,
+}
+
+impl StreamStats {
+    /// new creates the statistics of the source `ssrc`, whose RTP timestamps
+    /// use the given clock rate in Hz.
+    pub fn new(ssrc: u32, clock_rate: u32) -> Self {
+        StreamStats {
+            ssrc,
+            clock_rate,
+            epoch: None,
+            max_seq: 0,
+            cycles: 0,
+            base_seq: 0,
+            bad_seq: RTP_SEQ_MOD + 1,
+            received: 0,
+            expected_prior: 0,
+            received_prior: 0,
+            transit: None,
+            jitter: 0.0,
+            last_sender_report: None,
+        }
+    }
+
+    /// ssrc returns the SSRC of the source.
+    pub fn ssrc(&self) -> u32 {
+        self.ssrc
+    }
+
+    /// update records an RTP packet received from the source at `arrival`.
+    ///
+    /// A sequence number far from the highest one seen is ignored, unless the
+    /// next packet follows it, which means that the source restarted its
+    /// sequence numbering.
+    pub fn update(&mut self, sequence_number: u16, timestamp: u32, arrival: Instant) {
+        let first = self.epoch.is_none();
+        let epoch = *self.epoch.get_or_insert(arrival);
+        if first {
+            self.init_seq(sequence_number);
+        } else if !self.update_seq(sequence_number) {
+            return;
+        }
+        self.received = self.received.wrapping_add(1);
+
+        // units of the RTP timestamp elapsed since the first packet
+        let elapsed = arrival.saturating_duration_since(epoch).as_nanos();
+        let arrival = (elapsed * self.clock_rate as u128 / NANOS_PER_SECOND) as u32;
+
+        let transit = arrival.wrapping_sub(timestamp);
+        if let Some(last) = self.transit {
+            let d = (transit.wrapping_sub(last) as i32).unsigned_abs() as f64;
+            self.jitter += (d - self.jitter) / 16.0;
+        }
+        self.transit = Some(transit);
+    }
+
+    /// on_sender_report records a SenderReport of the source received at
+    /// `arrival`, which the next reception reports refer to.
+    pub fn on_sender_report(&mut self, sr: &SenderReport, arrival: Instant) {
+        self.last_sender_report = Some(LastSenderReport::new(sr, arrival));
+    }
+
+    /// highest_sequence_number returns the extended highest sequence number received.
+    pub fn highest_sequence_number(&self) -> u32 {
+        (self.cycles << 16) | self.max_seq as u32
+    }
+
+    /// packets_received returns the count of packets received, including late
+    /// and duplicate ones.
+    pub fn packets_received(&self) -> u32 {
+        self.received
+    }
+
+    /// packets_lost returns the cumulative number of packets lost, which
+    /// duplicates may make negative.
+    pub fn packets_lost(&self) -> i64 {
+        self.expected() as i64 - self.received as i64
+    }
+
+    /// jitter returns the interarrival jitter estimate, in timestamp units.
+    pub fn jitter(&self) -> f64 {
+        self.jitter
+    }
+
+    /// reception_report returns the report block about the source to be sent
+    /// at `now`, or None if no packet was received yet. The fraction lost is
+    /// computed over the packets since the previous call.
+    pub fn reception_report(&mut self, now: Instant) -> Option<ReceptionReport> {
+        self.epoch?;
+
+        let expected = self.expected();
+        let mut report = ReceptionReport::from_counters(
+            self.ssrc,
+            self.base_seq,
+            self.highest_sequence_number(),
+            self.received,
+            self.expected_prior,
+            self.received_prior,
+        );
+        report.jitter = self.jitter as u32;
+        if let Some(lsr) = &self.last_sender_report {
+            lsr.fill(&mut report, now);
+        }
+
+        self.expected_prior = expected;
+        self.received_prior = self.received;
+
+        Some(report)
+    }
+
+    /// receiver_report returns a ReceiverReport from `receiver_ssrc` carrying the
+    /// report block about the source, if any packet was received yet.
+    pub fn receiver_report(&mut self, receiver_ssrc: u32, now: Instant) -> ReceiverReport {
+        ReceiverReport {
+            ssrc: receiver_ssrc,
+            reports: self.reception_report(now).into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    fn expected(&self) -> u32 {
+        self.highest_sequence_number()
+            .wrapping_sub(self.base_seq)
+            .wrapping_add(1)
+    }
+
+    fn init_seq(&mut self, seq: u16) {
+        self.base_seq = seq as u32;
+        self.max_seq = seq;
+        self.bad_seq = RTP_SEQ_MOD + 1;
+        self.cycles = 0;
+        self.received = 0;
+        self.received_prior = 0;
+        self.expected_prior = 0;
+    }
+
+    // update_seq returns false if the packet is to be ignored.
+    fn update_seq(&mut self, seq: u16) -> bool {
+        let udelta = seq.wrapping_sub(self.max_seq);
+        if udelta < MAX_DROPOUT {
+            // in order, with permissible gap
+            if seq < self.max_seq {
+                // sequence number wrapped
+                self.cycles += 1;
+            }
+            self.max_seq = seq;
+        } else if udelta <= (RTP_SEQ_MOD - MAX_MISORDER as u32) as u16 {
+            // the sequence number made a very large jump
+            if seq as u32 == self.bad_seq {
+                // two sequential packets, assume that the other side restarted
+                // without telling us
+                self.init_seq(seq);
+            } else {
+                self.bad_seq = (seq as u32 + 1) & (RTP_SEQ_MOD - 1);
+                return false;
+            }
+        }
+        // otherwise duplicate or reordered packet
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_stream_stats_loss() {
+        let start = Instant::now();
+        let mut stats = StreamStats::new(0x902f9e2e, 8000);
+        assert_eq!(stats.reception_report(start), None);
+        assert!(stats.receiver_report(1, start).reports.is_empty());
+
+        // 10 packets, of which 3 and 4 are lost and 6 arrives late
+        for seq in [0u16, 1, 2, 5, 7, 6, 8, 9] {
+            stats.update(seq, seq as u32 * 160, start);
+        }
+        assert_eq!(stats.highest_sequence_number(), 9);
+        assert_eq!(stats.packets_received(), 8);
+        assert_eq!(stats.packets_lost(), 2);
+
+        let report = stats.reception_report(start).unwrap();
+        assert_eq!(report.ssrc, 0x902f9e2e);
+        assert_eq!(report.last_sequence_number, 9);
+        assert_eq!(report.total_lost, 2);
+        assert_eq!(report.fraction_lost, (2 * 256 / 10) as u8);
+        assert_eq!(report.last_sender_report, 0);
+
+        // the fraction lost only covers the packets since the previous report
+        for seq in 10..20 {
+            stats.update(seq, seq as u32 * 160, start);
+        }
+        let rr = stats.receiver_report(1, start);
+        assert_eq!(rr.ssrc, 1);
+        assert_eq!(rr.reports[0].total_lost, 2);
+        assert_eq!(rr.reports[0].fraction_lost, 0);
+
+        // duplicates make the loss negative
+        stats.update(19, 19 * 160, start);
+        stats.update(19, 19 * 160, start);
+        stats.update(19, 19 * 160, start);
+        assert_eq!(stats.packets_lost(), -1);
+        let report = stats.reception_report(start).unwrap();
+        assert_eq!(report.total_lost_signed(), -1);
+        assert_eq!(report.fraction_lost, 0);
+    }
+
+    #[test]
+    fn test_stream_stats_sequence_numbers() {
+        let start = Instant::now();
+        let mut stats = StreamStats::new(0x902f9e2e, 8000);
+
+        for seq in [65534u16, 65535, 0, 1] {
+            stats.update(seq, 0, start);
+        }
+        assert_eq!(stats.highest_sequence_number(), 0x0001_0001);
+        assert_eq!(stats.packets_lost(), 0);
+
+        // a single packet far away is ignored
+        stats.update(30000, 0, start);
+        assert_eq!(stats.highest_sequence_number(), 0x0001_0001);
+        assert_eq!(stats.packets_received(), 4);
+
+        // but the source restarted if the next one follows it
+        stats.update(30001, 0, start);
+        assert_eq!(stats.highest_sequence_number(), 30001);
+        assert_eq!(stats.packets_received(), 1);
+        assert_eq!(stats.packets_lost(), 0);
+    }
+
+    #[test]
+    fn test_stream_stats_jitter() {
+        let start = Instant::now();
+        let mut stats = StreamStats::new(0x902f9e2e, 8000);
+
+        // packets every 20ms, the second one 10ms (80 units) late
+        stats.update(0, 0, start);
+        stats.update(1, 160, start + Duration::from_millis(30));
+        assert_eq!(stats.jitter(), 5.0);
+        stats.update(2, 320, start + Duration::from_millis(40));
+        assert_eq!(stats.jitter(), 5.0 + (80.0 - 5.0) / 16.0);
+
+        let report = stats.reception_report(start).unwrap();
+        assert_eq!(report.jitter, 9);
+    }
+
+    #[test]
+    fn test_stream_stats_sender_report() {
+        let start = Instant::now();
+        let mut stats = StreamStats::new(0x902f9e2e, 8000);
+        stats.update(0, 0, start);
+
+        let sr = SenderReport {
+            ssrc: 0x902f9e2e,
+            ntp_time: 0x0000_b705_2000_0000,
+            ..Default::default()
+        };
+        stats.on_sender_report(&sr, start);
+
+        let report = stats
+            .reception_report(start + Duration::from_millis(5_250))
+            .unwrap();
+        assert_eq!(report.last_sender_report, 0xb705_2000);
+        assert_eq!(report.delay, 0x0005_4000);
+    }
+}
@@ -1,11 +1,13 @@
 use std::any::Any;
 use std::fmt;
+use std::time::Duration;
 
 use bytes::{Buf, BufMut};
 use util::marshal::{Marshal, MarshalSize, Unmarshal};
 
 use crate::error::Error;
 use crate::header::*;
+use crate::ntp;
 use crate::packet::*;
 use crate::util::*;
 
@@ -17,9 +19,13 @@ pub(crate) const JITTER_OFFSET: usize = 12;
 pub(crate) const LAST_SR_OFFSET: usize = 16;
 pub(crate) const DELAY_OFFSET: usize = 20;
 
+const TOTAL_LOST_MAX: i64 = 0x7f_ffff;
+const TOTAL_LOST_MIN: i64 = -0x80_0000;
+
 /// A ReceptionReport block conveys statistics on the reception of RTP packets
 /// from a single synchronization source.
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReceptionReport {
     /// The SSRC identifier of the source to which the information in this
     /// reception report block pertains.
@@ -49,9 +55,106 @@ pub struct ReceptionReport {
     pub delay: u32,
 }
 
+impl ReceptionReport {
+    /// from_counters creates a ReceptionReport from the sequence number counters
+    /// of a source, as described in RFC 3550 appendix A.3.
+    ///
+    /// `base_seq` and `extended_max` are the extended sequence numbers of the first
+    /// and the highest packet received, `received` is the count of packets received
+    /// including late and duplicate ones, and `expected_prior` / `received_prior` are
+    /// the values of `expected` and `received` when the previous report was sent.
+    pub fn from_counters(
+        ssrc: u32,
+        base_seq: u32,
+        extended_max: u32,
+        received: u32,
+        expected_prior: u32,
+        received_prior: u32,
+    ) -> Self {
+        let expected = extended_max.wrapping_sub(base_seq).wrapping_add(1);
+
+        ReceptionReport {
+            ssrc,
+            fraction_lost: Self::compute_fraction_lost(
+                expected.wrapping_sub(expected_prior),
+                received.wrapping_sub(received_prior),
+            ),
+            total_lost: Self::compute_total_lost(expected, received),
+            last_sequence_number: extended_max,
+            ..Default::default()
+        }
+    }
+
+    /// with_last_sender_report sets the LSR and DLSR fields from the NTP timestamp of
+    /// the last sender report received from the source, and the time elapsed since.
+    pub fn with_last_sender_report(mut self, ntp_time: u64, delay: Duration) -> Self {
+        self.last_sender_report = Self::compute_last_sender_report(ntp_time);
+        self.delay = Self::compute_delay(delay);
+        self
+    }
+
+    /// compute_fraction_lost returns the fraction of packets lost during an interval,
+    /// as a fixed point number with the binary point at the left edge of the field.
+    /// Duplicates may make the loss negative, in which case the fraction is zero.
+    pub fn compute_fraction_lost(expected_interval: u32, received_interval: u32) -> u8 {
+        if expected_interval == 0 || received_interval >= expected_interval {
+            return 0;
+        }
+
+        // a fraction of 1 does not fit in 8 bits, and is reported as 255/256 instead
+        let lost_interval = (expected_interval - received_interval) as u64;
+        ((lost_interval << 8) / expected_interval as u64).min(u8::MAX as u64) as u8
+    }
+
+    /// compute_total_lost returns the cumulative number of packets lost, clamped to
+    /// the signed 24-bit range and encoded as the two's complement carried on the wire.
+    pub fn compute_total_lost(expected: u32, received: u32) -> u32 {
+        let lost = (expected as i64 - received as i64).clamp(TOTAL_LOST_MIN, TOTAL_LOST_MAX);
+        (lost as u32) & 0xff_ffff
+    }
+
+    /// total_lost_signed decodes the 24-bit cumulative number of packets lost, which
+    /// may be negative if duplicates were received.
+    pub fn total_lost_signed(&self) -> i32 {
+        ((self.total_lost << 8) as i32) >> 8
+    }
+
+    /// extended_sequence_number combines the count of sequence number cycles with
+    /// the highest sequence number received.
+    pub fn extended_sequence_number(cycles: u16, seq: u16) -> u32 {
+        ((cycles as u32) << 16) | seq as u32
+    }
+
+    /// compute_last_sender_report returns the middle 32 bits of a 64-bit NTP timestamp.
+    pub fn compute_last_sender_report(ntp_time: u64) -> u32 {
+        ntp::to_compact_ntp(ntp_time)
+    }
+
+    /// compute_delay converts a duration to units of 1/65536 seconds, saturating
+    /// rather than wrapping for delays longer than the field can hold.
+    pub fn compute_delay(delay: Duration) -> u32 {
+        ntp::duration_to_compact_ntp(delay)
+    }
+
+    /// delay_duration returns the delay since the last sender report as a Duration.
+    pub fn delay_duration(&self) -> Duration {
+        ntp::compact_ntp_to_duration(self.delay)
+    }
+}
+
 impl fmt::Display for ReceptionReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{self:?}")
+        write!(
+            f,
+            "ReceptionReport {:x} lost {}/{} last sequence {} jitter {} lsr {:x} dlsr {}",
+            self.ssrc,
+            self.fraction_lost,
+            self.total_lost,
+            self.last_sequence_number,
+            self.jitter,
+            self.last_sender_report,
+            self.delay,
+        )
     }
 }
 
@@ -149,7 +252,13 @@ impl Unmarshal for ReceptionReport {
     {
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < RECEPTION_REPORT_LENGTH {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::Unsupported,
+                offset: 0,
+                expected: RECEPTION_REPORT_LENGTH,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         /*
@@ -204,3 +313,90 @@ impl Unmarshal for ReceptionReport {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reception_report_fraction_lost() {
+        let tests = vec![
+            (0, 0, 0),
+            (100, 100, 0),
+            (100, 110, 0),
+            (100, 75, 64),
+            (100, 0, 255),
+            (4, 2, 128),
+        ];
+
+        for (expected, received, want) in tests {
+            assert_eq!(
+                ReceptionReport::compute_fraction_lost(expected, received),
+                want,
+                "fraction lost of {received}/{expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_reception_report_total_lost() {
+        let tests = vec![
+            (100, 90, 10, 10),
+            (100, 105, 0xff_fffb, -5),
+            (0x0100_0000, 0, 0x7f_ffff, 0x7f_ffff),
+            (0, 0x0100_0000, 0x80_0000, -0x80_0000),
+        ];
+
+        for (expected, received, want, want_signed) in tests {
+            let total_lost = ReceptionReport::compute_total_lost(expected, received);
+            assert_eq!(total_lost, want, "total lost of {received}/{expected}");
+
+            let rr = ReceptionReport {
+                total_lost,
+                ..Default::default()
+            };
+            let decoded = ReceptionReport::unmarshal(&mut rr.marshal().unwrap()).unwrap();
+            assert_eq!(decoded.total_lost_signed(), want_signed);
+        }
+    }
+
+    #[test]
+    fn test_reception_report_from_counters() {
+        let base_seq = ReceptionReport::extended_sequence_number(0, 65000);
+        let extended_max = ReceptionReport::extended_sequence_number(1, 99);
+        assert_eq!(extended_max, 0x0001_0063);
+
+        // 636 packets expected, 600 received, 100 of 136 received since the last report
+        let rr = ReceptionReport::from_counters(0x902f9e2e, base_seq, extended_max, 600, 500, 500)
+            .with_last_sender_report(0xda8b_d1fc_dddd_a05a, Duration::from_millis(1500));
+
+        assert_eq!(
+            rr,
+            ReceptionReport {
+                ssrc: 0x902f9e2e,
+                fraction_lost: 67,
+                total_lost: 36,
+                last_sequence_number: 0x0001_0063,
+                jitter: 0,
+                last_sender_report: 0xd1fc_dddd,
+                delay: 98304,
+            }
+        );
+    }
+
+    #[test]
+    fn test_reception_report_delay() {
+        let tests = vec![
+            (Duration::ZERO, 0),
+            (Duration::from_secs(1), 65536),
+            (Duration::from_millis(500), 32768),
+            (Duration::from_secs(1 << 16), u32::MAX),
+            (Duration::from_secs(1 << 48), u32::MAX),
+            (Duration::MAX, u32::MAX),
+        ];
+
+        for (delay, want) in tests {
+            assert_eq!(ReceptionReport::compute_delay(delay), want, "{delay:?}");
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Round-trip time computation from the LSR and DLSR fields of reception reports,
+//! following RFC 3550 section 6.4.1.
+//!
+//! A sender computes the round-trip time to a receiver from the reception report
+//! that receiver sent about it, using [`round_trip_time`]. A receiver fills in the
+//! fields of its reception reports from the last SenderReport it received, stored
+//! as a [`LastSenderReport`].
+
+use std::time::{Duration, Instant};
+
+use crate::ntp;
+use crate::reception_report::ReceptionReport;
+use crate::sender_report::SenderReport;
+
+/// round_trip_time returns the round-trip time between the sender of a reception
+/// report and the source it reports about, given the 64-bit NTP time at which the
+/// source received the report. It returns None if the receiver has not received
+/// a SenderReport yet, in which case its report carries no LSR.
+///
+/// The computation is done in the compact NTP format, whose wraparound every 18
+/// hours is handled. A delay that exceeds the time elapsed since the SenderReport,
+/// which only clock inaccuracies cause, gives a zero round-trip time.
+pub fn round_trip_time(report: &ReceptionReport, arrival_ntp_time: u64) -> Option<Duration> {
+    if report.last_sender_report == 0 {
+        return None;
+    }
+
+    let arrival = ntp::to_compact_ntp(arrival_ntp_time);
+    let elapsed = arrival.wrapping_sub(report.last_sender_report);
+    let rtt = elapsed.saturating_sub(report.delay);
+
+    Some(ntp::compact_ntp_to_duration(rtt))
+}
+
+/// LastSenderReport keeps what a receiver needs from the last SenderReport of a
+/// source to fill the LSR and DLSR fields of its next reception reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastSenderReport {
+    /// The NTP timestamp of the SenderReport.
+    pub ntp_time: u64,
+    /// The local time at which the SenderReport was received.
+    pub arrival: Instant,
+}
+
+impl LastSenderReport {
+    /// new stores a SenderReport received at the given time.
+    pub fn new(sr: &SenderReport, arrival: Instant) -> Self {
+        LastSenderReport {
+            ntp_time: sr.ntp_time,
+            arrival,
+        }
+    }
+
+    /// fill sets the LSR and DLSR fields of a reception report to be sent at `now`.
+    pub fn fill(&self, report: &mut ReceptionReport, now: Instant) {
+        report.last_sender_report = ntp::to_compact_ntp(self.ntp_time);
+        report.delay = ntp::duration_to_compact_ntp(now.saturating_duration_since(self.arrival));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_time() {
+        let tests = vec![
+            // the example of RFC 3550 section 6.4.1
+            (
+                "rfc 3550",
+                0xb705_2000,
+                0x0005_4000,
+                0x0000_b710_8000_0000,
+                Some(Duration::from_millis(6_125)),
+            ),
+            (
+                "compact time wraparound",
+                0xffff_0000,
+                0x0000_8000,
+                0x0000_0001_0000_0000,
+                Some(Duration::from_millis(1_500)),
+            ),
+            (
+                "delay exceeds elapsed time",
+                0xb705_2000,
+                0x0020_0000,
+                0x0000_b710_8000_0000,
+                Some(Duration::ZERO),
+            ),
+            ("no sender report", 0, 0, 0x0000_b710_8000_0000, None),
+        ];
+
+        for (name, last_sender_report, delay, arrival, want) in tests {
+            let report = ReceptionReport {
+                last_sender_report,
+                delay,
+                ..Default::default()
+            };
+
+            assert_eq!(round_trip_time(&report, arrival), want, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_last_sender_report() {
+        let arrival = Instant::now();
+        let sr = SenderReport {
+            ntp_time: 0x0000_b705_2000_0000,
+            ..Default::default()
+        };
+        let last = LastSenderReport::new(&sr, arrival);
+
+        let mut report = ReceptionReport::default();
+        last.fill(&mut report, arrival + Duration::from_millis(5_250));
+        assert_eq!(report.last_sender_report, 0xb705_2000);
+        assert_eq!(report.delay, 0x0005_4000);
+
+        // the report reaches the sender at 46864.5s, as in RFC 3550 section 6.4.1
+        let rtt = round_trip_time(&report, 0x0000_b710_8000_0000);
+        assert_eq!(rtt, Some(Duration::from_millis(6_125)));
+    }
+}
@@ -0,0 +1,316 @@
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+/// Minimum average time between RTCP packets, in seconds.
+const RTCP_MIN_TIME: f64 = 5.0;
+/// Fraction of the RTCP bandwidth to be shared among active senders.
+const RTCP_SENDER_BW_FRACTION: f64 = 0.25;
+/// Fraction of the RTCP bandwidth to be shared among receivers.
+const RTCP_RCVR_BW_FRACTION: f64 = 1.0 - RTCP_SENDER_BW_FRACTION;
+/// To compensate for "timer reconsideration" converging to a value below the
+/// intended average.
+const COMPENSATION: f64 = std::f64::consts::E - 1.5;
+
+/// Scheduler computes when RTCP packets should be sent, following the interval
+/// computation of RFC 3550 section 6.3 and appendix A.7, including the
+/// randomization of the interval and timer reconsideration.
+///
+/// The caller reports events of the session to the scheduler, and calls
+/// [`Scheduler::poll`] once [`Scheduler::next_report_time`] has passed:
+///
+///```nobuild
+///     let mut scheduler = Scheduler::new(rtcp_bandwidth, packet_size, Instant::now());
+///     loop {
+///         sleep_until(scheduler.next_report_time());
+///         if scheduler.poll(Instant::now()) {
+///             let size = send_report();
+///             scheduler.on_rtcp_sent(size, Instant::now());
+///         }
+///     }
+///```
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    /// The target RTCP bandwidth, i.e. the total bandwidth that will be used for
+    /// RTCP packets by all members of this session, in octets per second.
+    rtcp_bandwidth: f64,
+    /// The most current estimate for the number of session members.
+    members: usize,
+    /// The estimated number of session members at the time tn was last recomputed.
+    pmembers: usize,
+    /// The most current estimate for the number of senders in the session.
+    senders: usize,
+    /// Whether the application has sent data since the 2nd previous RTCP report.
+    we_sent: bool,
+    /// The average compound RTCP packet size, in octets, over all RTCP packets
+    /// sent and received by this participant, including lower-layer headers.
+    avg_rtcp_size: f64,
+    /// Whether the application has not yet sent an RTCP packet.
+    initial: bool,
+    /// The last time an RTCP packet was transmitted.
+    tp: Instant,
+    /// The next scheduled transmission time of an RTCP packet.
+    tn: Instant,
+}
+
+impl Scheduler {
+    /// new creates a Scheduler for a session with the given RTCP bandwidth in
+    /// octets per second, usually 5% of the session bandwidth, and the probable
+    /// size of the first RTCP packet that will be constructed.
+    pub fn new(rtcp_bandwidth: f64, packet_size: usize, now: Instant) -> Self {
+        let mut s = Scheduler {
+            rtcp_bandwidth,
+            members: 1,
+            pmembers: 1,
+            senders: 0,
+            we_sent: false,
+            avg_rtcp_size: packet_size as f64,
+            initial: true,
+            tp: now,
+            tn: now,
+        };
+        s.tn = now + s.randomized_interval();
+        s
+    }
+
+    /// next_report_time returns the time at which [`Scheduler::poll`] should be called next.
+    pub fn next_report_time(&self) -> Instant {
+        self.tn
+    }
+
+    /// members returns the current estimate of the number of session members.
+    pub fn members(&self) -> usize {
+        self.members
+    }
+
+    /// senders returns the current estimate of the number of senders.
+    pub fn senders(&self) -> usize {
+        self.senders
+    }
+
+    /// avg_rtcp_size returns the average compound RTCP packet size, in octets.
+    pub fn avg_rtcp_size(&self) -> f64 {
+        self.avg_rtcp_size
+    }
+
+    /// set_we_sent records whether the application has sent RTP data since the
+    /// 2nd previous RTCP report.
+    pub fn set_we_sent(&mut self, we_sent: bool) {
+        self.we_sent = we_sent;
+    }
+
+    /// set_senders updates the estimated number of senders in the session.
+    pub fn set_senders(&mut self, senders: usize) {
+        self.senders = senders;
+    }
+
+    /// set_members updates the estimated number of session members. When members
+    /// leave, the next report is brought forward following the "reverse
+    /// reconsideration" algorithm of RFC 3550 section 6.3.4.
+    pub fn set_members(&mut self, members: usize, now: Instant) {
+        let members = members.max(1);
+        if members < self.pmembers {
+            let ratio = members as f64 / self.pmembers as f64;
+            self.tn = now + self.tn.saturating_duration_since(now).mul_f64(ratio);
+            self.tp = now
+                .checked_sub(now.saturating_duration_since(self.tp).mul_f64(ratio))
+                .unwrap_or(self.tp);
+            self.pmembers = members;
+        }
+        self.members = members;
+    }
+
+    /// on_rtcp_received updates the average RTCP packet size with the size of a
+    /// received compound RTCP packet, in octets.
+    pub fn on_rtcp_received(&mut self, packet_size: usize) {
+        self.update_avg_rtcp_size(packet_size);
+    }
+
+    /// on_rtcp_sent records that a compound RTCP packet of the given size, in
+    /// octets, was sent, and schedules the next one.
+    pub fn on_rtcp_sent(&mut self, packet_size: usize, now: Instant) {
+        self.update_avg_rtcp_size(packet_size);
+        self.initial = false;
+        self.tp = now;
+        self.pmembers = self.members;
+        self.tn = now + self.randomized_interval();
+    }
+
+    /// poll is called when the transmission timer expires. It returns true if an
+    /// RTCP packet should be sent now, after which [`Scheduler::on_rtcp_sent`] must
+    /// be called. Otherwise the transmission is rescheduled ("timer reconsideration")
+    /// and [`Scheduler::next_report_time`] returns the new time.
+    pub fn poll(&mut self, now: Instant) -> bool {
+        if now < self.tn {
+            return false;
+        }
+
+        let tn = self.tp + self.randomized_interval();
+        if tn <= now {
+            true
+        } else {
+            self.tn = tn;
+            false
+        }
+    }
+
+    /// interval returns the deterministic interval between RTCP packets, before
+    /// randomization, as computed in RFC 3550 appendix A.7.
+    pub fn interval(&self) -> Duration {
+        // very first call at application start-up uses half the min delay for
+        // quicker notification while still allowing some time before reporting
+        // for randomization and to learn about other sources so the report
+        // interval will converge to the correct interval more quickly.
+        let rtcp_min_time = if self.initial {
+            RTCP_MIN_TIME / 2.0
+        } else {
+            RTCP_MIN_TIME
+        };
+
+        // dedicate a fraction of the RTCP bandwidth to senders unless the number
+        // of senders is large enough that their share is more than that fraction.
+        let mut n = self.members as f64;
+        let mut rtcp_bw = self.rtcp_bandwidth;
+        let senders = self.senders as f64;
+        if senders <= n * RTCP_SENDER_BW_FRACTION {
+            if self.we_sent {
+                rtcp_bw *= RTCP_SENDER_BW_FRACTION;
+                n = senders;
+            } else {
+                rtcp_bw *= RTCP_RCVR_BW_FRACTION;
+                n -= senders;
+            }
+        }
+
+        // the effective number of sites times the average packet size is the
+        // total number of octets sent when each site sends a report. Dividing
+        // this by the effective bandwidth gives the time interval over which
+        // those packets must be sent in order to meet the bandwidth target,
+        // with a minimum enforced.
+        let t = if rtcp_bw > 0.0 {
+            (self.avg_rtcp_size * n / rtcp_bw).max(rtcp_min_time)
+        } else {
+            rtcp_min_time
+        };
+
+        Duration::from_secs_f64(t)
+    }
+
+    /// randomized_interval returns the interval to the next RTCP packet: the
+    /// deterministic interval randomized over [0.5, 1.5] times its value, to
+    /// avoid unintended synchronization with other sites, and divided by e-3/2
+    /// to compensate for timer reconsideration.
+    pub fn randomized_interval(&self) -> Duration {
+        let factor = rand::thread_rng().gen_range(0.5..1.5);
+        self.interval().mul_f64(factor / COMPENSATION)
+    }
+
+    fn update_avg_rtcp_size(&mut self, packet_size: usize) {
+        self.avg_rtcp_size = (1.0 / 16.0) * packet_size as f64 + (15.0 / 16.0) * self.avg_rtcp_size;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bounds(interval: Duration) -> (Duration, Duration) {
+        (
+            interval.mul_f64(0.5 / COMPENSATION),
+            interval.mul_f64(1.5 / COMPENSATION),
+        )
+    }
+
+    #[test]
+    fn test_scheduler_interval() {
+        let now = Instant::now();
+
+        // few members: the minimum interval applies, halved before the first report
+        let mut s = Scheduler::new(1000.0, 100, now);
+        assert_eq!(s.interval(), Duration::from_millis(2500));
+        s.on_rtcp_sent(100, now);
+        assert_eq!(s.interval(), Duration::from_secs(5));
+
+        // 1000 receivers sharing 75% of 1000 octets/s with 100 octet packets
+        s.set_members(1000, now);
+        assert_eq!(
+            s.interval(),
+            Duration::from_secs_f64(100.0 * 1000.0 / 750.0)
+        );
+
+        // 10 of them sending, including us, share the remaining 25%, which gives
+        // a 4s interval raised to the minimum
+        s.set_senders(10);
+        s.set_we_sent(true);
+        assert_eq!(s.interval(), Duration::from_secs(5));
+
+        // once senders are more than a quarter of the members, everyone shares it all
+        s.set_senders(500);
+        assert_eq!(
+            s.interval(),
+            Duration::from_secs_f64(100.0 * 1000.0 / 1000.0)
+        );
+
+        let (min, max) = bounds(s.interval());
+        for _ in 0..100 {
+            let t = s.randomized_interval();
+            assert!(min <= t && t <= max, "{t:?} not in [{min:?}, {max:?}]");
+        }
+    }
+
+    #[test]
+    fn test_scheduler_avg_rtcp_size() {
+        let now = Instant::now();
+        let mut s = Scheduler::new(1000.0, 100, now);
+
+        s.on_rtcp_received(260);
+        assert_eq!(s.avg_rtcp_size(), 110.0);
+        s.on_rtcp_sent(110, now);
+        assert_eq!(s.avg_rtcp_size(), 110.0);
+    }
+
+    #[test]
+    fn test_scheduler_reconsideration() {
+        let now = Instant::now();
+        let mut s = Scheduler::new(1000.0, 100, now);
+
+        let (min, max) = bounds(Duration::from_millis(2500));
+        let tn = s.next_report_time();
+        assert!(now + min <= tn && tn <= now + max);
+
+        // not yet due
+        assert!(!s.poll(now));
+
+        // due, and still due after reconsidering with the same membership
+        let later = now + Duration::from_secs(10);
+        assert!(s.poll(later));
+        s.on_rtcp_sent(100, later);
+
+        // the group grew meanwhile, so the transmission gets rescheduled
+        let (_, max) = bounds(Duration::from_secs(5));
+        s.set_members(10_000, later);
+        let due = later + max;
+        assert!(!s.poll(due));
+        assert!(s.next_report_time() > due);
+    }
+
+    #[test]
+    fn test_scheduler_reverse_reconsideration() {
+        let now = Instant::now();
+        let mut s = Scheduler::new(1000.0, 100, now);
+        s.set_members(1000, now);
+        s.on_rtcp_sent(100, now);
+
+        let tn = s.next_report_time();
+        s.set_members(500, now);
+        assert_eq!(s.members(), 500);
+
+        // half of the members left, so the next report comes twice as early
+        let want = (tn - now) / 2;
+        let got = s.next_report_time() - now;
+        assert!(
+            got.abs_diff(want) < Duration::from_millis(1),
+            "{got:?} != {want:?}"
+        );
+    }
+}
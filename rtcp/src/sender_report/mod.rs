@@ -3,13 +3,16 @@ mod sender_report_test;
 
 use std::any::Any;
 use std::fmt;
+use std::time::SystemTime;
 
 use bytes::{Buf, BufMut, Bytes};
 use util::marshal::{Marshal, MarshalSize, Unmarshal};
 
 use crate::error::Error;
 use crate::header::*;
+use crate::ntp;
 use crate::packet::*;
+use crate::receiver_report::{split_reports, ReceiverReport};
 use crate::reception_report::*;
 use crate::util::*;
 
@@ -30,6 +33,7 @@ pub(crate) const SR_OCTET_COUNT_LENGTH: usize = 4;
 
 /// A SenderReport (SR) packet provides reception quality feedback for an RTP stream
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SenderReport {
     /// The synchronization source identifier for the originator of this SR packet.
     pub ssrc: u32,
@@ -64,6 +68,53 @@ pub struct SenderReport {
     pub profile_extensions: Bytes,
 }
 
+impl SenderReport {
+    /// ntp_system_time returns the wallclock time of this report.
+    pub fn ntp_system_time(&self) -> SystemTime {
+        ntp::ntp_to_system_time(self.ntp_time)
+    }
+
+    /// set_ntp_system_time sets the NTP timestamp of this report from a wallclock time.
+    pub fn set_ntp_system_time(&mut self, t: SystemTime) {
+        self.ntp_time = ntp::system_time_to_ntp(t);
+    }
+
+    /// compact_ntp_time returns the middle 32 bits of the NTP timestamp, which
+    /// receivers echo in the LSR field of their reception reports.
+    pub fn compact_ntp_time(&self) -> u32 {
+        ntp::to_compact_ntp(self.ntp_time)
+    }
+
+    /// validate returns an error if the report blocks do not fit in the 5-bit
+    /// count field of the header.
+    pub fn validate(&self) -> Result<()> {
+        if self.reports.len() > COUNT_MAX {
+            return Err(Error::TooManyReports.into());
+        }
+
+        Ok(())
+    }
+
+    /// split returns this report with its first 31 report blocks, followed by
+    /// ReceiverReports from the same SSRC carrying the remaining ones, 31 at most
+    /// each, as RFC 3550 section 6.4.2 allows when more sources are heard.
+    pub fn split(&self) -> (SenderReport, Vec<ReceiverReport>) {
+        let n = self.reports.len().min(COUNT_MAX);
+        let sr = SenderReport {
+            reports: self.reports[..n].to_vec(),
+            ..self.clone()
+        };
+
+        let rrs = if n < self.reports.len() {
+            split_reports(self.ssrc, &self.reports[n..])
+        } else {
+            vec![]
+        };
+
+        (sr, rrs)
+    }
+}
+
 impl fmt::Display for SenderReport {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = format!("SenderReport from {}\n", self.ssrc);
@@ -139,9 +190,7 @@ impl MarshalSize for SenderReport {
 impl Marshal for SenderReport {
     /// Marshal encodes the packet in binary.
     fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize> {
-        if self.reports.len() > COUNT_MAX {
-            return Err(Error::TooManyReports.into());
-        }
+        self.validate()?;
 
         if buf.remaining_mut() < self.marshal_size() {
             return Err(Error::BufferTooShort.into());
@@ -255,12 +304,22 @@ impl Unmarshal for SenderReport {
          */
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < (HEADER_LENGTH + SR_HEADER_LENGTH) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::SenderReport,
+                offset: 0,
+                expected: HEADER_LENGTH + SR_HEADER_LENGTH,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let header = Header::unmarshal(raw_packet)?;
         if header.packet_type != PacketType::SenderReport {
-            return Err(Error::WrongType.into());
+            return Err(Error::WrongType {
+                packet_type: header.packet_type,
+                count: header.count,
+            }
+            .into());
         }
 
         let ssrc = raw_packet.get_u32();
@@ -273,7 +332,13 @@ impl Unmarshal for SenderReport {
         let mut reports = Vec::with_capacity(header.count as usize);
         for _ in 0..header.count {
             if offset + RECEPTION_REPORT_LENGTH > raw_packet_len {
-                return Err(Error::PacketTooShort.into());
+                return Err(Error::PacketTooShort {
+                    packet_type: PacketType::SenderReport,
+                    offset,
+                    expected: offset + RECEPTION_REPORT_LENGTH,
+                    got: raw_packet_len,
+                }
+                .into());
             }
             let reception_report = ReceptionReport::unmarshal(raw_packet)?;
             reports.push(reception_report);
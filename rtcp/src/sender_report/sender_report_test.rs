@@ -7,7 +7,12 @@ fn test_sender_report_unmarshal() {
             "nil",
             Bytes::from_static(&[]),
             SenderReport::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::SenderReport,
+                offset: 0,
+                expected: 28,
+                got: 0,
+            }),
         ),
         (
             "valid",
@@ -61,7 +66,10 @@ fn test_sender_report_unmarshal() {
                 0x0, 0x2, 0x4a, 0x79, // delay=150137
             ]),
             SenderReport::default(),
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::ReceiverReport,
+                count: 1,
+            }),
         ),
         (
             "bad count in header",
@@ -80,7 +88,12 @@ fn test_sender_report_unmarshal() {
                 0x0, 0x2, 0x4a, 0x79, // delay=150137
             ]),
             SenderReport::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::SenderReport,
+                offset: 52,
+                expected: 76,
+                got: 52,
+            }),
         ),
         (
             "with extension", // issue #447
@@ -250,3 +263,59 @@ fn test_sender_report_roundtrip() {
         }
     }
 }
+
+#[test]
+fn test_sender_report_ntp_time() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let now = UNIX_EPOCH + Duration::from_millis(1_700_000_000_500);
+    let mut sr = SenderReport::default();
+    sr.set_ntp_system_time(now);
+
+    assert_eq!(sr.ntp_time, 0xe8fe_6f80_8000_0000);
+    assert_eq!(sr.ntp_system_time(), now);
+    assert_eq!(sr.compact_ntp_time(), 0x6f80_8000);
+
+    // a receiver echoes the compact time in its report block
+    let rr =
+        ReceptionReport::default().with_last_sender_report(sr.ntp_time, Duration::from_millis(250));
+    assert_eq!(rr.last_sender_report, sr.compact_ntp_time());
+    assert_eq!(rr.delay_duration(), Duration::from_millis(250));
+}
+
+#[test]
+fn test_sender_report_split() {
+    let reports: Vec<ReceptionReport> = (0..70)
+        .map(|ssrc| ReceptionReport {
+            ssrc,
+            ..Default::default()
+        })
+        .collect();
+    let sr = SenderReport {
+        ssrc: 0x902f9e2e,
+        ntp_time: 0xda8bd1fcdddda05a,
+        reports: reports.clone(),
+        profile_extensions: Bytes::from_static(&[0x81, 0xca, 0x00, 0x00]),
+        ..Default::default()
+    };
+    assert_eq!(Error::TooManyReports, sr.validate().unwrap_err());
+
+    let (first, rrs) = sr.split();
+    assert!(first.validate().is_ok());
+    assert_eq!(first.ntp_time, sr.ntp_time);
+    assert_eq!(first.profile_extensions, sr.profile_extensions);
+    assert_eq!(first.reports, reports[..31]);
+
+    assert_eq!(rrs.len(), 2);
+    assert_eq!(rrs[0].reports, reports[31..62]);
+    assert_eq!(rrs[1].reports, reports[62..]);
+    for rr in &rrs {
+        assert_eq!(rr.ssrc, sr.ssrc);
+        assert!(rr.profile_extensions.is_empty());
+        assert!(rr.marshal().is_ok());
+    }
+
+    let (first, rrs) = first.split();
+    assert_eq!(first.reports, reports[..31]);
+    assert!(rrs.is_empty());
+}
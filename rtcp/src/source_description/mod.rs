@@ -25,6 +25,7 @@ const SDES_TEXT_OFFSET: usize = 2;
 /// SDESType is the item type used in the RTCP SDES control packet.
 /// RTP SDES item types registered with IANA. See: https://www.iana.org/assignments/rtp-parameters/rtp-parameters.xhtml#rtp-parameters-5
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum SdesType {
     #[default]
@@ -36,7 +37,7 @@ pub enum SdesType {
     SdesLocation = 5, // geographic user location        RFC 3550, 6.5.5
     SdesTool = 6,     // name of application or tool     RFC 3550, 6.5.6
     SdesNote = 7,     // notice about the source         RFC 3550, 6.5.7
-    SdesPrivate = 8,  // private extensions              RFC 3550, 6.5.8  (not implemented)
+    SdesPrivate = 8,  // private extensions              RFC 3550, 6.5.8
 }
 
 impl fmt::Display for SdesType {
@@ -74,6 +75,7 @@ impl From<u8> for SdesType {
 
 /// A SourceDescriptionChunk contains items describing a single RTP source
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SourceDescriptionChunk {
     /// The source (ssrc) or contributing source (csrc) identifier this packet describes
     pub source: u32,
@@ -147,7 +149,13 @@ impl Unmarshal for SourceDescriptionChunk {
          */
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < (SDES_SOURCE_LEN + SDES_TYPE_LEN) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::SourceDescription,
+                offset: 0,
+                expected: SDES_SOURCE_LEN + SDES_TYPE_LEN,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let source = raw_packet.get_u32();
@@ -163,19 +171,32 @@ impl Unmarshal for SourceDescriptionChunk {
                     raw_packet.advance(padding_len);
                     return Ok(SourceDescriptionChunk { source, items });
                 } else {
-                    return Err(Error::PacketTooShort.into());
+                    return Err(Error::PacketTooShort {
+                        packet_type: PacketType::SourceDescription,
+                        offset: offset + 1,
+                        expected: offset + 1 + padding_len,
+                        got: offset + 1 + raw_packet.remaining(),
+                    }
+                    .into());
                 }
             }
             offset += item.marshal_size();
             items.push(item);
         }
 
-        Err(Error::PacketTooShort.into())
+        Err(Error::PacketTooShort {
+            packet_type: PacketType::SourceDescription,
+            offset: raw_packet_len,
+            expected: raw_packet_len + SDES_TYPE_LEN,
+            got: raw_packet_len,
+        }
+        .into())
     }
 }
 
 /// A SourceDescriptionItem is a part of a SourceDescription that describes a stream.
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SourceDescriptionItem {
     /// The type identifier for this item. eg, SDESCNAME for canonical name description.
     ///
@@ -185,6 +206,79 @@ pub struct SourceDescriptionItem {
     pub text: Bytes,
 }
 
+impl SourceDescriptionItem {
+    /// new_private builds a PRIV item carrying an application-specific `prefix`
+    /// and its `value`, as described in RFC 3550 section 6.5.8:
+    ///
+    ///   0                   1                   2                   3
+    ///   0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+    ///  +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+    ///  |     PRIV=8    |     length    | prefix length |prefix string...
+    ///  +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+    ///  ...             |                  value string               ...
+    ///  +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+    pub fn new_private(prefix: &[u8], value: &[u8]) -> Result<Self> {
+        if 1 + prefix.len() + value.len() > SDES_MAX_OCTET_COUNT {
+            return Err(Error::SdesTextTooLong.into());
+        }
+
+        let mut text = Vec::with_capacity(1 + prefix.len() + value.len());
+        text.push(prefix.len() as u8);
+        text.extend_from_slice(prefix);
+        text.extend_from_slice(value);
+
+        Ok(SourceDescriptionItem {
+            sdes_type: SdesType::SdesPrivate,
+            text: Bytes::from(text),
+        })
+    }
+
+    /// private_prefix returns the prefix string of a PRIV item, or None for
+    /// any other item type or a malformed PRIV item.
+    pub fn private_prefix(&self) -> Option<Bytes> {
+        let prefix_len = self.private_prefix_len()?;
+        Some(self.text.slice(1..1 + prefix_len))
+    }
+
+    /// private_value returns the value string of a PRIV item, or None for
+    /// any other item type or a malformed PRIV item.
+    pub fn private_value(&self) -> Option<Bytes> {
+        let prefix_len = self.private_prefix_len()?;
+        Some(self.text.slice(1 + prefix_len..))
+    }
+
+    fn private_prefix_len(&self) -> Option<usize> {
+        if self.sdes_type != SdesType::SdesPrivate {
+            return None;
+        }
+        let prefix_len = *self.text.first()? as usize;
+        if 1 + prefix_len > self.text.len() {
+            return None;
+        }
+        Some(prefix_len)
+    }
+}
+
+impl fmt::Display for SourceDescriptionItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.private_prefix(), self.private_value()) {
+            (Some(prefix), Some(value)) => write!(
+                f,
+                "{}: {}={}",
+                self.sdes_type,
+                String::from_utf8_lossy(&prefix),
+                String::from_utf8_lossy(&value)
+            ),
+            _ => write!(
+                f,
+                "{}: {}",
+                self.sdes_type,
+                String::from_utf8_lossy(&self.text)
+            ),
+        }
+    }
+}
+
 impl MarshalSize for SourceDescriptionItem {
     fn marshal_size(&self) -> usize {
         /*
@@ -222,6 +316,9 @@ impl Marshal for SourceDescriptionItem {
         if self.text.len() > SDES_MAX_OCTET_COUNT {
             return Err(Error::SdesTextTooLong.into());
         }
+        if self.sdes_type == SdesType::SdesPrivate && self.private_prefix_len().is_none() {
+            return Err(Error::SdesInvalidPrivatePrefix.into());
+        }
         buf.put_u8(self.text.len() as u8);
         buf.put(self.text.clone());
 
@@ -246,7 +343,13 @@ impl Unmarshal for SourceDescriptionItem {
          */
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < SDES_TYPE_LEN {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::SourceDescription,
+                offset: 0,
+                expected: SDES_TYPE_LEN,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let sdes_type = SdesType::from(raw_packet.get_u8());
@@ -258,33 +361,87 @@ impl Unmarshal for SourceDescriptionItem {
         }
 
         if raw_packet_len < (SDES_TYPE_LEN + SDES_OCTET_COUNT_LEN) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::SourceDescription,
+                offset: SDES_TYPE_LEN,
+                expected: SDES_TYPE_LEN + SDES_OCTET_COUNT_LEN,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let octet_count = raw_packet.get_u8() as usize;
         if SDES_TEXT_OFFSET + octet_count > raw_packet_len {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::SourceDescription,
+                offset: SDES_TEXT_OFFSET,
+                expected: SDES_TEXT_OFFSET + octet_count,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let text = raw_packet.copy_to_bytes(octet_count);
 
-        Ok(SourceDescriptionItem { sdes_type, text })
+        let item = SourceDescriptionItem { sdes_type, text };
+        if sdes_type == SdesType::SdesPrivate && item.private_prefix_len().is_none() {
+            return Err(Error::SdesInvalidPrivatePrefix.into());
+        }
+
+        Ok(item)
     }
 }
 
 /// A SourceDescription (SDES) packet describes the sources in an RTP stream.
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SourceDescription {
     pub chunks: Vec<SourceDescriptionChunk>,
 }
 
+impl SourceDescription {
+    /// with_cname creates a SourceDescription holding a single chunk, which
+    /// describes `ssrc` by its CNAME.
+    pub fn with_cname(ssrc: u32, cname: impl Into<Bytes>) -> Self {
+        SourceDescription {
+            chunks: vec![SourceDescriptionChunk {
+                source: ssrc,
+                items: vec![SourceDescriptionItem {
+                    sdes_type: SdesType::SdesCname,
+                    text: cname.into(),
+                }],
+            }],
+        }
+    }
+
+    /// items_for returns the items of all the chunks describing `ssrc`.
+    pub fn items_for(&self, ssrc: u32) -> impl Iterator<Item = &SourceDescriptionItem> {
+        self.chunks
+            .iter()
+            .filter(move |c| c.source == ssrc)
+            .flat_map(|c| c.items.iter())
+    }
+
+    /// item_for returns the text of the first item of type `sdes_type` describing `ssrc`.
+    pub fn item_for(&self, ssrc: u32, sdes_type: SdesType) -> Option<&Bytes> {
+        self.items_for(ssrc)
+            .find(|it| it.sdes_type == sdes_type)
+            .map(|it| &it.text)
+    }
+
+    /// cname_for returns the CNAME of `ssrc`, if this packet describes it.
+    pub fn cname_for(&self, ssrc: u32) -> Option<&Bytes> {
+        self.item_for(ssrc, SdesType::SdesCname)
+    }
+}
+
 impl fmt::Display for SourceDescription {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = "Source Description:\n".to_string();
         for c in &self.chunks {
             out += format!("\t{:x}\n", c.source).as_str();
             for it in &c.items {
-                out += format!("\t\t{it:?}\n").as_str();
+                out += format!("\t\t{it}\n").as_str();
             }
         }
         write!(f, "{out}")
@@ -414,7 +571,11 @@ impl Unmarshal for SourceDescription {
 
         let h = Header::unmarshal(raw_packet)?;
         if h.packet_type != PacketType::SourceDescription {
-            return Err(Error::WrongType.into());
+            return Err(Error::WrongType {
+                packet_type: h.packet_type,
+                count: h.count,
+            }
+            .into());
         }
 
         let mut offset = HEADER_LENGTH;
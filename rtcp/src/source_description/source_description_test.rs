@@ -7,7 +7,12 @@ fn test_source_description_unmarshal() {
             "nil",
             Bytes::from_static(&[]),
             SourceDescription::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::Unsupported,
+                offset: 0,
+                expected: 4,
+                got: 0,
+            }),
         ),
         (
             "no chunks",
@@ -26,7 +31,12 @@ fn test_source_description_unmarshal() {
                 0x00, 0x00, 0x00, 0x00,
             ]),
             SourceDescription::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::SourceDescription,
+                offset: 0,
+                expected: 5,
+                got: 4,
+            }),
         ),
         (
             "bad cname length",
@@ -37,7 +47,12 @@ fn test_source_description_unmarshal() {
                 0x01, 0x01,
             ]),
             SourceDescription::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::SourceDescription,
+                offset: 2,
+                expected: 3,
+                got: 2,
+            }),
         ),
         (
             "short cname",
@@ -48,7 +63,12 @@ fn test_source_description_unmarshal() {
                 0x01,
             ]),
             SourceDescription::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::SourceDescription,
+                offset: 1,
+                expected: 2,
+                got: 1,
+            }),
         ),
         (
             "no end",
@@ -60,7 +80,12 @@ fn test_source_description_unmarshal() {
                 // Missing END
             ]),
             SourceDescription::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::SourceDescription,
+                offset: 2,
+                expected: 4,
+                got: 3,
+            }),
         ),
         (
             "bad octet count",
@@ -71,7 +96,12 @@ fn test_source_description_unmarshal() {
                 0x01, 0x01,
             ]),
             SourceDescription::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::SourceDescription,
+                offset: 2,
+                expected: 3,
+                got: 2,
+            }),
         ),
         (
             "zero item chunk",
@@ -98,7 +128,10 @@ fn test_source_description_unmarshal() {
                 0x00, 0x00, 0x00, 0x00,
             ]),
             SourceDescription::default(),
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::SenderReport,
+                count: 1,
+            }),
         ),
         (
             "bad count in header",
@@ -191,6 +224,38 @@ fn test_source_description_unmarshal() {
             },
             None,
         ),
+        (
+            "private item",
+            Bytes::from_static(&[
+                // v=2, p=0, count=1, SDES, len=16
+                0x81, 0xca, 0x00, 0x10, // ssrc=0x01020304
+                0x01, 0x02, 0x03, 0x04, // PRIV, len=4, prefix len=1, prefix=x, value=ab
+                0x08, 0x04, 0x01, 0x78, 0x61, 0x62, // END + padding
+                0x00, 0x00,
+            ]),
+            SourceDescription {
+                chunks: vec![SourceDescriptionChunk {
+                    source: 0x01020304,
+                    items: vec![SourceDescriptionItem {
+                        sdes_type: SdesType::SdesPrivate,
+                        text: Bytes::from_static(b"\x01xab"),
+                    }],
+                }],
+            },
+            None,
+        ),
+        (
+            "private prefix overflow",
+            Bytes::from_static(&[
+                // v=2, p=0, count=1, SDES, len=16
+                0x81, 0xca, 0x00, 0x10, // ssrc=0x01020304
+                0x01, 0x02, 0x03, 0x04, // PRIV, len=2, prefix len=5, prefix=x
+                0x08, 0x02, 0x05, 0x78, // END + padding
+                0x00, 0x00, 0x00, 0x00,
+            ]),
+            SourceDescription::default(),
+            Some(Error::SdesInvalidPrivatePrefix),
+        ),
     ];
 
     for (name, mut data, want, want_error) in tests {
@@ -259,6 +324,59 @@ fn test_source_description_roundtrip() {
             },
             None,
         ),
+        (
+            "all item types",
+            SourceDescription {
+                chunks: vec![SourceDescriptionChunk {
+                    source: 1,
+                    items: vec![
+                        SourceDescriptionItem {
+                            sdes_type: SdesType::SdesCname,
+                            text: Bytes::from_static(b"user@example.com"),
+                        },
+                        SourceDescriptionItem {
+                            sdes_type: SdesType::SdesName,
+                            text: Bytes::from_static(b"John Doe"),
+                        },
+                        SourceDescriptionItem {
+                            sdes_type: SdesType::SdesEmail,
+                            text: Bytes::from_static(b"john@example.com"),
+                        },
+                        SourceDescriptionItem {
+                            sdes_type: SdesType::SdesPhone,
+                            text: Bytes::from_static(b"+1 908 555 1212"),
+                        },
+                        SourceDescriptionItem {
+                            sdes_type: SdesType::SdesLocation,
+                            text: Bytes::from_static(b"Murray Hill, New Jersey"),
+                        },
+                        SourceDescriptionItem {
+                            sdes_type: SdesType::SdesTool,
+                            text: Bytes::from_static(b"webrtc-rs"),
+                        },
+                        SourceDescriptionItem {
+                            sdes_type: SdesType::SdesNote,
+                            text: Bytes::from_static(b"on the phone"),
+                        },
+                        SourceDescriptionItem::new_private(b"x-app", b"42").unwrap(),
+                    ],
+                }],
+            },
+            None,
+        ),
+        (
+            "private item without prefix",
+            SourceDescription {
+                chunks: vec![SourceDescriptionChunk {
+                    source: 1,
+                    items: vec![SourceDescriptionItem {
+                        sdes_type: SdesType::SdesPrivate,
+                        text: Bytes::new(),
+                    }],
+                }],
+            },
+            Some(Error::SdesInvalidPrivatePrefix),
+        ),
         (
             "item without type",
             SourceDescription {
@@ -357,3 +475,84 @@ fn test_source_description_roundtrip() {
         }
     }
 }
+
+#[test]
+fn test_source_description_private_item() {
+    let item = SourceDescriptionItem::new_private(b"x-app", b"some value").unwrap();
+    assert_eq!(item.sdes_type, SdesType::SdesPrivate);
+    assert_eq!(item.private_prefix(), Some(Bytes::from_static(b"x-app")));
+    assert_eq!(
+        item.private_value(),
+        Some(Bytes::from_static(b"some value"))
+    );
+
+    let empty = SourceDescriptionItem::new_private(b"", b"").unwrap();
+    assert_eq!(empty.private_prefix(), Some(Bytes::new()));
+    assert_eq!(empty.private_value(), Some(Bytes::new()));
+
+    let cname = SourceDescriptionItem {
+        sdes_type: SdesType::SdesCname,
+        text: Bytes::from_static(b"\x01xab"),
+    };
+    assert_eq!(cname.private_prefix(), None);
+    assert_eq!(cname.private_value(), None);
+
+    let too_long = vec![b'x'; SDES_MAX_OCTET_COUNT];
+    let got = SourceDescriptionItem::new_private(b"p", &too_long);
+    assert_eq!(Error::SdesTextTooLong, got.err().unwrap());
+}
+
+#[test]
+fn test_source_description_display() {
+    let sd = SourceDescription {
+        chunks: vec![SourceDescriptionChunk {
+            source: 0x902f9e2e,
+            items: vec![
+                SourceDescriptionItem {
+                    sdes_type: SdesType::SdesCname,
+                    text: Bytes::from_static(b"user@host"),
+                },
+                SourceDescriptionItem::new_private(b"x-app", b"42").unwrap(),
+            ],
+        }],
+    };
+
+    assert_eq!(
+        sd.to_string(),
+        "Source Description:\n\t902f9e2e\n\t\tCNAME: user@host\n\t\tPRIV: x-app=42\n"
+    );
+}
+
+#[test]
+fn test_source_description_lookup() {
+    let mut sd = SourceDescription::with_cname(0x902f9e2e, "user@host");
+    sd.chunks.push(SourceDescriptionChunk {
+        source: 0xbc5e9a40,
+        items: vec![SourceDescriptionItem {
+            sdes_type: SdesType::SdesName,
+            text: Bytes::from_static(b"other"),
+        }],
+    });
+    sd.chunks.push(SourceDescriptionChunk {
+        source: 0x902f9e2e,
+        items: vec![SourceDescriptionItem {
+            sdes_type: SdesType::SdesTool,
+            text: Bytes::from_static(b"tool"),
+        }],
+    });
+
+    assert_eq!(
+        sd.cname_for(0x902f9e2e),
+        Some(&Bytes::from_static(b"user@host"))
+    );
+    assert_eq!(sd.cname_for(0xbc5e9a40), None);
+    assert_eq!(sd.cname_for(0), None);
+    assert_eq!(
+        sd.item_for(0x902f9e2e, SdesType::SdesTool),
+        Some(&Bytes::from_static(b"tool"))
+    );
+
+    let types: Vec<SdesType> = sd.items_for(0x902f9e2e).map(|it| it.sdes_type).collect();
+    assert_eq!(types, vec![SdesType::SdesCname, SdesType::SdesTool]);
+    assert_eq!(sd.items_for(0xbc5e9a40).count(), 1);
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::header::PacketType;
+use crate::packet::Packet;
+use crate::payload_feedbacks::full_intra_request::FullIntraRequest;
+use crate::payload_feedbacks::picture_loss_indication::PictureLossIndication;
+use crate::receiver_report::ReceiverReport;
+use crate::reception_report::ReceptionReport;
+use crate::sender_report::SenderReport;
+use crate::transport_feedbacks::transport_layer_nack::TransportLayerNack;
+
+/// PacketTypeStats counts the packets of one type, and their size in octets.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PacketTypeStats {
+    pub packets: u64,
+    pub bytes: u64,
+}
+
+/// SsrcStats holds the feedback received about the media stream of one SSRC.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SsrcStats {
+    /// The number of TransportLayerNack packets about this SSRC
+    pub nack_packets: u64,
+    /// The number of sequence numbers requested by these NACKs
+    pub nacked_packets: u64,
+    /// The number of PictureLossIndication packets about this SSRC
+    pub pli_count: u64,
+    /// The number of FullIntraRequest entries for this SSRC
+    pub fir_count: u64,
+    /// The last report block received about this SSRC
+    pub last_report: Option<ReceptionReport>,
+    /// The time the last report block was received
+    pub last_report_time: Option<Instant>,
+}
+
+/// Accumulator tracks statistics about the RTCP traffic of a session: counts and
+/// sizes per packet type, and NACK, PLI and FIR counts and the last report block
+/// per media SSRC.
+///
+///```nobuild
+///     let mut acc = Accumulator::new(Instant::now());
+///     let packets = rtcp::packet::unmarshal(&mut data)?;
+///     acc.add(&packets, Instant::now());
+///     let nack_rate = acc.nack_rate(media_ssrc, Instant::now());
+///```
+#[derive(Debug, Clone)]
+pub struct Accumulator {
+    started: Instant,
+    total: PacketTypeStats,
+    by_type: HashMap<(PacketType, u8), PacketTypeStats>,
+    by_ssrc: HashMap<u32, SsrcStats>,
+}
+
+impl Accumulator {
+    /// new creates an Accumulator whose rates are computed from `now` on.
+    pub fn new(now: Instant) -> Self {
+        Accumulator {
+            started: now,
+            total: PacketTypeStats::default(),
+            by_type: HashMap::new(),
+            by_ssrc: HashMap::new(),
+        }
+    }
+
+    /// add accounts for packets received at `now`, e.g. the result of `unmarshal`.
+    pub fn add(&mut self, packets: &[Box<dyn Packet + Send + Sync>], now: Instant) {
+        for p in packets {
+            self.add_packet(p.as_ref(), now);
+        }
+    }
+
+    /// add_packet accounts for a packet received at `now`.
+    pub fn add_packet(&mut self, packet: &(dyn Packet + Send + Sync), now: Instant) {
+        let h = packet.header();
+        let bytes = packet.marshal_size() as u64;
+        for stats in [
+            &mut self.total,
+            self.by_type
+                .entry(type_key(h.packet_type, h.count))
+                .or_default(),
+        ] {
+            stats.packets += 1;
+            stats.bytes += bytes;
+        }
+
+        let p = packet.as_any();
+        if let Some(nack) = p.downcast_ref::<TransportLayerNack>() {
+            let stats = self.by_ssrc.entry(nack.media_ssrc).or_default();
+            stats.nack_packets += 1;
+            stats.nacked_packets += nack
+                .nacks
+                .iter()
+                .map(|n| n.packet_list().len() as u64)
+                .sum::<u64>();
+        } else if let Some(pli) = p.downcast_ref::<PictureLossIndication>() {
+            self.by_ssrc.entry(pli.media_ssrc).or_default().pli_count += 1;
+        } else if let Some(fir) = p.downcast_ref::<FullIntraRequest>() {
+            for e in &fir.fir {
+                self.by_ssrc.entry(e.ssrc).or_default().fir_count += 1;
+            }
+        } else if let Some(sr) = p.downcast_ref::<SenderReport>() {
+            self.add_reports(&sr.reports, now);
+        } else if let Some(rr) = p.downcast_ref::<ReceiverReport>() {
+            self.add_reports(&rr.reports, now);
+        }
+    }
+
+    /// total returns the count and size of all packets received.
+    pub fn total(&self) -> PacketTypeStats {
+        self.total
+    }
+
+    /// packet_type_stats returns the count and size of the packets received of a
+    /// type. For feedback packets, `format` selects the feedback message type, and
+    /// is ignored otherwise.
+    pub fn packet_type_stats(&self, packet_type: PacketType, format: u8) -> PacketTypeStats {
+        self.by_type
+            .get(&type_key(packet_type, format))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// ssrc_stats returns the feedback received about the media stream of an SSRC.
+    pub fn ssrc_stats(&self, ssrc: u32) -> Option<&SsrcStats> {
+        self.by_ssrc.get(&ssrc)
+    }
+
+    /// ssrcs returns the media SSRCs feedback was received about.
+    pub fn ssrcs(&self) -> impl Iterator<Item = &u32> {
+        self.by_ssrc.keys()
+    }
+
+    /// nack_rate returns the number of NACK packets per second received about an SSRC.
+    pub fn nack_rate(&self, ssrc: u32, now: Instant) -> f64 {
+        self.rate(self.ssrc_stats(ssrc).map_or(0, |s| s.nack_packets), now)
+    }
+
+    /// pli_rate returns the number of PLI packets per second received about an SSRC.
+    pub fn pli_rate(&self, ssrc: u32, now: Instant) -> f64 {
+        self.rate(self.ssrc_stats(ssrc).map_or(0, |s| s.pli_count), now)
+    }
+
+    /// reset clears all statistics, and restarts the computation of rates at `now`.
+    pub fn reset(&mut self, now: Instant) {
+        *self = Accumulator::new(now);
+    }
+
+    fn add_reports(&mut self, reports: &[ReceptionReport], now: Instant) {
+        for r in reports {
+            let stats = self.by_ssrc.entry(r.ssrc).or_default();
+            stats.last_report = Some(r.clone());
+            stats.last_report_time = Some(now);
+        }
+    }
+
+    fn rate(&self, count: u64, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.started).as_secs_f64();
+        if elapsed > 0.0 {
+            count as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+}
+
+// feedback packets are told apart by their format, carried in the count field
+fn type_key(packet_type: PacketType, count: u8) -> (PacketType, u8) {
+    match packet_type {
+        PacketType::TransportSpecificFeedback | PacketType::PayloadSpecificFeedback => {
+            (packet_type, count)
+        }
+        _ => (packet_type, 0),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::header::{FORMAT_PLI, FORMAT_TLN};
+    use crate::payload_feedbacks::full_intra_request::FirEntry;
+    use crate::transport_feedbacks::transport_layer_nack::NackPair;
+
+    #[test]
+    fn test_accumulator() {
+        let start = Instant::now();
+        let mut acc = Accumulator::new(start);
+
+        let packets: Vec<Box<dyn Packet + Send + Sync>> = vec![
+            Box::new(ReceiverReport {
+                ssrc: 1,
+                reports: vec![ReceptionReport {
+                    ssrc: 0xbc5e9a40,
+                    fraction_lost: 10,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            Box::new(TransportLayerNack {
+                sender_ssrc: 1,
+                media_ssrc: 0xbc5e9a40,
+                nacks: vec![NackPair {
+                    packet_id: 100,
+                    lost_packets: 0b101,
+                }],
+            }),
+            Box::new(PictureLossIndication {
+                sender_ssrc: 1,
+                media_ssrc: 0xbc5e9a40,
+            }),
+            Box::new(PictureLossIndication {
+                sender_ssrc: 1,
+                media_ssrc: 0x12345678,
+            }),
+            Box::new(FullIntraRequest {
+                sender_ssrc: 1,
+                media_ssrc: 0,
+                fir: vec![FirEntry {
+                    ssrc: 0xbc5e9a40,
+                    sequence_number: 1,
+                }],
+            }),
+        ];
+        let size: usize = packets.iter().map(|p| p.marshal_size()).sum();
+
+        acc.add(&packets, start + Duration::from_secs(1));
+        acc.add(&packets[1..2], start + Duration::from_secs(2));
+
+        assert_eq!(acc.total().packets, 6);
+        assert_eq!(acc.total().bytes, (size + packets[1].marshal_size()) as u64);
+        assert_eq!(
+            acc.packet_type_stats(PacketType::ReceiverReport, 0),
+            PacketTypeStats {
+                packets: 1,
+                bytes: 32,
+            }
+        );
+        assert_eq!(
+            acc.packet_type_stats(PacketType::PayloadSpecificFeedback, FORMAT_PLI)
+                .packets,
+            2
+        );
+        assert_eq!(
+            acc.packet_type_stats(PacketType::TransportSpecificFeedback, FORMAT_TLN)
+                .packets,
+            2
+        );
+        assert_eq!(
+            acc.packet_type_stats(PacketType::Goodbye, 0),
+            PacketTypeStats::default()
+        );
+
+        let stats = acc.ssrc_stats(0xbc5e9a40).expect("stats");
+        assert_eq!(stats.nack_packets, 2);
+        assert_eq!(stats.nacked_packets, 6);
+        assert_eq!(stats.pli_count, 1);
+        assert_eq!(stats.fir_count, 1);
+        assert_eq!(
+            stats.last_report.as_ref().map(|r| r.fraction_lost),
+            Some(10)
+        );
+        assert_eq!(stats.last_report_time, Some(start + Duration::from_secs(1)));
+        assert_eq!(acc.ssrcs().count(), 2);
+
+        let now = start + Duration::from_secs(4);
+        assert_eq!(acc.nack_rate(0xbc5e9a40, now), 0.5);
+        assert_eq!(acc.pli_rate(0x12345678, now), 0.25);
+        assert_eq!(acc.pli_rate(0, now), 0.0);
+
+        acc.reset(now);
+        assert_eq!(acc.total(), PacketTypeStats::default());
+        assert!(acc.ssrc_stats(0xbc5e9a40).is_none());
+    }
+}
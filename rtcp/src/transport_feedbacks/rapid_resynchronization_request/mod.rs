@@ -20,6 +20,7 @@ const RRR_MEDIA_OFFSET: usize = 4;
 
 /// The RapidResynchronizationRequest packet informs the encoder about the loss of an undefined amount of coded video data belonging to one or more pictures
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RapidResynchronizationRequest {
     /// SSRC of sender
     pub sender_ssrc: u32,
@@ -27,6 +28,17 @@ pub struct RapidResynchronizationRequest {
     pub media_ssrc: u32,
 }
 
+impl RapidResynchronizationRequest {
+    /// new creates a RapidResynchronizationRequest asking the media source
+    /// `media_ssrc` to resynchronize its decoder state.
+    pub fn new(sender_ssrc: u32, media_ssrc: u32) -> Self {
+        RapidResynchronizationRequest {
+            sender_ssrc,
+            media_ssrc,
+        }
+    }
+}
+
 impl fmt::Display for RapidResynchronizationRequest {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -118,13 +130,23 @@ impl Unmarshal for RapidResynchronizationRequest {
     {
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < (HEADER_LENGTH + (SSRC_LENGTH * 2)) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::TransportSpecificFeedback,
+                offset: 0,
+                expected: HEADER_LENGTH + (SSRC_LENGTH * 2),
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let h = Header::unmarshal(raw_packet)?;
 
         if h.packet_type != PacketType::TransportSpecificFeedback || h.count != FORMAT_RRR {
-            return Err(Error::WrongType.into());
+            return Err(Error::WrongType {
+                packet_type: h.packet_type,
+                count: h.count,
+            }
+            .into());
         }
 
         let sender_ssrc = raw_packet.get_u32();
@@ -26,7 +26,12 @@ fn test_rapid_resynchronization_request_unmarshal() {
                 // report ends early
             ]),
             RapidResynchronizationRequest::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::TransportSpecificFeedback,
+                offset: 0,
+                expected: 12,
+                got: 8,
+            }),
         ),
         (
             "wrong type",
@@ -41,13 +46,21 @@ fn test_rapid_resynchronization_request_unmarshal() {
                 0x0, 0x2, 0x4a, 0x79, // delay=150137
             ]),
             RapidResynchronizationRequest::default(),
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::SenderReport,
+                count: 1,
+            }),
         ),
         (
             "nil",
             Bytes::from_static(&[]),
             RapidResynchronizationRequest::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::TransportSpecificFeedback,
+                offset: 0,
+                expected: 12,
+                got: 0,
+            }),
         ),
     ];
 
@@ -114,3 +127,24 @@ fn test_rapid_resynchronization_request_roundtrip() {
         }
     }
 }
+
+#[test]
+fn test_rapid_resynchronization_request_new() {
+    let rrr = RapidResynchronizationRequest::new(0x902f9e2e, 0xbc5e9a40);
+    assert_eq!(rrr.sender_ssrc, 0x902f9e2e);
+    assert_eq!(rrr.destination_ssrc(), vec![0xbc5e9a40]);
+
+    let mut data = rrr.marshal().expect("marshal");
+    assert_eq!(
+        data,
+        Bytes::from_static(&[
+            0x85, 0xcd, 0x00, 0x02, // v=2, p=0, FMT=5, RTPFB, len=2
+            0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+            0xbc, 0x5e, 0x9a, 0x40, // media=0xbc5e9a40
+        ])
+    );
+    assert_eq!(
+        RapidResynchronizationRequest::unmarshal(&mut data).unwrap(),
+        rrr
+    );
+}
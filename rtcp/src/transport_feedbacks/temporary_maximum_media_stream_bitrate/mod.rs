@@ -0,0 +1,429 @@
+#[cfg(test)]
+mod temporary_maximum_media_stream_bitrate_test;
+
+use std::any::Any;
+use std::fmt;
+
+use bytes::{Buf, BufMut};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+use crate::header::*;
+use crate::packet::*;
+use crate::util::*;
+
+type Result<T> = std::result::Result<T, util::Error>;
+
+const TMMB_HEADER_LENGTH: usize = SSRC_LENGTH * 2;
+const TMMB_ENTRY_LENGTH: usize = 8;
+const TMMB_MANTISSA_BITS: u32 = 17;
+const TMMB_MANTISSA_MAX: u64 = (1 << TMMB_MANTISSA_BITS) - 1;
+const TMMB_OVERHEAD_MAX: u16 = (1 << 9) - 1;
+
+/// A TmmbEntry is the FCI entry shared by TMMBR and TMMBN, see RFC 5104 section 4.2.1.2
+/// and 4.2.2.2.
+///
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                              SSRC                             |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// | MxTBR Exp |  MxTBR Mantissa                 |Measured Overhead|
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TmmbEntry {
+    /// SSRC of the media sender the bitrate limit applies to
+    pub ssrc: u32,
+    /// Maximum total media bit rate in bits per second. On marshal it is
+    /// rounded down to the closest value representable by the 6-bit
+    /// exponent and 17-bit mantissa.
+    pub bitrate: u64,
+    /// Measured per-packet overhead in bytes (9 bits)
+    pub overhead: u16,
+}
+
+impl TmmbEntry {
+    /// bitrate_exp_mantissa returns the exponent and mantissa encoding the
+    /// largest representable bitrate not above `bitrate`.
+    pub fn bitrate_exp_mantissa(bitrate: u64) -> (u8, u32) {
+        let mut exp = 0;
+        let mut mantissa = bitrate;
+        while mantissa > TMMB_MANTISSA_MAX {
+            mantissa >>= 1;
+            exp += 1;
+        }
+        (exp, mantissa as u32)
+    }
+}
+
+impl MarshalSize for TmmbEntry {
+    fn marshal_size(&self) -> usize {
+        TMMB_ENTRY_LENGTH
+    }
+}
+
+impl Marshal for TmmbEntry {
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize> {
+        if self.overhead > TMMB_OVERHEAD_MAX {
+            return Err(Error::InvalidOverhead.into());
+        }
+        if buf.remaining_mut() < TMMB_ENTRY_LENGTH {
+            return Err(Error::BufferTooShort.into());
+        }
+
+        let (exp, mantissa) = TmmbEntry::bitrate_exp_mantissa(self.bitrate);
+
+        buf.put_u32(self.ssrc);
+        buf.put_u32(((exp as u32) << 26) | (mantissa << 9) | self.overhead as u32);
+
+        Ok(TMMB_ENTRY_LENGTH)
+    }
+}
+
+impl Unmarshal for TmmbEntry {
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < TMMB_ENTRY_LENGTH {
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::TransportSpecificFeedback,
+                offset: 0,
+                expected: TMMB_ENTRY_LENGTH,
+                got: raw_packet.remaining(),
+            }
+            .into());
+        }
+
+        let ssrc = raw_packet.get_u32();
+        let b = raw_packet.get_u32();
+
+        let exp = b >> 26;
+        let mantissa = ((b >> 9) as u64) & TMMB_MANTISSA_MAX;
+        let overhead = (b & TMMB_OVERHEAD_MAX as u32) as u16;
+
+        // the exponent can describe values that do not fit in 64 bits
+        if mantissa.leading_zeros() < exp {
+            return Err(Error::InvalidBitrate.into());
+        }
+
+        Ok(TmmbEntry {
+            ssrc,
+            bitrate: mantissa << exp,
+            overhead,
+        })
+    }
+}
+
+fn entries_unmarshal<B>(raw_packet: &mut B, format: u8) -> Result<(u32, u32, Vec<TmmbEntry>)>
+where
+    B: Buf,
+{
+    let raw_packet_len = raw_packet.remaining();
+    if raw_packet_len < (HEADER_LENGTH + TMMB_HEADER_LENGTH) {
+        return Err(Error::PacketTooShort {
+            packet_type: PacketType::TransportSpecificFeedback,
+            offset: 0,
+            expected: HEADER_LENGTH + TMMB_HEADER_LENGTH,
+            got: raw_packet_len,
+        }
+        .into());
+    }
+
+    let h = Header::unmarshal(raw_packet)?;
+
+    if h.packet_type != PacketType::TransportSpecificFeedback || h.count != format {
+        return Err(Error::WrongType {
+            packet_type: h.packet_type,
+            count: h.count,
+        }
+        .into());
+    }
+
+    let length = (h.length as usize) * 4;
+    if raw_packet_len < HEADER_LENGTH + length || length < TMMB_HEADER_LENGTH {
+        return Err(Error::PacketTooShort {
+            packet_type: h.packet_type,
+            offset: HEADER_LENGTH,
+            expected: HEADER_LENGTH + length.max(TMMB_HEADER_LENGTH),
+            got: raw_packet_len.min(HEADER_LENGTH + length),
+        }
+        .into());
+    }
+    let short_entry = (length - TMMB_HEADER_LENGTH) % TMMB_ENTRY_LENGTH;
+    if short_entry != 0 {
+        let offset = HEADER_LENGTH + length - short_entry;
+        return Err(Error::PacketTooShort {
+            packet_type: h.packet_type,
+            offset,
+            expected: offset + TMMB_ENTRY_LENGTH,
+            got: HEADER_LENGTH + length,
+        }
+        .into());
+    }
+
+    let sender_ssrc = raw_packet.get_u32();
+    let media_ssrc = raw_packet.get_u32();
+
+    let count = (length - TMMB_HEADER_LENGTH) / TMMB_ENTRY_LENGTH;
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        entries.push(TmmbEntry::unmarshal(raw_packet)?);
+    }
+
+    if
+    /*h.padding &&*/
+    raw_packet.has_remaining() {
+        raw_packet.advance(raw_packet.remaining());
+    }
+
+    Ok((sender_ssrc, media_ssrc, entries))
+}
+
+fn entries_marshal_to(
+    mut buf: &mut [u8],
+    h: &Header,
+    sender_ssrc: u32,
+    media_ssrc: u32,
+    entries: &[TmmbEntry],
+) -> Result<()> {
+    let n = h.marshal_to(buf)?;
+    buf = &mut buf[n..];
+
+    buf.put_u32(sender_ssrc);
+    buf.put_u32(media_ssrc);
+
+    for entry in entries {
+        let n = entry.marshal_to(buf)?;
+        buf = &mut buf[n..];
+    }
+
+    Ok(())
+}
+
+fn entries_fmt(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    sender_ssrc: u32,
+    entries: &[TmmbEntry],
+) -> fmt::Result {
+    let mut out = format!("{name} from {sender_ssrc:x}\n");
+    out += "\tSSRC    \tBitrate\tOverhead\n";
+    for e in entries {
+        out += format!("\t{:x}\t{}\t{}\n", e.ssrc, e.bitrate, e.overhead).as_str();
+    }
+    write!(f, "{out}")
+}
+
+/// The TemporaryMaximumMediaStreamBitrateRequest (TMMBR) packet is used by a
+/// media receiver to temporarily limit the total media bitrate of one or more
+/// media senders. See RFC 5104 section 4.2.1.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TemporaryMaximumMediaStreamBitrateRequest {
+    /// SSRC of sender
+    pub sender_ssrc: u32,
+    /// SSRC of the media source, always 0 per RFC 5104.
+    pub media_ssrc: u32,
+    pub entries: Vec<TmmbEntry>,
+}
+
+impl fmt::Display for TemporaryMaximumMediaStreamBitrateRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        entries_fmt(
+            f,
+            "TemporaryMaximumMediaStreamBitrateRequest",
+            self.sender_ssrc,
+            &self.entries,
+        )
+    }
+}
+
+impl Packet for TemporaryMaximumMediaStreamBitrateRequest {
+    /// Header returns the Header associated with this packet.
+    fn header(&self) -> Header {
+        Header {
+            padding: get_padding_size(self.raw_size()) != 0,
+            count: FORMAT_TMMBR,
+            packet_type: PacketType::TransportSpecificFeedback,
+            length: ((self.marshal_size() / 4) - 1) as u16,
+        }
+    }
+
+    /// destination_ssrc returns an array of SSRC values that this packet refers to.
+    fn destination_ssrc(&self) -> Vec<u32> {
+        self.entries.iter().map(|e| e.ssrc).collect()
+    }
+
+    fn raw_size(&self) -> usize {
+        HEADER_LENGTH + TMMB_HEADER_LENGTH + self.entries.len() * TMMB_ENTRY_LENGTH
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+
+    fn equal(&self, other: &(dyn Packet + Send + Sync)) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<TemporaryMaximumMediaStreamBitrateRequest>()
+            .map_or(false, |a| self == a)
+    }
+
+    fn cloned(&self) -> Box<dyn Packet + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+impl MarshalSize for TemporaryMaximumMediaStreamBitrateRequest {
+    fn marshal_size(&self) -> usize {
+        let l = self.raw_size();
+        // align to 32-bit boundary
+        l + get_padding_size(l)
+    }
+}
+
+impl Marshal for TemporaryMaximumMediaStreamBitrateRequest {
+    /// Marshal encodes the packet in binary.
+    fn marshal_to(&self, buf: &mut [u8]) -> Result<usize> {
+        if buf.remaining_mut() < self.marshal_size() {
+            return Err(Error::BufferTooShort.into());
+        }
+
+        /*
+         *  0                   1                   2                   3
+         *  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |V=2|P| FMT=3   |   PT=205      |          length               |
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |                  SSRC of packet sender                        |
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * |                  SSRC of media source = 0                     |
+         * +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+         * :            Feedback Control Information (FCI)                 :
+         * :                                                               :
+         */
+        let h = self.header();
+        entries_marshal_to(buf, &h, self.sender_ssrc, self.media_ssrc, &self.entries)?;
+
+        Ok(self.marshal_size())
+    }
+}
+
+impl Unmarshal for TemporaryMaximumMediaStreamBitrateRequest {
+    /// Unmarshal decodes the TemporaryMaximumMediaStreamBitrateRequest from binary
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let (sender_ssrc, media_ssrc, entries) = entries_unmarshal(raw_packet, FORMAT_TMMBR)?;
+
+        Ok(TemporaryMaximumMediaStreamBitrateRequest {
+            sender_ssrc,
+            media_ssrc,
+            entries,
+        })
+    }
+}
+
+/// The TemporaryMaximumMediaStreamBitrateNotification (TMMBN) packet is sent by a
+/// media sender in response to a TMMBR, listing the bounding set of limits it
+/// honors. See RFC 5104 section 4.2.2.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TemporaryMaximumMediaStreamBitrateNotification {
+    /// SSRC of sender
+    pub sender_ssrc: u32,
+    /// SSRC of the media source, always 0 per RFC 5104.
+    pub media_ssrc: u32,
+    pub entries: Vec<TmmbEntry>,
+}
+
+impl fmt::Display for TemporaryMaximumMediaStreamBitrateNotification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        entries_fmt(
+            f,
+            "TemporaryMaximumMediaStreamBitrateNotification",
+            self.sender_ssrc,
+            &self.entries,
+        )
+    }
+}
+
+impl Packet for TemporaryMaximumMediaStreamBitrateNotification {
+    /// Header returns the Header associated with this packet.
+    fn header(&self) -> Header {
+        Header {
+            padding: get_padding_size(self.raw_size()) != 0,
+            count: FORMAT_TMMBN,
+            packet_type: PacketType::TransportSpecificFeedback,
+            length: ((self.marshal_size() / 4) - 1) as u16,
+        }
+    }
+
+    /// destination_ssrc returns an array of SSRC values that this packet refers to.
+    fn destination_ssrc(&self) -> Vec<u32> {
+        self.entries.iter().map(|e| e.ssrc).collect()
+    }
+
+    fn raw_size(&self) -> usize {
+        HEADER_LENGTH + TMMB_HEADER_LENGTH + self.entries.len() * TMMB_ENTRY_LENGTH
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+
+    fn equal(&self, other: &(dyn Packet + Send + Sync)) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<TemporaryMaximumMediaStreamBitrateNotification>()
+            .map_or(false, |a| self == a)
+    }
+
+    fn cloned(&self) -> Box<dyn Packet + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+impl MarshalSize for TemporaryMaximumMediaStreamBitrateNotification {
+    fn marshal_size(&self) -> usize {
+        let l = self.raw_size();
+        // align to 32-bit boundary
+        l + get_padding_size(l)
+    }
+}
+
+impl Marshal for TemporaryMaximumMediaStreamBitrateNotification {
+    /// Marshal encodes the packet in binary.
+    fn marshal_to(&self, buf: &mut [u8]) -> Result<usize> {
+        if buf.remaining_mut() < self.marshal_size() {
+            return Err(Error::BufferTooShort.into());
+        }
+
+        let h = self.header();
+        entries_marshal_to(buf, &h, self.sender_ssrc, self.media_ssrc, &self.entries)?;
+
+        Ok(self.marshal_size())
+    }
+}
+
+impl Unmarshal for TemporaryMaximumMediaStreamBitrateNotification {
+    /// Unmarshal decodes the TemporaryMaximumMediaStreamBitrateNotification from binary
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let (sender_ssrc, media_ssrc, entries) = entries_unmarshal(raw_packet, FORMAT_TMMBN)?;
+
+        Ok(TemporaryMaximumMediaStreamBitrateNotification {
+            sender_ssrc,
+            media_ssrc,
+            entries,
+        })
+    }
+}
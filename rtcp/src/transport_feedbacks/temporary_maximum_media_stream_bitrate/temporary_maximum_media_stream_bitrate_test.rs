@@ -0,0 +1,234 @@
+use bytes::Bytes;
+
+use super::*;
+
+#[test]
+fn test_tmmbr_unmarshal() {
+    let tests = vec![
+        (
+            "valid",
+            Bytes::from_static(&[
+                0x83, 0xcd, 0x00, 0x04, // v=2, p=0, FMT=3, RTPFB, len=4
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0x00, 0x00, 0x00, 0x00, // media=0
+                0xbc, 0x5e, 0x9a, 0x40, // ssrc=0xbc5e9a40
+                0x0f, 0xd0, 0x90, 0x28, // exp=3, mantissa=125000, overhead=40
+            ]),
+            TemporaryMaximumMediaStreamBitrateRequest {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0,
+                entries: vec![TmmbEntry {
+                    ssrc: 0xbc5e9a40,
+                    bitrate: 1_000_000,
+                    overhead: 40,
+                }],
+            },
+            None,
+        ),
+        (
+            "no entries",
+            Bytes::from_static(&[
+                0x83, 0xcd, 0x00, 0x02, // v=2, p=0, FMT=3, RTPFB, len=2
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0x00, 0x00, 0x00, 0x00, // media=0
+            ]),
+            TemporaryMaximumMediaStreamBitrateRequest {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0,
+                entries: vec![],
+            },
+            None,
+        ),
+        (
+            "short entry",
+            Bytes::from_static(&[
+                0x83, 0xcd, 0x00, 0x03, // v=2, p=0, FMT=3, RTPFB, len=3
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0x00, 0x00, 0x00, 0x00, // media=0
+                0xbc, 0x5e, 0x9a, 0x40, // ssrc=0xbc5e9a40
+            ]),
+            TemporaryMaximumMediaStreamBitrateRequest::default(),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::TransportSpecificFeedback,
+                offset: 12,
+                expected: 20,
+                got: 16,
+            }),
+        ),
+        (
+            "bitrate overflow",
+            Bytes::from_static(&[
+                0x83, 0xcd, 0x00, 0x04, // v=2, p=0, FMT=3, RTPFB, len=4
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0x00, 0x00, 0x00, 0x00, // media=0
+                0xbc, 0x5e, 0x9a, 0x40, // ssrc=0xbc5e9a40
+                0xff, 0xff, 0xfe, 0x00, // exp=63, mantissa=131071, overhead=0
+            ]),
+            TemporaryMaximumMediaStreamBitrateRequest::default(),
+            Some(Error::InvalidBitrate),
+        ),
+        (
+            "wrong format",
+            Bytes::from_static(&[
+                0x84, 0xcd, 0x00, 0x02, // v=2, p=0, FMT=4, RTPFB, len=2
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0x00, 0x00, 0x00, 0x00, // media=0
+            ]),
+            TemporaryMaximumMediaStreamBitrateRequest::default(),
+            Some(Error::WrongType {
+                packet_type: PacketType::TransportSpecificFeedback,
+                count: 4,
+            }),
+        ),
+    ];
+
+    for (name, data, want, want_error) in tests {
+        let got = TemporaryMaximumMediaStreamBitrateRequest::unmarshal(&mut data.clone());
+
+        assert_eq!(
+            got.is_err(),
+            want_error.is_some(),
+            "Unmarshal {name}: err = {got:?}, want {want_error:?}"
+        );
+
+        if let Some(err) = want_error {
+            let got_err = got.err().unwrap();
+            assert_eq!(
+                err, got_err,
+                "Unmarshal {name}: err = {got_err:?}, want {err:?}",
+            );
+        } else {
+            let actual = got.unwrap();
+            assert_eq!(
+                actual, want,
+                "Unmarshal {name}: got {actual:?}, want {want:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_tmmbn_roundtrip() {
+    let tests = vec![
+        (
+            "valid",
+            TemporaryMaximumMediaStreamBitrateNotification {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0,
+                entries: vec![
+                    TmmbEntry {
+                        ssrc: 0xbc5e9a40,
+                        bitrate: 1_000_000,
+                        overhead: 40,
+                    },
+                    TmmbEntry {
+                        ssrc: 0x12345678,
+                        bitrate: 131071,
+                        overhead: 511,
+                    },
+                ],
+            },
+            None,
+        ),
+        (
+            "overhead too large",
+            TemporaryMaximumMediaStreamBitrateNotification {
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0,
+                entries: vec![TmmbEntry {
+                    ssrc: 0xbc5e9a40,
+                    bitrate: 1_000_000,
+                    overhead: 512,
+                }],
+            },
+            Some(Error::InvalidOverhead),
+        ),
+    ];
+
+    for (name, want, want_error) in tests {
+        let got = want.marshal();
+
+        assert_eq!(
+            got.is_ok(),
+            want_error.is_none(),
+            "Marshal {name}: err = {got:?}, want {want_error:?}"
+        );
+
+        if let Some(err) = want_error {
+            let got_err = got.err().unwrap();
+            assert_eq!(
+                err, got_err,
+                "Marshal {name}: err = {got_err:?}, want {err:?}",
+            );
+        } else {
+            let mut data = got.ok().unwrap();
+            let actual = TemporaryMaximumMediaStreamBitrateNotification::unmarshal(&mut data)
+                .unwrap_or_else(|_| panic!("Unmarshal {name}"));
+
+            assert_eq!(
+                actual, want,
+                "{name} round trip: got {actual:?}, want {want:?}"
+            )
+        }
+    }
+}
+
+#[test]
+fn test_tmmb_bitrate_rounding() {
+    let tests = vec![
+        (0, 0, 0),
+        (131071, 0, 131071),
+        (131072, 1, 65536),
+        (1_000_000, 3, 125000),
+        (1_000_001, 3, 125000),
+    ];
+
+    for (bitrate, want_exp, want_mantissa) in tests {
+        let (exp, mantissa) = TmmbEntry::bitrate_exp_mantissa(bitrate);
+        assert_eq!(exp, want_exp, "exp of {bitrate}");
+        assert_eq!(mantissa, want_mantissa, "mantissa of {bitrate}");
+    }
+
+    // an encoded bitrate is never larger than the requested one
+    let entry = TmmbEntry {
+        ssrc: 1,
+        bitrate: 1_000_001,
+        overhead: 0,
+    };
+    let decoded = TmmbEntry::unmarshal(&mut entry.marshal().unwrap()).unwrap();
+    assert_eq!(decoded.bitrate, 1_000_000);
+}
+
+#[test]
+fn test_tmmbr_dispatch() {
+    let packet = TemporaryMaximumMediaStreamBitrateRequest {
+        sender_ssrc: 0x902f9e2e,
+        media_ssrc: 0,
+        entries: vec![TmmbEntry {
+            ssrc: 0xbc5e9a40,
+            bitrate: 1_000_000,
+            overhead: 40,
+        }],
+    };
+    let notification = TemporaryMaximumMediaStreamBitrateNotification {
+        sender_ssrc: 0xbc5e9a40,
+        media_ssrc: 0,
+        entries: packet.entries.clone(),
+    };
+
+    let mut data = crate::packet::marshal(&[Box::new(packet.clone()), Box::new(notification)])
+        .expect("marshal");
+    let packets = crate::packet::unmarshal(&mut data).expect("unmarshal");
+
+    assert_eq!(packets.len(), 2);
+    assert_eq!(
+        packets[0]
+            .as_any()
+            .downcast_ref::<TemporaryMaximumMediaStreamBitrateRequest>(),
+        Some(&packet)
+    );
+    assert!(packets[1]
+        .as_any()
+        .downcast_ref::<TemporaryMaximumMediaStreamBitrateNotification>()
+        .is_some());
+}
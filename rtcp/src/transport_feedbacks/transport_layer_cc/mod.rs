@@ -44,6 +44,7 @@ type Result<T> = std::result::Result<T, util::Error>;
 // for packet status chunk
 /// type of packet status chunk
 #[derive(Default, PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u16)]
 pub enum StatusChunkTypeTcc {
     #[default]
@@ -53,6 +54,7 @@ pub enum StatusChunkTypeTcc {
 
 /// type of packet status symbol and recv delta
 #[derive(Default, PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u16)]
 pub enum SymbolTypeTcc {
     /// https://tools.ietf.org/html/draft-holmer-rmcat-transport-wide-cc-extensions-01#section-3.1.1
@@ -69,6 +71,7 @@ pub enum SymbolTypeTcc {
 
 /// for status vector chunk
 #[derive(Default, PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u16)]
 pub enum SymbolSizeTypeTcc {
     /// https://tools.ietf.org/html/draft-holmer-rmcat-transport-wide-cc-extensions-01#section-3.1.4
@@ -109,6 +112,7 @@ impl From<u16> for SymbolTypeTcc {
 /// PacketStatusChunk has two kinds:
 /// RunLengthChunk and StatusVectorChunk
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PacketStatusChunk {
     RunLengthChunk(RunLengthChunk),
     StatusVectorChunk(StatusVectorChunk),
@@ -140,6 +144,7 @@ impl Marshal for PacketStatusChunk {
 /// |T| S |       Run Length        |
 /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RunLengthChunk {
     /// T = TypeTCCRunLengthChunk
     pub type_tcc: StatusChunkTypeTcc,
@@ -213,6 +218,7 @@ impl Unmarshal for RunLengthChunk {
 /// |T|S|       symbol list         |
 /// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatusVectorChunk {
     /// T = TypeTCCRunLengthChunk
     pub type_tcc: StatusChunkTypeTcc,
@@ -310,6 +316,7 @@ impl Unmarshal for StatusVectorChunk {
 /// big delta is 2 bytes: [-8192.0, 8191.75]ms = [-8192000, 8191750]us = [-32768, 32767]*250us
 /// https://tools.ietf.org/html/draft-holmer-rmcat-transport-wide-cc-extensions-01#section-3.1.5
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RecvDelta {
     pub type_tcc_packet: SymbolTypeTcc,
     /// us
@@ -431,6 +438,7 @@ const PACKET_STATUS_CHUNK_LENGTH: usize = 2;
 /// TransportLayerCC for sender-BWE
 /// https://tools.ietf.org/html/draft-holmer-rmcat-transport-wide-cc-extensions-01#page-5
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransportLayerCc {
     /// SSRC of sender
     pub sender_ssrc: u32,
@@ -460,6 +468,9 @@ impl fmt::Display for TransportLayerCc {
         out += format!("\tReference Time {}\n", self.reference_time).as_str();
         out += format!("\tFeedback Packet Count {}\n", self.fb_pkt_count).as_str();
         out += "\tpacket_chunks ";
+        for chunk in &self.packet_chunks {
+            out += format!("{chunk:?} ").as_str();
+        }
         out += "\n\trecv_deltas ";
         for delta in &self.recv_deltas {
             out += format!("{delta:?} ").as_str();
@@ -570,7 +581,13 @@ impl Unmarshal for TransportLayerCc {
     {
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < (HEADER_LENGTH + SSRC_LENGTH) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::TransportSpecificFeedback,
+                offset: 0,
+                expected: HEADER_LENGTH + SSRC_LENGTH,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let h = Header::unmarshal(raw_packet)?;
@@ -580,15 +597,31 @@ impl Unmarshal for TransportLayerCc {
         let total_length = 4 * (h.length + 1) as usize;
 
         if total_length < HEADER_LENGTH + PACKET_CHUNK_OFFSET {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: h.packet_type,
+                offset: 0,
+                expected: HEADER_LENGTH + PACKET_CHUNK_OFFSET,
+                got: total_length,
+            }
+            .into());
         }
 
         if raw_packet_len < total_length {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: h.packet_type,
+                offset: HEADER_LENGTH,
+                expected: total_length,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         if h.packet_type != PacketType::TransportSpecificFeedback || h.count != FORMAT_TCC {
-            return Err(Error::WrongType.into());
+            return Err(Error::WrongType {
+                packet_type: h.packet_type,
+                count: h.count,
+            }
+            .into());
         }
 
         let sender_ssrc = raw_packet.get_u32();
@@ -609,7 +642,13 @@ impl Unmarshal for TransportLayerCc {
         let mut processed_packet_num = 0u16;
         while processed_packet_num < packet_status_count {
             if packet_status_pos + PACKET_STATUS_CHUNK_LENGTH >= total_length {
-                return Err(Error::PacketTooShort.into());
+                return Err(Error::PacketTooShort {
+                    packet_type: h.packet_type,
+                    offset: packet_status_pos,
+                    expected: packet_status_pos + PACKET_STATUS_CHUNK_LENGTH,
+                    got: total_length,
+                }
+                .into());
             }
 
             let mut chunk_reader = raw_packet.copy_to_bytes(PACKET_STATUS_CHUNK_LENGTH);
@@ -686,7 +725,13 @@ impl Unmarshal for TransportLayerCc {
 
         for delta in &mut recv_deltas {
             if recv_deltas_pos >= total_length {
-                return Err(Error::PacketTooShort.into());
+                return Err(Error::PacketTooShort {
+                    packet_type: h.packet_type,
+                    offset: recv_deltas_pos,
+                    expected: recv_deltas_pos + 1,
+                    got: total_length,
+                }
+                .into());
             }
 
             if delta.type_tcc_packet == SymbolTypeTcc::PacketReceivedSmallDelta {
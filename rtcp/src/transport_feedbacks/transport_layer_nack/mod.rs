@@ -19,6 +19,7 @@ type PacketBitmap = u16;
 /// NackPair is a wire-representation of a collection of
 /// Lost RTP packets
 #[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NackPair {
     /// ID of lost packets
     pub packet_id: u16,
@@ -105,6 +106,7 @@ const NACK_OFFSET: usize = 8;
 // IETF RFC 4585, Section 6.2.1
 // https://tools.ietf.org/html/rfc4585#section-6.2.1
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransportLayerNack {
     /// SSRC of sender
     pub sender_ssrc: u32,
@@ -209,17 +211,33 @@ impl Unmarshal for TransportLayerNack {
     {
         let raw_packet_len = raw_packet.remaining();
         if raw_packet_len < (HEADER_LENGTH + SSRC_LENGTH) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::TransportSpecificFeedback,
+                offset: 0,
+                expected: HEADER_LENGTH + SSRC_LENGTH,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         let h = Header::unmarshal(raw_packet)?;
 
         if raw_packet_len < (HEADER_LENGTH + (4 * h.length) as usize) {
-            return Err(Error::PacketTooShort.into());
+            return Err(Error::PacketTooShort {
+                packet_type: h.packet_type,
+                offset: HEADER_LENGTH,
+                expected: HEADER_LENGTH + (4 * h.length) as usize,
+                got: raw_packet_len,
+            }
+            .into());
         }
 
         if h.packet_type != PacketType::TransportSpecificFeedback || h.count != FORMAT_TLN {
-            return Err(Error::WrongType.into());
+            return Err(Error::WrongType {
+                packet_type: h.packet_type,
+                count: h.count,
+            }
+            .into());
         }
 
         let sender_ssrc = raw_packet.get_u32();
@@ -34,7 +34,12 @@ fn test_transport_layer_nack_unmarshal() {
                 // report ends early
             ]),
             TransportLayerNack::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::TransportSpecificFeedback,
+                offset: 4,
+                expected: 12,
+                got: 8,
+            }),
         ),
         (
             "wrong type",
@@ -50,13 +55,21 @@ fn test_transport_layer_nack_unmarshal() {
                 0x0, 0x2, 0x4a, 0x79,
             ]),
             TransportLayerNack::default(),
-            Some(Error::WrongType),
+            Some(Error::WrongType {
+                packet_type: PacketType::SenderReport,
+                count: 1,
+            }),
         ),
         (
             "nil",
             Bytes::from_static(&[]),
             TransportLayerNack::default(),
-            Some(Error::PacketTooShort),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::TransportSpecificFeedback,
+                offset: 0,
+                expected: 8,
+                got: 0,
+            }),
         ),
     ];
 
@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod unknown_feedback_test;
+
+use std::any::Any;
+use std::fmt;
+
+use bytes::{Buf, BufMut, Bytes};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+use crate::header::*;
+use crate::packet::*;
+use crate::util::*;
+
+type Result<T> = std::result::Result<T, util::Error>;
+
+const UNKNOWN_FEEDBACK_OFFSET: usize = 8;
+
+/// An UnknownFeedback packet is a transport layer or payload specific feedback
+/// message whose format is not known to this crate. The common feedback header
+/// is parsed, so that the packet can still be routed by its SSRCs and forwarded,
+/// while the feedback control information (FCI) is kept as is. See RFC 4585 section 6.1.
+///
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |V=2|P|   FMT   |       PT      |          length               |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                  SSRC of packet sender                        |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                  SSRC of media source                         |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// :            Feedback Control Information (FCI)                 :
+/// :                                                               :
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownFeedback {
+    /// Either TransportSpecificFeedback or PayloadSpecificFeedback
+    pub packet_type: PacketType,
+    /// The feedback message type
+    pub format: u8,
+    pub sender_ssrc: u32,
+    pub media_ssrc: u32,
+    /// The feedback control information, without padding
+    pub fci: Bytes,
+}
+
+impl fmt::Display for UnknownFeedback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "UnknownFeedback {} fmt {} {:x} {:x} fci {:?}",
+            self.packet_type, self.format, self.sender_ssrc, self.media_ssrc, self.fci
+        )
+    }
+}
+
+impl Packet for UnknownFeedback {
+    /// Header returns the Header associated with this packet.
+    fn header(&self) -> Header {
+        Header {
+            padding: get_padding_size(self.raw_size()) != 0,
+            count: self.format,
+            packet_type: self.packet_type,
+            length: ((self.marshal_size() / 4) - 1) as u16,
+        }
+    }
+
+    /// destination_ssrc returns an array of SSRC values that this packet refers to.
+    fn destination_ssrc(&self) -> Vec<u32> {
+        vec![self.media_ssrc]
+    }
+
+    fn raw_size(&self) -> usize {
+        HEADER_LENGTH + UNKNOWN_FEEDBACK_OFFSET + self.fci.len()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + Sync) {
+        self
+    }
+
+    fn equal(&self, other: &(dyn Packet + Send + Sync)) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<UnknownFeedback>()
+            .map_or(false, |a| self == a)
+    }
+
+    fn cloned(&self) -> Box<dyn Packet + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+impl MarshalSize for UnknownFeedback {
+    fn marshal_size(&self) -> usize {
+        let l = self.raw_size();
+        // align to 32-bit boundary
+        l + get_padding_size(l)
+    }
+}
+
+impl Marshal for UnknownFeedback {
+    /// marshal_to encodes the UnknownFeedback packet in binary
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize> {
+        if !is_feedback(self.packet_type) {
+            return Err(Error::WrongType {
+                packet_type: self.packet_type,
+                count: self.format,
+            }
+            .into());
+        }
+
+        if buf.remaining_mut() < self.marshal_size() {
+            return Err(Error::BufferTooShort.into());
+        }
+
+        let h = self.header();
+        let n = h.marshal_to(buf)?;
+        buf = &mut buf[n..];
+
+        buf.put_u32(self.sender_ssrc);
+        buf.put_u32(self.media_ssrc);
+        buf.put(self.fci.clone());
+
+        if h.padding {
+            put_padding(buf, self.raw_size());
+        }
+
+        Ok(self.marshal_size())
+    }
+}
+
+impl Unmarshal for UnknownFeedback {
+    /// Unmarshal decodes the UnknownFeedback packet from binary
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let raw_packet_len = raw_packet.remaining();
+        if raw_packet_len < (HEADER_LENGTH + UNKNOWN_FEEDBACK_OFFSET) {
+            return Err(Error::PacketTooShort {
+                packet_type: PacketType::Unsupported,
+                offset: 0,
+                expected: HEADER_LENGTH + UNKNOWN_FEEDBACK_OFFSET,
+                got: raw_packet_len,
+            }
+            .into());
+        }
+
+        let h = Header::unmarshal(raw_packet)?;
+        if !is_feedback(h.packet_type) {
+            return Err(Error::WrongType {
+                packet_type: h.packet_type,
+                count: h.count,
+            }
+            .into());
+        }
+
+        let sender_ssrc = raw_packet.get_u32();
+        let media_ssrc = raw_packet.get_u32();
+
+        let mut fci = raw_packet.copy_to_bytes(raw_packet.remaining());
+        if h.padding {
+            // the last octet counts the padding octets, including itself
+            let padding = fci.last().copied().unwrap_or(0) as usize;
+            if padding == 0 || padding > fci.len() {
+                return Err(Error::WrongPadding.into());
+            }
+            fci.truncate(fci.len() - padding);
+        }
+
+        Ok(UnknownFeedback {
+            packet_type: h.packet_type,
+            format: h.count,
+            sender_ssrc,
+            media_ssrc,
+            fci,
+        })
+    }
+}
+
+fn is_feedback(packet_type: PacketType) -> bool {
+    matches!(
+        packet_type,
+        PacketType::TransportSpecificFeedback | PacketType::PayloadSpecificFeedback
+    )
+}
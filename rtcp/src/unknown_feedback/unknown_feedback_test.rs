@@ -0,0 +1,142 @@
+use bytes::Bytes;
+
+use super::*;
+
+#[test]
+fn test_unknown_feedback_unmarshal() {
+    let tests = vec![
+        (
+            "valid",
+            Bytes::from_static(&[
+                0x89, 0xce, 0x00, 0x03, // v=2, p=0, FMT=9, PSFB, len=3
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0xbc, 0x5e, 0x9a, 0x40, // media=0xbc5e9a40
+                0x01, 0x02, 0x03, 0x04, // fci
+            ]),
+            UnknownFeedback {
+                packet_type: PacketType::PayloadSpecificFeedback,
+                format: 9,
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0xbc5e9a40,
+                fci: Bytes::from_static(&[0x01, 0x02, 0x03, 0x04]),
+            },
+            None,
+        ),
+        (
+            "padded",
+            Bytes::from_static(&[
+                0xbe, 0xcd, 0x00, 0x03, // v=2, p=1, FMT=30, RTPFB, len=3
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+                0xbc, 0x5e, 0x9a, 0x40, // media=0xbc5e9a40
+                0x01, 0x02, 0x00, 0x02, // fci, padding
+            ]),
+            UnknownFeedback {
+                packet_type: PacketType::TransportSpecificFeedback,
+                format: 30,
+                sender_ssrc: 0x902f9e2e,
+                media_ssrc: 0xbc5e9a40,
+                fci: Bytes::from_static(&[0x01, 0x02]),
+            },
+            None,
+        ),
+        (
+            "short packet",
+            Bytes::from_static(&[
+                0x89, 0xce, 0x00, 0x01, // v=2, p=0, FMT=9, PSFB, len=1
+                0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+            ]),
+            UnknownFeedback::default(),
+            Some(Error::PacketTooShort {
+                packet_type: PacketType::Unsupported,
+                offset: 0,
+                expected: 12,
+                got: 8,
+            }),
+        ),
+        (
+            "wrong type",
+            Bytes::from_static(&[
+                0x81, 0xc9, 0x00, 0x02, // v=2, p=0, count=1, RR, len=2
+                0x90, 0x2f, 0x9e, 0x2e, // ssrc=0x902f9e2e
+                0xbc, 0x5e, 0x9a, 0x40, //
+            ]),
+            UnknownFeedback::default(),
+            Some(Error::WrongType {
+                packet_type: PacketType::ReceiverReport,
+                count: 1,
+            }),
+        ),
+    ];
+
+    for (name, data, want, want_error) in tests {
+        let got = UnknownFeedback::unmarshal(&mut data.clone());
+
+        assert_eq!(
+            got.is_err(),
+            want_error.is_some(),
+            "Unmarshal {name}: err = {got:?}, want {want_error:?}"
+        );
+
+        if let Some(err) = want_error {
+            let got_err = got.err().unwrap();
+            assert_eq!(
+                err, got_err,
+                "Unmarshal {name}: err = {got_err:?}, want {err:?}",
+            );
+        } else {
+            let actual = got.unwrap();
+            assert_eq!(
+                actual, want,
+                "Unmarshal {name}: got {actual:?}, want {want:?}"
+            );
+
+            // forwarding the packet reproduces it
+            let data2 = crate::packet::marshal(&[Box::new(actual)]).unwrap();
+            assert_eq!(data2, data, "Marshal {name}");
+        }
+    }
+}
+
+#[test]
+fn test_unknown_feedback_dispatch() {
+    let mut data = Bytes::from_static(&[
+        0x89, 0xce, 0x00, 0x03, // v=2, p=0, FMT=9, PSFB, len=3
+        0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+        0xbc, 0x5e, 0x9a, 0x40, // media=0xbc5e9a40
+        0x01, 0x02, 0x03, 0x04, // fci
+        0x9f, 0xcd, 0x00, 0x02, // v=2, p=0, FMT=31, RTPFB, len=2
+        0x90, 0x2f, 0x9e, 0x2e, // sender=0x902f9e2e
+        0x12, 0x34, 0x56, 0x78, // media=0x12345678
+    ]);
+    let packets = crate::packet::unmarshal(&mut data).expect("unmarshal");
+
+    let formats: Vec<(PacketType, u8, Vec<u32>)> = packets
+        .iter()
+        .map(|p| {
+            let f = p
+                .as_any()
+                .downcast_ref::<UnknownFeedback>()
+                .expect("UnknownFeedback");
+            (f.packet_type, f.format, p.destination_ssrc())
+        })
+        .collect();
+    assert_eq!(
+        formats,
+        vec![
+            (PacketType::PayloadSpecificFeedback, 9, vec![0xbc5e9a40]),
+            (PacketType::TransportSpecificFeedback, 31, vec![0x12345678]),
+        ]
+    );
+
+    let invalid = UnknownFeedback {
+        packet_type: PacketType::Goodbye,
+        ..Default::default()
+    };
+    assert_eq!(
+        Error::WrongType {
+            packet_type: PacketType::Goodbye,
+            count: 0
+        },
+        invalid.marshal().err().unwrap()
+    );
+}
@@ -452,3 +452,114 @@ fn test_split_two_obus_into_two_packets() -> Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_depacketize_round_trip() -> Result<()> {
+    let frame = build_av1_frame(&vec![
+        Av1Obu::new(OBU_TYPE_SEQUENCE_HEADER).with_payload(vec![11, 12]),
+        Av1Obu::new(OBU_TYPE_METADATA).with_payload(vec![21, 22, 23]),
+        Av1Obu::new(OBU_TYPE_FRAME_HEADER)
+            .with_extension(OBU_EXTENSION_S1T1)
+            .with_payload(vec![31]),
+        Av1Obu::new(OBU_TYPE_TILE_GROUP).with_payload(vec![41, 42]),
+        Av1Obu::new(OBU_TYPE_FRAME).with_payload((0..=255).collect()),
+    ]);
+
+    for mtu in [3, 5, 8, 20, 100, 1200] {
+        let mut payloader = Av1Payloader {};
+        let payloads = payloader.payload(mtu, &frame)?;
+
+        let mut depacketizer = Av1Packet::default();
+        let mut depacketized = BytesMut::new();
+        for (i, payload) in payloads.iter().enumerate() {
+            depacketized.put(depacketizer.depacketize(payload)?);
+            assert_eq!(depacketizer.n, i == 0, "mtu {mtu}");
+            assert_eq!(
+                depacketizer.is_partition_head(payload),
+                !depacketizer.z,
+                "mtu {mtu}"
+            );
+        }
+        assert_eq!(depacketized.freeze(), frame, "mtu {mtu}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_depacketize_obu_without_size() -> Result<()> {
+    let mut depacketizer = Av1Packet::default();
+
+    // W=2, the size of the last element is omitted
+    let payload = depacketizer.depacketize(&Bytes::from_static(&[
+        0b0010_0000,
+        2,
+        OBU_TYPE_SEQUENCE_HEADER << 3,
+        11,
+        OBU_TYPE_FRAME << 3 | OBU_HAS_EXTENSION_BIT,
+        OBU_EXTENSION_S1T1,
+        1,
+        2,
+    ]))?;
+    assert_eq!(
+        payload,
+        Bytes::from_static(&[
+            OBU_TYPE_SEQUENCE_HEADER << 3 | OBU_HAS_SIZE_BIT,
+            1,
+            11,
+            OBU_TYPE_FRAME << 3 | OBU_HAS_EXTENSION_BIT | OBU_HAS_SIZE_BIT,
+            OBU_EXTENSION_S1T1,
+            2,
+            1,
+            2,
+        ])
+    );
+
+    // an OBU element size larger than the packet
+    let result = depacketizer.depacketize(&Bytes::from_static(&[0b0000_0000, 5, 1, 2]));
+    assert_eq!(result, Err(Error::ErrShortPacket));
+
+    Ok(())
+}
+
+#[test]
+fn test_depacketize_lost_fragments() -> Result<()> {
+    let mut depacketizer = Av1Packet::default();
+
+    // the OBU continues in a packet that is lost
+    let payload = depacketizer.depacketize(&Bytes::from_static(&[
+        0b0110_0000,
+        2,
+        OBU_TYPE_SEQUENCE_HEADER << 3,
+        11,
+        OBU_TYPE_FRAME << 3,
+        1,
+    ]))?;
+    assert_eq!(
+        payload,
+        Bytes::from_static(&[OBU_TYPE_SEQUENCE_HEADER << 3 | OBU_HAS_SIZE_BIT, 1, 11])
+    );
+
+    // the next packet starts with a new OBU, the buffered fragment is dropped
+    let payload =
+        depacketizer.depacketize(&Bytes::from_static(&[0b0001_0000, OBU_TYPE_FRAME << 3, 2]))?;
+    assert_eq!(
+        payload,
+        Bytes::from_static(&[OBU_TYPE_FRAME << 3 | OBU_HAS_SIZE_BIT, 1, 2])
+    );
+
+    // a continuation whose start was lost is dropped
+    let payload = depacketizer.depacketize(&Bytes::from_static(&[
+        0b1010_0000,
+        1,
+        3,
+        OBU_TYPE_TILE_GROUP << 3,
+        4,
+    ]))?;
+    assert_eq!(
+        payload,
+        Bytes::from_static(&[OBU_TYPE_TILE_GROUP << 3 | OBU_HAS_SIZE_BIT, 1, 4])
+    );
+
+    Ok(())
+}
@@ -1,11 +1,12 @@
 use bytes::{BufMut, Bytes, BytesMut};
 
-use crate::codecs::av1::leb128::BytesMutExt;
-use crate::codecs::av1::obu::{obu_has_extension, parse_obus, OBU_HAS_SIZE_BIT};
+use crate::codecs::av1::leb128::{read_leb128, BytesMutExt};
+use crate::codecs::av1::obu::{obu_has_extension, obu_has_size, parse_obus, OBU_HAS_SIZE_BIT};
 use crate::codecs::av1::packetizer::{
     get_aggregation_header, packetize, AGGREGATION_HEADER_SIZE, MAX_NUM_OBUS_TO_OMIT_SIZE,
 };
-use crate::packetizer::Payloader;
+use crate::error::{Error, Result};
+use crate::packetizer::{Depacketizer, Payloader};
 
 #[cfg(test)]
 mod av1_test;
@@ -19,7 +20,7 @@ pub struct Av1Payloader {}
 impl Payloader for Av1Payloader {
     /// Based on https://chromium.googlesource.com/external/webrtc/+/4e513346ec56c829b3a6010664998469fc237b35/modules/rtp_rtcp/source/rtp_packetizer_av1.cc
     /// Reference: https://aomediacodec.github.io/av1-rtp-spec/#45-payload-structure
-    fn payload(&mut self, mtu: usize, payload: &Bytes) -> crate::error::Result<Vec<Bytes>> {
+    fn payload(&mut self, mtu: usize, payload: &Bytes) -> Result<Vec<Bytes>> {
         // 0                   1                   2                   3
         // 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
         // +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
@@ -120,3 +121,123 @@ impl Payloader for Av1Payloader {
         Box::new(self.clone())
     }
 }
+
+/// Av1Packet represents the AV1 aggregation header that is stored in the payload
+/// of an RTP Packet, and reassembles the OBUs fragmented across packets.
+/// Reference: https://aomediacodec.github.io/av1-rtp-spec/#44-av1-aggregation-header
+#[derive(PartialEq, Eq, Debug, Default, Clone)]
+pub struct Av1Packet {
+    /// the first OBU element is the continuation of an OBU of the previous packet
+    pub z: bool,
+    /// the last OBU element continues in the next packet
+    pub y: bool,
+    /// the count of OBU elements, or 0 if each of them is preceded by its size
+    pub w: u8,
+    /// the packet is the first one of a coded video sequence
+    pub n: bool,
+
+    obu_buffer: Option<BytesMut>,
+}
+
+impl Depacketizer for Av1Packet {
+    /// depacketize returns the OBUs completed by the packet in the low overhead
+    /// bitstream format, that is with their size field. An OBU fragmented across
+    /// packets is returned with its last fragment, or dropped if one of its
+    /// fragments is lost.
+    fn depacketize(&mut self, packet: &Bytes) -> Result<Bytes> {
+        if packet.len() <= AGGREGATION_HEADER_SIZE {
+            return Err(Error::ErrShortPacket);
+        }
+
+        let b0 = packet[0];
+        self.z = b0 & 0x80 != 0;
+        self.y = b0 & 0x40 != 0;
+        self.w = (b0 >> 4) & 0x03;
+        self.n = b0 & 0x08 != 0;
+
+        if !self.z {
+            // the end of the buffered OBU was lost
+            self.obu_buffer = None;
+        }
+
+        let mut payload = BytesMut::new();
+        let mut offset = AGGREGATION_HEADER_SIZE;
+        let mut element_index = 0;
+        while offset < packet.len() {
+            element_index += 1;
+
+            // the last of W elements has no size field
+            let element_size = if element_index == self.w as usize {
+                packet.len() - offset
+            } else {
+                let (size, leb128_size) = read_leb128(&packet.slice(offset..));
+                if leb128_size == 0 {
+                    return Err(Error::ErrShortPacket);
+                }
+                offset += leb128_size;
+                size as usize
+            };
+            if packet.len() < offset + element_size {
+                return Err(Error::ErrShortPacket);
+            }
+            let element = packet.slice(offset..offset + element_size);
+            offset += element_size;
+
+            let obu = if element_index == 1 && self.z {
+                self.obu_buffer.take().map(|mut obu| {
+                    obu.put(element);
+                    obu
+                })
+            } else {
+                Some(BytesMut::from(&element[..]))
+            };
+            // the start of the OBU was lost
+            let Some(obu) = obu else {
+                continue;
+            };
+
+            if offset == packet.len() && self.y {
+                self.obu_buffer = Some(obu);
+            } else if !obu.is_empty() {
+                Av1Packet::put_obu(&obu, &mut payload)?;
+            }
+        }
+
+        Ok(payload.freeze())
+    }
+
+    /// is_partition_head checks whether the packet starts with a new OBU
+    fn is_partition_head(&self, payload: &Bytes) -> bool {
+        if payload.is_empty() {
+            false
+        } else {
+            (payload[0] & 0x80) == 0
+        }
+    }
+
+    fn is_partition_tail(&self, marker: bool, _payload: &Bytes) -> bool {
+        marker
+    }
+}
+
+impl Av1Packet {
+    /// put_obu writes an OBU element with its size field.
+    fn put_obu(obu: &[u8], out: &mut BytesMut) -> Result<()> {
+        let header = obu[0];
+        if obu_has_size(header) {
+            out.put(obu);
+            return Ok(());
+        }
+
+        let header_size = if obu_has_extension(header) { 2 } else { 1 };
+        if obu.len() < header_size {
+            return Err(Error::ErrPayloadTooSmallForObuExtensionHeader);
+        }
+        out.put_u8(header | OBU_HAS_SIZE_BIT);
+        out.put(&obu[1..header_size]);
+        out.put_leb128((obu.len() - header_size) as u32);
+        out.put(&obu[header_size..]);
+
+        Ok(())
+    }
+}
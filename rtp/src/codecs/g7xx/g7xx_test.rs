@@ -48,3 +48,17 @@ fn test_g7xx_payload() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_g7xx_samples() -> Result<()> {
+    let mut pck = G722Payloader::default();
+
+    // 20ms of G722 is 320 samples at 16 kHz, but 160 bytes and timestamp units
+    let frame = Bytes::from(vec![0u8; 160]);
+    let payloads = pck.payload(100, &frame)?;
+    let samples: Vec<Option<u32>> = payloads.iter().map(|p| pck.samples(p)).collect();
+    assert_eq!(samples, vec![Some(100), Some(60)]);
+    assert_eq!(G722_CLOCK_RATE, G711_CLOCK_RATE);
+
+    Ok(())
+}
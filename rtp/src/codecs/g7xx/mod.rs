@@ -6,11 +6,22 @@ use bytes::Bytes;
 use crate::error::Result;
 use crate::packetizer::Payloader;
 
+/// G711_CLOCK_RATE is the RTP clock rate of PCMU and PCMA, whose samples are
+/// one byte each.
+pub const G711_CLOCK_RATE: u32 = 8000;
+/// G722_CLOCK_RATE is the RTP clock rate of G722. Its sampling rate is 16 kHz,
+/// but RFC 3551 section 4.5.2 keeps the 8 kHz clock of the original payload
+/// type assignment, so that each byte of G722 data is one timestamp unit too.
+pub const G722_CLOCK_RATE: u32 = 8000;
+
 /// G711Payloader payloads G711 packets
 pub type G711Payloader = G7xxPayloader;
 /// G722Payloader payloads G722 packets
 pub type G722Payloader = G7xxPayloader;
 
+/// G7xxPayloader splits G711 and G722 data across packets at any byte. With
+/// the clock rates above, a packet covers as many timestamp units as it has
+/// bytes, which the packetizer uses to stamp the packets of a single frame.
 #[derive(Default, Debug, Copy, Clone)]
 pub struct G7xxPayloader;
 
@@ -40,4 +51,9 @@ impl Payloader for G7xxPayloader {
     fn clone_to(&self) -> Box<dyn Payloader + Send + Sync> {
         Box::new(*self)
     }
+
+    /// samples returns the timestamp units covered by a payload, one per byte.
+    fn samples(&self, payload: &Bytes) -> Option<u32> {
+        Some(payload.len() as u32)
+    }
 }
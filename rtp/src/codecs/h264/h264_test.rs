@@ -261,3 +261,104 @@ fn test_h264_payloader_payload_sps_and_pps_handling() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_h264_payloader_payload_sps_and_pps_larger_than_mtu() -> Result<()> {
+    let mut pck = H264Payloader::default();
+    let expected = vec![
+        Bytes::from_static(&[0x07, 0x00, 0x01]),
+        Bytes::from_static(&[0x08, 0x02, 0x03, 0x04, 0x05, 0x06]),
+        Bytes::from_static(&[0x05, 0x04, 0x05]),
+    ];
+
+    // The STAP-A would be 14 bytes long, so SPS and PPS are sent separately
+    let stream = Bytes::from_static(&[
+        0x00, 0x00, 0x01, 0x07, 0x00, 0x01, 0x00, 0x00, 0x01, 0x08, 0x02, 0x03, 0x04, 0x05, 0x06,
+        0x00, 0x00, 0x01, 0x05, 0x04, 0x05,
+    ]);
+    let actual = pck.payload(10, &stream)?;
+    assert_eq!(actual, expected, "SPS and PPS aren't sent separately");
+
+    // and are fragmented if they don't fit on their own either
+    let stream = Bytes::from_static(&[
+        0x00, 0x00, 0x01, 0x07, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x00, 0x00, 0x01, 0x08, 0x02,
+        0x00, 0x00, 0x01, 0x05, 0x04, 0x05,
+    ]);
+    let actual = pck.payload(5, &stream)?;
+    let expected = vec![
+        Bytes::from_static(&[0x1c, 0x87, 0x00, 0x01, 0x02]),
+        Bytes::from_static(&[0x1c, 0x47, 0x03, 0x04, 0x05]),
+        Bytes::from_static(&[0x08, 0x02]),
+        Bytes::from_static(&[0x05, 0x04, 0x05]),
+    ];
+    assert_eq!(actual, expected, "SPS isn't fragmented");
+
+    Ok(())
+}
+
+#[test]
+fn test_h264_packet_lost_fragments() -> Result<()> {
+    let mut pkt = H264Packet::default();
+
+    // the start of the first NALU and the end of the second one are lost
+    let fragments = vec![
+        Bytes::from_static(&[0x7c, 0x05, 0x01, 0x02]),
+        Bytes::from_static(&[0x7c, 0x45, 0x03, 0x04]),
+        Bytes::from_static(&[0x7c, 0x85, 0x05, 0x06]),
+        Bytes::from_static(&[0x7c, 0x05, 0x07, 0x08]),
+        Bytes::from_static(&[0x7c, 0x81, 0x09, 0x0a]),
+        Bytes::from_static(&[0x7c, 0x41, 0x0b, 0x0c]),
+    ];
+    let mut nalus = vec![];
+    for fragment in &fragments {
+        let payload = pkt.depacketize(fragment)?;
+        if !payload.is_empty() {
+            nalus.push(payload);
+        }
+    }
+    assert_eq!(
+        nalus,
+        vec![Bytes::from_static(&[
+            0x00, 0x00, 0x00, 0x01, 0x61, 0x09, 0x0a, 0x0b, 0x0c
+        ])],
+        "Fragments of incomplete NALUs must be dropped"
+    );
+
+    // a STAP-A cut in the middle of a NALU size
+    let data = Bytes::from_static(&[0x78, 0x00, 0x01, 0x09, 0x00]);
+    assert_eq!(pkt.depacketize(&data), Err(Error::ErrShortPacket));
+
+    Ok(())
+}
+
+#[test]
+fn test_h264_is_key_frame() {
+    let tests = vec![
+        ("empty", vec![], false),
+        ("idr slice", vec![0x65, 0x88, 0x84], true),
+        ("sps", vec![0x67, 0x42, 0xc0], true),
+        ("non-idr slice", vec![0x41, 0x9a, 0x02], false),
+        (
+            "stap-a with sps and pps",
+            vec![0x78, 0x00, 0x02, 0x67, 0x42, 0x00, 0x02, 0x68, 0xce],
+            true,
+        ),
+        (
+            "stap-a with sei and idr",
+            vec![0x78, 0x00, 0x02, 0x06, 0x05, 0x00, 0x02, 0x65, 0x88],
+            true,
+        ),
+        (
+            "stap-a with sei only",
+            vec![0x78, 0x00, 0x02, 0x06, 0x05],
+            false,
+        ),
+        ("fu-a idr start", vec![0x7c, 0x85, 0x88, 0x84], true),
+        ("fu-a idr middle", vec![0x7c, 0x05, 0x88, 0x84], false),
+        ("fu-a non-idr start", vec![0x5c, 0x81, 0x9a, 0x02], false),
+    ];
+
+    for (name, payload, want) in tests {
+        assert_eq!(is_key_frame(&Bytes::from(payload)), want, "{name}");
+    }
+}
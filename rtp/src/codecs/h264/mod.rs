@@ -13,6 +13,7 @@ pub struct H264Payloader {
     pps_nalu: Option<Bytes>,
 }
 
+pub const IDR_NALU_TYPE: u8 = 5;
 pub const STAPA_NALU_TYPE: u8 = 24;
 pub const FUA_NALU_TYPE: u8 = 28;
 pub const FUB_NALU_TYPE: u8 = 29;
@@ -56,7 +57,6 @@ impl H264Payloader {
         }
 
         let nalu_type = nalu[0] & NALU_TYPE_BITMASK;
-        let nalu_ref_idc = nalu[0] & NALU_REF_IDC_BITMASK;
 
         if nalu_type == AUD_NALU_TYPE || nalu_type == FILLER_NALU_TYPE {
             return;
@@ -79,6 +79,10 @@ impl H264Payloader {
             stap_a_nalu.extend_from_slice(pps_nalu);
             if stap_a_nalu.len() <= mtu {
                 payloads.push(Bytes::from(stap_a_nalu));
+            } else {
+                // SPS and PPS do not fit in a single STAP-A, send them on their own
+                H264Payloader::emit_nalu(sps_nalu, mtu, payloads);
+                H264Payloader::emit_nalu(pps_nalu, mtu, payloads);
             }
         }
 
@@ -87,6 +91,15 @@ impl H264Payloader {
             self.pps_nalu = None;
         }
 
+        H264Payloader::emit_nalu(nalu, mtu, payloads);
+    }
+
+    /// emit_nalu sends a NALU as a single NALU packet, or as FU-A packets if it
+    /// does not fit in the MTU.
+    fn emit_nalu(nalu: &Bytes, mtu: usize, payloads: &mut Vec<Bytes>) {
+        let nalu_type = nalu[0] & NALU_TYPE_BITMASK;
+        let nalu_ref_idc = nalu[0] & NALU_REF_IDC_BITMASK;
+
         // Single NALU
         if nalu.len() <= mtu {
             payloads.push(nalu.clone());
@@ -230,6 +243,9 @@ impl Depacketizer for H264Packet {
             STAPA_NALU_TYPE => {
                 let mut curr_offset = STAPA_HEADER_SIZE;
                 while curr_offset < packet.len() {
+                    if packet.len() < curr_offset + STAPA_NALU_LENGTH_SIZE {
+                        return Err(Error::ErrShortPacket);
+                    }
                     let nalu_size =
                         ((packet[curr_offset] as usize) << 8) | packet[curr_offset + 1] as usize;
                     curr_offset += STAPA_NALU_LENGTH_SIZE;
@@ -257,15 +273,19 @@ impl Depacketizer for H264Packet {
                     return Err(Error::ErrShortPacket);
                 }
 
-                if self.fua_buffer.is_none() {
+                let b1 = packet[1];
+                if b1 & FU_START_BITMASK != 0 {
+                    // drop the fragments of a NALU whose end was lost
                     self.fua_buffer = Some(BytesMut::new());
                 }
 
                 if let Some(fua_buffer) = &mut self.fua_buffer {
                     fua_buffer.put(&*packet.slice(FUA_HEADER_SIZE..));
+                } else {
+                    // the start of the NALU was lost
+                    return Ok(Bytes::new());
                 }
 
-                let b1 = packet[1];
                 if b1 & FU_END_BITMASK != 0 {
                     let nalu_ref_idc = b0 & NALU_REF_IDC_BITMASK;
                     let fragmented_nalu_type = b1 & NALU_TYPE_BITMASK;
@@ -308,3 +328,36 @@ impl Depacketizer for H264Packet {
         marker
     }
 }
+
+/// is_key_frame checks if the RTP payload starts a H264 key frame, as it
+/// carries an IDR slice or an SPS, or the first fragment of one.
+pub fn is_key_frame(payload: &Bytes) -> bool {
+    let is_key_nalu = |nalu_type: u8| nalu_type == IDR_NALU_TYPE || nalu_type == SPS_NALU_TYPE;
+
+    if payload.is_empty() {
+        return false;
+    }
+
+    match payload[0] & NALU_TYPE_BITMASK {
+        STAPA_NALU_TYPE => {
+            let mut curr_offset = STAPA_HEADER_SIZE;
+            while curr_offset + STAPA_NALU_LENGTH_SIZE < payload.len() {
+                let nalu_size =
+                    ((payload[curr_offset] as usize) << 8) | payload[curr_offset + 1] as usize;
+                curr_offset += STAPA_NALU_LENGTH_SIZE;
+
+                if is_key_nalu(payload[curr_offset] & NALU_TYPE_BITMASK) {
+                    return true;
+                }
+                curr_offset += nalu_size;
+            }
+            false
+        }
+        FUA_NALU_TYPE | FUB_NALU_TYPE => {
+            payload.len() >= FUA_HEADER_SIZE
+                && payload[1] & FU_START_BITMASK != 0
+                && is_key_nalu(payload[1] & NALU_TYPE_BITMASK)
+        }
+        nalu_type => is_key_nalu(nalu_type),
+    }
+}
@@ -731,6 +731,16 @@ fn test_h265_paci_packet() -> Result<()> {
         }
     }
 
+    let mut parsed = H265PACIPacket::default();
+    parsed.depacketize(&Bytes::from_static(&[
+        0x64, 0x01, 0x64, 0b00111000, 0xaa, 0xbb, 0x80, 0xab, 0xcd, 0xef,
+    ]))?;
+    let tsci = parsed.tsci().unwrap();
+    assert_eq!(tsci.tl0picidx(), 0xaa, "invalid TL0PICIDX");
+    assert_eq!(tsci.irap_pic_id(), 0xbb, "invalid IrapPicID");
+    assert!(tsci.s(), "invalid S");
+    assert!(!tsci.e(), "invalid E");
+
     Ok(())
 }
 
@@ -789,11 +799,19 @@ fn test_h265_packet() -> Result<()> {
         // Valid H265PACIPacket
         TestType {
             raw: Bytes::from_static(&[
-                0x64, 0x01, 0x64, 0b00111000, 0xaa, 0xbb, 0x80, 0xab, 0xcd, 0xef,
+                0x64, 0x01, 0x02, 0b00111000, 0xaa, 0xbb, 0x80, 0xab, 0xcd, 0xef,
             ]),
             expected_packet_type: Some(H265Payload::H265PACIPacket(H265PACIPacket::default())),
             ..Default::default()
         },
+        // Nested H265PACIPacket
+        TestType {
+            raw: Bytes::from_static(&[
+                0x64, 0x01, 0x64, 0b00111000, 0xaa, 0xbb, 0x80, 0xab, 0xcd, 0xef,
+            ]),
+            expected_err: Some(Error::ErrInvalidH265PacketType),
+            ..Default::default()
+        },
         // Valid H265FragmentationUnitPacket
         TestType {
             raw: Bytes::from_static(&[0x62, 0x01, 0x93, 0xcc, 0xdd, 0xaf, 0x0d, 0x5a]),
@@ -887,3 +905,222 @@ fn test_h265_packet_real() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_h265_packet_depacketize_nalus() -> Result<()> {
+    let tests = vec![
+        (
+            "single nal unit",
+            false,
+            vec![&[0x26, 0x01, 0xab, 0xcd][..]],
+            vec![&[0x00, 0x00, 0x00, 0x01, 0x26, 0x01, 0xab, 0xcd][..]],
+        ),
+        (
+            "single nal unit with donl",
+            true,
+            vec![&[0x26, 0x01, 0x00, 0x07, 0xab, 0xcd]],
+            vec![&[0x00, 0x00, 0x00, 0x01, 0x26, 0x01, 0xab, 0xcd]],
+        ),
+        (
+            "aggregation packet",
+            false,
+            vec![&[
+                0x60, 0x01, 0x00, 0x03, 0x40, 0x01, 0xaa, 0x00, 0x04, 0x42, 0x01, 0xbb, 0xcc,
+            ]],
+            vec![&[
+                0x00, 0x00, 0x00, 0x01, 0x40, 0x01, 0xaa, 0x00, 0x00, 0x00, 0x01, 0x42, 0x01, 0xbb,
+                0xcc,
+            ]],
+        ),
+        (
+            "aggregation packet with donl",
+            true,
+            vec![&[
+                0x60, 0x01, 0x00, 0x07, 0x00, 0x03, 0x40, 0x01, 0xaa, 0x00, 0x00, 0x03, 0x44, 0x01,
+                0xbb,
+            ]],
+            vec![&[
+                0x00, 0x00, 0x00, 0x01, 0x40, 0x01, 0xaa, 0x00, 0x00, 0x00, 0x01, 0x44, 0x01, 0xbb,
+            ]],
+        ),
+        (
+            "fragmentation units",
+            false,
+            vec![
+                &[0x62, 0x01, 0x93, 0xaa, 0xbb],
+                &[0x62, 0x01, 0x13, 0xcc],
+                &[0x62, 0x01, 0x53, 0xdd],
+            ],
+            vec![
+                &[],
+                &[],
+                &[0x00, 0x00, 0x00, 0x01, 0x26, 0x01, 0xaa, 0xbb, 0xcc, 0xdd],
+            ],
+        ),
+        (
+            "fragmentation units with donl",
+            true,
+            vec![
+                &[0x62, 0x01, 0x93, 0x00, 0x07, 0xaa],
+                &[0x62, 0x01, 0x53, 0xbb],
+            ],
+            vec![&[], &[0x00, 0x00, 0x00, 0x01, 0x26, 0x01, 0xaa, 0xbb]],
+        ),
+        (
+            "fragmentation units with lost start",
+            false,
+            vec![
+                &[0x62, 0x01, 0x13, 0xcc],
+                &[0x62, 0x01, 0x53, 0xdd],
+                &[0x62, 0x01, 0x93, 0xaa],
+                &[0x62, 0x01, 0x53, 0xbb],
+            ],
+            vec![
+                &[],
+                &[],
+                &[],
+                &[0x00, 0x00, 0x00, 0x01, 0x26, 0x01, 0xaa, 0xbb],
+            ],
+        ),
+        (
+            "fragmentation units with lost end",
+            false,
+            vec![
+                &[0x62, 0x01, 0x93, 0xaa],
+                &[0x62, 0x01, 0x81, 0xbb],
+                &[0x62, 0x01, 0x41, 0xcc],
+            ],
+            vec![&[], &[], &[0x00, 0x00, 0x00, 0x01, 0x02, 0x01, 0xbb, 0xcc]],
+        ),
+        (
+            "paci packet",
+            false,
+            vec![&[0x64, 0x01, 0x26, 0b00111000, 0xaa, 0xbb, 0x80, 0xab, 0xcd]],
+            vec![&[0x00, 0x00, 0x00, 0x01, 0x26, 0x01, 0xab, 0xcd]],
+        ),
+        (
+            "paci packet with fragmentation unit",
+            false,
+            vec![
+                &[0x64, 0x01, 0x62, 0b00010000, 0xaa, 0x93, 0xab],
+                &[0x62, 0x01, 0x53, 0xcd],
+            ],
+            vec![&[], &[0x00, 0x00, 0x00, 0x01, 0x26, 0x01, 0xab, 0xcd]],
+        ),
+    ];
+
+    for (name, with_donl, packets, expected) in tests {
+        let mut pck = H265Packet::default();
+        pck.with_donl(with_donl);
+
+        for (packet, expected) in packets.into_iter().zip(expected) {
+            let nalus = pck.depacketize(&Bytes::copy_from_slice(packet))?;
+            assert_eq!(&nalus[..], expected, "{name}");
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_h265_packet_is_partition_head() {
+    let pck = H265Packet::default();
+
+    assert!(pck.is_partition_head(&Bytes::from_static(&[0x26, 0x01, 0xab, 0xcd])));
+    assert!(pck.is_partition_head(&Bytes::from_static(&[0x62, 0x01, 0x93, 0xab])));
+    assert!(!pck.is_partition_head(&Bytes::from_static(&[0x62, 0x01, 0x13, 0xab])));
+    assert!(!pck.is_partition_head(&Bytes::from_static(&[0x62, 0x01, 0x53, 0xab])));
+}
+
+#[test]
+fn test_h265_payloader() -> Result<()> {
+    let mut pck = H265Payloader::default();
+
+    // Empty payload and zero MTU
+    assert!(pck.payload(1200, &Bytes::new())?.is_empty());
+    assert!(pck
+        .payload(0, &Bytes::from_static(&[0x26, 0x01, 0xab]))?
+        .is_empty());
+
+    // Payload without start code is a single NAL unit
+    let result = pck.payload(1200, &Bytes::from_static(&[0x26, 0x01, 0xab]))?;
+    assert_eq!(result, vec![Bytes::from_static(&[0x26, 0x01, 0xab])]);
+
+    // Small NAL units are aggregated, with the lowest tid in the AP header
+    let stream = Bytes::from_static(&[
+        0x00, 0x00, 0x00, 0x01, 0x40, 0x02, 0xaa, 0x00, 0x00, 0x01, 0x42, 0x01, 0xbb, 0xcc,
+    ]);
+    let result = pck.payload(1200, &stream)?;
+    assert_eq!(
+        result,
+        vec![Bytes::from_static(&[
+            0x60, 0x01, 0x00, 0x03, 0x40, 0x02, 0xaa, 0x00, 0x04, 0x42, 0x01, 0xbb, 0xcc,
+        ])]
+    );
+
+    // Units which do not fit in the same AP start a new one
+    let result = pck.payload(10, &stream)?;
+    assert_eq!(
+        result,
+        vec![
+            Bytes::from_static(&[0x40, 0x02, 0xaa]),
+            Bytes::from_static(&[0x42, 0x01, 0xbb, 0xcc]),
+        ]
+    );
+
+    // A NAL unit larger than the MTU is fragmented
+    let stream = Bytes::from_static(&[0x00, 0x00, 0x01, 0x26, 0x01, 0xaa, 0xbb, 0xcc, 0xdd, 0xee]);
+    let result = pck.payload(5, &stream)?;
+    assert_eq!(
+        result,
+        vec![
+            Bytes::from_static(&[0x62, 0x01, 0x93, 0xaa, 0xbb]),
+            Bytes::from_static(&[0x62, 0x01, 0x13, 0xcc, 0xdd]),
+            Bytes::from_static(&[0x62, 0x01, 0x53, 0xee]),
+        ]
+    );
+
+    // Aggregation can be disabled
+    let stream = Bytes::from_static(&[
+        0x00, 0x00, 0x00, 0x01, 0x40, 0x01, 0xaa, 0x00, 0x00, 0x00, 0x01, 0x42, 0x01, 0xbb,
+    ]);
+    let mut pck = H265Payloader {
+        skip_aggregation: true,
+    };
+    let result = pck.payload(1200, &stream)?;
+    assert_eq!(
+        result,
+        vec![
+            Bytes::from_static(&[0x40, 0x01, 0xaa]),
+            Bytes::from_static(&[0x42, 0x01, 0xbb]),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_h265_payloader_round_trip() -> Result<()> {
+    // VPS, SPS, PPS and an IDR slice
+    let mut stream = vec![];
+    for (header, size) in [(0x40, 20), (0x42, 40), (0x44, 8), (0x26, 3000)] {
+        stream.extend_from_slice(&[0x00, 0x00, 0x00, 0x01, header, 0x01]);
+        stream.extend((0..size).map(|i| (i % 251 + 2) as u8));
+    }
+    let stream = Bytes::from(stream);
+
+    for mtu in [5, 20, 100, 1200, 4000] {
+        let mut payloader = H265Payloader::default();
+        let payloads = payloader.payload(mtu, &stream)?;
+
+        let mut pck = H265Packet::default();
+        let mut out = vec![];
+        for payload in &payloads {
+            assert!(payload.len() <= mtu, "mtu {mtu}");
+            out.extend_from_slice(&pck.depacketize(payload)?);
+        }
+        assert_eq!(out, stream, "mtu {mtu}");
+    }
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
 
 use crate::error::{Error, Result};
-use crate::packetizer::Depacketizer;
+use crate::packetizer::{Depacketizer, Payloader};
 
 #[cfg(test)]
 mod h265_test;
@@ -17,6 +17,12 @@ const H265NALU_AGGREGATION_PACKET_TYPE: u8 = 48;
 const H265NALU_FRAGMENTATION_UNIT_TYPE: u8 = 49;
 /// https://datatracker.ietf.org/doc/html/rfc7798#section-4.4.4
 const H265NALU_PACI_PACKET_TYPE: u8 = 50;
+/// Mask of the Type field in a H265 NAL Unit Header.
+const H265NALU_TYPE_BITMASK: u16 = 0b01111110 << 8;
+/// Size of the NALU size field of an aggregation unit.
+const H265AGGREGATION_UNIT_SIZE_LENGTH: usize = 2;
+
+static ANNEXB_NALUSTART_CODE: Bytes = Bytes::from_static(&[0x00, 0x00, 0x00, 0x01]);
 
 /// H265NALUHeader is a H265 NAL Unit Header
 /// https://datatracker.ietf.org/doc/html/rfc7798#section-1.1.4
@@ -607,7 +613,9 @@ impl H265PACIPacket {
         }
 
         Some(H265TSCI(
-            ((self.phes[0] as u32) << 16) | ((self.phes[1] as u32) << 8) | self.phes[0] as u32,
+            ((self.phes[0] as u32) << 24)
+                | ((self.phes[1] as u32) << 16)
+                | ((self.phes[2] as u32) << 8),
         ))
     }
 
@@ -718,10 +726,15 @@ impl Default for H265Payload {
 ///
 
 /// H265Packet represents a H265 packet, stored in the payload of an RTP packet.
+///
+/// depacketize returns the NAL units carried by the packet in Annex B format,
+/// without their DONL and DOND fields. The fragments of a NAL unit are buffered
+/// until its last one, and a NAL unit whose first fragment was lost is dropped.
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct H265Packet {
     payload: H265Payload,
     might_need_donl: bool,
+    fu_buffer: Option<BytesMut>,
 }
 
 impl H265Packet {
@@ -740,11 +753,9 @@ impl H265Packet {
     pub fn payload(&self) -> &H265Payload {
         &self.payload
     }
-}
 
-impl Depacketizer for H265Packet {
-    /// depacketize parses the passed byte slice and stores the result in the H265Packet this method is called upon
-    fn depacketize(&mut self, payload: &Bytes) -> Result<Bytes> {
+    /// parse decodes the passed byte slice into the packet type given by its payload header.
+    fn parse(&self, payload: &Bytes) -> Result<H265Payload> {
         if payload.len() <= H265NALU_HEADER_SIZE {
             return Err(Error::ErrShortPacket);
         }
@@ -758,40 +769,294 @@ impl Depacketizer for H265Packet {
             let mut decoded = H265PACIPacket::default();
             decoded.depacketize(payload)?;
 
-            self.payload = H265Payload::H265PACIPacket(decoded);
+            Ok(H265Payload::H265PACIPacket(decoded))
         } else if payload_header.is_fragmentation_unit() {
             let mut decoded = H265FragmentationUnitPacket::default();
             decoded.with_donl(self.might_need_donl);
 
             decoded.depacketize(payload)?;
 
-            self.payload = H265Payload::H265FragmentationUnitPacket(decoded);
+            Ok(H265Payload::H265FragmentationUnitPacket(decoded))
         } else if payload_header.is_aggregation_packet() {
             let mut decoded = H265AggregationPacket::default();
             decoded.with_donl(self.might_need_donl);
 
             decoded.depacketize(payload)?;
 
-            self.payload = H265Payload::H265AggregationPacket(decoded);
+            Ok(H265Payload::H265AggregationPacket(decoded))
         } else {
             let mut decoded = H265SingleNALUnitPacket::default();
             decoded.with_donl(self.might_need_donl);
 
             decoded.depacketize(payload)?;
 
-            self.payload = H265Payload::H265SingleNALUnitPacket(decoded);
+            Ok(H265Payload::H265SingleNALUnitPacket(decoded))
+        }
+    }
+
+    /// emit returns the NAL units of a parsed packet in Annex B format.
+    fn emit(&mut self, payload: &H265Payload) -> Result<Bytes> {
+        let mut out = BytesMut::new();
+
+        match payload {
+            H265Payload::H265SingleNALUnitPacket(packet) => {
+                out.put(&*ANNEXB_NALUSTART_CODE);
+                out.put_u16(packet.payload_header().0);
+                out.put(&*packet.payload());
+            }
+            H265Payload::H265AggregationPacket(packet) => {
+                if let Some(first_unit) = packet.first_unit() {
+                    out.put(&*ANNEXB_NALUSTART_CODE);
+                    out.put(&*first_unit.nal_unit());
+                }
+                for unit in packet.other_units() {
+                    out.put(&*ANNEXB_NALUSTART_CODE);
+                    out.put(&*unit.nal_unit());
+                }
+            }
+            H265Payload::H265FragmentationUnitPacket(packet) => {
+                let fu_header = packet.fu_header();
+                if fu_header.s() {
+                    // the NAL unit header is the payload header with the type of the FU header
+                    let nalu_header = (packet.payload_header().0 & !H265NALU_TYPE_BITMASK)
+                        | ((fu_header.fu_type() as u16) << 9);
+
+                    // drop the fragments of a NAL unit whose end was lost
+                    let mut fu_buffer = BytesMut::new();
+                    fu_buffer.put_u16(nalu_header);
+                    self.fu_buffer = Some(fu_buffer);
+                }
+
+                if let Some(fu_buffer) = &mut self.fu_buffer {
+                    fu_buffer.put(&*packet.payload());
+                } else {
+                    // the start of the NAL unit was lost
+                    return Ok(Bytes::new());
+                }
+
+                if fu_header.e() {
+                    if let Some(fu_buffer) = self.fu_buffer.take() {
+                        out.put(&*ANNEXB_NALUSTART_CODE);
+                        out.put(fu_buffer);
+                    }
+                }
+            }
+            H265Payload::H265PACIPacket(packet) => {
+                // PACI packets must not be nested
+                if packet.ctype() == H265NALU_PACI_PACKET_TYPE {
+                    return Err(Error::ErrInvalidH265PacketType);
+                }
+
+                // the header of the PACI payload is the payload header with
+                // the F bit and type copied in the A and cType fields
+                let payload_header = ((packet.a() as u16) << 15)
+                    | ((packet.ctype() as u16) << 9)
+                    | (packet.payload_header().0 & !(H265NALU_TYPE_BITMASK | (1 << 15)));
+
+                let mut inner =
+                    BytesMut::with_capacity(H265NALU_HEADER_SIZE + packet.payload.len());
+                inner.put_u16(payload_header);
+                inner.put(&*packet.payload());
+
+                let inner = self.parse(&inner.freeze())?;
+                return self.emit(&inner);
+            }
         }
 
-        Ok(payload.clone())
+        Ok(out.freeze())
+    }
+}
+
+impl Depacketizer for H265Packet {
+    /// depacketize parses the passed byte slice and stores the result in the H265Packet this method is called upon
+    fn depacketize(&mut self, payload: &Bytes) -> Result<Bytes> {
+        let decoded = self.parse(payload)?;
+        let nalus = self.emit(&decoded)?;
+        self.payload = decoded;
+
+        Ok(nalus)
     }
 
     /// is_partition_head checks if this is the head of a packetized nalu stream.
-    fn is_partition_head(&self, _payload: &Bytes) -> bool {
-        //TODO:
-        true
+    fn is_partition_head(&self, payload: &Bytes) -> bool {
+        if payload.len() <= H265NALU_HEADER_SIZE + H265FRAGMENTATION_UNIT_HEADER_SIZE {
+            return true;
+        }
+
+        let payload_header = H265NALUHeader::new(payload[0], payload[1]);
+        if payload_header.is_fragmentation_unit() {
+            H265FragmentationUnitHeader(payload[2]).s()
+        } else {
+            true
+        }
     }
 
     fn is_partition_tail(&self, marker: bool, _payload: &Bytes) -> bool {
         marker
     }
 }
+
+/// H265Payloader payloads H265 packets.
+///
+/// NAL units larger than the MTU are sent as Fragmentation Units, and
+/// consecutive smaller ones are aggregated into Aggregation Packets. DONL
+/// fields are never sent, as `sprop-max-don-diff` is 0 for the streams it makes.
+#[derive(Default, Debug, Clone)]
+pub struct H265Payloader {
+    /// skip_aggregation sends every NAL unit which fits in the MTU in its own
+    /// packet, instead of aggregating them.
+    pub skip_aggregation: bool,
+}
+
+impl H265Payloader {
+    /// split_annexb returns the NAL units of an Annex B byte stream, or the
+    /// whole payload if it has no start code.
+    fn split_annexb(payload: &Bytes) -> Vec<Bytes> {
+        let mut nalus = vec![];
+        let mut zero_count = 0;
+        let mut start = None;
+
+        for (i, &b) in payload.iter().enumerate() {
+            if b == 0 {
+                zero_count += 1;
+                continue;
+            } else if b == 1 && zero_count >= 2 {
+                if let Some(start) = start {
+                    nalus.push(payload.slice(start..i - zero_count));
+                }
+                start = Some(i + 1);
+            }
+            zero_count = 0;
+        }
+
+        match start {
+            Some(start) => nalus.push(payload.slice(start..)),
+            None => nalus.push(payload.clone()),
+        }
+
+        nalus
+    }
+
+    /// flush sends the NAL units waiting to be aggregated, in a single NAL unit
+    /// packet if there is only one of them, or in an Aggregation Packet.
+    fn flush(pending: &mut Vec<Bytes>, payloads: &mut Vec<Bytes>) {
+        if pending.len() <= 1 {
+            payloads.append(pending);
+            return;
+        }
+
+        // The F bit of the AP is set if any aggregated NAL unit has it, and its
+        // layer_id and tid are the lowest ones of the aggregated NAL units.
+        let headers = pending
+            .iter()
+            .map(|nalu| H265NALUHeader::new(nalu[0], nalu[1]));
+        let f = headers.clone().any(|header| header.f());
+        let layer_id = headers
+            .clone()
+            .map(|header| header.layer_id())
+            .min()
+            .unwrap_or(0);
+        let tid = headers.map(|header| header.tid()).min().unwrap_or(0);
+
+        let size = H265NALU_HEADER_SIZE
+            + pending
+                .iter()
+                .map(|nalu| H265AGGREGATION_UNIT_SIZE_LENGTH + nalu.len())
+                .sum::<usize>();
+        let mut out = BytesMut::with_capacity(size);
+        out.put_u16(
+            ((f as u16) << 15)
+                | ((H265NALU_AGGREGATION_PACKET_TYPE as u16) << 9)
+                | ((layer_id as u16) << 3)
+                | tid as u16,
+        );
+        for nalu in pending.drain(..) {
+            out.put_u16(nalu.len() as u16);
+            out.put(nalu);
+        }
+
+        payloads.push(out.freeze());
+    }
+
+    /// emit_fragments sends a NAL unit which does not fit in the MTU as
+    /// Fragmentation Units.
+    fn emit_fragments(nalu: &Bytes, mtu: usize, payloads: &mut Vec<Bytes>) {
+        let max_fragment_size =
+            mtu.saturating_sub(H265NALU_HEADER_SIZE + H265FRAGMENTATION_UNIT_HEADER_SIZE);
+        if max_fragment_size == 0 {
+            return;
+        }
+
+        // The payload header copies the NAL unit header but for its type, which
+        // is carried in the FU header.
+        let nalu_header = H265NALUHeader::new(nalu[0], nalu[1]);
+        let payload_header = (nalu_header.0 & !H265NALU_TYPE_BITMASK)
+            | ((H265NALU_FRAGMENTATION_UNIT_TYPE as u16) << 9);
+
+        let data = nalu.slice(H265NALU_HEADER_SIZE..);
+        let count = data.len().div_ceil(max_fragment_size);
+        for (i, fragment) in data.chunks(max_fragment_size).enumerate() {
+            let mut fu_header = nalu_header.nalu_type();
+            if i == 0 {
+                fu_header |= 1 << 7;
+            }
+            if i == count - 1 {
+                fu_header |= 1 << 6;
+            }
+
+            let mut out = BytesMut::with_capacity(
+                H265NALU_HEADER_SIZE + H265FRAGMENTATION_UNIT_HEADER_SIZE + fragment.len(),
+            );
+            out.put_u16(payload_header);
+            out.put_u8(fu_header);
+            out.put(fragment);
+            payloads.push(out.freeze());
+        }
+    }
+}
+
+impl Payloader for H265Payloader {
+    /// payload fragments a H265 packet across one or more byte arrays
+    fn payload(&mut self, mtu: usize, payload: &Bytes) -> Result<Vec<Bytes>> {
+        if payload.is_empty() || mtu == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut payloads = vec![];
+        let mut pending = vec![];
+        let mut pending_size = H265NALU_HEADER_SIZE;
+
+        for nalu in H265Payloader::split_annexb(payload) {
+            if nalu.len() < H265NALU_HEADER_SIZE {
+                continue;
+            }
+
+            let unit_size = H265AGGREGATION_UNIT_SIZE_LENGTH + nalu.len();
+            if self.skip_aggregation || H265NALU_HEADER_SIZE + unit_size > mtu {
+                H265Payloader::flush(&mut pending, &mut payloads);
+                pending_size = H265NALU_HEADER_SIZE;
+
+                if nalu.len() <= mtu {
+                    payloads.push(nalu);
+                } else {
+                    H265Payloader::emit_fragments(&nalu, mtu, &mut payloads);
+                }
+                continue;
+            }
+
+            if pending_size + unit_size > mtu {
+                H265Payloader::flush(&mut pending, &mut payloads);
+                pending_size = H265NALU_HEADER_SIZE;
+            }
+            pending.push(nalu);
+            pending_size += unit_size;
+        }
+        H265Payloader::flush(&mut pending, &mut payloads);
+
+        Ok(payloads)
+    }
+
+    fn clone_to(&self) -> Box<dyn Payloader + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
@@ -44,3 +44,42 @@ impl Depacketizer for OpusPacket {
         true
     }
 }
+
+/// packet_samples returns the duration of an Opus packet in samples at the 48 kHz
+/// RTP clock rate of Opus, which is the timestamp increment of the next packet.
+/// It is read from the TOC byte and frame count of the packet, see RFC 6716
+/// section 3.1.
+pub fn packet_samples(packet: &Bytes) -> Result<u32> {
+    if packet.is_empty() {
+        return Err(Error::ErrShortPacket);
+    }
+
+    let config = packet[0] >> 3;
+    let frame_samples = match config {
+        // SILK: 10, 20, 40 or 60 ms
+        0..=11 => [480, 960, 1920, 2880][config as usize % 4],
+        // Hybrid: 10 or 20 ms
+        12..=15 => [480, 960][config as usize % 2],
+        // CELT: 2.5, 5, 10 or 20 ms
+        _ => [120, 240, 480, 960][config as usize % 4],
+    };
+
+    let frames = match packet[0] & 0x03 {
+        0 => 1,
+        1 | 2 => 2,
+        _ => {
+            if packet.len() < 2 {
+                return Err(Error::ErrShortPacket);
+            }
+            (packet[1] & 0x3F) as u32
+        }
+    };
+
+    // a packet lasts at most 120 ms
+    let samples = frame_samples * frames;
+    if frames == 0 || samples > 5760 {
+        return Err(Error::ErrInvalidOpusPacket);
+    }
+
+    Ok(samples)
+}
@@ -49,3 +49,41 @@ fn test_opus_is_partition_head() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_opus_packet_samples() {
+    let tests = vec![
+        ("silk 10ms", vec![0x00, 0xAA], Ok(480)),
+        ("silk 60ms", vec![0x18, 0xAA], Ok(2880)),
+        ("hybrid 20ms", vec![0x68, 0xAA], Ok(960)),
+        ("celt 2.5ms", vec![0x80, 0xAA], Ok(120)),
+        ("celt 20ms", vec![0xF8, 0xAA], Ok(960)),
+        ("two frames", vec![0xF9, 0xAA], Ok(1920)),
+        (
+            "two frames of different sizes",
+            vec![0xFA, 0x01, 0xAA],
+            Ok(1920),
+        ),
+        ("arbitrary frames", vec![0xFB, 0x03, 0xAA], Ok(2880)),
+        ("empty", vec![], Err(Error::ErrShortPacket)),
+        (
+            "missing frame count",
+            vec![0xFB],
+            Err(Error::ErrShortPacket),
+        ),
+        (
+            "no frames",
+            vec![0xFB, 0x00],
+            Err(Error::ErrInvalidOpusPacket),
+        ),
+        (
+            "more than 120ms",
+            vec![0xFB, 0x07],
+            Err(Error::ErrInvalidOpusPacket),
+        ),
+    ];
+
+    for (name, packet, want) in tests {
+        assert_eq!(packet_samples(&Bytes::from(packet)), want, "{name}");
+    }
+}
@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod red_test;
+
+use std::collections::VecDeque;
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::error::{Error, Result};
+use crate::packetizer::Depacketizer;
+
+/// Size of the header of a redundant block.
+const RED_BLOCK_HEADER_SIZE: usize = 4;
+/// Size of the header of the primary block.
+const RED_PRIMARY_HEADER_SIZE: usize = 1;
+/// Largest timestamp offset of a redundant block, on 14 bits.
+pub const RED_MAX_TIMESTAMP_OFFSET: u32 = 0x3FFF;
+/// Largest length of a redundant block, on 10 bits.
+pub const RED_MAX_BLOCK_LENGTH: usize = 0x3FF;
+
+/// RedBlock is one of the encodings carried by a RED packet.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct RedBlock {
+    pub payload_type: u8,
+    /// timestamp_offset is the offset of the block from the timestamp of the
+    /// packet, which is 0 for the primary block.
+    pub timestamp_offset: u16,
+    pub payload: Bytes,
+}
+
+impl RedBlock {
+    /// timestamp returns the timestamp of the block in a packet of the given timestamp.
+    pub fn timestamp(&self, packet_timestamp: u32) -> u32 {
+        packet_timestamp.wrapping_sub(self.timestamp_offset as u32)
+    }
+}
+
+/// RedEncoder builds RED payloads, as described in RFC 2198, which carry each
+/// encoding along with up to `distance` previous ones.
+///
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |F|   block PT  |  timestamp offset         |   block length    |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |0|   block PT  |
+/// +-+-+-+-+-+-+-+-+
+#[derive(Debug, Clone)]
+pub struct RedEncoder {
+    payload_type: u8,
+    distance: usize,
+    history: VecDeque<(u32, Bytes)>,
+}
+
+impl RedEncoder {
+    /// new creates a RedEncoder for encodings of the given payload type, which
+    /// keeps `distance` previous encodings as redundancy.
+    pub fn new(payload_type: u8, distance: usize) -> Self {
+        RedEncoder {
+            payload_type: payload_type & 0x7F,
+            distance,
+            history: VecDeque::with_capacity(distance),
+        }
+    }
+
+    /// encode returns the RED payload of a packet carrying `payload` of the
+    /// given timestamp as its primary encoding. The previous encodings whose
+    /// timestamp offset or length do not fit in a block header are left out.
+    pub fn encode(&mut self, payload: &Bytes, timestamp: u32) -> Bytes {
+        let redundant: Vec<(u16, &Bytes)> = self
+            .history
+            .iter()
+            .filter_map(|(ts, block)| {
+                let offset = timestamp.wrapping_sub(*ts);
+                if offset == 0 || offset > RED_MAX_TIMESTAMP_OFFSET {
+                    return None;
+                }
+                if block.len() > RED_MAX_BLOCK_LENGTH {
+                    return None;
+                }
+                Some((offset as u16, block))
+            })
+            .collect();
+
+        let size = redundant
+            .iter()
+            .map(|(_, block)| RED_BLOCK_HEADER_SIZE + block.len())
+            .sum::<usize>()
+            + RED_PRIMARY_HEADER_SIZE
+            + payload.len();
+        let mut out = BytesMut::with_capacity(size);
+        for (offset, block) in &redundant {
+            out.put_u32(
+                ((0x80 | self.payload_type as u32) << 24)
+                    | ((*offset as u32) << 10)
+                    | block.len() as u32,
+            );
+        }
+        out.put_u8(self.payload_type);
+        for (_, block) in &redundant {
+            out.put(&***block);
+        }
+        out.put(&**payload);
+
+        if self.distance > 0 {
+            if self.history.len() == self.distance {
+                self.history.pop_front();
+            }
+            self.history.push_back((timestamp, payload.clone()));
+        }
+
+        out.freeze()
+    }
+}
+
+/// RedPacket represents a RED payload, as described in RFC 2198.
+///
+/// depacketize returns the primary encoding, while the redundant ones, from
+/// the oldest, are kept along with it in the blocks of the packet.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct RedPacket {
+    blocks: Vec<RedBlock>,
+}
+
+impl RedPacket {
+    /// blocks returns the encodings of the packet, the primary one being last.
+    pub fn blocks(&self) -> &[RedBlock] {
+        &self.blocks
+    }
+
+    /// primary returns the primary encoding of the packet.
+    pub fn primary(&self) -> Option<&RedBlock> {
+        self.blocks.last()
+    }
+}
+
+impl Depacketizer for RedPacket {
+    fn depacketize(&mut self, packet: &Bytes) -> Result<Bytes> {
+        self.blocks.clear();
+
+        // headers of the redundant blocks, then of the primary one
+        let mut headers = vec![];
+        let mut offset = 0;
+        loop {
+            if packet.len() <= offset {
+                return Err(Error::ErrShortPacket);
+            }
+            let b0 = packet[offset];
+            let payload_type = b0 & 0x7F;
+            if b0 & 0x80 == 0 {
+                headers.push((payload_type, 0, None));
+                offset += RED_PRIMARY_HEADER_SIZE;
+                break;
+            }
+
+            if packet.len() < offset + RED_BLOCK_HEADER_SIZE {
+                return Err(Error::ErrShortPacket);
+            }
+            let rest = ((packet[offset + 1] as u32) << 16)
+                | ((packet[offset + 2] as u32) << 8)
+                | packet[offset + 3] as u32;
+            let timestamp_offset = (rest >> 10) as u16;
+            let length = (rest & 0x3FF) as usize;
+            headers.push((payload_type, timestamp_offset, Some(length)));
+            offset += RED_BLOCK_HEADER_SIZE;
+        }
+
+        let mut blocks = Vec::with_capacity(headers.len());
+        for (payload_type, timestamp_offset, length) in headers {
+            // the primary block takes the rest of the packet
+            let length = length.unwrap_or(packet.len().saturating_sub(offset));
+            if packet.len() < offset + length {
+                return Err(Error::ErrShortPacket);
+            }
+            blocks.push(RedBlock {
+                payload_type,
+                timestamp_offset,
+                payload: packet.slice(offset..offset + length),
+            });
+            offset += length;
+        }
+
+        self.blocks = blocks;
+        Ok(self
+            .primary()
+            .map(|block| block.payload.clone())
+            .unwrap_or_default())
+    }
+
+    fn is_partition_head(&self, _payload: &Bytes) -> bool {
+        true
+    }
+
+    fn is_partition_tail(&self, _marker: bool, _payload: &Bytes) -> bool {
+        true
+    }
+}
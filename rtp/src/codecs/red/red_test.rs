@@ -0,0 +1,109 @@
+use super::*;
+
+#[test]
+fn test_red_encoder() -> Result<()> {
+    let mut encoder = RedEncoder::new(111, 2);
+
+    // nothing to repeat in the first packet
+    let out = encoder.encode(&Bytes::from_static(&[0x01, 0x02]), 1000);
+    assert_eq!(out, Bytes::from_static(&[111, 0x01, 0x02]));
+
+    let out = encoder.encode(&Bytes::from_static(&[0x03]), 1960);
+    assert_eq!(
+        out,
+        Bytes::from_static(&[
+            0x80 | 111,
+            0x0F, // timestamp offset 960 << 10 | length 2
+            0x00,
+            0x02,
+            111,
+            0x01,
+            0x02,
+            0x03,
+        ])
+    );
+
+    // only the last two encodings are kept
+    let out = encoder.encode(&Bytes::from_static(&[0x04]), 2920);
+    let mut pck = RedPacket::default();
+    assert_eq!(pck.depacketize(&out)?, Bytes::from_static(&[0x04]));
+    let offsets: Vec<u16> = pck.blocks().iter().map(|b| b.timestamp_offset).collect();
+    assert_eq!(offsets, vec![1920, 960, 0]);
+
+    let out = encoder.encode(&Bytes::from_static(&[0x05]), 3880);
+    pck.depacketize(&out)?;
+    let timestamps: Vec<u32> = pck.blocks().iter().map(|b| b.timestamp(3880)).collect();
+    assert_eq!(timestamps, vec![1960, 2920, 3880]);
+
+    // encodings too old for the timestamp offset are left out
+    let out = encoder.encode(&Bytes::from_static(&[0x06]), 3880 + 0x4000);
+    pck.depacketize(&out)?;
+    assert_eq!(pck.blocks().len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_red_packet_depacketize() -> Result<()> {
+    let tests = vec![
+        ("empty", vec![], Err(Error::ErrShortPacket)),
+        (
+            "truncated block header",
+            vec![0x80 | 111, 0x0F],
+            Err(Error::ErrShortPacket),
+        ),
+        (
+            "missing primary header",
+            vec![0x80 | 111, 0x0F, 0x00, 0x00],
+            Err(Error::ErrShortPacket),
+        ),
+        (
+            "block longer than packet",
+            vec![0x80 | 111, 0x0F, 0x00, 0x04, 111, 0x01],
+            Err(Error::ErrShortPacket),
+        ),
+        (
+            "primary only",
+            vec![111, 0xaa, 0xbb],
+            Ok(vec![RedBlock {
+                payload_type: 111,
+                timestamp_offset: 0,
+                payload: Bytes::from_static(&[0xaa, 0xbb]),
+            }]),
+        ),
+        (
+            "empty primary",
+            vec![0x80 | 100, 0x0F, 0x00, 0x01, 101, 0xaa],
+            Ok(vec![
+                RedBlock {
+                    payload_type: 100,
+                    timestamp_offset: 960,
+                    payload: Bytes::from_static(&[0xaa]),
+                },
+                RedBlock {
+                    payload_type: 101,
+                    timestamp_offset: 0,
+                    payload: Bytes::new(),
+                },
+            ]),
+        ),
+    ];
+
+    for (name, raw, expected) in tests {
+        let mut pck = RedPacket::default();
+        let result = pck.depacketize(&Bytes::from(raw));
+        match expected {
+            Ok(blocks) => {
+                let primary = result.unwrap_or_else(|err| panic!("{name}: {err}"));
+                assert_eq!(primary, blocks.last().unwrap().payload, "{name}");
+                assert_eq!(pck.blocks(), &blocks[..], "{name}");
+            }
+            Err(err) => {
+                assert_eq!(result, Err(err), "{name}");
+                assert!(pck.blocks().is_empty(), "{name}");
+            }
+        }
+    }
+
+    Ok(())
+}
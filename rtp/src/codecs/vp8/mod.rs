@@ -166,6 +166,9 @@ impl Depacketizer for Vp8Packet {
         //T/K:|tid|Y| KEYIDX  | (OPTIONAL)
         //    +-+-+-+-+-+-+-+-+
 
+        // the optional fields of the previous packet must not leak into this one
+        *self = Vp8Packet::default();
+
         let reader = &mut packet.clone();
         let mut payload_index = 0;
 
@@ -244,3 +247,43 @@ impl Depacketizer for Vp8Packet {
         marker
     }
 }
+
+/// rewrite_picture_id overwrites the PictureID and TL0PICIDX of a VP8 payload
+/// descriptor in place, so that a forwarder switching between streams can keep
+/// them continuous for the receiver. The fields missing from the descriptor are
+/// left out, and a 7 bits PictureID keeps its width, wrapping at 128.
+pub fn rewrite_picture_id(payload: &mut [u8], picture_id: u16, tl0_pic_idx: u8) -> Result<()> {
+    if payload.len() < 2 || payload[0] & 0x80 == 0 {
+        return Ok(());
+    }
+
+    let (i, l) = (payload[1] & 0x80 != 0, payload[1] & 0x40 != 0);
+    let mut index = 2;
+
+    if i {
+        if payload.len() <= index {
+            return Err(Error::ErrShortPacket);
+        }
+        if payload[index] & 0x80 != 0 {
+            // M == 1, PID is 16bit
+            if payload.len() <= index + 1 {
+                return Err(Error::ErrShortPacket);
+            }
+            payload[index] = 0x80 | ((picture_id >> 8) & 0x7F) as u8;
+            payload[index + 1] = (picture_id & 0xFF) as u8;
+            index += 2;
+        } else {
+            payload[index] = (picture_id & 0x7F) as u8;
+            index += 1;
+        }
+    }
+
+    if l {
+        if payload.len() <= index {
+            return Err(Error::ErrShortPacket);
+        }
+        payload[index] = tl0_pic_idx;
+    }
+
+    Ok(())
+}
@@ -223,3 +223,89 @@ fn test_vp8_partition_head_checker_is_partition_head() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_vp8_unmarshal_resets_optional_fields() -> Result<()> {
+    let mut pck = Vp8Packet::default();
+
+    let raw_bytes = Bytes::from_static(&[0x90, 0xe0, 0x81, 0x23, 0x05, 0x40, 0xaa]);
+    let payload = pck.depacketize(&raw_bytes)?;
+    assert_eq!(payload, Bytes::from_static(&[0xaa]));
+    assert_eq!(pck.picture_id, 0x0123);
+    assert_eq!(pck.tl0_pic_idx, 5);
+    assert_eq!(pck.tid, 1);
+
+    // no extended control bits, the previous ones must not be used
+    let raw_bytes = Bytes::from_static(&[0x10, 0xbb, 0xcc, 0xdd]);
+    let payload = pck.depacketize(&raw_bytes)?;
+    assert_eq!(payload, Bytes::from_static(&[0xbb, 0xcc, 0xdd]));
+    assert_eq!(pck.i, 0, "I must be 0");
+    assert_eq!(pck.picture_id, 0);
+    assert_eq!(pck.tl0_pic_idx, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_vp8_rewrite_picture_id() -> Result<()> {
+    let tests = vec![
+        (
+            "15 bits picture id and tl0picidx",
+            vec![0x90, 0xe0, 0x81, 0x23, 0x05, 0x40, 0xaa],
+            vec![0x90, 0xe0, 0xb4, 0x56, 0x07, 0x40, 0xaa],
+            0x7fff,
+            7,
+        ),
+        (
+            "7 bits picture id wraps",
+            vec![0x90, 0x80, 0x12, 0xaa],
+            vec![0x90, 0x80, 0x56, 0xaa],
+            0x7f,
+            0,
+        ),
+        (
+            "tl0picidx only",
+            vec![0x90, 0x40, 0x05, 0xaa],
+            vec![0x90, 0x40, 0x07, 0xaa],
+            0,
+            7,
+        ),
+        (
+            "no extension",
+            vec![0x10, 0xaa, 0xbb, 0xcc],
+            vec![0x10, 0xaa, 0xbb, 0xcc],
+            0,
+            0,
+        ),
+    ];
+
+    for (name, mut payload, want, picture_id_mask, tl0_pic_idx) in tests {
+        rewrite_picture_id(&mut payload, 0x3456, 7)?;
+        assert_eq!(payload, want, "{name}");
+
+        let mut pck = Vp8Packet::default();
+        pck.depacketize(&Bytes::from(payload))?;
+        assert_eq!(pck.picture_id, 0x3456 & picture_id_mask, "{name}");
+        assert_eq!(pck.tl0_pic_idx, tl0_pic_idx, "{name}");
+    }
+
+    let mut truncated = vec![0x90, 0x80, 0x81];
+    assert_eq!(
+        rewrite_picture_id(&mut truncated, 0x3456, 7),
+        Err(Error::ErrShortPacket)
+    );
+
+    // a payloader keeps the picture id continuous across frames
+    let mut pck = Vp8Payloader {
+        enable_picture_id: true,
+        ..Default::default()
+    };
+    let mut depacketizer = Vp8Packet::default();
+    for want in 0..200u16 {
+        let payloads = pck.payload(100, &Bytes::from_static(&[0x01, 0x02]))?;
+        depacketizer.depacketize(&payloads[0])?;
+        assert_eq!(depacketizer.picture_id, want);
+    }
+
+    Ok(())
+}
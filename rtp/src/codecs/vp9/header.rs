@@ -0,0 +1,105 @@
+use crate::error::{Error, Result};
+
+const FRAME_MARKER: u64 = 2;
+const SYNC_CODE: u64 = 0x49_83_42;
+const CS_RGB: u64 = 7;
+
+/// FrameHeader holds the fields of the uncompressed header of a VP9 frame that
+/// the payloader needs.
+/// See https://storage.googleapis.com/downloads.webmproject.org/docs/vp9/vp9-bitstream-specification-v0.6-20160331.pdf
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct FrameHeader {
+    pub(super) profile: u8,
+    pub(super) show_existing_frame: bool,
+    pub(super) non_key_frame: bool,
+    /// frame width and height in pixels, parsed for key frames only
+    pub(super) frame_size: Option<(u16, u16)>,
+}
+
+impl FrameHeader {
+    /// is_key_frame is false for frames predicted from other frames and for
+    /// frames that only show a previously decoded one.
+    pub(super) fn is_key_frame(&self) -> bool {
+        !self.show_existing_frame && !self.non_key_frame
+    }
+
+    pub(super) fn unmarshal(buf: &[u8]) -> Result<Self> {
+        let mut r = BitReader { buf, pos: 0 };
+        let mut h = FrameHeader::default();
+
+        if r.read_bits(2)? != FRAME_MARKER {
+            return Err(Error::ErrInvalidVp9FrameHeader);
+        }
+        let profile_low_bit = r.read_bits(1)? as u8;
+        let profile_high_bit = r.read_bits(1)? as u8;
+        h.profile = (profile_high_bit << 1) + profile_low_bit;
+        if h.profile == 3 {
+            r.read_bits(1)?; // reserved_zero
+        }
+
+        h.show_existing_frame = r.read_flag()?;
+        if h.show_existing_frame {
+            r.read_bits(3)?; // frame_to_show_map_idx
+            return Ok(h);
+        }
+
+        h.non_key_frame = r.read_flag()?;
+        r.read_bits(2)?; // show_frame, error_resilient_mode
+        if h.non_key_frame {
+            return Ok(h);
+        }
+
+        if r.read_bits(24)? != SYNC_CODE {
+            return Err(Error::ErrInvalidVp9FrameHeader);
+        }
+        h.skip_color_config(&mut r)?;
+
+        let width = r.read_bits(16)? as u16 + 1;
+        let height = r.read_bits(16)? as u16 + 1;
+        h.frame_size = Some((width, height));
+
+        Ok(h)
+    }
+
+    // color_config(), of which only the length depends on the profile
+    fn skip_color_config(&self, r: &mut BitReader<'_>) -> Result<()> {
+        if self.profile >= 2 {
+            r.read_bits(1)?; // ten_or_twelve_bit
+        }
+        let color_space = r.read_bits(3)?;
+        if color_space != CS_RGB {
+            r.read_bits(1)?; // color_range
+            if self.profile == 1 || self.profile == 3 {
+                r.read_bits(3)?; // subsampling_x, subsampling_y, reserved_zero
+            }
+        } else if self.profile == 1 || self.profile == 3 {
+            r.read_bits(1)?; // reserved_zero
+        }
+        Ok(())
+    }
+}
+
+/// BitReader reads the MSB first bit fields of the uncompressed header.
+struct BitReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    fn read_bits(&mut self, n: usize) -> Result<u64> {
+        if self.pos + n > self.buf.len() * 8 {
+            return Err(Error::ErrShortPacket);
+        }
+        let mut v = 0u64;
+        for _ in 0..n {
+            let bit = (self.buf[self.pos / 8] >> (7 - self.pos % 8)) & 1;
+            v = (v << 1) | bit as u64;
+            self.pos += 1;
+        }
+        Ok(v)
+    }
+
+    fn read_flag(&mut self) -> Result<bool> {
+        Ok(self.read_bits(1)? == 1)
+    }
+}
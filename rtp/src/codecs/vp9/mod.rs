@@ -1,3 +1,4 @@
+mod header;
 #[cfg(test)]
 mod vp9_test;
 
@@ -6,11 +7,15 @@ use std::sync::Arc;
 
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 
+use crate::codecs::vp9::header::FrameHeader;
 use crate::error::{Error, Result};
 use crate::packetizer::{Depacketizer, Payloader};
 
 /// Flexible mode 15 bit picture ID
 const VP9HEADER_SIZE: usize = 3;
+/// Scalability structure of a single spatial layer with its resolution and a
+/// picture group of one picture
+const VP9SS_SIZE: usize = 8;
 const MAX_SPATIAL_LAYERS: u8 = 5;
 const MAX_VP9REF_PICS: usize = 3;
 
@@ -18,12 +23,28 @@ const MAX_VP9REF_PICS: usize = 3;
 pub type InitialPictureIDFn = Arc<dyn (Fn() -> u16) + Send + Sync>;
 
 /// Vp9Payloader payloads VP9 packets
-#[derive(Default, Clone)]
+#[derive(Clone)]
 pub struct Vp9Payloader {
     picture_id: u16,
     initialized: bool,
 
     pub initial_picture_id_fn: Option<InitialPictureIDFn>,
+    /// flexible_mode selects the flexible mode descriptor, the default. In
+    /// non-flexible mode the first packet of each key frame carries the
+    /// scalability structure (SS) with the frame resolution, and the P flag is
+    /// set on the packets of the other frames.
+    pub flexible_mode: bool,
+}
+
+impl Default for Vp9Payloader {
+    fn default() -> Self {
+        Vp9Payloader {
+            picture_id: 0,
+            initialized: false,
+            initial_picture_id_fn: None,
+            flexible_mode: true,
+        }
+    }
 }
 
 impl fmt::Debug for Vp9Payloader {
@@ -31,6 +52,7 @@ impl fmt::Debug for Vp9Payloader {
         f.debug_struct("Vp9Payloader")
             .field("picture_id", &self.picture_id)
             .field("initialized", &self.initialized)
+            .field("flexible_mode", &self.flexible_mode)
             .finish()
     }
 }
@@ -93,51 +115,97 @@ impl Payloader for Vp9Payloader {
             self.initialized = true;
         }
 
-        let max_fragment_size = mtu as isize - VP9HEADER_SIZE as isize;
-        let mut payloads = vec![];
-        let mut payload_data_remaining = payload.len();
-        let mut payload_data_index = 0;
-
-        if std::cmp::min(max_fragment_size, payload_data_remaining as isize) <= 0 {
-            return Ok(vec![]);
+        let payloads = if self.flexible_mode {
+            let descriptor = self.flexible_descriptor();
+            fragment(mtu, payload, &descriptor, &descriptor)
+        } else {
+            let frame_header = FrameHeader::unmarshal(payload)?;
+            let descriptor = self.non_flexible_descriptor(&frame_header);
+            let mut first_descriptor = Vec::with_capacity(VP9HEADER_SIZE + VP9SS_SIZE);
+            first_descriptor.extend_from_slice(&descriptor);
+            if let Some((width, height)) = frame_header.frame_size {
+                first_descriptor[0] |= 0x02; // V=1
+                first_descriptor.put_u8(0x18); // N_S=0 Y=1 G=1
+                first_descriptor.put_u16(width);
+                first_descriptor.put_u16(height);
+                first_descriptor.put_u8(1); // N_G=1
+                first_descriptor.put_u8(0x14); // T=0 U=1 R=1
+                first_descriptor.put_u8(1); // P_DIFF=1
+            }
+            fragment(mtu, payload, &first_descriptor, &descriptor)
+        };
+        if !payloads.is_empty() {
+            self.picture_id += 1;
+            self.picture_id &= 0x7FFF;
         }
 
-        while payload_data_remaining > 0 {
-            let current_fragment_size =
-                std::cmp::min(max_fragment_size as usize, payload_data_remaining);
-            let mut out = BytesMut::with_capacity(VP9HEADER_SIZE + current_fragment_size);
-            let mut buf = [0u8; VP9HEADER_SIZE];
-            buf[0] = 0x90; // F=1 I=1
-            if payload_data_index == 0 {
-                buf[0] |= 0x08; // B=1
-            }
-            if payload_data_remaining == current_fragment_size {
-                buf[0] |= 0x04; // E=1
-            }
-            buf[1] = (self.picture_id >> 8) as u8 | 0x80;
-            buf[2] = (self.picture_id & 0xFF) as u8;
+        Ok(payloads)
+    }
 
-            out.put(&buf[..]);
+    fn clone_to(&self) -> Box<dyn Payloader + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
 
-            out.put(
-                &*payload.slice(payload_data_index..payload_data_index + current_fragment_size),
-            );
+impl Vp9Payloader {
+    fn flexible_descriptor(&self) -> [u8; VP9HEADER_SIZE] {
+        [
+            0x90, // F=1 I=1
+            (self.picture_id >> 8) as u8 | 0x80,
+            (self.picture_id & 0xFF) as u8,
+        ]
+    }
 
-            payloads.push(out.freeze());
+    fn non_flexible_descriptor(&self, frame_header: &FrameHeader) -> [u8; VP9HEADER_SIZE] {
+        let mut b = 0x81; // I=1 Z=1
+        if !frame_header.is_key_frame() {
+            b |= 0x40; // P=1
+        }
+        [
+            b,
+            (self.picture_id >> 8) as u8 | 0x80,
+            (self.picture_id & 0xFF) as u8,
+        ]
+    }
+}
 
-            payload_data_remaining -= current_fragment_size;
-            payload_data_index += current_fragment_size;
+/// fragment splits payload into packets of at most mtu bytes, the first one
+/// starting with first_descriptor and the others with descriptor, with the B
+/// and E flags set on the first and last ones. No packet is returned if the
+/// MTU cannot hold a descriptor and a byte of payload.
+fn fragment(mtu: usize, payload: &Bytes, first_descriptor: &[u8], descriptor: &[u8]) -> Vec<Bytes> {
+    let mut payloads = vec![];
+    let mut payload_data_remaining = payload.len();
+    let mut payload_data_index = 0;
+
+    while payload_data_remaining > 0 {
+        let descriptor = if payload_data_index == 0 {
+            first_descriptor
+        } else {
+            descriptor
+        };
+        if mtu <= descriptor.len() {
+            return vec![];
+        }
+        let current_fragment_size = std::cmp::min(mtu - descriptor.len(), payload_data_remaining);
+        let mut out = BytesMut::with_capacity(descriptor.len() + current_fragment_size);
+        out.put(descriptor);
+        if payload_data_index == 0 {
+            out[0] |= 0x08; // B=1
+        }
+        if payload_data_remaining == current_fragment_size {
+            out[0] |= 0x04; // E=1
         }
 
-        self.picture_id += 1;
-        self.picture_id &= 0x7FFF;
+        out.put(&*payload.slice(payload_data_index..payload_data_index + current_fragment_size));
 
-        Ok(payloads)
-    }
+        payloads.push(out.freeze());
 
-    fn clone_to(&self) -> Box<dyn Payloader + Send + Sync> {
-        Box::new(self.clone())
+        payload_data_remaining -= current_fragment_size;
+        payload_data_index += current_fragment_size;
     }
+
+    payloads
 }
 
 /// Vp9Packet represents the VP9 header that is stored in the payload of an RTP Packet
@@ -206,6 +274,9 @@ impl Depacketizer for Vp9Packet {
             return Err(Error::ErrShortPacket);
         }
 
+        // the fields of the previous packet must not leak into this one
+        *self = Vp9Packet::default();
+
         let reader = &mut packet.clone();
         let b = reader.get_u8();
 
@@ -368,13 +439,13 @@ impl Vp9Packet {
             if reader.remaining() == 0 {
                 return Err(Error::ErrShortPacket);
             }
+            if self.pdiff.len() == MAX_VP9REF_PICS {
+                return Err(Error::ErrTooManyPDiff);
+            }
             b = reader.get_u8();
             payload_index += 1;
 
             self.pdiff.push(b >> 1);
-            if self.pdiff.len() >= MAX_VP9REF_PICS {
-                return Err(Error::ErrTooManyPDiff);
-            }
         }
 
         Ok(payload_index)
@@ -410,7 +481,7 @@ impl Vp9Packet {
 
         self.ns = b >> 5;
         self.y = b & 0x10 != 0;
-        self.g = (b >> 1) & 0x7 != 0;
+        self.g = b & 0x08 != 0;
 
         let ns = (self.ns + 1) as usize;
         self.ng = 0;
@@ -120,6 +120,20 @@ fn test_vp9_packet_unmarshal() -> Result<()> {
             Bytes::new(),
             Some(Error::ErrTooManyPDiff),
         ),
+        (
+            "FlexiblePictureIDRefIndex_MaxPDiff",
+            Bytes::from_static(&[0xD0, 0x02, 0x03, 0x05, 0x06, 0xAA]),
+            Vp9Packet {
+                i: true,
+                p: true,
+                f: true,
+                picture_id: 0x02,
+                pdiff: vec![0x01, 0x02, 0x03],
+                ..Default::default()
+            },
+            Bytes::from_static(&[0xAA]),
+            None,
+        ),
         (
             "FlexiblePictureIDRefIndexNoPayload",
             Bytes::from_static(&[0xD0, 0x02, 0x03, 0x04]),
@@ -208,6 +222,22 @@ fn test_vp9_packet_unmarshal() -> Result<()> {
             Bytes::new(),
             None,
         ),
+        (
+            "ScalabilityStructureReservedBits",
+            Bytes::from_static(&[
+                0x0A,
+                (1 << 5) | 0x07, // NS:1 Y:0 G:0 reserved bits set
+                0xAA,
+            ]),
+            Vp9Packet {
+                b: true,
+                v: true,
+                ns: 1,
+                ..Default::default()
+            },
+            Bytes::from_static(&[0xAA]),
+            None,
+        ),
     ];
 
     for (name, b, pkt, expected, err) in tests {
@@ -232,6 +262,42 @@ fn test_vp9_packet_unmarshal() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_vp9_packet_unmarshal_resets_fields() -> Result<()> {
+    let mut p = Vp9Packet::default();
+
+    let flexible = Bytes::from_static(&[0xF0, 0x82, 0x01, 0x25, 0x03, 0x04, 0xAA]);
+    let payload = p.depacketize(&flexible)?;
+    assert_eq!(payload, Bytes::from_static(&[0xAA]));
+    assert_eq!(p.picture_id, 0x0201);
+    assert_eq!((p.tid, p.u, p.sid, p.d), (1, false, 2, true));
+    assert_eq!(p.pdiff, vec![0x01, 0x02]);
+
+    // the same packet gives the same fields
+    p.depacketize(&flexible)?;
+    assert_eq!(p.pdiff, vec![0x01, 0x02]);
+
+    // a non-flexible packet carries its own layer indices
+    let non_flexible = Bytes::from_static(&[0xA8, 0x05, 0x44, 0x07, 0xBB]);
+    let payload = p.depacketize(&non_flexible)?;
+    assert_eq!(payload, Bytes::from_static(&[0xBB]));
+    assert_eq!(
+        p,
+        Vp9Packet {
+            i: true,
+            l: true,
+            b: true,
+            picture_id: 0x05,
+            tid: 2,
+            sid: 2,
+            tl0picidx: 0x07,
+            ..Default::default()
+        }
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_vp9_payloader_payload() -> Result<()> {
     let mut r0 = 8692;
@@ -340,6 +406,130 @@ fn test_vp9_payloader_payload() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_vp9_payloader_payload_non_flexible() -> Result<()> {
+    // profile 0 key frame of 640x480, followed by 2 bytes of compressed data
+    let key_frame = Bytes::from_static(&[
+        0x82, 0x49, 0x83, 0x42, 0x00, 0x27, 0xF0, 0x1D, 0xF0, 0xAA, 0xBB,
+    ]);
+    // inter frame
+    let non_key_frame = Bytes::from_static(&[0x86, 0x00, 0x40, 0x92]);
+
+    let mut pck = Vp9Payloader {
+        initial_picture_id_fn: Some(Arc::new(|| -> u16 { 8692 })),
+        flexible_mode: false,
+        ..Default::default()
+    };
+
+    let ss = [0x18, 0x02, 0x80, 0x01, 0xE0, 0x01, 0x14, 0x01];
+    let mut want = vec![0x8F, 0xA1, 0xF4];
+    want.extend_from_slice(&ss);
+    want.extend_from_slice(&key_frame);
+    assert_eq!(pck.payload(100, &key_frame)?, vec![Bytes::from(want)]);
+
+    assert_eq!(
+        pck.payload(100, &non_key_frame)?,
+        vec![Bytes::from_static(&[
+            0xCD, 0xA1, 0xF5, 0x86, 0x00, 0x40, 0x92
+        ])]
+    );
+
+    // only the first packet of the frame carries the SS
+    let payloads = pck.payload(15, &key_frame)?;
+    let mut want = vec![0x8B, 0xA1, 0xF6];
+    want.extend_from_slice(&ss);
+    want.extend_from_slice(&key_frame[..4]);
+    assert_eq!(payloads[0], Bytes::from(want));
+    let mut want = vec![0x85, 0xA1, 0xF6];
+    want.extend_from_slice(&key_frame[4..]);
+    assert_eq!(payloads[1], Bytes::from(want));
+    assert_eq!(payloads.len(), 2);
+
+    let mut p = Vp9Packet::default();
+    let frame = p.depacketize(&payloads[0])?;
+    assert_eq!(frame, key_frame.slice(..4));
+    assert_eq!(
+        p,
+        Vp9Packet {
+            i: true,
+            b: true,
+            v: true,
+            z: true,
+            picture_id: 8694,
+            y: true,
+            g: true,
+            ng: 1,
+            width: vec![640],
+            height: vec![480],
+            pgtid: vec![0],
+            pgu: vec![true],
+            pgpdiff: vec![vec![1]],
+            ..Default::default()
+        }
+    );
+
+    // the MTU cannot hold the SS and a byte of the frame
+    assert_eq!(pck.payload(11, &key_frame)?, Vec::<Bytes>::new());
+    assert!(!pck.payload(4, &non_key_frame)?.is_empty());
+
+    assert_eq!(
+        pck.payload(100, &Bytes::from_static(&[0x00, 0x01])),
+        Err(Error::ErrInvalidVp9FrameHeader),
+        "invalid frame marker"
+    );
+    assert_eq!(
+        pck.payload(100, &Bytes::from_static(&[0x82, 0x49, 0x83, 0x43, 0x00])),
+        Err(Error::ErrInvalidVp9FrameHeader),
+        "invalid sync code"
+    );
+    assert_eq!(
+        pck.payload(100, &key_frame.slice(..7)),
+        Err(Error::ErrShortPacket),
+        "truncated frame size"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_vp9_frame_header_unmarshal() -> Result<()> {
+    let tests = vec![
+        (
+            "profile 1 key frame of 320x240",
+            vec![0xA2, 0x49, 0x83, 0x42, 0x48, 0x02, 0x7E, 0x01, 0xDE],
+            FrameHeader {
+                profile: 1,
+                frame_size: Some((320, 240)),
+                ..Default::default()
+            },
+        ),
+        (
+            "profile 0 RGB key frame of 256x256",
+            vec![0x82, 0x49, 0x83, 0x42, 0xE0, 0x1F, 0xE0, 0x1F, 0xE0],
+            FrameHeader {
+                frame_size: Some((256, 256)),
+                ..Default::default()
+            },
+        ),
+        (
+            "show existing frame",
+            vec![0x88],
+            FrameHeader {
+                show_existing_frame: true,
+                ..Default::default()
+            },
+        ),
+    ];
+
+    for (name, buf, want) in tests {
+        let h = FrameHeader::unmarshal(&buf)?;
+        assert_eq!(h, want, "{name}");
+        assert_eq!(h.is_key_frame(), want.frame_size.is_some(), "{name}");
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_vp9_partition_head_checker_is_partition_head() -> Result<()> {
     let vp9 = Vp9Packet::default();
@@ -11,6 +11,8 @@ pub enum Error {
     ErrHeaderSizeInsufficientForExtension,
     #[error("buffer too small")]
     ErrBufferTooSmall,
+    #[error("MTU too small for the RTP header")]
+    ErrMtuTooSmall,
     #[error("extension not enabled")]
     ErrHeaderExtensionsNotEnabled,
     #[error("extension not found")]
@@ -18,7 +20,7 @@ pub enum Error {
 
     #[error("header extension id must be between 1 and 14 for RFC 5285 extensions")]
     ErrRfc8285oneByteHeaderIdrange,
-    #[error("header extension payload must be 16bytes or less for RFC 5285 one byte extensions")]
+    #[error("header extension payload must be 1 to 16 bytes for RFC 5285 one byte extensions")]
     ErrRfc8285oneByteHeaderSize,
 
     #[error("header extension id must be between 1 and 255 for RFC 5285 extensions")]
@@ -33,10 +35,14 @@ pub enum Error {
     ErrShortPacket,
     #[error("invalid nil packet")]
     ErrNilPacket,
+    #[error("padding size must be 1 to 255 bytes")]
+    ErrInvalidPaddingSize,
     #[error("too many PDiff")]
     ErrTooManyPDiff,
     #[error("too many spatial layers")]
     ErrTooManySpatialLayers,
+    #[error("invalid VP9 frame header")]
+    ErrInvalidVp9FrameHeader,
     #[error("NALU Type is unhandled")]
     ErrUnhandledNaluType,
 
@@ -45,6 +51,14 @@ pub enum Error {
     #[error("invalid h265 packet type")]
     ErrInvalidH265PacketType,
 
+    #[error("invalid opus packet")]
+    ErrInvalidOpusPacket,
+
+    #[error("invalid FEC packet")]
+    ErrInvalidFecPacket,
+    #[error("protected packets do not fit in the FEC mask")]
+    ErrFecMaskOverflow,
+
     #[error("payload is too small for OBU extension header")]
     ErrPayloadTooSmallForObuExtensionHeader,
     #[error("payload is too small for OBU payload size")]
@@ -54,6 +68,14 @@ pub enum Error {
     HeaderExtensionPayloadNot32BitWords,
     #[error("audio level overflow")]
     AudioLevelOverflow,
+    #[error("invalid SDES extension value")]
+    InvalidSdesValue,
+    #[error("playout delay overflow")]
+    PlayoutDelayOverflow,
+    #[error("frame marking temporal id overflow")]
+    FrameMarkingTemporalIdOverflow,
+    #[error("invalid extmap attribute")]
+    InvalidExtmap,
     #[error("payload is not large enough")]
     PayloadIsNotLargeEnough,
     #[error("STAP-A declared size({0}) is larger than buffer({1})")]
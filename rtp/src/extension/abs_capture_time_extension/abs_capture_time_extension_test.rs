@@ -0,0 +1,101 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use bytes::{Bytes, BytesMut};
+
+use super::*;
+use crate::error::Result;
+
+#[test]
+fn test_abs_capture_time_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; 7][..];
+    let result = AbsCaptureTimeExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_abs_capture_time_extension() -> Result<()> {
+    let tests = vec![
+        (
+            "capture time only",
+            vec![0xa0, 0xc6, 0x5b, 0x1f, 0x80, 0x00, 0x00, 0x00],
+            AbsCaptureTimeExtension {
+                timestamp: 0xa0c65b1f80000000,
+                estimated_capture_clock_offset: None,
+            },
+        ),
+        (
+            "positive clock offset",
+            vec![
+                0xa0, 0xc6, 0x5b, 0x1f, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x40, 0x00,
+                0x00, 0x00,
+            ],
+            AbsCaptureTimeExtension {
+                timestamp: 0xa0c65b1f80000000,
+                estimated_capture_clock_offset: Some(0x0000000140000000),
+            },
+        ),
+        (
+            "negative clock offset",
+            vec![
+                0xa0, 0xc6, 0x5b, 0x1f, 0x80, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x80, 0x00,
+                0x00, 0x00,
+            ],
+            AbsCaptureTimeExtension {
+                timestamp: 0xa0c65b1f80000000,
+                estimated_capture_clock_offset: Some(-0x80000000),
+            },
+        ),
+    ];
+
+    for (name, raw, a2) in tests {
+        let raw = Bytes::from(raw);
+        let buf = &mut raw.clone();
+        let a1 = AbsCaptureTimeExtension::unmarshal(buf)?;
+        assert_eq!(a1, a2, "{name}");
+
+        let mut dst = BytesMut::with_capacity(a2.marshal_size());
+        dst.resize(a2.marshal_size(), 0);
+        a2.marshal_to(&mut dst)?;
+        assert_eq!(raw, dst.freeze(), "{name}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_abs_capture_time_extension_new() {
+    let capture_time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_250);
+
+    let ext = AbsCaptureTimeExtension::new(capture_time, None);
+    assert_eq!(ext.estimated_capture_clock_offset, None);
+    assert_eq!(ext.estimated_capture_clock_offset_seconds(), None);
+    let diff = ext
+        .capture_time()
+        .duration_since(capture_time)
+        .unwrap_or_else(|err| err.duration());
+    assert!(diff < Duration::from_micros(1), "{diff:?}");
+
+    let ext = AbsCaptureTimeExtension::new(capture_time, Some(-1.25));
+    assert_eq!(ext.estimated_capture_clock_offset, Some(-0x140000000));
+    assert_eq!(ext.estimated_capture_clock_offset_seconds(), Some(-1.25));
+}
+
+#[test]
+fn test_set_abs_capture_time() -> Result<()> {
+    let mut header = Header::default();
+    assert_eq!(get_abs_capture_time(&header, 5)?, None);
+
+    let ext = AbsCaptureTimeExtension {
+        timestamp: 0xa0c65b1f80000000,
+        estimated_capture_clock_offset: Some(0x0000000140000000),
+    };
+    set_abs_capture_time(&mut header, 5, &ext)?;
+
+    assert!(header.extension);
+    assert_eq!(header.get_extension(5).map(|raw| raw.len()), Some(16));
+    assert_eq!(get_abs_capture_time(&header, 5)?, Some(ext));
+
+    Ok(())
+}
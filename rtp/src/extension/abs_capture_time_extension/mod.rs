@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod abs_capture_time_extension_test;
+
+use std::time::SystemTime;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+use crate::extension::abs_send_time_extension::{ntp2unix, unix2ntp};
+use crate::header::Header;
+
+// ABS_CAPTURE_TIME_EXTENSION_SIZE One byte header size, without the estimated
+// capture clock offset
+pub const ABS_CAPTURE_TIME_EXTENSION_SIZE: usize = 8;
+// ABS_CAPTURE_TIME_EXTENDED_EXTENSION_SIZE One byte header size, with the
+// estimated capture clock offset
+pub const ABS_CAPTURE_TIME_EXTENDED_EXTENSION_SIZE: usize = 16;
+
+/// The scale of the 32.32 fixed-point formats.
+const FIXED_POINT_SCALE: f64 = (1u64 << 32) as f64;
+
+/// AbsCaptureTimeExtension is a extension payload format in
+/// http://www.webrtc.org/experiments/rtp-hdrext/abs-capture-time
+///
+/// It carries the NTP time at which the first frame of the packet was
+/// captured, in the UQ32.32 format, and optionally the estimated offset of the
+/// clock of the capturing system from the clock of the sender, in the Q32.32
+/// format. Both are kept across mixers and SFUs, which update the offset, so
+/// that receivers can synchronize streams and measure the end-to-end delay.
+///
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | len=7 |     absolute capture timestamp (bit 0-23)     |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |             absolute capture timestamp (bit 24-55)            |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ... (56-63)  |
+/// +-+-+-+-+-+-+-+-+
+///
+/// With the estimated capture clock offset:
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | len=15|     absolute capture timestamp (bit 0-23)     |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |             absolute capture timestamp (bit 24-55)            |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ... (56-63)  |   estimated capture clock offset (bit 0-23)   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |           estimated capture clock offset (bit 24-55)          |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ... (56-63)  |
+/// +-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+pub struct AbsCaptureTimeExtension {
+    pub timestamp: u64,
+    pub estimated_capture_clock_offset: Option<i64>,
+}
+
+impl AbsCaptureTimeExtension {
+    /// new makes an AbsCaptureTimeExtension from the capture time, and the
+    /// estimated offset in seconds of the capture clock from the sender clock.
+    pub fn new(capture_time: SystemTime, estimated_capture_clock_offset: Option<f64>) -> Self {
+        AbsCaptureTimeExtension {
+            timestamp: unix2ntp(capture_time),
+            estimated_capture_clock_offset: estimated_capture_clock_offset
+                .map(|offset| (offset * FIXED_POINT_SCALE).round() as i64),
+        }
+    }
+
+    /// capture_time returns the capture time.
+    pub fn capture_time(&self) -> SystemTime {
+        ntp2unix(self.timestamp)
+    }
+
+    /// estimated_capture_clock_offset_seconds returns the estimated offset in
+    /// seconds of the capture clock from the sender clock, if known.
+    pub fn estimated_capture_clock_offset_seconds(&self) -> Option<f64> {
+        self.estimated_capture_clock_offset
+            .map(|offset| offset as f64 / FIXED_POINT_SCALE)
+    }
+}
+
+impl Unmarshal for AbsCaptureTimeExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members.
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < ABS_CAPTURE_TIME_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let timestamp = raw_packet.get_u64();
+        let estimated_capture_clock_offset = (raw_packet.remaining()
+            >= ABS_CAPTURE_TIME_EXTENDED_EXTENSION_SIZE - ABS_CAPTURE_TIME_EXTENSION_SIZE)
+            .then(|| raw_packet.get_i64());
+
+        Ok(AbsCaptureTimeExtension {
+            timestamp,
+            estimated_capture_clock_offset,
+        })
+    }
+}
+
+impl MarshalSize for AbsCaptureTimeExtension {
+    /// MarshalSize returns the size of the AbsCaptureTimeExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        if self.estimated_capture_clock_offset.is_some() {
+            ABS_CAPTURE_TIME_EXTENDED_EXTENSION_SIZE
+        } else {
+            ABS_CAPTURE_TIME_EXTENSION_SIZE
+        }
+    }
+}
+
+impl Marshal for AbsCaptureTimeExtension {
+    /// MarshalTo serializes the members to buffer.
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        let size = self.marshal_size();
+        if buf.remaining_mut() < size {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        buf.put_u64(self.timestamp);
+        if let Some(offset) = self.estimated_capture_clock_offset {
+            buf.put_i64(offset);
+        }
+
+        Ok(size)
+    }
+}
+
+/// set_abs_capture_time stamps an outgoing packet header with an
+/// abs-capture-time extension of the given id.
+pub fn set_abs_capture_time(
+    header: &mut Header,
+    id: u8,
+    ext: &AbsCaptureTimeExtension,
+) -> crate::error::Result<()> {
+    let mut raw = BytesMut::zeroed(ext.marshal_size());
+    ext.marshal_to(&mut raw)?;
+
+    header.set_extension(id, Bytes::from(raw))
+}
+
+/// get_abs_capture_time returns the abs-capture-time extension of the given id
+/// of a received packet header, if it carries one.
+pub fn get_abs_capture_time(
+    header: &Header,
+    id: u8,
+) -> crate::error::Result<Option<AbsCaptureTimeExtension>> {
+    let Some(mut raw) = header.get_extension(id) else {
+        return Ok(None);
+    };
+
+    Ok(Some(AbsCaptureTimeExtension::unmarshal(&mut raw)?))
+}
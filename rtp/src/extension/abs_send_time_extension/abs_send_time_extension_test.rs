@@ -119,3 +119,36 @@ fn test_abs_send_time_extension_estimate() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_abs_send_time_extension_new() -> Result<()> {
+    // 31.5 seconds into the 64 seconds cycle of the 6.18 fixed-point time
+    let send_time = ntp2unix(0xa0c65b1f80000000);
+    let ext = AbsSendTimeExtension::new(send_time);
+    assert_eq!(ext.timestamp, 0x7E0000);
+
+    let mut raw = BytesMut::with_capacity(ext.marshal_size());
+    raw.resize(ext.marshal_size(), 0);
+    ext.marshal_to(&mut raw)?;
+    let out = AbsSendTimeExtension::unmarshal(&mut raw.freeze())?;
+    assert_eq!(out, ext);
+
+    // times before the unix epoch do not panic
+    assert_eq!(ntp2unix(0), UNIX_EPOCH);
+
+    Ok(())
+}
+
+#[test]
+fn test_set_abs_send_time() -> Result<()> {
+    let mut header = Header::default();
+    set_abs_send_time(&mut header, 3, ntp2unix(0xa0c65b1f80000000))?;
+
+    assert!(header.extension);
+    assert_eq!(
+        header.get_extension(3),
+        Some(Bytes::from_static(&[0x7E, 0x00, 0x00]))
+    );
+
+    Ok(())
+}
@@ -3,10 +3,11 @@ mod abs_send_time_extension_test;
 
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use bytes::{Buf, BufMut};
+use bytes::{Buf, BufMut, Bytes};
 use util::marshal::{Marshal, MarshalSize, Unmarshal};
 
 use crate::error::Error;
+use crate::header::Header;
 
 pub const ABS_SEND_TIME_EXTENSION_SIZE: usize = 3;
 
@@ -74,13 +75,29 @@ impl AbsSendTimeExtension {
     }
 
     /// NewAbsSendTimeExtension makes new AbsSendTimeExtension from time.Time.
+    /// The timestamp keeps the 24 bits of the 6.18 fixed-point send time, so it
+    /// wraps around every 64 seconds.
     pub fn new(send_time: SystemTime) -> Self {
         AbsSendTimeExtension {
-            timestamp: unix2ntp(send_time) >> 14,
+            timestamp: (unix2ntp(send_time) >> 14) & 0xFFFFFF,
         }
     }
 }
 
+/// set_abs_send_time stamps an outgoing packet header with an abs-send-time
+/// extension of the given id, carrying the time at which it is sent.
+pub fn set_abs_send_time(
+    header: &mut Header,
+    id: u8,
+    send_time: SystemTime,
+) -> crate::error::Result<()> {
+    let ext = AbsSendTimeExtension::new(send_time);
+    let mut raw = [0u8; ABS_SEND_TIME_EXTENSION_SIZE];
+    ext.marshal_to(&mut raw)?;
+
+    header.set_extension(id, Bytes::copy_from_slice(&raw))
+}
+
 pub fn unix2ntp(st: SystemTime) -> u64 {
     let u = st
         .duration_since(UNIX_EPOCH)
@@ -97,11 +114,14 @@ pub fn unix2ntp(st: SystemTime) -> u64 {
 }
 
 pub fn ntp2unix(t: u64) -> SystemTime {
-    let mut s = t >> 32;
+    let s = t >> 32;
     let mut f = t & 0xFFFFFFFF;
     f *= 1_000_000_000;
     f >>= 32;
-    s -= 0x83AA7E80;
+    // times before the unix epoch are clamped to it
+    let Some(s) = s.checked_sub(0x83AA7E80) else {
+        return UNIX_EPOCH;
+    };
     let u = s * 1_000_000_000 + f;
 
     UNIX_EPOCH
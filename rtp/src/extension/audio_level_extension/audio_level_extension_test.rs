@@ -64,3 +64,28 @@ fn test_audio_level_extension_level_overflow() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_audio_level_extension_dbov() -> Result<()> {
+    let tests = vec![
+        ("loudest", 0, 0),
+        ("typical speech", -30, 30),
+        ("silence", -127, 127),
+        ("below range", -128, 127),
+        ("above range", 10, 0),
+    ];
+
+    for (name, dbov, level) in tests {
+        let a = AudioLevelExtension::new(dbov, true);
+        assert_eq!(a.level, level, "{name}");
+        assert!(a.voice, "{name}");
+        assert_eq!(a.dbov(), dbov.clamp(-127, 0), "{name}");
+    }
+
+    let raw = Bytes::from_static(&[0x9e]);
+    let a = AudioLevelExtension::unmarshal(&mut raw.clone())?;
+    assert_eq!(a, AudioLevelExtension::new(-30, true));
+    assert_eq!(a.dbov(), -30);
+
+    Ok(())
+}
@@ -35,6 +35,22 @@ pub struct AudioLevelExtension {
     pub voice: bool,
 }
 
+impl AudioLevelExtension {
+    /// new returns the extension for an audio level in dBov, where -127 is the
+    /// quietest and 0 the loudest level. Levels out of that range are clamped.
+    pub fn new(dbov: i8, voice: bool) -> Self {
+        AudioLevelExtension {
+            level: dbov.clamp(-127, 0).unsigned_abs(),
+            voice,
+        }
+    }
+
+    /// dbov returns the audio level in dBov, from -127 to 0.
+    pub fn dbov(&self) -> i8 {
+        -((self.level & 0x7F) as i8)
+    }
+}
+
 impl Unmarshal for AudioLevelExtension {
     /// Unmarshal parses the passed byte slice and stores the result in the members
     fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
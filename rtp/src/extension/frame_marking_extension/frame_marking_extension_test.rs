@@ -0,0 +1,100 @@
+use bytes::{Bytes, BytesMut};
+
+use super::*;
+use crate::error::Result;
+
+#[test]
+fn test_frame_marking_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; 0][..];
+    let result = FrameMarkingExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_frame_marking_extension() -> Result<()> {
+    let tests = vec![
+        (
+            "non-scalable key frame",
+            vec![0xE0],
+            FrameMarkingExtension {
+                start_of_frame: true,
+                end_of_frame: true,
+                independent: true,
+                ..Default::default()
+            },
+        ),
+        (
+            "temporal layer",
+            vec![0x5B],
+            FrameMarkingExtension {
+                end_of_frame: true,
+                discardable: true,
+                base_layer_sync: true,
+                temporal_id: 3,
+                ..Default::default()
+            },
+        ),
+        (
+            "layer id",
+            vec![0x81, 0x02],
+            FrameMarkingExtension {
+                start_of_frame: true,
+                temporal_id: 1,
+                layer_id: Some(2),
+                ..Default::default()
+            },
+        ),
+        (
+            "tl0picidx",
+            vec![0xA2, 0x01, 0xFE],
+            FrameMarkingExtension {
+                start_of_frame: true,
+                independent: true,
+                temporal_id: 2,
+                layer_id: Some(1),
+                tl0_pic_idx: Some(254),
+                ..Default::default()
+            },
+        ),
+    ];
+
+    for (name, raw, a2) in tests {
+        let raw = Bytes::from(raw);
+        let buf = &mut raw.clone();
+        let a1 = FrameMarkingExtension::unmarshal(buf)?;
+        assert_eq!(a1, a2, "{name}");
+
+        let mut dst = BytesMut::with_capacity(a2.marshal_size());
+        dst.resize(a2.marshal_size(), 0);
+        a2.marshal_to(&mut dst)?;
+        assert_eq!(raw, dst.freeze(), "{name}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_frame_marking_extension_tl0_pic_idx_without_layer_id() -> Result<()> {
+    let a = FrameMarkingExtension {
+        tl0_pic_idx: Some(7),
+        ..Default::default()
+    };
+    assert_eq!(a.marshal()?, Bytes::from_static(&[0x00, 0x00, 0x07]));
+
+    Ok(())
+}
+
+#[test]
+fn test_frame_marking_extension_temporal_id_overflow() {
+    let a = FrameMarkingExtension {
+        temporal_id: 8,
+        ..Default::default()
+    };
+
+    let mut dst = BytesMut::with_capacity(a.marshal_size());
+    dst.resize(a.marshal_size(), 0);
+    let result = a.marshal_to(&mut dst);
+    assert!(result.is_err());
+}
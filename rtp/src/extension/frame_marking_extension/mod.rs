@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod frame_marking_extension_test;
+
+use bytes::{Buf, BufMut};
+use serde::{Deserialize, Serialize};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+
+// FRAME_MARKING_EXTENSION_SIZE One byte header size, without the layer
+// information of scalable streams
+pub const FRAME_MARKING_EXTENSION_SIZE: usize = 1;
+/// FRAME_MARKING_MAX_TEMPORAL_ID is the largest temporal id the 3-bit TID
+/// field holds.
+pub const FRAME_MARKING_MAX_TEMPORAL_ID: u8 = 7;
+
+const START_OF_FRAME: u8 = 0x80;
+const END_OF_FRAME: u8 = 0x40;
+const INDEPENDENT: u8 = 0x20;
+const DISCARDABLE: u8 = 0x10;
+const BASE_LAYER_SYNC: u8 = 0x08;
+
+/// FrameMarkingExtension is a extension payload format described in
+/// https://tools.ietf.org/html/draft-ietf-avtext-framemarking-07
+///
+/// It tells the frame boundaries and the dependencies of the frame a packet
+/// belongs to, so that middleboxes can forward or drop packets whose payload
+/// is encrypted or which they cannot parse.
+///
+/// S = Start of frame, E = End of frame, I = Independent frame,
+/// D = Discardable frame, B = Base layer sync, TID = Temporal layer id,
+/// LID = Layer id, TL0PICIDX = Temporal layer 0 picture index.
+///
+/// Non-scalable streams:
+/// 0                   1
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | len=0 |S|E|I|D|0 0 0 0|
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+///
+/// Scalable streams, of which the LID and TL0PICIDX, or the TL0PICIDX alone,
+/// may be left out:
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | len=2 |S|E|I|D|B| TID |      LID      |   TL0PICIDX   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+pub struct FrameMarkingExtension {
+    pub start_of_frame: bool,
+    pub end_of_frame: bool,
+    pub independent: bool,
+    pub discardable: bool,
+    pub base_layer_sync: bool,
+    pub temporal_id: u8,
+    pub layer_id: Option<u8>,
+    /// The TL0PICIDX is only carried along with the layer id, which is then
+    /// written as 0 if it is not set.
+    pub tl0_pic_idx: Option<u8>,
+}
+
+impl Unmarshal for FrameMarkingExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < FRAME_MARKING_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let b = raw_packet.get_u8();
+        let layer_id = raw_packet.has_remaining().then(|| raw_packet.get_u8());
+        let tl0_pic_idx = raw_packet.has_remaining().then(|| raw_packet.get_u8());
+
+        Ok(FrameMarkingExtension {
+            start_of_frame: b & START_OF_FRAME != 0,
+            end_of_frame: b & END_OF_FRAME != 0,
+            independent: b & INDEPENDENT != 0,
+            discardable: b & DISCARDABLE != 0,
+            base_layer_sync: b & BASE_LAYER_SYNC != 0,
+            temporal_id: b & FRAME_MARKING_MAX_TEMPORAL_ID,
+            layer_id,
+            tl0_pic_idx,
+        })
+    }
+}
+
+impl MarshalSize for FrameMarkingExtension {
+    /// MarshalSize returns the size of the FrameMarkingExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        if self.tl0_pic_idx.is_some() {
+            FRAME_MARKING_EXTENSION_SIZE + 2
+        } else if self.layer_id.is_some() {
+            FRAME_MARKING_EXTENSION_SIZE + 1
+        } else {
+            FRAME_MARKING_EXTENSION_SIZE
+        }
+    }
+}
+
+impl Marshal for FrameMarkingExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        let size = self.marshal_size();
+        if buf.remaining_mut() < size {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        if self.temporal_id > FRAME_MARKING_MAX_TEMPORAL_ID {
+            return Err(Error::FrameMarkingTemporalIdOverflow.into());
+        }
+
+        let flag = |set: bool, bit: u8| if set { bit } else { 0 };
+        buf.put_u8(
+            flag(self.start_of_frame, START_OF_FRAME)
+                | flag(self.end_of_frame, END_OF_FRAME)
+                | flag(self.independent, INDEPENDENT)
+                | flag(self.discardable, DISCARDABLE)
+                | flag(self.base_layer_sync, BASE_LAYER_SYNC)
+                | self.temporal_id,
+        );
+        if size > FRAME_MARKING_EXTENSION_SIZE {
+            buf.put_u8(self.layer_id.unwrap_or(0));
+        }
+        if let Some(tl0_pic_idx) = self.tl0_pic_idx {
+            buf.put_u8(tl0_pic_idx);
+        }
+
+        Ok(size)
+    }
+}
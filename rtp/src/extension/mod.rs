@@ -3,17 +3,44 @@ use std::fmt;
 
 use util::{Marshal, MarshalSize};
 
+pub mod abs_capture_time_extension;
 pub mod abs_send_time_extension;
 pub mod audio_level_extension;
+pub mod frame_marking_extension;
+pub mod playout_delay_extension;
+pub mod registry;
+pub mod sdes_extension;
 pub mod transport_cc_extension;
 pub mod video_orientation_extension;
 
+pub use registry::Registry;
+
+pub const ABS_SEND_TIME_URI: &str = "http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time";
+pub const AUDIO_LEVEL_URI: &str = "urn:ietf:params:rtp-hdrext:ssrc-audio-level";
+pub const TRANSPORT_CC_URI: &str =
+    "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01";
+pub const VIDEO_ORIENTATION_URI: &str = "urn:3gpp:video-orientation";
+pub const SDES_MID_URI: &str = "urn:ietf:params:rtp-hdrext:sdes:mid";
+pub const SDES_RTP_STREAM_ID_URI: &str = "urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id";
+pub const SDES_REPAIRED_RTP_STREAM_ID_URI: &str =
+    "urn:ietf:params:rtp-hdrext:sdes:repaired-rtp-stream-id";
+pub const PLAYOUT_DELAY_URI: &str = "http://www.webrtc.org/experiments/rtp-hdrext/playout-delay";
+pub const FRAME_MARKING_URI: &str = "urn:ietf:params:rtp-hdrext:framemarking";
+pub const ABS_CAPTURE_TIME_URI: &str =
+    "http://www.webrtc.org/experiments/rtp-hdrext/abs-capture-time";
+
 /// A generic RTP header extension.
 pub enum HeaderExtension {
     AbsSendTime(abs_send_time_extension::AbsSendTimeExtension),
     AudioLevel(audio_level_extension::AudioLevelExtension),
     TransportCc(transport_cc_extension::TransportCcExtension),
     VideoOrientation(video_orientation_extension::VideoOrientationExtension),
+    SdesMid(sdes_extension::SdesMidExtension),
+    RtpStreamId(sdes_extension::RtpStreamIdExtension),
+    RepairedRtpStreamId(sdes_extension::RepairedRtpStreamIdExtension),
+    PlayoutDelay(playout_delay_extension::PlayoutDelayExtension),
+    FrameMarking(frame_marking_extension::FrameMarkingExtension),
+    AbsCaptureTime(abs_capture_time_extension::AbsCaptureTimeExtension),
 
     /// A custom extension
     Custom {
@@ -27,12 +54,16 @@ impl HeaderExtension {
         use HeaderExtension::*;
 
         match self {
-            AbsSendTime(_) => "http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time".into(),
-            AudioLevel(_) => "urn:ietf:params:rtp-hdrext:ssrc-audio-level".into(),
-            TransportCc(_) => {
-                "http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01".into()
-            }
-            VideoOrientation(_) => "urn:3gpp:video-orientation".into(),
+            AbsSendTime(_) => ABS_SEND_TIME_URI.into(),
+            AudioLevel(_) => AUDIO_LEVEL_URI.into(),
+            TransportCc(_) => TRANSPORT_CC_URI.into(),
+            VideoOrientation(_) => VIDEO_ORIENTATION_URI.into(),
+            SdesMid(_) => SDES_MID_URI.into(),
+            RtpStreamId(_) => SDES_RTP_STREAM_ID_URI.into(),
+            RepairedRtpStreamId(_) => SDES_REPAIRED_RTP_STREAM_ID_URI.into(),
+            PlayoutDelay(_) => PLAYOUT_DELAY_URI.into(),
+            FrameMarking(_) => FRAME_MARKING_URI.into(),
+            AbsCaptureTime(_) => ABS_CAPTURE_TIME_URI.into(),
             Custom { uri, .. } => uri.clone(),
         }
     }
@@ -44,6 +75,12 @@ impl HeaderExtension {
             (AudioLevel(_), AudioLevel(_)) => true,
             (TransportCc(_), TransportCc(_)) => true,
             (VideoOrientation(_), VideoOrientation(_)) => true,
+            (SdesMid(_), SdesMid(_)) => true,
+            (RtpStreamId(_), RtpStreamId(_)) => true,
+            (RepairedRtpStreamId(_), RepairedRtpStreamId(_)) => true,
+            (PlayoutDelay(_), PlayoutDelay(_)) => true,
+            (FrameMarking(_), FrameMarking(_)) => true,
+            (AbsCaptureTime(_), AbsCaptureTime(_)) => true,
             (Custom { uri, .. }, Custom { uri: other_uri, .. }) => uri == other_uri,
             _ => false,
         }
@@ -58,6 +95,12 @@ impl MarshalSize for HeaderExtension {
             AudioLevel(ext) => ext.marshal_size(),
             TransportCc(ext) => ext.marshal_size(),
             VideoOrientation(ext) => ext.marshal_size(),
+            SdesMid(ext) => ext.marshal_size(),
+            RtpStreamId(ext) => ext.marshal_size(),
+            RepairedRtpStreamId(ext) => ext.marshal_size(),
+            PlayoutDelay(ext) => ext.marshal_size(),
+            FrameMarking(ext) => ext.marshal_size(),
+            AbsCaptureTime(ext) => ext.marshal_size(),
             Custom { extension: ext, .. } => ext.marshal_size(),
         }
     }
@@ -71,6 +114,12 @@ impl Marshal for HeaderExtension {
             AudioLevel(ext) => ext.marshal_to(buf),
             TransportCc(ext) => ext.marshal_to(buf),
             VideoOrientation(ext) => ext.marshal_to(buf),
+            SdesMid(ext) => ext.marshal_to(buf),
+            RtpStreamId(ext) => ext.marshal_to(buf),
+            RepairedRtpStreamId(ext) => ext.marshal_to(buf),
+            PlayoutDelay(ext) => ext.marshal_to(buf),
+            FrameMarking(ext) => ext.marshal_to(buf),
+            AbsCaptureTime(ext) => ext.marshal_to(buf),
             Custom { extension: ext, .. } => ext.marshal_to(buf),
         }
     }
@@ -85,6 +134,12 @@ impl fmt::Debug for HeaderExtension {
             AudioLevel(ext) => f.debug_tuple("AudioLevel").field(ext).finish(),
             TransportCc(ext) => f.debug_tuple("TransportCc").field(ext).finish(),
             VideoOrientation(ext) => f.debug_tuple("VideoOrientation").field(ext).finish(),
+            SdesMid(ext) => f.debug_tuple("SdesMid").field(ext).finish(),
+            RtpStreamId(ext) => f.debug_tuple("RtpStreamId").field(ext).finish(),
+            RepairedRtpStreamId(ext) => f.debug_tuple("RepairedRtpStreamId").field(ext).finish(),
+            PlayoutDelay(ext) => f.debug_tuple("PlayoutDelay").field(ext).finish(),
+            FrameMarking(ext) => f.debug_tuple("FrameMarking").field(ext).finish(),
+            AbsCaptureTime(ext) => f.debug_tuple("AbsCaptureTime").field(ext).finish(),
             Custom { uri, extension: _ } => f.debug_struct("Custom").field("uri", uri).finish(),
         }
     }
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod playout_delay_extension_test;
+
+use std::time::Duration;
+
+use bytes::{Buf, BufMut};
+use serde::{Deserialize, Serialize};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+
+// PLAYOUT_DELAY_EXTENSION_SIZE One byte header size
+pub const PLAYOUT_DELAY_EXTENSION_SIZE: usize = 3;
+/// PLAYOUT_DELAY_MAX_VALUE is the largest delay the 12-bit fields hold, in
+/// PLAYOUT_DELAY_GRANULARITY units.
+pub const PLAYOUT_DELAY_MAX_VALUE: u16 = 0x0FFF;
+/// PLAYOUT_DELAY_GRANULARITY is the unit of the delays.
+pub const PLAYOUT_DELAY_GRANULARITY: Duration = Duration::from_millis(10);
+
+/// PlayoutDelayExtension is a extension payload format described in
+/// http://www.webrtc.org/experiments/rtp-hdrext/playout-delay
+///
+/// The sender hints the receiver at the range of delay from capture to render
+/// it wants, in units of 10ms, so that the receiver sizes its jitter buffer
+/// accordingly. A zero minimum and maximum delay asks for rendering frames as
+/// soon as possible.
+///
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   | len=2 |       MIN delay       |       MAX delay       |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone, Serialize, Deserialize)]
+pub struct PlayoutDelayExtension {
+    pub min_delay: u16,
+    pub max_delay: u16,
+}
+
+impl PlayoutDelayExtension {
+    /// new returns the extension for the given delays, rounded down to the
+    /// granularity and clamped to the largest delay the fields hold.
+    pub fn new(min_delay: Duration, max_delay: Duration) -> Self {
+        let units = |delay: Duration| {
+            let units = delay.as_millis() / PLAYOUT_DELAY_GRANULARITY.as_millis();
+            units.min(PLAYOUT_DELAY_MAX_VALUE as u128) as u16
+        };
+
+        PlayoutDelayExtension {
+            min_delay: units(min_delay),
+            max_delay: units(max_delay),
+        }
+    }
+
+    /// min_delay_duration returns the minimum delay.
+    pub fn min_delay_duration(&self) -> Duration {
+        PLAYOUT_DELAY_GRANULARITY * self.min_delay as u32
+    }
+
+    /// max_delay_duration returns the maximum delay.
+    pub fn max_delay_duration(&self) -> Duration {
+        PLAYOUT_DELAY_GRANULARITY * self.max_delay as u32
+    }
+}
+
+impl Unmarshal for PlayoutDelayExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        if raw_packet.remaining() < PLAYOUT_DELAY_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
+        let b0 = raw_packet.get_u8() as u16;
+        let b1 = raw_packet.get_u8() as u16;
+        let b2 = raw_packet.get_u8() as u16;
+
+        Ok(PlayoutDelayExtension {
+            min_delay: (b0 << 4) | (b1 >> 4),
+            max_delay: ((b1 & 0x0F) << 8) | b2,
+        })
+    }
+}
+
+impl MarshalSize for PlayoutDelayExtension {
+    /// MarshalSize returns the size of the PlayoutDelayExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        PLAYOUT_DELAY_EXTENSION_SIZE
+    }
+}
+
+impl Marshal for PlayoutDelayExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+        if buf.remaining_mut() < PLAYOUT_DELAY_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+        if self.min_delay > PLAYOUT_DELAY_MAX_VALUE || self.max_delay > PLAYOUT_DELAY_MAX_VALUE {
+            return Err(Error::PlayoutDelayOverflow.into());
+        }
+
+        buf.put_u8((self.min_delay >> 4) as u8);
+        buf.put_u8(((self.min_delay << 4) as u8) | (self.max_delay >> 8) as u8);
+        buf.put_u8(self.max_delay as u8);
+
+        Ok(PLAYOUT_DELAY_EXTENSION_SIZE)
+    }
+}
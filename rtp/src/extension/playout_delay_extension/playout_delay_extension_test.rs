@@ -0,0 +1,72 @@
+use bytes::{Bytes, BytesMut};
+
+use super::*;
+use crate::error::Result;
+
+#[test]
+fn test_playout_delay_extension_too_small() -> Result<()> {
+    let mut buf = &vec![0u8; 2][..];
+    let result = PlayoutDelayExtension::unmarshal(&mut buf);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_playout_delay_extension() -> Result<()> {
+    let tests = vec![
+        ("zero", [0x00, 0x00, 0x00], 0, 0),
+        ("min and max", [0x01, 0x40, 0x64], 20, 100),
+        ("largest", [0xFF, 0xFF, 0xFF], 0x0FFF, 0x0FFF),
+    ];
+
+    for (name, raw, min_delay, max_delay) in tests {
+        let raw = Bytes::copy_from_slice(&raw);
+        let buf = &mut raw.clone();
+        let a1 = PlayoutDelayExtension::unmarshal(buf)?;
+        let a2 = PlayoutDelayExtension {
+            min_delay,
+            max_delay,
+        };
+        assert_eq!(a1, a2, "{name}");
+
+        let mut dst = BytesMut::with_capacity(a2.marshal_size());
+        dst.resize(a2.marshal_size(), 0);
+        a2.marshal_to(&mut dst)?;
+        assert_eq!(raw, dst.freeze(), "{name}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_playout_delay_extension_overflow() {
+    let a = PlayoutDelayExtension {
+        min_delay: 0,
+        max_delay: 0x1000,
+    };
+
+    let mut dst = BytesMut::with_capacity(a.marshal_size());
+    dst.resize(a.marshal_size(), 0);
+    let result = a.marshal_to(&mut dst);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_playout_delay_extension_durations() {
+    let a = PlayoutDelayExtension::new(Duration::from_millis(209), Duration::from_secs(1));
+    assert_eq!(
+        a,
+        PlayoutDelayExtension {
+            min_delay: 20,
+            max_delay: 100,
+        }
+    );
+    assert_eq!(a.min_delay_duration(), Duration::from_millis(200));
+    assert_eq!(a.max_delay_duration(), Duration::from_secs(1));
+
+    // delays too long for the fields are clamped
+    let a = PlayoutDelayExtension::new(Duration::ZERO, Duration::from_secs(60));
+    assert_eq!(a.max_delay, PLAYOUT_DELAY_MAX_VALUE);
+    assert_eq!(a.max_delay_duration(), Duration::from_millis(40950));
+}
@@ -0,0 +1,241 @@
+#[cfg(test)]
+mod registry_test;
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use bytes::Bytes;
+use util::marshal::{Marshal, Unmarshal};
+
+use super::abs_capture_time_extension::AbsCaptureTimeExtension;
+use super::abs_send_time_extension::AbsSendTimeExtension;
+use super::audio_level_extension::AudioLevelExtension;
+use super::sdes_extension::{RepairedRtpStreamIdExtension, RtpStreamIdExtension, SdesMidExtension};
+use super::transport_cc_extension::TransportCcExtension;
+use super::*;
+use crate::error::{Error, Result};
+use crate::header::Header;
+use crate::packet::Packet;
+
+/// Registry maps the header extension ids negotiated with a peer to the URIs
+/// of the extensions, as announced by the extmap attributes of its session
+/// description, so that extensions are read and written by URI rather than by
+/// ids which differ from a peer to another.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Registry {
+    extensions: BTreeMap<u8, Cow<'static, str>>,
+}
+
+impl Registry {
+    /// new returns a registry without any mapping.
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// register maps an id to an extension URI, replacing the previous mapping
+    /// of either of them.
+    pub fn register(&mut self, id: u8, uri: impl Into<Cow<'static, str>>) -> Result<()> {
+        if id == 0 {
+            return Err(Error::ErrRfc8285twoByteHeaderIdrange);
+        }
+
+        let uri = uri.into();
+        self.extensions.retain(|_, registered| *registered != uri);
+        self.extensions.insert(id, uri);
+
+        Ok(())
+    }
+
+    /// register_extmap registers the mapping of an extmap attribute, with or
+    /// without its `extmap:` key, as described in
+    /// https://tools.ietf.org/html/rfc8285#section-8
+    pub fn register_extmap(&mut self, extmap: &str) -> Result<()> {
+        let extmap = extmap.strip_prefix("extmap:").unwrap_or(extmap);
+        let mut fields = extmap.split_whitespace();
+        let (Some(value), Some(uri)) = (fields.next(), fields.next()) else {
+            return Err(Error::InvalidExtmap);
+        };
+        let id = match value.split_once('/') {
+            Some((id, _direction)) => id,
+            None => value,
+        };
+        let id = id.parse().map_err(|_| Error::InvalidExtmap)?;
+
+        self.register(id, uri.to_owned())
+    }
+
+    /// unregister removes the mapping of an id, returning its URI.
+    pub fn unregister(&mut self, id: u8) -> Option<Cow<'static, str>> {
+        self.extensions.remove(&id)
+    }
+
+    /// id returns the id an extension URI is mapped to.
+    pub fn id(&self, uri: &str) -> Option<u8> {
+        self.extensions
+            .iter()
+            .find(|(_, registered)| *registered == uri)
+            .map(|(id, _)| *id)
+    }
+
+    /// uri returns the extension URI an id is mapped to.
+    pub fn uri(&self, id: u8) -> Option<&str> {
+        self.extensions.get(&id).map(|uri| uri.as_ref())
+    }
+
+    /// iter returns the mappings, ordered by id.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &str)> {
+        self.extensions.iter().map(|(id, uri)| (*id, uri.as_ref()))
+    }
+
+    /// len returns the number of mappings.
+    pub fn len(&self) -> usize {
+        self.extensions.len()
+    }
+
+    /// is_empty reports whether no extension is mapped.
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+
+    /// get_extension returns the payload of the extension of a URI carried by
+    /// a header, if the extension was negotiated.
+    pub fn get_extension(&self, header: &Header, uri: &str) -> Option<Bytes> {
+        header.get_extension(self.id(uri)?)
+    }
+
+    /// set_extension writes an extension to a header under its negotiated id.
+    /// It returns false, leaving the header untouched, if the extension was
+    /// not negotiated.
+    pub fn set_extension(&self, header: &mut Header, extension: &HeaderExtension) -> Result<bool> {
+        let Some(id) = self.id(&extension.uri()) else {
+            return Ok(false);
+        };
+
+        header.set_extension(id, extension.marshal()?)?;
+
+        Ok(true)
+    }
+
+    /// unmarshal parses the extension of a URI carried by a packet, if any.
+    fn unmarshal<T: Unmarshal>(&self, packet: &Packet, uri: &str) -> Result<Option<T>> {
+        let Some(mut payload) = self.get_extension(&packet.header, uri) else {
+            return Ok(None);
+        };
+
+        Ok(Some(T::unmarshal(&mut payload)?))
+    }
+
+    /// get_mid returns the media identification of a packet.
+    pub fn get_mid(&self, packet: &Packet) -> Result<Option<String>> {
+        Ok(self
+            .unmarshal::<SdesMidExtension>(packet, SDES_MID_URI)?
+            .map(|ext| ext.mid))
+    }
+
+    /// get_rid returns the RTP stream identifier of a packet.
+    pub fn get_rid(&self, packet: &Packet) -> Result<Option<String>> {
+        Ok(self
+            .unmarshal::<RtpStreamIdExtension>(packet, SDES_RTP_STREAM_ID_URI)?
+            .map(|ext| ext.rid))
+    }
+
+    /// get_repaired_rid returns the RTP stream identifier of the stream a
+    /// packet repairs.
+    pub fn get_repaired_rid(&self, packet: &Packet) -> Result<Option<String>> {
+        Ok(self
+            .unmarshal::<RepairedRtpStreamIdExtension>(packet, SDES_REPAIRED_RTP_STREAM_ID_URI)?
+            .map(|ext| ext.rid))
+    }
+
+    /// get_audio_level returns the audio level of a packet.
+    pub fn get_audio_level(&self, packet: &Packet) -> Result<Option<AudioLevelExtension>> {
+        self.unmarshal(packet, AUDIO_LEVEL_URI)
+    }
+
+    /// get_transport_cc returns the transport-wide sequence number of a packet.
+    pub fn get_transport_cc(&self, packet: &Packet) -> Result<Option<u16>> {
+        Ok(self
+            .unmarshal::<TransportCcExtension>(packet, TRANSPORT_CC_URI)?
+            .map(|ext| ext.transport_sequence))
+    }
+
+    /// get_abs_send_time returns the time a packet was sent at.
+    pub fn get_abs_send_time(&self, packet: &Packet) -> Result<Option<AbsSendTimeExtension>> {
+        self.unmarshal(packet, ABS_SEND_TIME_URI)
+    }
+
+    /// get_abs_capture_time returns the time the media of a packet was
+    /// captured at.
+    pub fn get_abs_capture_time(&self, packet: &Packet) -> Result<Option<AbsCaptureTimeExtension>> {
+        let Some(id) = self.id(ABS_CAPTURE_TIME_URI) else {
+            return Ok(None);
+        };
+
+        abs_capture_time_extension::get_abs_capture_time(&packet.header, id)
+    }
+
+    /// set_mid sets the media identification of a packet.
+    pub fn set_mid(&self, packet: &mut Packet, mid: &str) -> Result<bool> {
+        let ext = SdesMidExtension {
+            mid: mid.to_owned(),
+        };
+        self.set_extension(&mut packet.header, &HeaderExtension::SdesMid(ext))
+    }
+
+    /// set_rid sets the RTP stream identifier of a packet.
+    pub fn set_rid(&self, packet: &mut Packet, rid: &str) -> Result<bool> {
+        let ext = RtpStreamIdExtension {
+            rid: rid.to_owned(),
+        };
+        self.set_extension(&mut packet.header, &HeaderExtension::RtpStreamId(ext))
+    }
+
+    /// set_repaired_rid sets the RTP stream identifier of the stream a packet
+    /// repairs.
+    pub fn set_repaired_rid(&self, packet: &mut Packet, rid: &str) -> Result<bool> {
+        let ext = RepairedRtpStreamIdExtension {
+            rid: rid.to_owned(),
+        };
+        self.set_extension(
+            &mut packet.header,
+            &HeaderExtension::RepairedRtpStreamId(ext),
+        )
+    }
+
+    /// set_audio_level sets the audio level of a packet.
+    pub fn set_audio_level(&self, packet: &mut Packet, ext: AudioLevelExtension) -> Result<bool> {
+        self.set_extension(&mut packet.header, &HeaderExtension::AudioLevel(ext))
+    }
+
+    /// set_transport_cc sets the transport-wide sequence number of a packet.
+    pub fn set_transport_cc(&self, packet: &mut Packet, transport_sequence: u16) -> Result<bool> {
+        let ext = TransportCcExtension { transport_sequence };
+        self.set_extension(&mut packet.header, &HeaderExtension::TransportCc(ext))
+    }
+
+    /// set_abs_send_time stamps a packet with the time it is sent at.
+    pub fn set_abs_send_time(&self, packet: &mut Packet, send_time: SystemTime) -> Result<bool> {
+        let Some(id) = self.id(ABS_SEND_TIME_URI) else {
+            return Ok(false);
+        };
+        abs_send_time_extension::set_abs_send_time(&mut packet.header, id, send_time)?;
+
+        Ok(true)
+    }
+
+    /// set_abs_capture_time stamps a packet with the time its media was
+    /// captured at.
+    pub fn set_abs_capture_time(
+        &self,
+        packet: &mut Packet,
+        ext: AbsCaptureTimeExtension,
+    ) -> Result<bool> {
+        let Some(id) = self.id(ABS_CAPTURE_TIME_URI) else {
+            return Ok(false);
+        };
+        abs_capture_time_extension::set_abs_capture_time(&mut packet.header, id, &ext)?;
+
+        Ok(true)
+    }
+}
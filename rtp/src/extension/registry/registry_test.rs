@@ -0,0 +1,148 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::*;
+
+#[test]
+fn test_registry_register() -> Result<()> {
+    let mut registry = Registry::new();
+    assert!(registry.is_empty());
+    assert_eq!(
+        registry.register(0, SDES_MID_URI),
+        Err(Error::ErrRfc8285twoByteHeaderIdrange)
+    );
+
+    registry.register(3, SDES_MID_URI)?;
+    registry.register(5, TRANSPORT_CC_URI)?;
+    assert_eq!(registry.id(SDES_MID_URI), Some(3));
+    assert_eq!(registry.uri(5), Some(TRANSPORT_CC_URI));
+    assert_eq!(registry.id(AUDIO_LEVEL_URI), None);
+
+    // a URI is mapped to a single id
+    registry.register(4, SDES_MID_URI)?;
+    assert_eq!(registry.id(SDES_MID_URI), Some(4));
+    assert_eq!(registry.uri(3), None);
+
+    // as an id is to a single URI
+    registry.register(5, AUDIO_LEVEL_URI)?;
+    assert_eq!(registry.id(TRANSPORT_CC_URI), None);
+    assert_eq!(
+        registry.iter().collect::<Vec<_>>(),
+        vec![(4, SDES_MID_URI), (5, AUDIO_LEVEL_URI)]
+    );
+
+    assert_eq!(registry.unregister(4).as_deref(), Some(SDES_MID_URI));
+    assert_eq!(registry.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_registry_register_extmap() -> Result<()> {
+    let tests = vec![
+        (
+            "id",
+            "1 urn:ietf:params:rtp-hdrext:sdes:mid",
+            Some((1, SDES_MID_URI)),
+        ),
+        (
+            "key",
+            "extmap:2 urn:ietf:params:rtp-hdrext:ssrc-audio-level",
+            Some((2, AUDIO_LEVEL_URI)),
+        ),
+        (
+            "direction and attributes",
+            "extmap:3/sendonly urn:ietf:params:rtp-hdrext:ssrc-audio-level vad=on",
+            Some((3, AUDIO_LEVEL_URI)),
+        ),
+        (
+            "two byte id",
+            "extmap:200 urn:ietf:params:rtp-hdrext:sdes:mid",
+            Some((200, SDES_MID_URI)),
+        ),
+        ("missing uri", "extmap:4", None),
+        (
+            "invalid id",
+            "extmap:x urn:ietf:params:rtp-hdrext:sdes:mid",
+            None,
+        ),
+        (
+            "id out of range",
+            "extmap:4096 urn:ietf:params:rtp-hdrext:sdes:mid",
+            None,
+        ),
+        (
+            "zero id",
+            "extmap:0 urn:ietf:params:rtp-hdrext:sdes:mid",
+            None,
+        ),
+    ];
+
+    for (name, extmap, expected) in tests {
+        let mut registry = Registry::new();
+        let result = registry.register_extmap(extmap);
+        match expected {
+            Some((id, uri)) => {
+                assert_eq!(result, Ok(()), "{name}");
+                assert_eq!(registry.uri(id), Some(uri), "{name}");
+            }
+            None => {
+                assert!(result.is_err(), "{name}");
+                assert!(registry.is_empty(), "{name}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_registry_extensions() -> Result<()> {
+    let mut local = Registry::new();
+    local.register_extmap("extmap:1 urn:ietf:params:rtp-hdrext:sdes:mid")?;
+    local.register_extmap("extmap:2 urn:ietf:params:rtp-hdrext:sdes:rtp-stream-id")?;
+    local.register_extmap(
+        "extmap:3 http://www.ietf.org/id/draft-holmer-rmcat-transport-wide-cc-extensions-01",
+    )?;
+    local.register_extmap("extmap:4 http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time")?;
+    local.register_extmap("extmap:5 urn:ietf:params:rtp-hdrext:ssrc-audio-level")?;
+
+    let mut packet = Packet::default();
+    assert_eq!(local.get_mid(&packet)?, None);
+
+    assert!(local.set_mid(&mut packet, "audio")?);
+    assert!(local.set_rid(&mut packet, "hi")?);
+    assert!(local.set_transport_cc(&mut packet, 4321)?);
+    let send_time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    assert!(local.set_abs_send_time(&mut packet, send_time)?);
+    let level = AudioLevelExtension {
+        level: 30,
+        voice: true,
+    };
+    assert!(local.set_audio_level(&mut packet, level)?);
+    // extensions which were not negotiated are not written
+    assert!(!local.set_repaired_rid(&mut packet, "lo")?);
+    assert!(!local.set_abs_capture_time(&mut packet, AbsCaptureTimeExtension::default())?);
+
+    assert_eq!(packet.header.get_extension_ids(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(
+        packet.header.get_extension(1),
+        Some(Bytes::from_static(b"audio"))
+    );
+    assert_eq!(local.get_mid(&packet)?.as_deref(), Some("audio"));
+    assert_eq!(local.get_rid(&packet)?.as_deref(), Some("hi"));
+    assert_eq!(local.get_repaired_rid(&packet)?, None);
+    assert_eq!(local.get_transport_cc(&packet)?, Some(4321));
+    assert_eq!(
+        local.get_abs_send_time(&packet)?,
+        Some(AbsSendTimeExtension::new(send_time))
+    );
+    assert_eq!(local.get_audio_level(&packet)?, Some(level));
+
+    // a peer which negotiated other ids reads the extensions of its ids
+    let mut remote = Registry::new();
+    remote.register(2, SDES_MID_URI)?;
+    assert_eq!(remote.get_mid(&packet)?.as_deref(), Some("hi"));
+    assert_eq!(remote.get_transport_cc(&packet)?, None);
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod sdes_extension_test;
+
+use bytes::{Buf, BufMut};
+use serde::{Deserialize, Serialize};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use crate::error::Error;
+
+/// SDES_EXTENSION_MAX_SIZE is the largest SDES item carried in a header extension,
+/// which requires the two byte header format above 16 bytes.
+pub const SDES_EXTENSION_MAX_SIZE: usize = 255;
+
+/// SdesMidExtension carries the identification tag of the media section of a
+/// BUNDLE group a packet belongs to, as described in
+/// https://tools.ietf.org/html/rfc9143#section-15
+///
+/// One byte format:
+/// 0                   1                   2                   3
+/// 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |  ID   |  len  | MID value (UTF-8, not null terminated)...     |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct SdesMidExtension {
+    pub mid: String,
+}
+
+/// RtpStreamIdExtension carries the RTP stream identifier of a packet, which
+/// tells the simulcast layer it belongs to, as described in
+/// https://tools.ietf.org/html/rfc8852#section-3.1
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RtpStreamIdExtension {
+    pub rid: String,
+}
+
+/// RepairedRtpStreamIdExtension carries the RTP stream identifier of the stream
+/// a redundancy or retransmission packet repairs, as described in
+/// https://tools.ietf.org/html/rfc8852#section-3.2
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RepairedRtpStreamIdExtension {
+    pub rid: String,
+}
+
+/// unmarshal_sdes parses an SDES item value. Trailing null bytes, which some
+/// senders pad the value with, are ignored.
+fn unmarshal_sdes<B: Buf>(raw_packet: &mut B) -> Result<String, util::Error> {
+    let mut value = vec![0u8; raw_packet.remaining()];
+    raw_packet.copy_to_slice(&mut value);
+    while value.last() == Some(&0) {
+        value.pop();
+    }
+
+    if value.is_empty() || value.len() > SDES_EXTENSION_MAX_SIZE {
+        return Err(Error::InvalidSdesValue.into());
+    }
+
+    String::from_utf8(value).map_err(|_| Error::InvalidSdesValue.into())
+}
+
+/// marshal_sdes serializes an SDES item value to buffer.
+fn marshal_sdes(value: &str, mut buf: &mut [u8]) -> Result<usize, util::Error> {
+    if value.is_empty() || value.len() > SDES_EXTENSION_MAX_SIZE {
+        return Err(Error::InvalidSdesValue.into());
+    }
+    if buf.remaining_mut() < value.len() {
+        return Err(Error::ErrBufferTooSmall.into());
+    }
+
+    buf.put_slice(value.as_bytes());
+
+    Ok(value.len())
+}
+
+/// is_valid_rid returns whether the value only uses the characters allowed in
+/// an RTP stream identifier by https://tools.ietf.org/html/rfc8851#section-10
+fn is_valid_rid(rid: &str) -> bool {
+    rid.bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+impl Unmarshal for SdesMidExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        Ok(SdesMidExtension {
+            mid: unmarshal_sdes(raw_packet)?,
+        })
+    }
+}
+
+impl MarshalSize for SdesMidExtension {
+    /// MarshalSize returns the size of the SdesMidExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        self.mid.len()
+    }
+}
+
+impl Marshal for SdesMidExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, buf: &mut [u8]) -> Result<usize, util::Error> {
+        marshal_sdes(&self.mid, buf)
+    }
+}
+
+impl Unmarshal for RtpStreamIdExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let rid = unmarshal_sdes(raw_packet)?;
+        if !is_valid_rid(&rid) {
+            return Err(Error::InvalidSdesValue.into());
+        }
+
+        Ok(RtpStreamIdExtension { rid })
+    }
+}
+
+impl MarshalSize for RtpStreamIdExtension {
+    /// MarshalSize returns the size of the RtpStreamIdExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        self.rid.len()
+    }
+}
+
+impl Marshal for RtpStreamIdExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, buf: &mut [u8]) -> Result<usize, util::Error> {
+        if !is_valid_rid(&self.rid) {
+            return Err(Error::InvalidSdesValue.into());
+        }
+
+        marshal_sdes(&self.rid, buf)
+    }
+}
+
+impl Unmarshal for RepairedRtpStreamIdExtension {
+    /// Unmarshal parses the passed byte slice and stores the result in the members
+    fn unmarshal<B>(raw_packet: &mut B) -> Result<Self, util::Error>
+    where
+        Self: Sized,
+        B: Buf,
+    {
+        let rid = unmarshal_sdes(raw_packet)?;
+        if !is_valid_rid(&rid) {
+            return Err(Error::InvalidSdesValue.into());
+        }
+
+        Ok(RepairedRtpStreamIdExtension { rid })
+    }
+}
+
+impl MarshalSize for RepairedRtpStreamIdExtension {
+    /// MarshalSize returns the size of the RepairedRtpStreamIdExtension once marshaled.
+    fn marshal_size(&self) -> usize {
+        self.rid.len()
+    }
+}
+
+impl Marshal for RepairedRtpStreamIdExtension {
+    /// MarshalTo serializes the members to buffer
+    fn marshal_to(&self, buf: &mut [u8]) -> Result<usize, util::Error> {
+        if !is_valid_rid(&self.rid) {
+            return Err(Error::InvalidSdesValue.into());
+        }
+
+        marshal_sdes(&self.rid, buf)
+    }
+}
@@ -0,0 +1,94 @@
+use bytes::{Bytes, BytesMut};
+
+use super::*;
+use crate::error::Result;
+
+#[test]
+fn test_sdes_mid_extension() -> Result<()> {
+    let raw = Bytes::from_static(b"audio0");
+    let buf = &mut raw.clone();
+    let m1 = SdesMidExtension::unmarshal(buf)?;
+    let m2 = SdesMidExtension {
+        mid: "audio0".to_owned(),
+    };
+    assert_eq!(m1, m2);
+
+    let mut dst = BytesMut::with_capacity(m2.marshal_size());
+    dst.resize(m2.marshal_size(), 0);
+    m2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    Ok(())
+}
+
+#[test]
+fn test_sdes_extension_null_padding() -> Result<()> {
+    let buf = &mut Bytes::from_static(b"0\x00\x00");
+    let m = SdesMidExtension::unmarshal(buf)?;
+    assert_eq!(m.mid, "0");
+
+    Ok(())
+}
+
+#[test]
+fn test_sdes_extension_invalid() -> Result<()> {
+    let tests = vec![
+        ("empty", &b""[..]),
+        ("only padding", &b"\x00\x00"[..]),
+        ("not utf-8", &b"\xff\xfe"[..]),
+    ];
+
+    for (name, raw) in tests {
+        let buf = &mut Bytes::from_static(raw);
+        let result = SdesMidExtension::unmarshal(buf);
+        assert!(result.is_err(), "{name}");
+    }
+
+    let too_long = SdesMidExtension {
+        mid: "m".repeat(SDES_EXTENSION_MAX_SIZE + 1),
+    };
+    let mut dst = vec![0u8; too_long.marshal_size()];
+    assert!(too_long.marshal_to(&mut dst).is_err());
+
+    let mid = SdesMidExtension {
+        mid: "mid".to_owned(),
+    };
+    let mut dst = [0u8; 2];
+    assert!(mid.marshal_to(&mut dst).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_rtp_stream_id_extension() -> Result<()> {
+    let raw = Bytes::from_static(b"hi-res_1");
+    let buf = &mut raw.clone();
+    let r1 = RtpStreamIdExtension::unmarshal(buf)?;
+    let r2 = RtpStreamIdExtension {
+        rid: "hi-res_1".to_owned(),
+    };
+    assert_eq!(r1, r2);
+
+    let mut dst = BytesMut::with_capacity(r2.marshal_size());
+    dst.resize(r2.marshal_size(), 0);
+    r2.marshal_to(&mut dst)?;
+    assert_eq!(raw, dst.freeze());
+
+    let buf = &mut raw.clone();
+    let repaired = RepairedRtpStreamIdExtension::unmarshal(buf)?;
+    assert_eq!(repaired.rid, "hi-res_1");
+
+    // RIDs are restricted to alphanumeric characters, '-' and '_'
+    let buf = &mut Bytes::from_static(b"f r");
+    assert!(RtpStreamIdExtension::unmarshal(buf).is_err());
+    let buf = &mut Bytes::from_static(b"f.r");
+    assert!(RepairedRtpStreamIdExtension::unmarshal(buf).is_err());
+
+    let invalid = RtpStreamIdExtension {
+        rid: "f r".to_owned(),
+    };
+    let mut dst = vec![0u8; invalid.marshal_size()];
+    assert!(invalid.marshal_to(&mut dst).is_err());
+
+    Ok(())
+}
@@ -1,11 +1,15 @@
 #[cfg(test)]
 mod transport_cc_extension_test;
 
-use bytes::{Buf, BufMut};
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::Arc;
+
+use bytes::{Buf, BufMut, Bytes};
 use serde::{Deserialize, Serialize};
 use util::marshal::{Marshal, MarshalSize, Unmarshal};
 
 use crate::error::Error;
+use crate::header::Header;
 
 // transport-wide sequence
 pub const TRANSPORT_CC_EXTENSION_SIZE: usize = 2;
@@ -59,3 +63,44 @@ impl Marshal for TransportCcExtension {
         Ok(TRANSPORT_CC_EXTENSION_SIZE)
     }
 }
+
+/// TransportCcSequencer allocates the transport-wide sequence numbers of the
+/// packets sent on a transport, wrapping around after 65535. Clones share the
+/// same counter, so that all the streams of the transport can draw from it.
+#[derive(Debug, Clone, Default)]
+pub struct TransportCcSequencer {
+    next_sequence: Arc<AtomicU16>,
+}
+
+impl TransportCcSequencer {
+    /// new returns a TransportCcSequencer whose first sequence number is `start`.
+    pub fn new(start: u16) -> Self {
+        TransportCcSequencer {
+            next_sequence: Arc::new(AtomicU16::new(start)),
+        }
+    }
+
+    /// next_extension allocates the sequence number of the next packet sent.
+    pub fn next_extension(&self) -> TransportCcExtension {
+        TransportCcExtension {
+            transport_sequence: self.next_sequence.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+
+    /// set_extension allocates the sequence number of the next packet sent and
+    /// sets it as the extension of the given id on its header. It returns the
+    /// sequence number. No sequence number is allocated if the extension cannot
+    /// be set.
+    pub fn set_extension(&self, header: &mut Header, id: u8) -> crate::error::Result<u16> {
+        // a placeholder checks that the extension can be set, so that a failure
+        // does not leave a gap in the sequence numbers
+        header.set_extension(id, Bytes::from_static(&[0; TRANSPORT_CC_EXTENSION_SIZE]))?;
+        let ext = self.next_extension();
+        header.set_extension(
+            id,
+            Bytes::copy_from_slice(&ext.transport_sequence.to_be_bytes()),
+        )?;
+
+        Ok(ext.transport_sequence)
+    }
+}
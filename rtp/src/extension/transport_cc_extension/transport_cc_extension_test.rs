@@ -2,6 +2,7 @@ use bytes::{Bytes, BytesMut};
 
 use super::*;
 use crate::error::Result;
+use crate::header::EXTENSION_PROFILE_TWO_BYTE;
 
 #[test]
 fn test_transport_cc_extension_too_small() -> Result<()> {
@@ -42,3 +43,48 @@ fn test_transport_cc_extension_extra_bytes() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_transport_cc_sequencer() -> Result<()> {
+    let sequencer = TransportCcSequencer::new(u16::MAX - 1);
+    let shared = sequencer.clone();
+
+    assert_eq!(sequencer.next_extension().transport_sequence, u16::MAX - 1);
+    assert_eq!(shared.next_extension().transport_sequence, u16::MAX);
+    assert_eq!(sequencer.next_extension().transport_sequence, 0);
+
+    let mut header = Header::default();
+    assert_eq!(shared.set_extension(&mut header, 5)?, 1);
+    assert_eq!(
+        header.get_extension(5),
+        Some(Bytes::from_static(&[0x00, 0x01]))
+    );
+    let mut buf = header.get_extension(5).unwrap();
+    assert_eq!(
+        TransportCcExtension::unmarshal(&mut buf)?,
+        TransportCcExtension {
+            transport_sequence: 1
+        }
+    );
+
+    // the extension cannot be set, the sequence number is not used
+    let mut header = Header {
+        extension: true,
+        extension_profile: EXTENSION_PROFILE_TWO_BYTE,
+        ..Default::default()
+    };
+    assert_eq!(
+        shared.set_extension(&mut header, 0),
+        Err(Error::ErrRfc8285twoByteHeaderIdrange)
+    );
+    assert_eq!(sequencer.next_extension().transport_sequence, 2);
+
+    assert_eq!(
+        TransportCcSequencer::default()
+            .next_extension()
+            .transport_sequence,
+        0
+    );
+
+    Ok(())
+}
@@ -59,6 +59,14 @@ pub enum VideoRotation {
     Degree270 = 3,
 }
 
+impl VideoRotation {
+    /// degrees returns the rotation of the video as sent on the link, which
+    /// the receiver must undo before rendering it.
+    pub fn degrees(&self) -> u16 {
+        *self as u16 * 90
+    }
+}
+
 impl MarshalSize for VideoOrientationExtension {
     fn marshal_size(&self) -> usize {
         VIDEO_ORIENTATION_EXTENSION_SIZE
@@ -91,6 +99,10 @@ impl Unmarshal for VideoOrientationExtension {
 
 impl Marshal for VideoOrientationExtension {
     fn marshal_to(&self, mut buf: &mut [u8]) -> util::Result<usize> {
+        if buf.remaining_mut() < VIDEO_ORIENTATION_EXTENSION_SIZE {
+            return Err(Error::ErrBufferTooSmall.into());
+        }
+
         let c = (self.direction as u8) << 3;
         let f = if self.flip { 0b0100 } else { 0 };
         let r = self.rotation as u8;
@@ -111,3 +111,26 @@ fn test_video_orientation_extension_degree_270() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_video_orientation_extension_marshal_too_small() -> Result<()> {
+    let a = VideoOrientationExtension::default();
+    let mut dst = [0u8; 0];
+    let result = a.marshal_to(&mut dst);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_video_rotation_degrees() -> Result<()> {
+    let tests = vec![(0b0000, 0), (0b0001, 90), (0b1110, 180), (0b0111, 270)];
+
+    for (raw, degrees) in tests {
+        let buf = &mut Bytes::copy_from_slice(&[raw]);
+        let a = VideoOrientationExtension::unmarshal(buf)?;
+        assert_eq!(a.rotation.degrees(), degrees, "{raw:#06b}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,274 @@
+use util::marshal::Unmarshal;
+
+use super::*;
+use crate::header::Header;
+
+fn media_packet(ssrc: u32, sequence_number: u16, marker: bool, payload: &[u8]) -> Packet {
+    Packet {
+        header: Header {
+            version: 2,
+            marker,
+            payload_type: 96,
+            sequence_number,
+            timestamp: 3000 + sequence_number as u32 * 90,
+            ssrc,
+            ..Default::default()
+        },
+        payload: Bytes::copy_from_slice(payload),
+    }
+}
+
+fn media(sequence_numbers: &[u16]) -> Vec<Bytes> {
+    sequence_numbers
+        .iter()
+        .map(|&seq| {
+            let payload = vec![seq as u8; 4 + seq as usize % 7];
+            media_packet(0x1234ABCD, seq, seq % 5 == 0, &payload)
+                .marshal()
+                .unwrap()
+        })
+        .collect()
+}
+
+fn media_of(ssrc: u32, sequence_number: u16) -> Bytes {
+    media_packet(ssrc, sequence_number, false, &[1, 2, 3])
+        .marshal()
+        .unwrap()
+}
+
+fn protected(payload: &Bytes) -> Vec<u16> {
+    match FlexfecPacket::unmarshal(payload).unwrap() {
+        FlexfecPacket::Repair { repair, .. } => repair.protected,
+        FlexfecPacket::Retransmission(_) => vec![],
+    }
+}
+
+#[test]
+fn test_flexfec_encode() -> Result<()> {
+    let packets = vec![
+        Bytes::from_static(&[
+            0x80, 0x60, 0x00, 0x02, 0x00, 0x00, 0x00, 0x10, 0x12, 0x34, 0xAB, 0xCD, 0x01, 0x02,
+        ]),
+        Bytes::from_static(&[
+            0x80, 0xE0, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x12, 0x34, 0xAB, 0xCD, 0x10, 0x20,
+            0x30,
+        ]),
+    ];
+
+    let fec = encode(&packets)?;
+    assert_eq!(
+        fec,
+        Bytes::from_static(&[
+            // P, X, CC, M and PT recovery, length recovery
+            0x00, 0x80, 0x00, 0x01, //
+            // TS recovery
+            0x00, 0x00, 0x00, 0x11, //
+            // SSRCCount, reserved
+            0x01, 0x00, 0x00, 0x00, //
+            // SSRC
+            0x12, 0x34, 0xAB, 0xCD, //
+            // SN base, mask with the k bit set
+            0x00, 0x01, 0xE0, 0x00, //
+            // repair payload
+            0x11, 0x22, 0x30,
+        ])
+    );
+    assert_eq!(
+        FlexfecPacket::unmarshal(&fec)?,
+        FlexfecPacket::Repair {
+            ssrc: 0x1234ABCD,
+            repair: RepairPacket {
+                recovery: Recovery {
+                    header: [0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11],
+                    length: 1,
+                    payload: vec![0x11, 0x22, 0x30],
+                },
+                protected: vec![1, 2],
+            },
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_flexfec_mask() -> Result<()> {
+    let tests = vec![
+        ("first part", vec![0u16, 14], vec![0xC0, 0x01]),
+        (
+            "second part",
+            vec![0, 15],
+            vec![0x40, 0x00, 0xC0, 0x00, 0x00, 0x00],
+        ),
+        (
+            "end of second part",
+            vec![0, 45],
+            vec![0x40, 0x00, 0x80, 0x00, 0x00, 0x01],
+        ),
+        (
+            "third part",
+            vec![0, 46],
+            vec![
+                0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            ],
+        ),
+        (
+            "end of third part",
+            vec![0, 108],
+            vec![
+                0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+            ],
+        ),
+    ];
+
+    for (name, sequence_numbers, want) in tests {
+        let fec = encode(&media(&sequence_numbers))?;
+        let mask_offset = FLEXFEC_HEADER_SIZE + STREAM_HEADER_SIZE;
+        assert_eq!(
+            &fec[mask_offset..mask_offset + want.len()],
+            &want[..],
+            "{name}"
+        );
+        assert_eq!(protected(&fec), sequence_numbers, "{name}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_flexfec_errors() {
+    assert_eq!(encode(&[]), Err(Error::ErrNilPacket));
+    assert_eq!(
+        encode(&[Bytes::from_static(&[0x80, 0x60, 0x00, 0x01])]),
+        Err(Error::ErrShortPacket)
+    );
+    assert_eq!(encode(&media(&[1, 110])), Err(Error::ErrFecMaskOverflow));
+    let mut packets = media(&[1]);
+    packets.push(media_packet(1, 2, false, &[0]).marshal().unwrap());
+    assert_eq!(encode(&packets), Err(Error::ErrInvalidFecPacket));
+    assert_eq!(
+        encode_retransmission(&Bytes::from_static(&[0x80, 0x60])),
+        Err(Error::ErrShortPacket)
+    );
+
+    let repair = encode(&media(&[1, 2])).unwrap();
+    let with = |i: usize, value: u8| {
+        let mut raw = repair.to_vec();
+        raw[i] = value;
+        Bytes::from(raw)
+    };
+    let tests = vec![
+        ("short", repair.slice(..11), Error::ErrShortPacket),
+        ("short mask", repair.slice(..19), Error::ErrShortPacket),
+        ("fixed mask", with(0, 0x40), Error::ErrInvalidFecPacket),
+        ("several streams", with(8, 2), Error::ErrInvalidFecPacket),
+        ("no k bit", with(18, 0x60), Error::ErrShortPacket),
+    ];
+    for (name, raw, want) in tests {
+        assert_eq!(FlexfecPacket::unmarshal(&raw), Err(want), "{name}");
+    }
+
+    // the k bit of the third part must be set
+    let mut raw = repair.to_vec();
+    raw[18] = 0x60;
+    raw.resize(FLEXFEC_HEADER_SIZE + STREAM_HEADER_SIZE + 14, 0);
+    assert_eq!(
+        FlexfecPacket::unmarshal(&Bytes::from(raw)),
+        Err(Error::ErrInvalidFecPacket)
+    );
+}
+
+#[test]
+fn test_flexfec_recover() -> Result<()> {
+    let tests = vec![
+        ("short mask", vec![10u16, 11, 12, 13, 14]),
+        ("long mask", vec![100, 101, 130, 150, 208]),
+        ("wrapping", vec![65533, 65534, 65535, 0, 1]),
+        ("unordered", vec![7, 3, 5, 4]),
+    ];
+
+    for (name, sequence_numbers) in tests {
+        let packets = media(&sequence_numbers);
+        let fec = encode(&packets)?;
+
+        for lost in 0..packets.len() {
+            let mut decoder = FlexfecDecoder::new(0x1234ABCD);
+            for (i, p) in packets.iter().enumerate() {
+                if i != lost {
+                    assert!(decoder.push_media(p.clone())?.is_empty(), "{name}");
+                }
+            }
+
+            let recovered = decoder.push_fec(&fec)?;
+            assert_eq!(recovered, vec![packets[lost].clone()], "{name} lost {lost}");
+
+            let packet = Packet::unmarshal(&mut recovered[0].clone())?;
+            assert_eq!(
+                packet.header.sequence_number, sequence_numbers[lost],
+                "{name}"
+            );
+        }
+    }
+
+    // repair packets of other streams are ignored
+    let mut decoder = FlexfecDecoder::new(0x5678);
+    decoder.push_media(media_of(0x5678, 2))?;
+    assert!(decoder.push_fec(&encode(&media(&[1, 2]))?)?.is_empty());
+    assert_eq!(decoder.recoverer.pending(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_flexfec_retransmission() -> Result<()> {
+    let packets = media(&[1, 2, 3]);
+    let fec = encode(&packets)?;
+
+    let retransmission = encode_retransmission(&packets[0])?;
+    assert_eq!(retransmission[0] & 0xC0, RETRANSMISSION_FLAG);
+    assert_eq!(&retransmission[1..], &packets[0][1..]);
+
+    // the retransmitted packet lets the repair packet recover another one
+    let mut decoder = FlexfecDecoder::new(0x1234ABCD);
+    assert!(decoder.push_fec(&fec)?.is_empty());
+    assert!(decoder.push_media(packets[2].clone())?.is_empty());
+    assert_eq!(
+        decoder.push_fec(&retransmission)?,
+        vec![packets[0].clone(), packets[1].clone()]
+    );
+
+    // a packet already received is not returned again
+    assert!(decoder.push_fec(&retransmission)?.is_empty());
+
+    // nor a packet of another stream
+    let other = encode_retransmission(&media_of(0x5678, 9))?;
+    assert!(decoder.push_fec(&other)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_flexfec_encoder() -> Result<()> {
+    let mut encoder = FlexfecEncoder::new(3);
+
+    assert_eq!(encoder.push(&media_packet(1, 1, false, &[1]))?, None);
+    assert_eq!(encoder.push(&media_packet(1, 2, false, &[2]))?, None);
+    let fec = encoder.push(&media_packet(1, 3, false, &[3]))?.unwrap();
+    assert_eq!(protected(&fec), vec![1, 2, 3]);
+
+    // the end of a frame completes the group
+    assert_eq!(encoder.push(&media_packet(1, 4, false, &[4]))?, None);
+    let fec = encoder.push(&media_packet(1, 5, true, &[5]))?.unwrap();
+    assert_eq!(protected(&fec), vec![4, 5]);
+
+    let packet = media_packet(1, 6, false, &[6]);
+    assert_eq!(
+        FlexfecPacket::unmarshal(&encoder.retransmit(&packet)?)?,
+        FlexfecPacket::Retransmission(packet.marshal()?)
+    );
+
+    assert_eq!(FlexfecEncoder::new(0).group_size, 1);
+    assert_eq!(FlexfecEncoder::new(200).group_size, FLEXFEC_MAX_PROTECTED);
+
+    Ok(())
+}
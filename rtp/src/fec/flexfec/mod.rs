@@ -0,0 +1,326 @@
+#[cfg(test)]
+mod flexfec_test;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use util::marshal::Marshal;
+
+use super::{
+    base_sequence_number, sequence_number, ssrc, Recoverer, Recovery, RepairPacket, RECOVERY_BITS,
+    RECOVERY_SIZE, RTP_FIXED_HEADER_SIZE,
+};
+use crate::error::{Error, Result};
+use crate::packet::Packet;
+
+/// FLEXFEC_HEADER_SIZE is the size of the part of the FlexFEC header before
+/// the protected SSRCs.
+pub const FLEXFEC_HEADER_SIZE: usize = 12;
+/// FLEXFEC_MAX_PROTECTED is the largest span of sequence numbers a FlexFEC
+/// packet protects, with the longest flexible mask.
+pub const FLEXFEC_MAX_PROTECTED: usize = 109;
+
+const RETRANSMISSION_FLAG: u8 = 0x80;
+const FIXED_MASK_FLAG: u8 = 0x40;
+const K_BIT: u8 = 0x80;
+/// The size of the SSRC and SN base of a protected stream.
+const STREAM_HEADER_SIZE: usize = 6;
+/// The size of each part of the flexible mask, and the count of packets it
+/// covers, the first bit of each part being the k bit.
+const MASK_PARTS: [(usize, usize); 3] = [(2, 15), (4, 31), (8, 63)];
+
+/// encode returns the payload of the FlexFEC repair packet protecting the
+/// given media packets, in the format of draft-ietf-payload-flexible-fec-scheme-03
+/// as implemented by libwebrtc. The packets are marshaled RTP packets of a
+/// single stream whose sequence numbers span at most FLEXFEC_MAX_PROTECTED,
+/// which the flexible mask lists, in parts ended by a set k bit.
+///
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |0|0|P|X|  CC   |M| PT recovery |        length recovery        |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                          TS recovery                          |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |   SSRCCount   |                    reserved                   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                             SSRC_i                            |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |           SN base_i           |k|          Mask [0-14]        |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |k|                   Mask [15-45] (optional)                   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |k|                                                             |
+/// +-+                   Mask [46-108] (optional)                  |
+/// |                                                               |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                        Repair Payload                         |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+pub fn encode(packets: &[Bytes]) -> Result<Bytes> {
+    let first = packets.first().ok_or(Error::ErrNilPacket)?;
+    if packets.iter().any(|p| p.len() < RTP_FIXED_HEADER_SIZE) {
+        return Err(Error::ErrShortPacket);
+    }
+    let media_ssrc = ssrc(first);
+    if packets.iter().any(|p| ssrc(p) != media_ssrc) {
+        return Err(Error::ErrInvalidFecPacket);
+    }
+
+    let sn_base = base_sequence_number(packets);
+    let mut offsets = vec![];
+    for p in packets {
+        let offset = sequence_number(p).wrapping_sub(sn_base) as usize;
+        if offset >= FLEXFEC_MAX_PROTECTED {
+            return Err(Error::ErrFecMaskOverflow);
+        }
+        offsets.push(offset);
+    }
+    let mask = marshal_mask(&offsets);
+
+    let recovery = Recovery::encode(packets);
+    let mut out = BytesMut::with_capacity(
+        FLEXFEC_HEADER_SIZE + STREAM_HEADER_SIZE + mask.len() + recovery.payload.len(),
+    );
+    out.put_u8(recovery.header[0] & RECOVERY_BITS);
+    out.put_u8(recovery.header[1]);
+    out.put_u16(recovery.length);
+    out.put(&recovery.header[4..RECOVERY_SIZE]);
+    out.put_u32(1 << 24);
+    out.put_u32(media_ssrc);
+    out.put_u16(sn_base);
+    out.put(&*mask);
+    out.put(&*recovery.payload);
+
+    Ok(out.freeze())
+}
+
+/// encode_retransmission returns the payload of the FlexFEC retransmission
+/// packet carrying a marshaled media packet. Its header, with the R bit set,
+/// is the RTP header of the media packet, whose version bits it overlays.
+///
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |1|0|P|X|  CC   |M| Payload Type|        Sequence Number        |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                           Timestamp                           |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                              SSRC                             |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                     Retransmission Payload                    |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+pub fn encode_retransmission(packet: &Bytes) -> Result<Bytes> {
+    if packet.len() < RTP_FIXED_HEADER_SIZE {
+        return Err(Error::ErrShortPacket);
+    }
+
+    let mut out = BytesMut::from(&packet[..]);
+    out[0] = RETRANSMISSION_FLAG | (packet[0] & RECOVERY_BITS);
+
+    Ok(out.freeze())
+}
+
+fn marshal_mask(offsets: &[usize]) -> Vec<u8> {
+    let max_offset = offsets.iter().copied().max().unwrap_or(0);
+    let mut parts = 0;
+    let mut covered = 0;
+    let mut size = 0;
+    for (part_size, part_bits) in MASK_PARTS {
+        parts += 1;
+        covered += part_bits;
+        size += part_size;
+        if max_offset < covered {
+            break;
+        }
+    }
+
+    let mut mask = vec![0u8; size];
+    // the k bit of the last part ends the mask
+    let last_part_start = MASK_PARTS[..parts - 1].iter().map(|p| p.0).sum::<usize>();
+    mask[last_part_start] |= K_BIT;
+    for &offset in offsets {
+        let bit = mask_bit_position(offset);
+        mask[bit / 8] |= 0x80 >> (bit % 8);
+    }
+
+    mask
+}
+
+/// mask_bit_position returns the position in the mask of the bit of the packet
+/// at the given offset from SN base, skipping the k bits.
+fn mask_bit_position(offset: usize) -> usize {
+    let mut part_start = 0;
+    let mut covered = 0;
+    for (part_size, part_bits) in MASK_PARTS {
+        if offset < covered + part_bits {
+            return part_start * 8 + 1 + offset - covered;
+        }
+        part_start += part_size;
+        covered += part_bits;
+    }
+
+    unreachable!("offset past FLEXFEC_MAX_PROTECTED")
+}
+
+/// FlexfecPacket is the parsed payload of a FlexFEC packet.
+#[derive(Debug, Clone, PartialEq)]
+enum FlexfecPacket {
+    /// A repair packet of the stream with the given SSRC.
+    Repair { ssrc: u32, repair: RepairPacket },
+    /// A retransmission of the marshaled media packet.
+    Retransmission(Bytes),
+}
+
+impl FlexfecPacket {
+    /// unmarshal parses a FlexFEC packet. Like libwebrtc, only repair packets
+    /// protecting a single stream with a flexible mask are supported.
+    fn unmarshal(raw: &Bytes) -> Result<Self> {
+        if raw.len() < RTP_FIXED_HEADER_SIZE {
+            return Err(Error::ErrShortPacket);
+        }
+        if raw[0] & RETRANSMISSION_FLAG != 0 {
+            let mut packet = BytesMut::from(&raw[..]);
+            packet[0] = 0x80 | (raw[0] & RECOVERY_BITS);
+            return Ok(FlexfecPacket::Retransmission(packet.freeze()));
+        }
+        if raw[0] & FIXED_MASK_FLAG != 0 || raw[8] != 1 {
+            return Err(Error::ErrInvalidFecPacket);
+        }
+
+        let mask_offset = FLEXFEC_HEADER_SIZE + STREAM_HEADER_SIZE;
+        let mut mask_size = 0;
+        let mut covered = 0;
+        for (i, (part_size, part_bits)) in MASK_PARTS.into_iter().enumerate() {
+            if raw.len() < mask_offset + mask_size + part_size {
+                return Err(Error::ErrShortPacket);
+            }
+            let k = raw[mask_offset + mask_size] & K_BIT != 0;
+            mask_size += part_size;
+            covered += part_bits;
+            if k {
+                break;
+            }
+            if i == MASK_PARTS.len() - 1 {
+                return Err(Error::ErrInvalidFecPacket);
+            }
+        }
+
+        let mask = &raw[mask_offset..mask_offset + mask_size];
+        let sn_base = u16::from_be_bytes([raw[16], raw[17]]);
+        let protected = (0..covered)
+            .filter(|&offset| {
+                let bit = mask_bit_position(offset);
+                mask[bit / 8] & (0x80 >> (bit % 8)) != 0
+            })
+            .map(|offset| sn_base.wrapping_add(offset as u16))
+            .collect();
+
+        let mut header = [0u8; RECOVERY_SIZE];
+        header[..2].copy_from_slice(&raw[..2]);
+        header[4..].copy_from_slice(&raw[4..RECOVERY_SIZE]);
+
+        Ok(FlexfecPacket::Repair {
+            ssrc: u32::from_be_bytes([raw[12], raw[13], raw[14], raw[15]]),
+            repair: RepairPacket {
+                recovery: Recovery {
+                    header,
+                    length: u16::from_be_bytes([raw[2], raw[3]]),
+                    payload: raw[mask_offset + mask_size..].to_vec(),
+                },
+                protected,
+            },
+        })
+    }
+}
+
+/// FlexfecEncoder protects the packets of a media stream, as they leave the
+/// packetizer, in groups which each get a FlexFEC repair packet. The sender
+/// carries the FlexFEC payloads in packets of the FEC stream, with its own
+/// SSRC and sequence numbers.
+#[derive(Debug, Clone)]
+pub struct FlexfecEncoder {
+    group_size: usize,
+    packets: Vec<Bytes>,
+}
+
+impl FlexfecEncoder {
+    /// new creates an encoder protecting groups of at most `group_size` packets,
+    /// which is clamped to 1..=FLEXFEC_MAX_PROTECTED.
+    pub fn new(group_size: usize) -> Self {
+        FlexfecEncoder {
+            group_size: group_size.clamp(1, FLEXFEC_MAX_PROTECTED),
+            packets: vec![],
+        }
+    }
+
+    /// push adds a media packet to the current group, and returns the payload of
+    /// the repair packet protecting the group once it is complete: after
+    /// `group_size` packets, or at the last packet of a frame so that FEC does
+    /// not delay it.
+    pub fn push(&mut self, packet: &Packet) -> Result<Option<Bytes>> {
+        self.packets.push(packet.marshal()?);
+        if self.packets.len() < self.group_size && !packet.header.marker {
+            return Ok(None);
+        }
+
+        let packets = std::mem::take(&mut self.packets);
+        encode(&packets).map(Some)
+    }
+
+    /// retransmit returns the payload of the retransmission packet resending a
+    /// media packet in the FEC stream.
+    pub fn retransmit(&self, packet: &Packet) -> Result<Bytes> {
+        encode_retransmission(&packet.marshal()?)
+    }
+}
+
+/// FlexfecDecoder recovers the lost packets of a media stream from the
+/// FlexFEC packets protecting them. It is fed the marshaled media packets as
+/// they are received, and the payloads of the packets of the FEC stream,
+/// which may protect several media streams: those about other streams are
+/// ignored.
+#[derive(Debug, Clone)]
+pub struct FlexfecDecoder {
+    recoverer: Recoverer,
+}
+
+impl FlexfecDecoder {
+    /// new creates a decoder for the media stream with the given SSRC.
+    pub fn new(ssrc: u32) -> Self {
+        FlexfecDecoder {
+            recoverer: Recoverer::new(ssrc),
+        }
+    }
+
+    /// push_media records a received media packet, and returns the marshaled
+    /// packets it allowed to recover.
+    pub fn push_media(&mut self, raw: Bytes) -> Result<Vec<Bytes>> {
+        if raw.len() < RTP_FIXED_HEADER_SIZE {
+            return Err(Error::ErrShortPacket);
+        }
+
+        Ok(self.recoverer.push_media(raw))
+    }
+
+    /// push_fec records the payload of a received FlexFEC packet, and returns
+    /// the marshaled packets it allowed to recover. A retransmitted packet
+    /// which was missing is returned first.
+    pub fn push_fec(&mut self, payload: &Bytes) -> Result<Vec<Bytes>> {
+        match FlexfecPacket::unmarshal(payload)? {
+            FlexfecPacket::Repair { ssrc, repair } => {
+                if ssrc != self.recoverer.ssrc() {
+                    return Ok(vec![]);
+                }
+                Ok(self.recoverer.push_repair(repair))
+            }
+            FlexfecPacket::Retransmission(raw) => {
+                if ssrc(&raw) != self.recoverer.ssrc()
+                    || self.recoverer.contains(sequence_number(&raw))
+                {
+                    return Ok(vec![]);
+                }
+                let mut recovered = vec![raw.clone()];
+                recovered.extend(self.recoverer.push_media(raw));
+                Ok(recovered)
+            }
+        }
+    }
+}
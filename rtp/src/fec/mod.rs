@@ -0,0 +1,226 @@
+pub mod flexfec;
+pub mod ulpfec;
+
+use std::collections::{HashMap, VecDeque};
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+/// The size of the fixed RTP header, which FEC recovers from its own fields
+/// rather than from the XOR of the payloads.
+pub(crate) const RTP_FIXED_HEADER_SIZE: usize = 12;
+/// The size of the first bytes of the RTP header which FEC headers XOR in
+/// place: the sequence number is replaced, and the SSRC is not protected.
+pub(crate) const RECOVERY_SIZE: usize = 8;
+/// The P, X and CC bits of the first byte of an RTP header.
+pub(crate) const RECOVERY_BITS: u8 = 0x3F;
+
+/// The count of received media packets a decoder keeps to recover others.
+const MEDIA_HISTORY_SIZE: usize = 512;
+/// The count of FEC packets a decoder keeps until they are of use.
+const FEC_HISTORY_SIZE: usize = 32;
+
+pub(crate) fn sequence_number(raw: &[u8]) -> u16 {
+    u16::from_be_bytes([raw[2], raw[3]])
+}
+
+pub(crate) fn timestamp(raw: &[u8]) -> u32 {
+    u32::from_be_bytes([raw[4], raw[5], raw[6], raw[7]])
+}
+
+pub(crate) fn ssrc(raw: &[u8]) -> u32 {
+    u32::from_be_bytes([raw[8], raw[9], raw[10], raw[11]])
+}
+
+/// xor_into XORs `src` into the start of `dst`, up to the length of `dst`.
+pub(crate) fn xor_into(dst: &mut [u8], src: &[u8]) {
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d ^= s;
+    }
+}
+
+/// base_sequence_number returns the earliest sequence number of the packets,
+/// taking wrapping into account.
+pub(crate) fn base_sequence_number(packets: &[Bytes]) -> u16 {
+    let first = packets.first().map(|p| sequence_number(p)).unwrap_or(0);
+    packets
+        .iter()
+        .map(|p| sequence_number(p))
+        .min_by_key(|&seq| seq.wrapping_sub(first) as i16)
+        .unwrap_or(first)
+}
+
+/// Recovery is the XOR of marshaled RTP packets, as carried by the fields of
+/// a FEC packet.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Recovery {
+    /// The XOR of the first bytes of the headers, of which the P, X, CC, M,
+    /// PT and timestamp fields are of use.
+    pub(crate) header: [u8; RECOVERY_SIZE],
+    /// The XOR of the lengths of the packets past their fixed header.
+    pub(crate) length: u16,
+    /// The XOR of the packets past their fixed header.
+    pub(crate) payload: Vec<u8>,
+}
+
+impl Recovery {
+    /// encode returns the XOR of packets, which are at least
+    /// RTP_FIXED_HEADER_SIZE long.
+    pub(crate) fn encode(packets: &[Bytes]) -> Self {
+        let payload_length = packets
+            .iter()
+            .map(|p| p.len() - RTP_FIXED_HEADER_SIZE)
+            .max()
+            .unwrap_or(0);
+        let mut recovery = Recovery {
+            payload: vec![0u8; payload_length],
+            ..Default::default()
+        };
+        for p in packets {
+            recovery.add(p);
+        }
+
+        recovery
+    }
+
+    /// add XORs a packet in. Only the part of the payload which the recovery
+    /// covers is taken.
+    pub(crate) fn add(&mut self, raw: &[u8]) {
+        xor_into(&mut self.header, &raw[..RECOVERY_SIZE]);
+        self.length ^= (raw.len() - RTP_FIXED_HEADER_SIZE) as u16;
+        xor_into(&mut self.payload, &raw[RTP_FIXED_HEADER_SIZE..]);
+    }
+
+    /// packet returns the marshaled packet left once all the others it covers
+    /// were added, or None if its length is past the covered payload.
+    pub(crate) fn packet(&self, sequence_number: u16, ssrc: u32) -> Option<Bytes> {
+        let length = self.length as usize;
+        if length > self.payload.len() {
+            return None;
+        }
+
+        let mut out = BytesMut::with_capacity(RTP_FIXED_HEADER_SIZE + length);
+        out.put_u8(0x80 | (self.header[0] & RECOVERY_BITS));
+        out.put_u8(self.header[1]);
+        out.put_u16(sequence_number);
+        out.put_u32(timestamp(&self.header));
+        out.put_u32(ssrc);
+        out.put(&self.payload[..length]);
+
+        Some(out.freeze())
+    }
+}
+
+/// RepairPacket is a parsed FEC packet protecting packets of a media stream.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RepairPacket {
+    pub(crate) recovery: Recovery,
+    /// The sequence numbers of the protected packets.
+    pub(crate) protected: Vec<u16>,
+}
+
+/// Recoverer keeps the received media packets and FEC packets of a stream, and
+/// recovers a packet as soon as it is the only one missing among those a FEC
+/// packet protects. A recovered packet may in turn allow recovering others.
+#[derive(Debug, Clone)]
+pub(crate) struct Recoverer {
+    ssrc: u32,
+    media: HashMap<u16, Bytes>,
+    media_order: VecDeque<u16>,
+    repair: VecDeque<RepairPacket>,
+}
+
+impl Recoverer {
+    pub(crate) fn new(ssrc: u32) -> Self {
+        Recoverer {
+            ssrc,
+            media: HashMap::new(),
+            media_order: VecDeque::new(),
+            repair: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn ssrc(&self) -> u32 {
+        self.ssrc
+    }
+
+    pub(crate) fn contains(&self, sequence_number: u16) -> bool {
+        self.media.contains_key(&sequence_number)
+    }
+
+    pub(crate) fn pending(&self) -> usize {
+        self.repair.len()
+    }
+
+    /// push_media records a media packet, and returns the packets it allowed
+    /// to recover.
+    pub(crate) fn push_media(&mut self, raw: Bytes) -> Vec<Bytes> {
+        self.insert_media(raw);
+        self.recover()
+    }
+
+    /// push_repair records a FEC packet, and returns the packets it allowed to
+    /// recover.
+    pub(crate) fn push_repair(&mut self, repair: RepairPacket) -> Vec<Bytes> {
+        if self.repair.len() == FEC_HISTORY_SIZE {
+            self.repair.pop_front();
+        }
+        self.repair.push_back(repair);
+        self.recover()
+    }
+
+    fn insert_media(&mut self, raw: Bytes) {
+        let seq = sequence_number(&raw);
+        if self.media.contains_key(&seq) {
+            return;
+        }
+        if self.media_order.len() == MEDIA_HISTORY_SIZE {
+            if let Some(oldest) = self.media_order.pop_front() {
+                self.media.remove(&oldest);
+            }
+        }
+        self.media_order.push_back(seq);
+        self.media.insert(seq, raw);
+    }
+
+    fn recover(&mut self) -> Vec<Bytes> {
+        let mut recovered = vec![];
+
+        loop {
+            let mut progress = false;
+            for repair in std::mem::take(&mut self.repair) {
+                let missing: Vec<u16> = repair
+                    .protected
+                    .iter()
+                    .copied()
+                    .filter(|seq| !self.media.contains_key(seq))
+                    .collect();
+                match missing[..] {
+                    // nothing left to recover
+                    [] => {}
+                    [seq] => {
+                        // a FEC packet inconsistent with the media is dropped
+                        if let Some(raw) = self.rebuild(&repair, seq) {
+                            self.insert_media(raw.clone());
+                            recovered.push(raw);
+                            progress = true;
+                        }
+                    }
+                    _ => self.repair.push_back(repair),
+                }
+            }
+
+            if !progress {
+                return recovered;
+            }
+        }
+    }
+
+    fn rebuild(&self, repair: &RepairPacket, missing: u16) -> Option<Bytes> {
+        let mut recovery = repair.recovery.clone();
+        for seq in repair.protected.iter().filter(|&&seq| seq != missing) {
+            recovery.add(self.media.get(seq)?);
+        }
+
+        recovery.packet(missing, self.ssrc)
+    }
+}
@@ -0,0 +1,227 @@
+#[cfg(test)]
+mod ulpfec_test;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use util::marshal::Marshal;
+
+use super::{
+    base_sequence_number, sequence_number, Recoverer, Recovery, RepairPacket, RECOVERY_BITS,
+    RECOVERY_SIZE, RTP_FIXED_HEADER_SIZE,
+};
+use crate::error::{Error, Result};
+use crate::packet::Packet;
+
+/// ULPFEC_HEADER_SIZE is the size of the FEC header which starts the payload
+/// of a ULPFEC packet.
+pub const ULPFEC_HEADER_SIZE: usize = 10;
+/// ULPFEC_MAX_PROTECTED is the largest span of sequence numbers a ULPFEC
+/// packet protects, with the long mask.
+pub const ULPFEC_MAX_PROTECTED: usize = 48;
+
+const LEVEL_HEADER_SIZE_SHORT: usize = 4;
+const LEVEL_HEADER_SIZE_LONG: usize = 8;
+const SHORT_MASK_PROTECTED: usize = 16;
+const EXTENSION_FLAG: u8 = 0x80;
+const LONG_MASK_FLAG: u8 = 0x40;
+
+/// encode returns the payload of the ULPFEC packet protecting the given media
+/// packets, as described in RFC 5109 section 7. The packets are marshaled RTP
+/// packets of a single stream whose sequence numbers span at most
+/// ULPFEC_MAX_PROTECTED. They are fully protected by the level 0 of the FEC
+/// packet, which thus allows recovering any one of them.
+///
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |E|L|P|X|  CC   |M| PT recovery |            SN base            |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                          TS recovery                          |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |        length recovery        |       Protection Length       |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |             mask              |    mask cont. (present only   |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+                               +
+/// |         when L = 1)           |                               |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+                               +
+/// |                      Level 0 Payload                          |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+pub fn encode(packets: &[Bytes]) -> Result<Bytes> {
+    if packets.is_empty() {
+        return Err(Error::ErrNilPacket);
+    }
+    if packets.iter().any(|p| p.len() < RTP_FIXED_HEADER_SIZE) {
+        return Err(Error::ErrShortPacket);
+    }
+
+    let sn_base = base_sequence_number(packets);
+    let mut mask = 0u64;
+    for p in packets {
+        let offset = sequence_number(p).wrapping_sub(sn_base) as usize;
+        if offset >= ULPFEC_MAX_PROTECTED {
+            return Err(Error::ErrFecMaskOverflow);
+        }
+        mask |= 1 << (ULPFEC_MAX_PROTECTED - 1 - offset);
+    }
+    let long_mask = mask.trailing_zeros() < (ULPFEC_MAX_PROTECTED - SHORT_MASK_PROTECTED) as u32;
+    let level_header_size = if long_mask {
+        LEVEL_HEADER_SIZE_LONG
+    } else {
+        LEVEL_HEADER_SIZE_SHORT
+    };
+
+    let recovery = Recovery::encode(packets);
+    let mut out =
+        BytesMut::with_capacity(ULPFEC_HEADER_SIZE + level_header_size + recovery.payload.len());
+    let flags = if long_mask { LONG_MASK_FLAG } else { 0 };
+    out.put_u8(flags | (recovery.header[0] & RECOVERY_BITS));
+    out.put_u8(recovery.header[1]);
+    out.put_u16(sn_base);
+    out.put(&recovery.header[4..RECOVERY_SIZE]);
+    out.put_u16(recovery.length);
+    out.put_u16(recovery.payload.len() as u16);
+    let mask = mask.to_be_bytes();
+    out.put(&mask[2..level_header_size]);
+    out.put(&*recovery.payload);
+
+    Ok(out.freeze())
+}
+
+/// UlpfecPacket is the parsed payload of a ULPFEC packet.
+#[derive(Debug, Clone, PartialEq)]
+struct UlpfecPacket {
+    /// The recovery fields and the level 0 payload. The SN base stands in
+    /// place of the sequence number in the header.
+    recovery: Recovery,
+    sn_base: u16,
+    /// The protected packets, from the most significant bit for SN base.
+    mask: u64,
+}
+
+impl UlpfecPacket {
+    fn unmarshal(raw: &Bytes) -> Result<Self> {
+        if raw.len() < ULPFEC_HEADER_SIZE + LEVEL_HEADER_SIZE_SHORT {
+            return Err(Error::ErrShortPacket);
+        }
+        if raw[0] & EXTENSION_FLAG != 0 {
+            return Err(Error::ErrInvalidFecPacket);
+        }
+
+        let level_header_size = if raw[0] & LONG_MASK_FLAG != 0 {
+            LEVEL_HEADER_SIZE_LONG
+        } else {
+            LEVEL_HEADER_SIZE_SHORT
+        };
+        let payload_offset = ULPFEC_HEADER_SIZE + level_header_size;
+        if raw.len() < payload_offset {
+            return Err(Error::ErrShortPacket);
+        }
+        let protection_length = u16::from_be_bytes([raw[10], raw[11]]) as usize;
+        if raw.len() < payload_offset + protection_length {
+            return Err(Error::ErrShortPacket);
+        }
+
+        let mut mask = [0u8; 8];
+        mask[2..level_header_size].copy_from_slice(&raw[12..payload_offset]);
+        let mut header = [0u8; RECOVERY_SIZE];
+        header.copy_from_slice(&raw[..RECOVERY_SIZE]);
+
+        Ok(UlpfecPacket {
+            recovery: Recovery {
+                header,
+                length: u16::from_be_bytes([raw[8], raw[9]]),
+                payload: raw[payload_offset..payload_offset + protection_length].to_vec(),
+            },
+            sn_base: sequence_number(raw),
+            mask: u64::from_be_bytes(mask),
+        })
+    }
+
+    /// protected returns the sequence numbers of the protected packets.
+    fn protected(&self) -> impl Iterator<Item = u16> + '_ {
+        (0..ULPFEC_MAX_PROTECTED)
+            .filter(|i| self.mask & (1 << (ULPFEC_MAX_PROTECTED - 1 - i)) != 0)
+            .map(|i| self.sn_base.wrapping_add(i as u16))
+    }
+}
+
+impl From<UlpfecPacket> for RepairPacket {
+    fn from(fec: UlpfecPacket) -> Self {
+        RepairPacket {
+            protected: fec.protected().collect(),
+            recovery: fec.recovery,
+        }
+    }
+}
+
+/// UlpfecEncoder protects the packets of a media stream, as they leave the
+/// packetizer, in groups which each get a ULPFEC packet. The sender carries
+/// the FEC payloads either in RED blocks of the media stream, as browsers do,
+/// or in packets of a separate FEC stream.
+#[derive(Debug, Clone)]
+pub struct UlpfecEncoder {
+    group_size: usize,
+    packets: Vec<Bytes>,
+}
+
+impl UlpfecEncoder {
+    /// new creates an encoder protecting groups of at most `group_size` packets,
+    /// which is clamped to 1..=ULPFEC_MAX_PROTECTED.
+    pub fn new(group_size: usize) -> Self {
+        UlpfecEncoder {
+            group_size: group_size.clamp(1, ULPFEC_MAX_PROTECTED),
+            packets: vec![],
+        }
+    }
+
+    /// push adds a media packet to the current group, and returns the payload of
+    /// the ULPFEC packet protecting the group once it is complete: after
+    /// `group_size` packets, or at the last packet of a frame so that FEC does
+    /// not delay it.
+    pub fn push(&mut self, packet: &Packet) -> Result<Option<Bytes>> {
+        self.packets.push(packet.marshal()?);
+        if self.packets.len() < self.group_size && !packet.header.marker {
+            return Ok(None);
+        }
+
+        let packets = std::mem::take(&mut self.packets);
+        encode(&packets).map(Some)
+    }
+}
+
+/// UlpfecDecoder recovers the lost packets of a media stream from the ULPFEC
+/// packets protecting them. It is fed the marshaled media packets as they are
+/// received, and the payloads of the ULPFEC packets, taken out of their RED
+/// blocks or FEC stream. A packet is recovered as soon as it is the only one
+/// missing among those protected by a FEC packet, and it may in turn allow
+/// recovering others.
+#[derive(Debug, Clone)]
+pub struct UlpfecDecoder {
+    recoverer: Recoverer,
+}
+
+impl UlpfecDecoder {
+    /// new creates a decoder for the media stream with the given SSRC, which
+    /// the recovered packets get.
+    pub fn new(ssrc: u32) -> Self {
+        UlpfecDecoder {
+            recoverer: Recoverer::new(ssrc),
+        }
+    }
+
+    /// push_media records a received media packet, and returns the marshaled
+    /// packets it allowed to recover.
+    pub fn push_media(&mut self, raw: Bytes) -> Result<Vec<Bytes>> {
+        if raw.len() < RTP_FIXED_HEADER_SIZE {
+            return Err(Error::ErrShortPacket);
+        }
+
+        Ok(self.recoverer.push_media(raw))
+    }
+
+    /// push_fec records the payload of a received ULPFEC packet, and returns the
+    /// marshaled packets it allowed to recover.
+    pub fn push_fec(&mut self, payload: &Bytes) -> Result<Vec<Bytes>> {
+        let fec = UlpfecPacket::unmarshal(payload)?;
+        Ok(self.recoverer.push_repair(fec.into()))
+    }
+}
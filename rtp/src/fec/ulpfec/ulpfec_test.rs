@@ -0,0 +1,200 @@
+use util::marshal::Unmarshal;
+
+use super::*;
+use crate::header::Header;
+
+fn media_packet(sequence_number: u16, marker: bool, payload: &[u8]) -> Packet {
+    Packet {
+        header: Header {
+            version: 2,
+            marker,
+            payload_type: 96,
+            sequence_number,
+            timestamp: 3000 + sequence_number as u32 * 90,
+            ssrc: 0x1234ABCD,
+            ..Default::default()
+        },
+        payload: Bytes::copy_from_slice(payload),
+    }
+}
+
+fn media(sequence_numbers: &[u16]) -> Vec<Bytes> {
+    sequence_numbers
+        .iter()
+        .map(|&seq| {
+            let payload = vec![seq as u8; 4 + seq as usize % 7];
+            media_packet(seq, seq % 5 == 0, &payload).marshal().unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_ulpfec_encode() -> Result<()> {
+    let packets = vec![
+        Bytes::from_static(&[
+            0x80, 0x60, 0x00, 0x02, 0x00, 0x00, 0x00, 0x10, 0x12, 0x34, 0xAB, 0xCD, 0x01, 0x02,
+        ]),
+        Bytes::from_static(&[
+            0x80, 0xE0, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x12, 0x34, 0xAB, 0xCD, 0x10, 0x20,
+            0x30,
+        ]),
+    ];
+
+    let fec = encode(&packets)?;
+    assert_eq!(
+        fec,
+        Bytes::from_static(&[
+            // P, X, CC, M and PT recovery, SN base
+            0x00, 0x80, 0x00, 0x01, //
+            // TS recovery
+            0x00, 0x00, 0x00, 0x11, //
+            // length recovery, protection length
+            0x00, 0x01, 0x00, 0x03, //
+            // short mask
+            0xC0, 0x00, //
+            // level 0 payload
+            0x11, 0x22, 0x30,
+        ])
+    );
+
+    let fec = UlpfecPacket::unmarshal(&fec)?;
+    assert_eq!(fec.sn_base, 1);
+    assert_eq!(fec.protected().collect::<Vec<_>>(), vec![1, 2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_ulpfec_encode_errors() {
+    assert_eq!(encode(&[]), Err(Error::ErrNilPacket));
+    assert_eq!(
+        encode(&[Bytes::from_static(&[0x80, 0x60, 0x00, 0x01])]),
+        Err(Error::ErrShortPacket)
+    );
+    assert_eq!(encode(&media(&[1, 49])), Err(Error::ErrFecMaskOverflow));
+    assert_eq!(encode(&media(&[65535, 47])), Err(Error::ErrFecMaskOverflow));
+}
+
+#[test]
+fn test_ulpfec_unmarshal_errors() {
+    let tests = vec![
+        ("short", vec![0x00; 13], Error::ErrShortPacket),
+        (
+            "extension",
+            vec![0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x80, 0],
+            Error::ErrInvalidFecPacket,
+        ),
+        (
+            "short long mask",
+            vec![0x40, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x80, 0],
+            Error::ErrShortPacket,
+        ),
+        (
+            "short payload",
+            vec![0x00, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0x80, 0, 0xFF],
+            Error::ErrShortPacket,
+        ),
+    ];
+
+    for (name, raw, want) in tests {
+        assert_eq!(
+            UlpfecPacket::unmarshal(&Bytes::from(raw)),
+            Err(want),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_ulpfec_recover() -> Result<()> {
+    let tests = vec![
+        ("short mask", vec![10u16, 11, 12, 13, 14], false),
+        ("long mask", vec![100, 101, 110, 120, 147], true),
+        ("wrapping", vec![65533, 65534, 65535, 0, 1], false),
+        ("unordered", vec![7, 3, 5, 4], false),
+    ];
+
+    for (name, sequence_numbers, long_mask) in tests {
+        let packets = media(&sequence_numbers);
+        let fec = encode(&packets)?;
+        assert_eq!(fec[0] & LONG_MASK_FLAG != 0, long_mask, "{name}");
+
+        for lost in 0..packets.len() {
+            let mut decoder = UlpfecDecoder::new(0x1234ABCD);
+            for (i, p) in packets.iter().enumerate() {
+                if i != lost {
+                    assert!(decoder.push_media(p.clone())?.is_empty(), "{name}");
+                }
+            }
+
+            let recovered = decoder.push_fec(&fec)?;
+            assert_eq!(recovered, vec![packets[lost].clone()], "{name} lost {lost}");
+
+            let packet = Packet::unmarshal(&mut recovered[0].clone())?;
+            assert_eq!(
+                packet.header.sequence_number, sequence_numbers[lost],
+                "{name}"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_ulpfec_decoder() -> Result<()> {
+    let packets = media(&[1, 2, 3, 4]);
+    let fec_all = encode(&packets)?;
+    let fec_tail = encode(&packets[2..])?;
+
+    // two packets lost: the first FEC packet waits for more media, the second
+    // one recovers 4, which lets the first one recover 2
+    let mut decoder = UlpfecDecoder::new(0x1234ABCD);
+    assert!(decoder.push_fec(&fec_all)?.is_empty());
+    assert!(decoder.push_media(packets[0].clone())?.is_empty());
+    assert!(decoder.push_media(packets[2].clone())?.is_empty());
+    assert_eq!(
+        decoder.push_fec(&fec_tail)?,
+        vec![packets[3].clone(), packets[1].clone()]
+    );
+    assert_eq!(decoder.recoverer.pending(), 0);
+
+    // FEC packets whose media all arrived are dropped, and duplicate media is
+    // ignored
+    let mut decoder = UlpfecDecoder::new(0x1234ABCD);
+    for p in &packets {
+        decoder.push_media(p.clone())?;
+    }
+    assert!(decoder.push_media(packets[0].clone())?.is_empty());
+    assert!(decoder.push_fec(&fec_all)?.is_empty());
+    assert_eq!(decoder.recoverer.pending(), 0);
+
+    assert_eq!(
+        decoder.push_media(Bytes::from_static(&[0x80, 0x60])),
+        Err(Error::ErrShortPacket)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_ulpfec_encoder() -> Result<()> {
+    let mut encoder = UlpfecEncoder::new(3);
+
+    assert_eq!(encoder.push(&media_packet(1, false, &[1]))?, None);
+    assert_eq!(encoder.push(&media_packet(2, false, &[2]))?, None);
+    let fec = encoder.push(&media_packet(3, false, &[3]))?.unwrap();
+    let fec = UlpfecPacket::unmarshal(&fec)?;
+    assert_eq!(fec.protected().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    // the end of a frame completes the group
+    assert_eq!(encoder.push(&media_packet(4, false, &[4]))?, None);
+    let fec = encoder.push(&media_packet(5, true, &[5]))?.unwrap();
+    let fec = UlpfecPacket::unmarshal(&fec)?;
+    assert_eq!(fec.protected().collect::<Vec<_>>(), vec![4, 5]);
+
+    assert_eq!(UlpfecEncoder::new(0).group_size, 1);
+    assert_eq!(UlpfecEncoder::new(100).group_size, ULPFEC_MAX_PROTECTED);
+
+    Ok(())
+}
@@ -47,6 +47,8 @@ pub struct Header {
     pub csrc: Vec<u32>,
     pub extension_profile: u16,
     pub extensions: Vec<Extension>,
+    /// The padding bytes following the extensions, which marshal writes back
+    /// (rounded up to the 32-bit alignment) so the header keeps its size.
     pub extensions_padding: usize,
 }
 
@@ -135,6 +137,9 @@ impl Unmarshal for Header {
                         if extid == EXTENSION_ID_RESERVED {
                             break;
                         }
+                        if curr_offset + len > end {
+                            return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
+                        }
 
                         extensions.push(Extension {
                             id: extid,
@@ -157,9 +162,15 @@ impl Unmarshal for Header {
 
                         let extid = b;
                         curr_offset += 1;
+                        if curr_offset == end {
+                            return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
+                        }
 
                         let len = raw_packet.get_u8() as usize;
                         curr_offset += 1;
+                        if curr_offset + len > end {
+                            return Err(Error::ErrHeaderSizeInsufficientForExtension.into());
+                        }
 
                         extensions.push(Extension {
                             id: extid,
@@ -275,13 +286,17 @@ impl Marshal for Header {
                 //the payload must be in 32-bit words.
                 return Err(Error::HeaderExtensionPayloadNot32BitWords.into());
             }
-            let extension_payload_size = (extension_payload_len as u16 + 3) / 4;
+            let extension_payload_size =
+                ((extension_payload_len + self.extensions_padding) as u16 + 3) / 4;
             buf.put_u16(extension_payload_size);
 
             match self.extension_profile {
                 // RFC 8285 RTP One Byte Header Extension
                 EXTENSION_PROFILE_ONE_BYTE => {
                     for extension in &self.extensions {
+                        if !(1..=16).contains(&extension.payload.len()) {
+                            return Err(Error::ErrRfc8285oneByteHeaderSize.into());
+                        }
                         buf.put_u8((extension.id << 4) | (extension.payload.len() as u8 - 1));
                         buf.put(&*extension.payload);
                     }
@@ -289,6 +304,9 @@ impl Marshal for Header {
                 // RFC 8285 RTP Two Byte Header Extension
                 EXTENSION_PROFILE_TWO_BYTE => {
                     for extension in &self.extensions {
+                        if extension.payload.len() > 255 {
+                            return Err(Error::ErrRfc8285twoByteHeaderSize.into());
+                        }
                         buf.put_u8(extension.id);
                         buf.put_u8(extension.payload.len() as u8);
                         buf.put(&*extension.payload);
@@ -340,15 +358,30 @@ impl Header {
     }
 
     /// SetExtension sets an RTP header extension
+    ///
+    /// The header switches from the one byte to the two byte form of RFC 8285
+    /// if the extension does not fit in the former, because of an id above 14
+    /// or a payload that is empty or longer than 16 bytes.
     pub fn set_extension(&mut self, id: u8, payload: Bytes) -> Result<(), Error> {
         let payload_len = payload.len() as isize;
         if self.extension {
+            if self.extension_profile == EXTENSION_PROFILE_ONE_BYTE
+                && id != 0
+                && (id > 14 || !(1..=16).contains(&payload_len))
+            {
+                if payload_len > 255 {
+                    return Err(Error::ErrRfc8285twoByteHeaderSize);
+                }
+                self.extension_profile = EXTENSION_PROFILE_TWO_BYTE;
+                self.extensions_padding = (4 - self.get_extension_payload_len() % 4) % 4;
+            }
+
             let extension_profile_len = match self.extension_profile {
                 EXTENSION_PROFILE_ONE_BYTE => {
                     if !(1..=14).contains(&id) {
                         return Err(Error::ErrRfc8285oneByteHeaderIdrange);
                     }
-                    if payload_len > 16 {
+                    if !(1..=16).contains(&payload_len) {
                         return Err(Error::ErrRfc8285oneByteHeaderSize);
                     }
                     1
@@ -404,11 +437,11 @@ impl Header {
             self.extension = true;
             let mut extension_profile_len = 0;
             self.extension_profile = match payload_len {
-                0..=16 => {
+                1..=16 if id <= 14 => {
                     extension_profile_len = 1;
                     EXTENSION_PROFILE_ONE_BYTE
                 }
-                17..=255 => {
+                0..=255 => {
                     extension_profile_len = 2;
                     EXTENSION_PROFILE_TWO_BYTE
                 }
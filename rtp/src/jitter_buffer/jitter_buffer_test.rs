@@ -0,0 +1,121 @@
+use super::*;
+use crate::header::Header;
+
+fn packet(sequence_number: u16) -> Packet {
+    Packet {
+        header: Header {
+            sequence_number,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn pop_all(buffer: &mut JitterBuffer, now: Instant) -> Vec<u16> {
+    let mut popped = vec![];
+    while let Some(p) = buffer.pop(now) {
+        popped.push(p.header.sequence_number);
+    }
+    popped
+}
+
+#[test]
+fn test_jitter_buffer_reordering() {
+    let start = Instant::now();
+    let latency = Duration::from_millis(50);
+    let mut buffer = JitterBuffer::new(latency);
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.pop(start), None);
+    assert_eq!(buffer.next_deadline(), None);
+
+    for (i, seq) in [3u16, 1, 2, 5, 4].into_iter().enumerate() {
+        assert!(buffer.push(packet(seq), start + Duration::from_millis(i as u64)));
+    }
+    assert_eq!(buffer.len(), 5);
+
+    // the packets are held for the latency, in sequence order even if they
+    // arrived earlier
+    assert_eq!(
+        buffer.next_deadline(),
+        Some(start + Duration::from_millis(51))
+    );
+    assert_eq!(
+        pop_all(&mut buffer, start + Duration::from_millis(50)),
+        vec![]
+    );
+    assert_eq!(
+        pop_all(&mut buffer, start + Duration::from_millis(51)),
+        vec![1]
+    );
+    assert_eq!(
+        pop_all(&mut buffer, start + Duration::from_millis(52)),
+        vec![2, 3]
+    );
+    assert_eq!(
+        pop_all(&mut buffer, start + Duration::from_millis(53)),
+        vec![]
+    );
+    assert_eq!(
+        pop_all(&mut buffer, start + Duration::from_millis(54)),
+        vec![4, 5]
+    );
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_jitter_buffer_duplicates() {
+    let start = Instant::now();
+    let mut buffer = JitterBuffer::new(Duration::ZERO);
+
+    assert!(buffer.push(packet(10), start));
+    assert!(!buffer.push(packet(10), start));
+    assert_eq!(pop_all(&mut buffer, start), vec![10]);
+
+    // the place of packets 9 and 10 was released
+    assert!(!buffer.push(packet(10), start));
+    assert!(!buffer.push(packet(9), start));
+    assert!(buffer.push(packet(11), start));
+    assert_eq!(pop_all(&mut buffer, start), vec![11]);
+}
+
+#[test]
+fn test_jitter_buffer_missing() {
+    let start = Instant::now();
+    let latency = Duration::from_millis(50);
+    let mut buffer = JitterBuffer::new(latency);
+    assert_eq!(buffer.missing(), Vec::<u16>::new());
+
+    for seq in [65533u16, 65534, 1, 3] {
+        buffer.push(packet(seq), start);
+    }
+    assert_eq!(buffer.missing(), vec![65535, 0, 2]);
+
+    // a missing packet is waited for until the deadline of the one after it
+    assert_eq!(pop_all(&mut buffer, start), vec![]);
+    assert!(buffer.push(packet(0), start + Duration::from_millis(10)));
+    assert_eq!(pop_all(&mut buffer, start + latency), vec![65533, 65534]);
+    assert_eq!(buffer.missing(), vec![65535, 2]);
+
+    // and skipped after it
+    assert_eq!(
+        pop_all(&mut buffer, start + Duration::from_millis(60)),
+        vec![0, 1, 3]
+    );
+    assert_eq!(buffer.missing(), Vec::<u16>::new());
+    assert!(!buffer.push(packet(65535), start + Duration::from_millis(60)));
+    assert!(!buffer.push(packet(2), start + Duration::from_millis(60)));
+}
+
+#[test]
+fn test_jitter_buffer_set_latency() {
+    let start = Instant::now();
+    let mut buffer = JitterBuffer::new(Duration::from_millis(100));
+    buffer.push(packet(1), start);
+
+    buffer.set_latency(Duration::from_millis(20));
+    assert_eq!(buffer.latency(), Duration::from_millis(20));
+    assert_eq!(
+        pop_all(&mut buffer, start + Duration::from_millis(20)),
+        vec![1]
+    );
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod jitter_buffer_test;
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use crate::packet::Packet;
+use crate::sequence::Unwrapper;
+
+/// JitterBuffer holds the received packets of a stream for a fixed latency,
+/// so that packets which arrive reordered or with jitter are released in
+/// sequence order. Each packet is released once it has been held for the
+/// latency, and after the packets before it: a missing packet is waited for
+/// until the deadline of the packet after it, and is then skipped.
+///
+/// Duplicate packets and packets arriving after their place was released are
+/// dropped. The sequence numbers missing before the highest one received are
+/// reported, for the receiver to request their retransmission.
+///
+///```nobuild
+///     let mut buffer = JitterBuffer::new(Duration::from_millis(50));
+///     buffer.push(packet, Instant::now());
+///     // ...
+///     while let Some(packet) = buffer.pop(Instant::now()) {
+///         // decode the packet
+///     }
+///```
+#[derive(Debug, Clone)]
+pub struct JitterBuffer {
+    latency: Duration,
+    unwrapper: Unwrapper<u16>,
+    /// The held packets with their arrival time, by extended sequence number.
+    packets: BTreeMap<i64, (Instant, Packet)>,
+    /// The extended sequence number after the last packet released.
+    next: Option<i64>,
+}
+
+impl JitterBuffer {
+    /// new creates a buffer holding packets for the given latency.
+    pub fn new(latency: Duration) -> Self {
+        JitterBuffer {
+            latency,
+            unwrapper: Unwrapper::default(),
+            packets: BTreeMap::new(),
+            next: None,
+        }
+    }
+
+    /// latency returns the time the packets are held for.
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    /// set_latency changes the time the packets are held for, which applies to
+    /// the packets already held as well.
+    pub fn set_latency(&mut self, latency: Duration) {
+        self.latency = latency;
+    }
+
+    /// len returns the count of packets held.
+    pub fn len(&self) -> usize {
+        self.packets.len()
+    }
+
+    /// is_empty returns true if no packet is held.
+    pub fn is_empty(&self) -> bool {
+        self.packets.is_empty()
+    }
+
+    /// push holds a packet received at `arrival`. It returns false if the packet
+    /// is dropped, being a duplicate or too late.
+    pub fn push(&mut self, packet: Packet, arrival: Instant) -> bool {
+        let seq = self.unwrapper.unwrap(packet.header.sequence_number);
+        if self.next.is_some_and(|next| seq < next) || self.packets.contains_key(&seq) {
+            return false;
+        }
+
+        self.packets.insert(seq, (arrival, packet));
+        true
+    }
+
+    /// next_deadline returns the time at which the next packet is to be
+    /// released, if any is held.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.packets
+            .first_key_value()
+            .map(|(_, (arrival, _))| *arrival + self.latency)
+    }
+
+    /// pop releases the next packet in sequence order if its deadline has passed
+    /// at `now`, skipping the missing packets before it.
+    pub fn pop(&mut self, now: Instant) -> Option<Packet> {
+        if self.next_deadline()? > now {
+            return None;
+        }
+
+        let (seq, (_, packet)) = self.packets.pop_first()?;
+        self.next = Some(seq + 1);
+        Some(packet)
+    }
+
+    /// missing returns the sequence numbers, which are yet to be released, of
+    /// the packets missing before the highest one received.
+    pub fn missing(&self) -> Vec<u16> {
+        let (Some((&first, _)), Some((&last, _))) = (
+            self.packets.first_key_value(),
+            self.packets.last_key_value(),
+        ) else {
+            return vec![];
+        };
+
+        let start = self.next.unwrap_or(first);
+        (start..last)
+            .filter(|seq| !self.packets.contains_key(seq))
+            .map(|seq| seq as u16)
+            .collect()
+    }
+}
@@ -0,0 +1,130 @@
+use util::marshal::Unmarshal;
+
+use super::*;
+
+fn header() -> Header {
+    Header {
+        version: 2,
+        marker: true,
+        payload_type: 111,
+        sequence_number: 27023,
+        timestamp: 3653407706,
+        ssrc: 476325762,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_marshal_keep_alive_packet() -> Result<()> {
+    let tests = vec![
+        (
+            "unknown payload type",
+            KeepAliveFormat::default(),
+            vec![
+                0x80, 0x14, 0x69, 0x8F, 0xD9, 0xC2, 0x93, 0xDA, 0x1C, 0x64, 0x27, 0x82,
+            ],
+            false,
+        ),
+        (
+            "configured unknown payload type",
+            KeepAliveFormat::UnknownPayloadType(21),
+            vec![
+                0x80, 0x15, 0x69, 0x8F, 0xD9, 0xC2, 0x93, 0xDA, 0x1C, 0x64, 0x27, 0x82,
+            ],
+            false,
+        ),
+        (
+            "padding",
+            KeepAliveFormat::Padding,
+            vec![
+                0xA0, 0xEF, 0x69, 0x8F, 0xD9, 0xC2, 0x93, 0xDA, 0x1C, 0x64, 0x27, 0x82, 0x01,
+            ],
+            true,
+        ),
+        (
+            "empty",
+            KeepAliveFormat::Empty,
+            vec![
+                0x80, 0xEF, 0x69, 0x8F, 0xD9, 0xC2, 0x93, 0xDA, 0x1C, 0x64, 0x27, 0x82,
+            ],
+            true,
+        ),
+    ];
+
+    for (name, format, expected, marker) in tests {
+        let raw = marshal_keep_alive_packet(&header(), format)?;
+        assert_eq!(raw, Bytes::from(expected), "{name}");
+
+        let packet = Packet::unmarshal(&mut raw.clone())?;
+        assert!(is_keep_alive(&packet, format), "{name}");
+        assert_eq!(packet.header.marker, marker, "{name}");
+        assert_eq!(packet.header.sequence_number, 27023, "{name}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_is_keep_alive() {
+    let tests = vec![
+        (
+            "media",
+            KeepAliveFormat::default(),
+            header(),
+            Bytes::from_static(&[0x01, 0x02]),
+            false,
+        ),
+        (
+            "keep-alive payload type",
+            KeepAliveFormat::default(),
+            Header {
+                payload_type: KEEP_ALIVE_PAYLOAD_TYPE,
+                ..header()
+            },
+            Bytes::from_static(&[0x01]),
+            true,
+        ),
+        (
+            "not the configured keep-alive payload type",
+            KeepAliveFormat::UnknownPayloadType(21),
+            Header {
+                payload_type: KEEP_ALIVE_PAYLOAD_TYPE,
+                ..header()
+            },
+            Bytes::from_static(&[0x01]),
+            false,
+        ),
+        (
+            "no keep-alive payload type configured",
+            KeepAliveFormat::Padding,
+            Header {
+                payload_type: KEEP_ALIVE_PAYLOAD_TYPE,
+                ..header()
+            },
+            Bytes::from_static(&[0x01]),
+            false,
+        ),
+        (
+            "padding only",
+            KeepAliveFormat::default(),
+            Header {
+                padding: true,
+                ..header()
+            },
+            Bytes::new(),
+            true,
+        ),
+        (
+            "empty payload",
+            KeepAliveFormat::Empty,
+            header(),
+            Bytes::new(),
+            true,
+        ),
+    ];
+
+    for (name, format, header, payload, expected) in tests {
+        let packet = Packet { header, payload };
+        assert_eq!(is_keep_alive(&packet, format), expected, "{name}");
+    }
+}
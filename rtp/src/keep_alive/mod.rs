@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod keep_alive_test;
+
+use bytes::Bytes;
+use util::marshal::Marshal;
+
+use crate::error::Result;
+use crate::header::Header;
+use crate::packet::Packet;
+use crate::padding::{is_padding_only, marshal_padding_packet};
+
+/// KEEP_ALIVE_PAYLOAD_TYPE is the payload type of keep-alive packets, which is
+/// unassigned so that receivers drop them, as recommended by
+/// https://tools.ietf.org/html/rfc6263#section-4.6
+pub const KEEP_ALIVE_PAYLOAD_TYPE: u8 = 20;
+
+/// KeepAliveFormat is the format of the RTP packets sent to keep the NAT
+/// bindings of a stream open while it does not send media, as during silence
+/// suppression.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KeepAliveFormat {
+    /// An empty packet of a payload type the session did not negotiate, as
+    /// recommended by RFC 6263 when RTP and RTCP are not multiplexed. The
+    /// marker bit is cleared, as the packet is not part of the media.
+    UnknownPayloadType(u8),
+    /// A packet of the payload type of the stream carrying a byte of padding.
+    /// The marker bit of the header is kept.
+    Padding,
+    /// A packet of the payload type of the stream carrying no payload. The
+    /// marker bit of the header is kept.
+    Empty,
+}
+
+impl Default for KeepAliveFormat {
+    fn default() -> Self {
+        KeepAliveFormat::UnknownPayloadType(KEEP_ALIVE_PAYLOAD_TYPE)
+    }
+}
+
+/// marshal_keep_alive_packet returns a keep-alive packet of the given format
+/// with the given header, which should take the next sequence number of the
+/// stream and the timestamp of its last packet.
+pub fn marshal_keep_alive_packet(header: &Header, format: KeepAliveFormat) -> Result<Bytes> {
+    match format {
+        KeepAliveFormat::UnknownPayloadType(payload_type) => {
+            let header = Header {
+                payload_type,
+                marker: false,
+                padding: false,
+                ..header.clone()
+            };
+            Ok(header.marshal()?)
+        }
+        KeepAliveFormat::Padding => marshal_padding_packet(header, 1),
+        KeepAliveFormat::Empty => {
+            let header = Header {
+                padding: false,
+                ..header.clone()
+            };
+            Ok(header.marshal()?)
+        }
+    }
+}
+
+/// is_keep_alive returns true if the unmarshaled packet carried no media: a
+/// packet of the payload type of the given UnknownPayloadType format, a
+/// padding-only packet, or a packet with an empty payload.
+pub fn is_keep_alive(packet: &Packet, format: KeepAliveFormat) -> bool {
+    let keep_alive_payload_type = match format {
+        KeepAliveFormat::UnknownPayloadType(payload_type) => {
+            packet.header.payload_type == payload_type
+        }
+        KeepAliveFormat::Padding | KeepAliveFormat::Empty => false,
+    };
+
+    keep_alive_payload_type || is_padding_only(packet) || packet.payload.is_empty()
+}
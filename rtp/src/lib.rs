@@ -4,9 +4,15 @@
 pub mod codecs;
 mod error;
 pub mod extension;
+pub mod fec;
 pub mod header;
+pub mod jitter_buffer;
+pub mod keep_alive;
 pub mod packet;
 pub mod packetizer;
+pub mod padding;
+pub mod pool;
+pub mod rtx;
 pub mod sequence;
 
 pub use error::Error;
@@ -3,7 +3,7 @@ mod packet_test;
 
 use std::fmt;
 
-use bytes::{Buf, BufMut, Bytes};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use util::marshal::{Marshal, MarshalSize, Unmarshal};
 
 use crate::error::Error;
@@ -66,18 +66,7 @@ impl Unmarshal for Packet {
 impl MarshalSize for Packet {
     /// MarshalSize returns the size of the packet once marshaled.
     fn marshal_size(&self) -> usize {
-        let payload_len = self.payload.len();
-        let padding_len = if self.header.padding {
-            let padding_len = get_padding(payload_len);
-            if padding_len == 0 {
-                4
-            } else {
-                padding_len
-            }
-        } else {
-            0
-        };
-        self.header.marshal_size() + payload_len + padding_len
+        self.header.marshal_size() + self.payload.len() + self.padding_len()
     }
 }
 
@@ -91,27 +80,72 @@ impl Marshal for Packet {
         let n = self.header.marshal_to(buf)?;
         buf = &mut buf[n..];
         buf.put(&*self.payload);
-        let padding_len = if self.header.padding {
-            let mut padding_len = get_padding(self.payload.len());
-            if padding_len == 0 {
-                padding_len = 4;
-            }
-            for i in 0..padding_len {
-                if i != padding_len - 1 {
-                    buf.put_u8(0);
-                } else {
-                    buf.put_u8(padding_len as u8);
-                }
-            }
-            padding_len
-        } else {
-            0
-        };
+        let padding_len = self.padding_len();
+        put_padding(buf, padding_len);
 
         Ok(n + self.payload.len() + padding_len)
     }
 }
 
+impl Packet {
+    /// marshal_into appends the marshaled packet to `buf`, and returns its size.
+    /// The buffer only allocates if it lacks the capacity, so that a buffer
+    /// reused from packet to packet, such as one of a BufferPool, makes the
+    /// marshaling free of allocations. On error the buffer is left unchanged.
+    pub fn marshal_into(&self, buf: &mut BytesMut) -> Result<usize, util::Error> {
+        let start = buf.len();
+        buf.resize(start + self.marshal_size(), 0);
+        let result = self.marshal_to(&mut buf[start..]);
+        if result.is_err() {
+            buf.truncate(start);
+        }
+        result
+    }
+
+    /// marshal_vectored returns the marshaled packet as the chunks which, once
+    /// written in order with a vectored write, make the packet: the header, the
+    /// payload and the padding. The payload is shared with the packet rather
+    /// than copied, while the header and padding are written to the scratch
+    /// buffer `buf`, whose content is discarded. Once the chunks of the
+    /// previous packets are dropped, the buffer reuses its capacity, so that
+    /// forwarding packets neither copies nor allocates.
+    pub fn marshal_vectored(&self, buf: &mut BytesMut) -> Result<[Bytes; 3], util::Error> {
+        let header_len = self.header.marshal_size();
+        let padding_len = self.padding_len();
+
+        buf.clear();
+        buf.resize(header_len + padding_len, 0);
+        self.header.marshal_to(&mut buf[..header_len])?;
+        put_padding(&mut buf[header_len..], padding_len);
+
+        let header = buf.split_to(header_len).freeze();
+        let padding = buf.split().freeze();
+
+        Ok([header, self.payload.clone(), padding])
+    }
+
+    /// padding_len returns the size of the padding which aligns the packet to
+    /// 32 bits, when the padding bit is set.
+    fn padding_len(&self) -> usize {
+        if !self.header.padding {
+            return 0;
+        }
+
+        match get_padding(self.payload.len()) {
+            0 => 4,
+            padding_len => padding_len,
+        }
+    }
+}
+
+fn put_padding(mut buf: &mut [u8], padding_len: usize) {
+    if padding_len == 0 {
+        return;
+    }
+    buf.put_bytes(0, padding_len - 1);
+    buf.put_u8(padding_len as u8);
+}
+
 /// getPadding Returns the padding required to make the length a multiple of 4
 fn get_padding(len: usize) -> usize {
     if len % 4 == 0 {
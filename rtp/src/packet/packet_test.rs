@@ -171,6 +171,74 @@ fn test_packet_marshal_unmarshal() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_packet_marshal_into() -> Result<()> {
+    let mut pkt = Packet {
+        header: Header {
+            version: 2,
+            marker: true,
+            payload_type: 96,
+            sequence_number: 27023,
+            timestamp: 3653407706,
+            ssrc: 476325762,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x98, 0x36, 0xbe, 0x88, 0x9e]),
+    };
+
+    for padding in [false, true] {
+        pkt.header.padding = padding;
+        let raw = pkt.marshal()?;
+
+        // the packet is appended to the content of the buffer
+        let mut buf = BytesMut::from(&[0xAA][..]);
+        assert_eq!(pkt.marshal_into(&mut buf)?, raw.len());
+        assert_eq!(&buf[..1], &[0xAA]);
+        assert_eq!(&buf[1..], &raw[..]);
+
+        let chunks = pkt.marshal_vectored(&mut buf)?;
+        assert_eq!(chunks.concat(), raw.to_vec());
+        assert_eq!(chunks[1].as_ptr(), pkt.payload.as_ptr());
+        assert_eq!(chunks[2].is_empty(), !padding);
+    }
+
+    // a one byte header extension cannot be empty
+    pkt.header.extension = true;
+    pkt.header.extension_profile = EXTENSION_PROFILE_ONE_BYTE;
+    pkt.header.extensions = vec![Extension {
+        id: 1,
+        payload: Bytes::new(),
+    }];
+    let mut buf = BytesMut::from(&[0xAA][..]);
+    assert!(pkt.marshal_into(&mut buf).is_err());
+    assert_eq!(&buf[..], &[0xAA]);
+
+    Ok(())
+}
+
+#[test]
+fn test_packet_marshal_vectored_reuses_buffer() -> Result<()> {
+    let pkt = Packet {
+        header: Header {
+            version: 2,
+            csrc: vec![1, 2],
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0xFFu8; 100]),
+    };
+
+    let mut buf = BytesMut::with_capacity(64);
+    let start = buf.as_ptr() as usize;
+    for _ in 0..100 {
+        let chunks = pkt.marshal_vectored(&mut buf)?;
+        // the headers are written within the original allocation
+        let header = chunks[0].as_ptr() as usize;
+        assert!(header >= start && header + chunks[0].len() <= start + 64);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_rfc_8285_one_byte_extension() -> Result<()> {
     let raw_pkt = Bytes::from_static(&[
@@ -839,6 +907,7 @@ fn test_rfc8285_set_extension_should_update_existing_extension() -> Result<()> {
     Ok(())
 }
 
+#[test]
 fn test_rfc8285_one_byte_set_extension_should_error_when_invalid_id_provided() {
     let payload = Bytes::from_static(&[0x98u8, 0x36, 0xbe, 0x88, 0x9e]);
 
@@ -868,12 +937,6 @@ fn test_rfc8285_one_byte_set_extension_should_error_when_invalid_id_provided() {
             .is_err(),
         "set_extension did not error on invalid id"
     );
-    assert!(
-        p.header
-            .set_extension(15, Bytes::from_static(&[0xBBu8]))
-            .is_err(),
-        "set_extension did not error on invalid id"
-    );
 }
 
 fn test_rfc8285_one_byte_extension_terminate_processing_when_reserved_id_encountered() -> Result<()>
@@ -897,6 +960,7 @@ fn test_rfc8285_one_byte_extension_terminate_processing_when_reserved_id_encount
     Ok(())
 }
 
+#[test]
 fn test_rfc8285_one_byte_set_extension_should_error_when_payload_too_large() {
     let payload = Bytes::from_static(&[0x98u8, 0x36, 0xbe, 0x88, 0x9e]);
 
@@ -920,20 +984,114 @@ fn test_rfc8285_one_byte_set_extension_should_error_when_payload_too_large() {
         ..Default::default()
     };
 
-    let res = p.header.set_extension(
-        1,
-        Bytes::from_static(&[
-            0xBBu8, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB, 0xBB,
-            0xBB, 0xBB, 0xBB,
-        ]),
-    );
+    // too large for the two byte form the header would switch to
+    let res = p
+        .header
+        .set_extension(1, Bytes::from_static(&[0xBBu8; 256]));
 
     assert!(
         res.is_err(),
         "set_extension did not error on too large payload"
     );
+    assert_eq!(p.header.extension_profile, EXTENSION_PROFILE_ONE_BYTE);
+}
+
+#[test]
+fn test_rfc8285_one_byte_marshal_should_error_when_payload_empty() {
+    let header = Header {
+        extension: true,
+        extension_profile: EXTENSION_PROFILE_ONE_BYTE,
+        extensions: vec![Extension {
+            id: 1,
+            payload: Bytes::new(),
+        }],
+        ..Default::default()
+    };
+
+    // one byte extensions cannot carry an empty payload
+    assert!(header.marshal().is_err());
+}
+
+#[test]
+fn test_rfc8285_one_byte_set_and_del_extension_round_trip() -> Result<()> {
+    let mut header = Header {
+        version: 2,
+        payload_type: 96,
+        sequence_number: 27023,
+        timestamp: 3653407706,
+        ssrc: 476325762,
+        ..Default::default()
+    };
+
+    let extensions = [
+        (1, Bytes::from_static(&[0xAA])),
+        (2, Bytes::from_static(&[0xBB, 0xBB])),
+        (3, Bytes::from_static(&[0xCC; 3])),
+        (14, Bytes::from_static(&[0xDD; 16])),
+    ];
+    for (id, payload) in &extensions {
+        header.set_extension(*id, payload.clone())?;
+    }
+    assert_eq!(header.extension_profile, EXTENSION_PROFILE_ONE_BYTE);
+
+    for del in [None, Some(2), Some(1), Some(14)] {
+        if let Some(id) = del {
+            header.del_extension(id)?;
+        }
+
+        let raw = header.marshal()?;
+        assert_eq!(raw.len(), header.marshal_size());
+        assert_eq!(raw.len() % 4, 0);
+
+        let parsed = Header::unmarshal(&mut raw.clone())?;
+        assert_eq!(
+            parsed.extensions, header.extensions,
+            "after deleting {del:?}"
+        );
+        for (id, _) in &extensions {
+            assert_eq!(parsed.get_extension(*id), header.get_extension(*id));
+        }
+    }
+    assert_eq!(header.get_extension_ids(), vec![3]);
+
+    Ok(())
+}
+
+#[test]
+fn test_rfc8285_one_byte_extension_with_extra_padding() -> Result<()> {
+    let raw_header = Bytes::from_static(&[
+        0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, 0x1c, 0x64, 0x27, 0x82, 0xBE, 0xDE, 0x00,
+        0x02, 0x10, 0xAA, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ]);
+    let header = Header::unmarshal(&mut raw_header.clone())?;
+    assert_eq!(header.extensions_padding, 6);
+
+    // the padding is kept, so the size is that of the header on the wire
+    assert_eq!(header.marshal_size(), raw_header.len());
+    assert_eq!(header.marshal()?, raw_header);
+
+    let mut raw_pkt = BytesMut::from(&raw_header[..]);
+    raw_pkt.extend_from_slice(&[0x98, 0x36, 0xbe, 0x88, 0x9e]);
+    let raw_pkt = raw_pkt.freeze();
+    let packet = Packet::unmarshal(&mut raw_pkt.clone())?;
+    assert_eq!(packet.payload, raw_pkt.slice(raw_header.len()..));
+    assert_eq!(packet.marshal_size(), raw_pkt.len());
+    assert_eq!(packet.marshal()?, raw_pkt);
+
+    // the extension set takes up the padding
+    let mut header = header;
+    header.set_extension(2, Bytes::from_static(&[0xBB]))?;
+    assert_eq!(header.extensions_padding, 4);
+    let raw = header.marshal()?;
+    assert_eq!(raw.len(), raw_header.len());
+    assert_eq!(
+        Header::unmarshal(&mut raw.clone())?.extensions,
+        header.extensions
+    );
+    Ok(())
 }
 
+#[test]
 fn test_rfc8285_two_bytes_set_extension_should_enable_extension_when_adding() -> Result<()> {
     let payload = Bytes::from_static(&[0x98u8, 0x36, 0xbe, 0x88, 0x9e]);
 
@@ -979,6 +1137,53 @@ fn test_rfc8285_two_bytes_set_extension_should_enable_extension_when_adding() ->
     Ok(())
 }
 
+#[test]
+fn test_rfc8285_set_extension_should_promote_to_two_byte() -> Result<()> {
+    let mut header = Header {
+        version: 2,
+        payload_type: 96,
+        sequence_number: 27023,
+        timestamp: 3653407706,
+        ssrc: 476325762,
+        ..Default::default()
+    };
+    header.set_extension(1, Bytes::from_static(&[0xAA]))?;
+    header.set_extension(2, Bytes::from_static(&[0xBB, 0xBB]))?;
+    assert_eq!(header.extension_profile, EXTENSION_PROFILE_ONE_BYTE);
+
+    let tests = vec![
+        ("large id", 15, Bytes::from_static(&[0xCC])),
+        ("large payload", 3, Bytes::from_static(&[0xDD; 17])),
+        ("empty payload", 4, Bytes::new()),
+    ];
+
+    for (name, id, payload) in tests {
+        let mut promoted = header.clone();
+        promoted.set_extension(id, payload.clone())?;
+        assert_eq!(
+            promoted.extension_profile, EXTENSION_PROFILE_TWO_BYTE,
+            "{name}"
+        );
+
+        let raw = promoted.marshal()?;
+        assert_eq!(raw.len(), promoted.marshal_size(), "{name}");
+        let parsed = Header::unmarshal(&mut raw.clone())?;
+        assert_eq!(
+            parsed.extension_profile, EXTENSION_PROFILE_TWO_BYTE,
+            "{name}"
+        );
+        assert_eq!(parsed.extensions, promoted.extensions, "{name}");
+        assert_eq!(parsed.get_extension(id), Some(payload), "{name}");
+    }
+
+    // a header without extensions starts in the two byte form
+    let mut header = Header::default();
+    header.set_extension(20, Bytes::from_static(&[0xAA]))?;
+    assert_eq!(header.extension_profile, EXTENSION_PROFILE_TWO_BYTE);
+
+    Ok(())
+}
+
 fn test_rfc8285_two_byte_set_extension_should_update_existing_extension() -> Result<()> {
     let payload = Bytes::from_static(&[0x98u8, 0x36, 0xbe, 0x88, 0x9e]);
 
@@ -1200,6 +1405,36 @@ fn test_unmarshal_error_handling() {
         },
     );
 
+    cases.insert(
+        "ExtensionPayloadPastExtensions",
+        Cases {
+            input: Bytes::from_static(&[
+                0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+                0xBE, 0xDE, 0x00, 0x01, // have 1 extension
+                0x17, 0xAA, 0xBB,
+                0xCC, // length of the payload is 8, but the extensions are 4 bytes long
+                0x98, 0x36, 0xbe, 0x88, 0x9e, // payload
+            ]),
+            err: Error::ErrHeaderSizeInsufficientForExtension,
+        },
+    );
+
+    cases.insert(
+        "TwoByteExtensionPayloadPastExtensions",
+        Cases {
+            input: Bytes::from_static(&[
+                0x90, 0xe0, 0x69, 0x8f, 0xd9, 0xc2, 0x93, 0xda, // timestamp
+                0x1c, 0x64, 0x27, 0x82, // SSRC
+                0x10, 0x00, 0x00, 0x01, // have 1 extension
+                0x01, 0x05, 0xAA,
+                0xBB, // length of the payload is 5, but the extensions are 4 bytes long
+                0x98, 0x36, 0xbe, 0x88, 0x9e, // payload
+            ]),
+            err: Error::ErrHeaderSizeInsufficientForExtension,
+        },
+    );
+
     for (name, mut test_case) in cases.drain() {
         let result = Header::unmarshal(&mut test_case.input);
         let err = result.err().unwrap();
@@ -5,10 +5,10 @@ use std::fmt;
 use std::sync::Arc;
 use std::time::SystemTime;
 
-use bytes::{Bytes, BytesMut};
-use util::marshal::{Marshal, MarshalSize};
+use bytes::Bytes;
+use util::marshal::MarshalSize;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::extension::abs_send_time_extension::*;
 use crate::header::*;
 use crate::packet::*;
@@ -18,6 +18,14 @@ use crate::sequence::*;
 pub trait Payloader: fmt::Debug {
     fn payload(&mut self, mtu: usize, b: &Bytes) -> Result<Vec<Bytes>>;
     fn clone_to(&self) -> Box<dyn Payloader + Send + Sync>;
+
+    /// samples returns the RTP timestamp units covered by one of the payloads
+    /// returned by payload, for formats made of fixed-size samples. The packets
+    /// of a single packetize call then get increasing timestamps, instead of the
+    /// timestamp of the frame they are all part of.
+    fn samples(&self, _payload: &Bytes) -> Option<u32> {
+        None
+    }
 }
 
 impl Clone for Box<dyn Payloader + Send + Sync> {
@@ -106,16 +114,58 @@ pub fn new_packetizer(
     }
 }
 
+impl PacketizerImpl {
+    /// header_size returns the size of the RTP header of the last packet, which
+    /// carries the abs-send-time extension when it is enabled.
+    fn header_size(&self) -> usize {
+        let mut header = Header::default();
+        if self.abs_send_time != 0 {
+            let _ = header.set_extension(self.abs_send_time, Bytes::from_static(&[0; 3]));
+        }
+        header.marshal_size()
+    }
+}
+
 impl Packetizer for PacketizerImpl {
     fn enable_abs_send_time(&mut self, value: u8) {
         self.abs_send_time = value
     }
 
+    /// packetize splits the payload into packets that each fit in the MTU,
+    /// including the abs-send-time extension of the last packet when it is
+    /// enabled. ErrMtuTooSmall is returned if the MTU cannot hold the RTP header
+    /// and at least one byte of payload.
     fn packetize(&mut self, payload: &Bytes, samples: u32) -> Result<Vec<Packet>> {
-        let payloads = self.payloader.payload(self.mtu - 12, payload)?;
+        let header_size = self.header_size();
+        if self.mtu <= header_size {
+            return Err(Error::ErrMtuTooSmall);
+        }
+
+        let payloads = if self.abs_send_time == 0 {
+            self.payloader.payload(self.mtu - header_size, payload)?
+        } else {
+            // Only the last packet carries the extension, so the space is first
+            // left to the payload. The payloader is cloned because the payload
+            // is split again, with the space reserved, if the last packet then
+            // exceeds the MTU.
+            let mut payloader = self.payloader.clone();
+            let payloads =
+                payloader.payload(self.mtu - Header::default().marshal_size(), payload)?;
+            if payloads
+                .last()
+                .is_some_and(|p| header_size + p.len() > self.mtu)
+            {
+                self.payloader.payload(self.mtu - header_size, payload)?
+            } else {
+                self.payloader = payloader;
+                payloads
+            }
+        };
         let payloads_len = payloads.len();
         let mut packets = Vec::with_capacity(payloads_len);
+        let mut timestamp = self.timestamp;
         for (i, payload) in payloads.into_iter().enumerate() {
+            let samples = self.payloader.samples(&payload);
             packets.push(Packet {
                 header: Header {
                     version: 2,
@@ -124,12 +174,15 @@ impl Packetizer for PacketizerImpl {
                     marker: i == payloads_len - 1,
                     payload_type: self.payload_type,
                     sequence_number: self.sequencer.next_sequence_number(),
-                    timestamp: self.timestamp, //TODO: Figure out how to do timestamps
+                    timestamp,
                     ssrc: self.ssrc,
                     ..Default::default()
                 },
                 payload,
             });
+            if let Some(samples) = samples {
+                timestamp = timestamp.wrapping_add(samples);
+            }
         }
 
         self.timestamp = self.timestamp.wrapping_add(samples);
@@ -140,14 +193,12 @@ impl Packetizer for PacketizerImpl {
             } else {
                 SystemTime::now()
             };
-            let send_time = AbsSendTimeExtension::new(st);
             //apply http://www.webrtc.org/experiments/rtp-hdrext/abs-send-time
-            let mut raw = BytesMut::with_capacity(send_time.marshal_size());
-            raw.resize(send_time.marshal_size(), 0);
-            let _ = send_time.marshal_to(&mut raw)?;
-            packets[payloads_len - 1]
-                .header
-                .set_extension(self.abs_send_time, raw.freeze())?;
+            set_abs_send_time(
+                &mut packets[payloads_len - 1].header,
+                self.abs_send_time,
+                st,
+            )?;
         }
 
         Ok(packets)
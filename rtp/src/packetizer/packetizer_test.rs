@@ -4,7 +4,7 @@ use chrono::prelude::*;
 
 use super::*;
 use crate::codecs::*;
-use crate::error::Result;
+use crate::error::{Error, Result};
 
 #[test]
 fn test_packetizer() -> Result<()> {
@@ -108,3 +108,116 @@ fn test_packetizer_timestamp_rollover_does_not_panic() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_packetizer_mtu() -> Result<()> {
+    // 176 bytes fill two packets with no room left for the extension of the
+    // last one, so the space for it is then reserved in every packet: 8 bytes
+    // for a one-byte extension, 12 for a two-byte one
+    for (payload_len, first_lens) in [(1000, [88, 88, 88]), (176, [88, 80, 76])] {
+        let payload = Bytes::from(vec![0; payload_len]);
+
+        for (abs_send_time, first_len) in [0, 1, 15].into_iter().zip(first_lens) {
+            let g722 = Box::new(g7xx::G722Payloader {});
+            let seq = Box::new(new_random_sequencer());
+            let mut packetizer = new_packetizer(100, 98, 0x1234ABCD, g722, seq, 90000);
+            packetizer.enable_abs_send_time(abs_send_time);
+
+            let packets = packetizer.packetize(&payload, 2000)?;
+            let total: usize = packets.iter().map(|p| p.payload.len()).sum();
+            assert_eq!(total, payload.len(), "abs-send-time id {abs_send_time}");
+            for p in &packets {
+                assert!(
+                    p.marshal_size() <= 100,
+                    "packet of {} bytes with abs-send-time id {abs_send_time}",
+                    p.marshal_size()
+                );
+            }
+            assert_eq!(
+                packets.last().map(|p| p.header.extension),
+                Some(abs_send_time != 0)
+            );
+            assert_eq!(
+                packets[0].payload.len(),
+                first_len,
+                "{payload_len} bytes with abs-send-time id {abs_send_time}"
+            );
+        }
+    }
+
+    // the MTU cannot hold the RTP header and a payload byte
+    for (mtu, abs_send_time) in [(8, 0), (12, 0), (13, 1), (20, 1)] {
+        let g722 = Box::new(g7xx::G722Payloader {});
+        let seq = Box::new(new_random_sequencer());
+        let mut packetizer = new_packetizer(mtu, 98, 0x1234ABCD, g722, seq, 90000);
+        packetizer.enable_abs_send_time(abs_send_time);
+        assert_eq!(
+            packetizer.packetize(&Bytes::from_static(&[0; 10]), 2000),
+            Err(Error::ErrMtuTooSmall),
+            "mtu {mtu} with abs-send-time id {abs_send_time}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_packetizer_audio_timestamps() -> Result<()> {
+    // 20ms frames of G711, split in two packets
+    let g711 = Box::new(g7xx::G711Payloader {});
+    let seq = Box::new(new_fixed_sequencer(1));
+    let mut packetizer = PacketizerImpl {
+        mtu: 100,
+        payload_type: 0,
+        ssrc: 0x1234ABCD,
+        payloader: g711,
+        sequencer: seq,
+        timestamp: u32::MAX - 50,
+        clock_rate: g7xx::G711_CLOCK_RATE,
+        abs_send_time: 0,
+        time_gen: None,
+    };
+
+    let frame = Bytes::from_static(&[0xff; 160]);
+    let packets = packetizer.packetize(&frame, 160)?;
+    let timestamps: Vec<u32> = packets.iter().map(|p| p.header.timestamp).collect();
+    assert_eq!(timestamps, vec![u32::MAX - 50, 37]);
+
+    let packets = packetizer.packetize(&frame, 160)?;
+    let timestamps: Vec<u32> = packets.iter().map(|p| p.header.timestamp).collect();
+    assert_eq!(timestamps, vec![109, 197]);
+
+    Ok(())
+}
+
+#[test]
+fn test_packetizer_custom_sequencer() -> Result<()> {
+    // a retransmission stream takes its sequence numbers from its own counter,
+    // here one that only hands out even numbers
+    #[derive(Debug, Clone, Default)]
+    struct EvenSequencer(Arc<std::sync::atomic::AtomicU16>);
+
+    impl Sequencer for EvenSequencer {
+        fn next_sequence_number(&self) -> u16 {
+            self.0.fetch_add(2, std::sync::atomic::Ordering::SeqCst)
+        }
+
+        fn roll_over_count(&self) -> u64 {
+            0
+        }
+
+        fn clone_to(&self) -> Box<dyn Sequencer + Send + Sync> {
+            Box::new(self.clone())
+        }
+    }
+
+    let g722 = Box::new(g7xx::G722Payloader {});
+    let seq = Box::<EvenSequencer>::default();
+    let mut packetizer = new_packetizer(100, 98, 0x1234ABCD, g722, seq, 8000);
+
+    let packets = packetizer.packetize(&Bytes::from_static(&[0; 200]), 200)?;
+    let sequence_numbers: Vec<u16> = packets.iter().map(|p| p.header.sequence_number).collect();
+    assert_eq!(sequence_numbers, vec![0, 2, 4]);
+
+    Ok(())
+}
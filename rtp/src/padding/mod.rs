@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod padding_test;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use util::marshal::{Marshal, MarshalSize};
+
+use crate::error::{Error, Result};
+use crate::header::Header;
+use crate::packet::Packet;
+use crate::sequence::Sequencer;
+
+/// MAX_PADDING_SIZE is the largest padding of an RTP packet, whose size the
+/// last byte of the padding gives.
+pub const MAX_PADDING_SIZE: usize = 255;
+
+/// marshal_padding_packet returns a padding-only packet with the given header,
+/// whose padding bit it sets, and `size` bytes of padding. Padding-only
+/// packets carry no payload, and let congestion controllers probe for the
+/// available bandwidth.
+pub fn marshal_padding_packet(header: &Header, size: usize) -> Result<Bytes> {
+    if size == 0 || size > MAX_PADDING_SIZE {
+        return Err(Error::ErrInvalidPaddingSize);
+    }
+
+    let header = Header {
+        padding: true,
+        ..header.clone()
+    };
+    let mut out = BytesMut::with_capacity(header.marshal_size() + size);
+    out.put(header.marshal()?);
+    out.put_bytes(0, size - 1);
+    out.put_u8(size as u8);
+
+    Ok(out.freeze())
+}
+
+/// is_padding_only returns true if the unmarshaled packet only carried
+/// padding, which receivers account for but do not depacketize.
+pub fn is_padding_only(packet: &Packet) -> bool {
+    packet.header.padding && packet.payload.is_empty()
+}
+
+/// PaddingGenerator generates the padding-only packets of a stream, the way
+/// libwebrtc does. They take the next sequence numbers of the stream, so that
+/// they are counted as received, but reuse the timestamp of the last media
+/// packet sent, so that they do not disturb the timing of the receiver. No
+/// padding is thus generated before the first media packet.
+///
+/// The padding is sent either on the media stream, sharing the sequencer of
+/// its packetizer, or on its RTX stream.
+#[derive(Debug, Clone)]
+pub struct PaddingGenerator {
+    payload_type: u8,
+    ssrc: u32,
+    sequencer: Box<dyn Sequencer + Send + Sync>,
+    timestamp: Option<u32>,
+}
+
+impl PaddingGenerator {
+    /// new creates a generator of padding packets with the given payload type
+    /// and SSRC, taking their sequence numbers from `sequencer`.
+    pub fn new(payload_type: u8, ssrc: u32, sequencer: Box<dyn Sequencer + Send + Sync>) -> Self {
+        PaddingGenerator {
+            payload_type,
+            ssrc,
+            sequencer,
+            timestamp: None,
+        }
+    }
+
+    /// on_media records a media packet sent, whose timestamp the next padding
+    /// packets reuse.
+    pub fn on_media(&mut self, packet: &Packet) {
+        self.timestamp = Some(packet.header.timestamp);
+    }
+
+    /// generate returns marshaled padding-only packets carrying `size` bytes of
+    /// padding in total, in packets of at most MAX_PADDING_SIZE bytes of
+    /// padding. It returns no packet before the first media packet.
+    pub fn generate(&mut self, size: usize) -> Result<Vec<Bytes>> {
+        let Some(timestamp) = self.timestamp else {
+            return Ok(vec![]);
+        };
+
+        let mut packets = Vec::with_capacity(size.div_ceil(MAX_PADDING_SIZE));
+        let mut remaining = size;
+        while remaining > 0 {
+            let padding = remaining.min(MAX_PADDING_SIZE);
+            let header = Header {
+                version: 2,
+                payload_type: self.payload_type,
+                sequence_number: self.sequencer.next_sequence_number(),
+                timestamp,
+                ssrc: self.ssrc,
+                ..Default::default()
+            };
+            packets.push(marshal_padding_packet(&header, padding)?);
+            remaining -= padding;
+        }
+
+        Ok(packets)
+    }
+}
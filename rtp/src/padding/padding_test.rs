@@ -0,0 +1,106 @@
+use util::marshal::Unmarshal;
+
+use super::*;
+use crate::sequence::new_fixed_sequencer;
+
+#[test]
+fn test_marshal_padding_packet() -> Result<()> {
+    let header = Header {
+        version: 2,
+        marker: true,
+        payload_type: 96,
+        sequence_number: 27023,
+        timestamp: 3653407706,
+        ssrc: 476325762,
+        ..Default::default()
+    };
+
+    let raw = marshal_padding_packet(&header, 3)?;
+    assert_eq!(
+        raw,
+        Bytes::from_static(&[
+            0xA0, 0xE0, 0x69, 0x8F, 0xD9, 0xC2, 0x93, 0xDA, 0x1C, 0x64, 0x27, 0x82, 0x00, 0x00,
+            0x03,
+        ])
+    );
+
+    let packet = Packet::unmarshal(&mut raw.clone())?;
+    assert!(is_padding_only(&packet));
+    assert_eq!(packet.header.sequence_number, 27023);
+
+    let raw = marshal_padding_packet(&header, MAX_PADDING_SIZE)?;
+    assert_eq!(raw.len(), 12 + MAX_PADDING_SIZE);
+    assert!(is_padding_only(&Packet::unmarshal(&mut raw.clone())?));
+
+    assert_eq!(
+        marshal_padding_packet(&header, 0),
+        Err(Error::ErrInvalidPaddingSize)
+    );
+    assert_eq!(
+        marshal_padding_packet(&header, MAX_PADDING_SIZE + 1),
+        Err(Error::ErrInvalidPaddingSize)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_is_padding_only() {
+    let tests = vec![
+        ("padding only", true, vec![], true),
+        ("padded media", true, vec![1, 2, 3], false),
+        ("empty", false, vec![], false),
+        ("media", false, vec![1, 2, 3], false),
+    ];
+
+    for (name, padding, payload, want) in tests {
+        let packet = Packet {
+            header: Header {
+                padding,
+                ..Default::default()
+            },
+            payload: Bytes::from(payload),
+        };
+        assert_eq!(is_padding_only(&packet), want, "{name}");
+    }
+}
+
+#[test]
+fn test_padding_generator() -> Result<()> {
+    let sequencer: Box<dyn Sequencer + Send + Sync> = Box::new(new_fixed_sequencer(65535));
+    let mut generator = PaddingGenerator::new(96, 0x1234ABCD, sequencer.clone());
+
+    // no padding before the first media packet
+    assert!(generator.generate(500)?.is_empty());
+
+    let media = Packet {
+        header: Header {
+            sequence_number: sequencer.next_sequence_number(),
+            timestamp: 90000,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    generator.on_media(&media);
+
+    let packets = generator.generate(600)?;
+    assert_eq!(packets.len(), 3);
+    for (raw, (sequence_number, padding)) in packets.iter().zip([(0, 255), (1, 255), (2, 90)]) {
+        assert_eq!(raw.len(), 12 + padding);
+        assert_eq!(raw[raw.len() - 1] as usize, padding);
+
+        let packet = Packet::unmarshal(&mut raw.clone())?;
+        assert!(is_padding_only(&packet));
+        assert!(!packet.header.marker);
+        assert_eq!(packet.header.payload_type, 96);
+        assert_eq!(packet.header.ssrc, 0x1234ABCD);
+        assert_eq!(packet.header.sequence_number, sequence_number);
+        assert_eq!(packet.header.timestamp, 90000);
+    }
+
+    // the sequence numbers are shared with the media stream
+    assert_eq!(sequencer.next_sequence_number(), 3);
+    assert!(generator.generate(0)?.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod pool_test;
+
+use std::sync::{Arc, Mutex};
+
+use bytes::BytesMut;
+
+use crate::packet::Packet;
+
+/// BufferPool recycles the buffers packets are marshaled into, so that the
+/// send path does not allocate a buffer per packet. Clones of a pool share
+/// its buffers, and may be used from several threads.
+#[derive(Debug, Clone)]
+pub struct BufferPool {
+    buffers: Arc<Mutex<Vec<BytesMut>>>,
+    buffer_size: usize,
+    max_buffers: usize,
+}
+
+impl BufferPool {
+    /// new creates a pool of buffers of `buffer_size` bytes, typically the MTU,
+    /// keeping at most `max_buffers` of them for reuse.
+    pub fn new(buffer_size: usize, max_buffers: usize) -> Self {
+        BufferPool {
+            buffers: Arc::new(Mutex::new(Vec::with_capacity(max_buffers))),
+            buffer_size,
+            max_buffers,
+        }
+    }
+
+    /// get returns an empty buffer of the pool, or a new one if the pool has
+    /// none left.
+    pub fn get(&self) -> BytesMut {
+        let buffer = match self.buffers.lock() {
+            Ok(mut buffers) => buffers.pop(),
+            Err(_) => None,
+        };
+
+        buffer.unwrap_or_else(|| BytesMut::with_capacity(self.buffer_size))
+    }
+
+    /// put gives a buffer back to the pool once the packet it holds was sent.
+    /// Buffers which lost their capacity, having been split, are dropped, as
+    /// well as those past the count the pool keeps.
+    pub fn put(&self, mut buffer: BytesMut) {
+        buffer.clear();
+        if buffer.capacity() < self.buffer_size {
+            return;
+        }
+
+        if let Ok(mut buffers) = self.buffers.lock() {
+            if buffers.len() < self.max_buffers {
+                buffers.push(buffer);
+            }
+        }
+    }
+
+    /// len returns the count of buffers available for reuse.
+    pub fn len(&self) -> usize {
+        self.buffers
+            .lock()
+            .map(|buffers| buffers.len())
+            .unwrap_or(0)
+    }
+
+    /// is_empty returns true if no buffer is available for reuse.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// marshal returns a buffer of the pool holding the marshaled packet, to be
+    /// given back with put once sent.
+    pub fn marshal(&self, packet: &Packet) -> Result<BytesMut, util::Error> {
+        let mut buffer = self.get();
+        if let Err(err) = packet.marshal_into(&mut buffer) {
+            self.put(buffer);
+            return Err(err);
+        }
+
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,69 @@
+use bytes::Bytes;
+use util::marshal::Marshal;
+
+use super::*;
+use crate::header::Header;
+
+#[test]
+fn test_buffer_pool_reuse() {
+    let pool = BufferPool::new(1500, 2);
+    assert!(pool.is_empty());
+
+    let buffer = pool.get();
+    assert!(buffer.capacity() >= 1500);
+    let ptr = buffer.as_ptr();
+    pool.put(buffer);
+    assert_eq!(pool.len(), 1);
+
+    // the buffer is reused, emptied
+    let mut buffer = pool.get();
+    assert_eq!(buffer.as_ptr(), ptr);
+    assert!(buffer.is_empty());
+    buffer.extend_from_slice(&[1, 2, 3]);
+    pool.put(buffer);
+    assert!(pool.get().is_empty());
+
+    // no more than max_buffers are kept
+    for _ in 0..3 {
+        pool.put(BytesMut::with_capacity(1500));
+    }
+    assert_eq!(pool.len(), 2);
+
+    // nor buffers too small
+    let pool = BufferPool::new(1500, 2);
+    pool.put(BytesMut::with_capacity(100));
+    assert!(pool.is_empty());
+
+    // clones share the buffers
+    pool.clone().put(BytesMut::with_capacity(1500));
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn test_buffer_pool_marshal() -> Result<(), util::Error> {
+    let pool = BufferPool::new(1500, 4);
+    let packet = Packet {
+        header: Header {
+            version: 2,
+            padding: true,
+            marker: true,
+            payload_type: 96,
+            sequence_number: 27023,
+            timestamp: 3653407706,
+            ssrc: 476325762,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x98, 0x36, 0xbe, 0x88, 0x9e]),
+    };
+
+    let buffer = pool.marshal(&packet)?;
+    assert_eq!(buffer, packet.marshal()?);
+    let ptr = buffer.as_ptr();
+    pool.put(buffer);
+
+    let buffer = pool.marshal(&packet)?;
+    assert_eq!(buffer.as_ptr(), ptr);
+    assert_eq!(buffer, packet.marshal()?);
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod rtx_test;
+
+use bytes::{BufMut, BytesMut};
+
+use crate::error::{Error, Result};
+use crate::header::Header;
+use crate::packet::Packet;
+use crate::sequence::Sequencer;
+
+/// RTX_OSN_SIZE is the size of the original sequence number which starts the
+/// payload of an RTX packet.
+pub const RTX_OSN_SIZE: usize = 2;
+
+/// encapsulate returns the retransmission of a packet in an RTX stream, as
+/// described in RFC 4588 section 4. The RTX stream has its own payload type,
+/// SSRC and sequence numbers, the latter taken from `sequencer`, while the RTX
+/// packet keeps the timestamp, marker, CSRCs and header extensions of the
+/// original packet. Its payload is the original sequence number (OSN),
+/// followed by the original payload.
+///
+///  0                   1                   2                   3
+///  0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |                         RTP Header                            |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+/// |            OSN                |                               |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+                               |
+/// |                  Original RTP Packet Payload                  |
+/// |                                                               |
+/// +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+pub fn encapsulate(
+    packet: &Packet,
+    payload_type: u8,
+    ssrc: u32,
+    sequencer: &dyn Sequencer,
+) -> Packet {
+    let mut payload = BytesMut::with_capacity(RTX_OSN_SIZE + packet.payload.len());
+    payload.put_u16(packet.header.sequence_number);
+    payload.put(&*packet.payload);
+
+    Packet {
+        header: Header {
+            payload_type,
+            sequence_number: sequencer.next_sequence_number(),
+            ssrc,
+            ..packet.header.clone()
+        },
+        payload: payload.freeze(),
+    }
+}
+
+/// decapsulate returns the original packet carried by an RTX packet, given the
+/// payload type and SSRC of the original stream. The session maps the RTX
+/// stream to them with the apt format parameter and the SSRC group of the
+/// streams.
+pub fn decapsulate(packet: &Packet, payload_type: u8, ssrc: u32) -> Result<Packet> {
+    if packet.payload.len() < RTX_OSN_SIZE {
+        return Err(Error::ErrShortPacket);
+    }
+
+    let osn = ((packet.payload[0] as u16) << 8) | packet.payload[1] as u16;
+
+    Ok(Packet {
+        header: Header {
+            payload_type,
+            sequence_number: osn,
+            ssrc,
+            ..packet.header.clone()
+        },
+        payload: packet.payload.slice(RTX_OSN_SIZE..),
+    })
+}
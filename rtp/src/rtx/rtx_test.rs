@@ -0,0 +1,83 @@
+use bytes::{Bytes, BytesMut};
+use util::marshal::{Marshal, MarshalSize, Unmarshal};
+
+use super::*;
+use crate::header::Extension;
+use crate::sequence::new_fixed_sequencer;
+
+fn original() -> Packet {
+    Packet {
+        header: Header {
+            version: 2,
+            marker: true,
+            payload_type: 96,
+            sequence_number: 0x1234,
+            timestamp: 0xdeadbeef,
+            ssrc: 0x11223344,
+            csrc: vec![0x55667788],
+            extension: true,
+            extension_profile: 0xBEDE,
+            extensions: vec![Extension {
+                id: 1,
+                payload: Bytes::from_static(&[0xaa, 0xbb]),
+            }],
+            extensions_padding: 1,
+            ..Default::default()
+        },
+        payload: Bytes::from_static(&[0x01, 0x02, 0x03]),
+    }
+}
+
+#[test]
+fn test_rtx_encapsulate() -> Result<()> {
+    let sequencer = new_fixed_sequencer(100);
+    let packet = original();
+
+    let rtx = encapsulate(&packet, 97, 0x99aabbcc, &sequencer);
+    assert_eq!(rtx.header.payload_type, 97);
+    assert_eq!(rtx.header.ssrc, 0x99aabbcc);
+    assert_eq!(rtx.header.sequence_number, 100);
+    assert_eq!(rtx.header.timestamp, packet.header.timestamp);
+    assert_eq!(rtx.header.marker, packet.header.marker);
+    assert_eq!(rtx.header.csrc, packet.header.csrc);
+    assert_eq!(rtx.header.extensions, packet.header.extensions);
+    assert_eq!(
+        rtx.payload,
+        Bytes::from_static(&[0x12, 0x34, 0x01, 0x02, 0x03])
+    );
+
+    // the RTX stream keeps its own sequence numbers
+    let rtx = encapsulate(&packet, 97, 0x99aabbcc, &sequencer);
+    assert_eq!(rtx.header.sequence_number, 101);
+
+    Ok(())
+}
+
+#[test]
+fn test_rtx_round_trip() -> Result<()> {
+    let sequencer = new_fixed_sequencer(u16::MAX);
+    let packet = original();
+
+    let rtx = encapsulate(&packet, 97, 0x99aabbcc, &sequencer);
+    let mut raw = BytesMut::zeroed(rtx.marshal_size());
+    rtx.marshal_to(&mut raw)?;
+    let received = Packet::unmarshal(&mut raw.freeze())?;
+
+    let decapsulated = decapsulate(&received, 96, 0x11223344)?;
+    assert_eq!(decapsulated, packet);
+
+    // padding only RTX packets carry no OSN
+    let empty = Packet {
+        header: Header {
+            padding: true,
+            ..rtx.header.clone()
+        },
+        payload: Bytes::new(),
+    };
+    assert_eq!(
+        decapsulate(&empty, 96, 0x11223344),
+        Err(Error::ErrShortPacket)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod sequence_test;
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Sequencer generates sequential sequence numbers for building RTP packets
+pub trait Sequencer: fmt::Debug {
+    fn next_sequence_number(&self) -> u16;
+    fn roll_over_count(&self) -> u64;
+    fn clone_to(&self) -> Box<dyn Sequencer + Send + Sync>;
+}
+
+impl Clone for Box<dyn Sequencer + Send + Sync> {
+    fn clone(&self) -> Box<dyn Sequencer + Send + Sync> {
+        self.clone_to()
+    }
+}
+
+/// NewRandomSequencer returns a new sequencer starting from a random sequence
+/// number, so that the sequence numbers of a stream are hard to guess as
+/// recommended by RFC 3550 section 5.1
+pub fn new_random_sequencer() -> impl Sequencer {
+    new_fixed_sequencer(rand::random::<u16>())
+}
+
+/// NewFixedSequencer returns a new sequencer starting from a specific
+/// sequence number
+pub fn new_fixed_sequencer(s: u16) -> impl Sequencer {
+    SequencerImpl {
+        next_sequence_number: Arc::new(AtomicU64::new(s as u64)),
+    }
+}
+
+/// SequencerImpl counts the sequence numbers it hands out in 64 bits, from which
+/// both the 16-bit sequence number and the roll over count are taken. Clones
+/// share the same counter.
+#[derive(Debug, Clone)]
+struct SequencerImpl {
+    next_sequence_number: Arc<AtomicU64>,
+}
+
+impl Sequencer for SequencerImpl {
+    /// NextSequenceNumber increment and returns a new sequence number for
+    /// building RTP packets
+    fn next_sequence_number(&self) -> u16 {
+        self.next_sequence_number.fetch_add(1, Ordering::SeqCst) as u16
+    }
+
+    /// RollOverCount returns the amount of times the 16bit sequence number
+    /// has wrapped
+    fn roll_over_count(&self) -> u64 {
+        let next = self.next_sequence_number.load(Ordering::SeqCst);
+        next.saturating_sub(1) >> 16
+    }
+
+    fn clone_to(&self) -> Box<dyn Sequencer + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+/// Wrapping is implemented by the wrapping counters of RTP packets, the 16-bit
+/// sequence number and the 32-bit timestamp, which an Unwrapper extends.
+pub trait Wrapping: Copy + Into<u64> {
+    const BITS: u32;
+}
+
+impl Wrapping for u16 {
+    const BITS: u32 = 16;
+}
+
+impl Wrapping for u32 {
+    const BITS: u32 = 32;
+}
+
+/// Unwrapper extends the wrapping sequence numbers or timestamps of a stream to
+/// 64-bit values which keep increasing across wraparounds.
+///
+/// Each value is taken as the closest one to the highest value seen so far, so
+/// that reordered packets get their place before it, without moving it back.
+/// The first value is extended to itself, and reordered values from before it
+/// may thus be negative.
+///
+/// ```
+/// use rtp::sequence::Unwrapper;
+///
+/// let mut unwrapper = Unwrapper::<u16>::default();
+/// assert_eq!(unwrapper.unwrap(65535), 65535);
+/// assert_eq!(unwrapper.unwrap(1), 65537);
+/// assert_eq!(unwrapper.unwrap(0), 65536);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Unwrapper<T: Wrapping> {
+    highest: Option<i64>,
+    _wrapping: PhantomData<T>,
+}
+
+impl<T: Wrapping> Default for Unwrapper<T> {
+    fn default() -> Self {
+        Unwrapper {
+            highest: None,
+            _wrapping: PhantomData,
+        }
+    }
+}
+
+impl<T: Wrapping> Unwrapper<T> {
+    /// unwrap returns the extended value of the next value of the stream.
+    pub fn unwrap(&mut self, value: T) -> i64 {
+        let value = value.into() as i64;
+        let Some(highest) = self.highest else {
+            self.highest = Some(value);
+            return value;
+        };
+
+        let modulus = 1i64 << T::BITS;
+        let mut delta = value - highest.rem_euclid(modulus);
+        if delta > modulus / 2 {
+            delta -= modulus;
+        } else if delta <= -modulus / 2 {
+            delta += modulus;
+        }
+
+        let extended = highest + delta;
+        if extended > highest {
+            self.highest = Some(extended);
+        }
+
+        extended
+    }
+
+    /// highest returns the highest extended value seen so far.
+    pub fn highest(&self) -> Option<i64> {
+        self.highest
+    }
+}
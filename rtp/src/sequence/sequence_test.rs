@@ -0,0 +1,123 @@
+use std::thread;
+
+use super::*;
+
+#[test]
+fn test_fixed_sequencer() {
+    let tests = vec![
+        ("start at zero", 0u16, vec![0u16, 1, 2], 0u64),
+        ("wrap", u16::MAX - 1, vec![u16::MAX - 1, u16::MAX, 0, 1], 1),
+        ("start at max", u16::MAX, vec![u16::MAX, 0], 1),
+    ];
+
+    for (name, start, expected, roll_over_count) in tests {
+        let sequencer = new_fixed_sequencer(start);
+        assert_eq!(sequencer.roll_over_count(), 0, "{name}");
+
+        let got: Vec<u16> = expected
+            .iter()
+            .map(|_| sequencer.next_sequence_number())
+            .collect();
+        assert_eq!(got, expected, "{name}");
+        assert_eq!(sequencer.roll_over_count(), roll_over_count, "{name}");
+    }
+}
+
+#[test]
+fn test_sequencer_roll_over_count() {
+    let sequencer = new_fixed_sequencer(10);
+    for _ in 0..65536 * 3 {
+        sequencer.next_sequence_number();
+    }
+
+    // the last one handed out was 9, after wrapping three times
+    assert_eq!(sequencer.roll_over_count(), 3);
+    assert_eq!(sequencer.next_sequence_number(), 10);
+}
+
+#[test]
+fn test_sequencer_shared_between_threads() {
+    let sequencer = new_random_sequencer();
+    let start = sequencer.next_sequence_number();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let sequencer = sequencer.clone_to();
+            thread::spawn(move || {
+                (0..10000)
+                    .map(|_| sequencer.next_sequence_number())
+                    .collect::<Vec<u16>>()
+            })
+        })
+        .collect();
+
+    let mut got: Vec<u16> = vec![];
+    for handle in handles {
+        got.extend(handle.join().unwrap());
+    }
+
+    // every number following start is handed out exactly once
+    let mut got: Vec<u16> = got.into_iter().map(|seq| seq.wrapping_sub(start)).collect();
+    got.sort_unstable();
+    let expected: Vec<u16> = (1..=40000).collect();
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn test_sequence_number_unwrapper() {
+    let tests = vec![
+        ("in order", vec![1u16, 2, 3], vec![1i64, 2, 3]),
+        (
+            "wrap",
+            vec![65534, 65535, 0, 1],
+            vec![65534, 65535, 65536, 65537],
+        ),
+        (
+            "reordered across wrap",
+            vec![65535, 1, 0, 2],
+            vec![65535, 65537, 65536, 65538],
+        ),
+        (
+            "reordered before first",
+            vec![2, 0, 65535, 3],
+            vec![2, 0, -1, 3],
+        ),
+        (
+            "gap",
+            vec![0, 30000, 60000, 10],
+            vec![0, 30000, 60000, 65546],
+        ),
+        // a jump of half the range is taken forward
+        ("half range", vec![0, 32768, 0], vec![0, 32768, 65536]),
+        ("duplicates", vec![7, 7, 7], vec![7, 7, 7]),
+    ];
+
+    for (name, values, expected) in tests {
+        let mut unwrapper = Unwrapper::<u16>::default();
+        let got: Vec<i64> = values.into_iter().map(|v| unwrapper.unwrap(v)).collect();
+        assert_eq!(got, expected, "{name}");
+        assert_eq!(
+            unwrapper.highest(),
+            expected.iter().max().copied(),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_timestamp_unwrapper() {
+    let mut unwrapper = Unwrapper::<u32>::default();
+    assert_eq!(unwrapper.highest(), None);
+
+    assert_eq!(unwrapper.unwrap(u32::MAX - 1000), (u32::MAX - 1000) as i64);
+    assert_eq!(unwrapper.unwrap(2000), (1i64 << 32) + 2000);
+    assert_eq!(unwrapper.unwrap(u32::MAX - 3000), (u32::MAX - 3000) as i64);
+
+    // wrap many times, 1 << 30 at a time
+    let mut ts = 2000u32;
+    for i in 1..=16i64 {
+        ts = ts.wrapping_add(1 << 30);
+        assert_eq!(unwrapper.unwrap(ts), (1i64 << 32) + 2000 + (i << 30));
+    }
+    assert_eq!(unwrapper.highest(), Some((5i64 << 32) + 2000));
+}
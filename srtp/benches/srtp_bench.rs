@@ -148,11 +148,98 @@ fn benchmark_decrypt_rtcp_aes_128_cm_hmac_sha1(c: &mut Criterion) {
     });
 }
 
+fn benchmark_encrypt_rtp_aead_aes_128_gcm(c: &mut Criterion) {
+    let mut ctx = Context::new(
+        MASTER_KEY,
+        &MASTER_SALT[..12],
+        ProtectionProfile::AeadAes128Gcm,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let mut pld = BytesMut::new();
+    for i in 0..1200 {
+        pld.extend_from_slice(&[i as u8]);
+    }
+
+    c.bench_function("Benchmark RTP encrypt AEAD_AES_128_GCM", |b| {
+        let mut seq = 1;
+        b.iter_batched(
+            || {
+                let pkt = rtp::packet::Packet {
+                    header: rtp::header::Header {
+                        sequence_number: seq,
+                        timestamp: seq.into(),
+                        payload_type: 96,
+                        ..Default::default()
+                    },
+                    payload: pld.clone().into(),
+                };
+                seq += 1;
+                pkt.marshal().unwrap()
+            },
+            |pkt_raw| {
+                ctx.encrypt_rtp(&pkt_raw).unwrap();
+            },
+            criterion::BatchSize::LargeInput,
+        );
+    });
+}
+
+fn benchmark_decrypt_rtp_aead_aes_128_gcm(c: &mut Criterion) {
+    let mut setup_ctx = Context::new(
+        MASTER_KEY,
+        &MASTER_SALT[..12],
+        ProtectionProfile::AeadAes128Gcm,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let mut ctx = Context::new(
+        MASTER_KEY,
+        &MASTER_SALT[..12],
+        ProtectionProfile::AeadAes128Gcm,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let mut pld = BytesMut::new();
+    for i in 0..1200 {
+        pld.extend_from_slice(&[i as u8]);
+    }
+
+    c.bench_function("Benchmark RTP decrypt AEAD_AES_128_GCM", |b| {
+        let mut seq = 1;
+        b.iter_batched(
+            || {
+                let pkt = rtp::packet::Packet {
+                    header: rtp::header::Header {
+                        sequence_number: seq,
+                        timestamp: seq.into(),
+                        payload_type: 96,
+                        ..Default::default()
+                    },
+                    payload: pld.clone().into(),
+                };
+                seq += 1;
+                setup_ctx.encrypt_rtp(&pkt.marshal().unwrap()).unwrap()
+            },
+            |encrypted| ctx.decrypt_rtp(&encrypted).unwrap(),
+            criterion::BatchSize::LargeInput,
+        );
+    });
+}
+
 criterion_group!(
     benches,
     benchmark_encrypt_rtp_aes_128_cm_hmac_sha1,
     benchmark_decrypt_rtp_aes_128_cm_hmac_sha1,
     benchmark_encrypt_rtcp_aes_128_cm_hmac_sha1,
-    benchmark_decrypt_rtcp_aes_128_cm_hmac_sha1
+    benchmark_decrypt_rtcp_aes_128_cm_hmac_sha1,
+    benchmark_encrypt_rtp_aead_aes_128_gcm,
+    benchmark_decrypt_rtp_aead_aes_128_gcm
 );
 criterion_main!(benches);
@@ -1,6 +1,6 @@
 use aes_gcm::aead::generic_array::GenericArray;
-use aes_gcm::aead::{Aead, Payload};
-use aes_gcm::{Aes128Gcm, KeyInit, Nonce};
+use aes_gcm::aead::{Aead, AeadInPlace, Payload};
+use aes_gcm::{Aes128Gcm, KeyInit};
 use byteorder::{BigEndian, ByteOrder};
 use bytes::{Bytes, BytesMut};
 use util::marshal::*;
@@ -13,15 +13,16 @@ pub const CIPHER_AEAD_AES_GCM_AUTH_TAG_LEN: usize = 16;
 
 const RTCP_ENCRYPTION_FLAG: u8 = 0x80;
 
-/// AEAD Cipher based on AES.
-pub(crate) struct CipherAeadAesGcm {
-    srtp_cipher: aes_gcm::Aes128Gcm,
-    srtcp_cipher: aes_gcm::Aes128Gcm,
+/// AEAD Cipher based on AES, of 128-bit keys by default, or of 256-bit keys
+/// with aes_gcm::Aes256Gcm.
+pub(crate) struct CipherAeadAesGcm<C = Aes128Gcm> {
+    srtp_cipher: C,
+    srtcp_cipher: C,
     srtp_session_salt: Vec<u8>,
     srtcp_session_salt: Vec<u8>,
 }
 
-impl Cipher for CipherAeadAesGcm {
+impl<C: AeadInPlace + KeyInit> Cipher for CipherAeadAesGcm<C> {
     fn auth_tag_len(&self) -> usize {
         CIPHER_AEAD_AES_GCM_AUTH_TAG_LEN
     }
@@ -35,21 +36,19 @@ impl Cipher for CipherAeadAesGcm {
         // Grow the given buffer to fit the output.
         let header_len = header.marshal_size();
         let mut writer = BytesMut::with_capacity(payload.len() + self.auth_tag_len());
-
-        // Copy header unencrypted.
-        writer.extend_from_slice(&payload[..header_len]);
+        writer.extend_from_slice(payload);
 
         let nonce = self.rtp_initialization_vector(header, roc);
 
-        let encrypted = self.srtp_cipher.encrypt(
-            Nonce::from_slice(&nonce),
-            Payload {
-                msg: &payload[header_len..],
-                aad: &writer,
-            },
+        // Encrypt the payload in place, authenticating the unencrypted header.
+        let (aad, msg) = writer.split_at_mut(header_len);
+        let tag = self.srtp_cipher.encrypt_in_place_detached(
+            GenericArray::from_slice(&nonce),
+            aad,
+            msg,
         )?;
 
-        writer.extend(encrypted);
+        writer.extend_from_slice(&tag);
         Ok(writer.freeze())
     }
 
@@ -59,24 +58,25 @@ impl Cipher for CipherAeadAesGcm {
         header: &rtp::header::Header,
         roc: u32,
     ) -> Result<Bytes> {
-        if ciphertext.len() < self.auth_tag_len() {
+        let payload_offset = header.marshal_size();
+        if ciphertext.len() < payload_offset + self.auth_tag_len() {
             return Err(Error::ErrFailedToVerifyAuthTag);
         }
 
         let nonce = self.rtp_initialization_vector(header, roc);
-        let payload_offset = header.marshal_size();
-        let decrypted_msg: Vec<u8> = self.srtp_cipher.decrypt(
-            Nonce::from_slice(&nonce),
-            Payload {
-                msg: &ciphertext[payload_offset..],
-                aad: &ciphertext[..payload_offset],
-            },
+        let tag_offset = ciphertext.len() - self.auth_tag_len();
+
+        // Decrypt the payload in place, verifying the tag of the header and
+        // payload.
+        let mut writer = BytesMut::from(&ciphertext[..tag_offset]);
+        let (aad, msg) = writer.split_at_mut(payload_offset);
+        self.srtp_cipher.decrypt_in_place_detached(
+            GenericArray::from_slice(&nonce),
+            aad,
+            msg,
+            GenericArray::from_slice(&ciphertext[tag_offset..]),
         )?;
 
-        let mut writer = BytesMut::with_capacity(payload_offset + decrypted_msg.len());
-        writer.extend_from_slice(&ciphertext[..payload_offset]);
-        writer.extend(decrypted_msg);
-
         Ok(writer.freeze())
     }
 
@@ -85,7 +85,7 @@ impl Cipher for CipherAeadAesGcm {
         let aad = self.rtcp_additional_authenticated_data(decrypted, srtcp_index);
 
         let encrypted_data = self.srtcp_cipher.encrypt(
-            Nonce::from_slice(&iv),
+            GenericArray::from_slice(&iv),
             Payload {
                 msg: &decrypted[8..],
                 aad: &aad,
@@ -94,7 +94,7 @@ impl Cipher for CipherAeadAesGcm {
 
         let mut writer = BytesMut::with_capacity(encrypted_data.len() + aad.len());
         writer.extend_from_slice(&decrypted[..8]);
-        writer.extend(encrypted_data);
+        writer.extend_from_slice(&encrypted_data);
         writer.extend_from_slice(&aad[8..]);
 
         Ok(writer.freeze())
@@ -106,10 +106,33 @@ impl Cipher for CipherAeadAesGcm {
         }
 
         let nonce = self.rtcp_initialization_vector(srtcp_index, ssrc);
+
+        // Packets whose E-flag is not set are authenticated, but not encrypted:
+        // the whole packet and the ESRTCP word are then the additional
+        // authenticated data of an empty message, as described in
+        // https://tools.ietf.org/html/rfc7714#section-9.2
+        let tail_offset = encrypted.len() - SRTCP_INDEX_SIZE;
+        if encrypted[tail_offset] & RTCP_ENCRYPTION_FLAG == 0 {
+            let tag_offset = tail_offset - self.auth_tag_len();
+            let mut aad = Vec::with_capacity(tag_offset + SRTCP_INDEX_SIZE);
+            aad.extend_from_slice(&encrypted[..tag_offset]);
+            aad.extend_from_slice(&encrypted[tail_offset..]);
+
+            self.srtcp_cipher.decrypt(
+                GenericArray::from_slice(&nonce),
+                Payload {
+                    msg: &encrypted[tag_offset..tail_offset],
+                    aad: &aad,
+                },
+            )?;
+
+            return Ok(Bytes::copy_from_slice(&encrypted[..tag_offset]));
+        }
+
         let aad = self.rtcp_additional_authenticated_data(encrypted, srtcp_index);
 
         let decrypted_data = self.srtcp_cipher.decrypt(
-            Nonce::from_slice(&nonce),
+            GenericArray::from_slice(&nonce),
             Payload {
                 msg: &encrypted[8..(encrypted.len() - SRTCP_INDEX_SIZE)],
                 aad: &aad,
@@ -118,7 +141,7 @@ impl Cipher for CipherAeadAesGcm {
 
         let mut writer = BytesMut::with_capacity(8 + decrypted_data.len());
         writer.extend_from_slice(&encrypted[..8]);
-        writer.extend(decrypted_data);
+        writer.extend_from_slice(&decrypted_data);
 
         Ok(writer.freeze())
     }
@@ -131,47 +154,51 @@ impl Cipher for CipherAeadAesGcm {
     }
 }
 
-impl CipherAeadAesGcm {
+impl<C: AeadInPlace + KeyInit> CipherAeadAesGcm<C> {
     /// Create a new AEAD instance.
-    pub(crate) fn new(master_key: &[u8], master_salt: &[u8]) -> Result<CipherAeadAesGcm> {
+    pub(crate) fn new(
+        master_key: &[u8],
+        master_salt: &[u8],
+        index_over_kdr: u64,
+    ) -> Result<CipherAeadAesGcm<C>> {
         let srtp_session_key = aes_cm_key_derivation(
             LABEL_SRTP_ENCRYPTION,
             master_key,
             master_salt,
-            0,
+            index_over_kdr,
             master_key.len(),
         )?;
 
         let srtp_block = GenericArray::from_slice(&srtp_session_key);
 
-        let srtp_cipher = Aes128Gcm::new(srtp_block);
+        let srtp_cipher = C::new(srtp_block);
 
         let srtcp_session_key = aes_cm_key_derivation(
             LABEL_SRTCP_ENCRYPTION,
             master_key,
             master_salt,
-            0,
+            index_over_kdr,
             master_key.len(),
         )?;
 
         let srtcp_block = GenericArray::from_slice(&srtcp_session_key);
 
-        let srtcp_cipher = Aes128Gcm::new(srtcp_block);
+        let srtcp_cipher = C::new(srtcp_block);
 
         let srtp_session_salt = aes_cm_key_derivation(
             LABEL_SRTP_SALT,
             master_key,
             master_salt,
-            0,
-            master_key.len(),
+            index_over_kdr,
+            master_salt.len(),
         )?;
 
         let srtcp_session_salt = aes_cm_key_derivation(
             LABEL_SRTCP_SALT,
             master_key,
             master_salt,
-            0,
-            master_key.len(),
+            index_over_kdr,
+            master_salt.len(),
         )?;
 
         Ok(CipherAeadAesGcm {
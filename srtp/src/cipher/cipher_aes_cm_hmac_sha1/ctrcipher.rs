@@ -1,5 +1,6 @@
 use aes::cipher::generic_array::GenericArray;
-use aes::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+use aes::cipher::{InnerIvInit, KeyInit, StreamCipher, StreamCipherCoreWrapper};
+use aes::Aes128;
 use bytes::{BufMut, Bytes};
 use rtcp::header::{HEADER_LENGTH, SSRC_LENGTH};
 use subtle::ConstantTimeEq;
@@ -9,41 +10,49 @@ use super::{Cipher, CipherInner};
 use crate::error::{Error, Result};
 use crate::key_derivation::*;
 
-type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
 
 pub(crate) struct CipherAesCmHmacSha1 {
     inner: CipherInner,
-    srtp_session_key: Vec<u8>,
-    srtcp_session_key: Vec<u8>,
+    /// The block ciphers of the session keys, whose key schedules are expanded
+    /// once rather than for every packet.
+    srtp_block_cipher: Aes128,
+    srtcp_block_cipher: Aes128,
 }
 
 impl CipherAesCmHmacSha1 {
-    pub fn new(master_key: &[u8], master_salt: &[u8]) -> Result<Self> {
-        let inner = CipherInner::new(master_key, master_salt)?;
+    pub fn new(master_key: &[u8], master_salt: &[u8], index_over_kdr: u64) -> Result<Self> {
+        let inner = CipherInner::new(master_key, master_salt, index_over_kdr)?;
 
         let srtp_session_key = aes_cm_key_derivation(
             LABEL_SRTP_ENCRYPTION,
             master_key,
             master_salt,
-            0,
+            index_over_kdr,
             master_key.len(),
         )?;
         let srtcp_session_key = aes_cm_key_derivation(
             LABEL_SRTCP_ENCRYPTION,
             master_key,
             master_salt,
-            0,
+            index_over_kdr,
             master_key.len(),
         )?;
 
         Ok(CipherAesCmHmacSha1 {
             inner,
-            srtp_session_key,
-            srtcp_session_key,
+            srtp_block_cipher: Aes128::new(GenericArray::from_slice(&srtp_session_key)),
+            srtcp_block_cipher: Aes128::new(GenericArray::from_slice(&srtcp_session_key)),
         })
     }
 }
 
+/// new_stream creates the AES-CM keystream of a block cipher and counter.
+fn new_stream(block_cipher: &Aes128, counter: &[u8]) -> Aes128Ctr {
+    let core = ctr::CtrCore::inner_iv_init(block_cipher.clone(), GenericArray::from_slice(counter));
+    StreamCipherCoreWrapper::from_core(core)
+}
+
 impl Cipher for CipherAesCmHmacSha1 {
     fn auth_tag_len(&self) -> usize {
         self.inner.auth_tag_len()
@@ -71,9 +80,7 @@ impl Cipher for CipherAesCmHmacSha1 {
             header.ssrc,
             &self.inner.srtp_session_salt,
         );
-        let key = GenericArray::from_slice(&self.srtp_session_key);
-        let nonce = GenericArray::from_slice(&counter);
-        let mut stream = Aes128Ctr::new(key, nonce);
+        let mut stream = new_stream(&self.srtp_block_cipher, &counter);
         stream.apply_keystream(&mut writer[header.marshal_size()..]);
 
         // Generate the auth tag.
@@ -121,10 +128,7 @@ impl Cipher for CipherAesCmHmacSha1 {
             &self.inner.srtp_session_salt,
         );
 
-        let key = GenericArray::from_slice(&self.srtp_session_key);
-        let nonce = GenericArray::from_slice(&counter);
-        let mut stream = Aes128Ctr::new(key, nonce);
-        stream.seek(0);
+        let mut stream = new_stream(&self.srtp_block_cipher, &counter);
         stream.apply_keystream(&mut writer[header.marshal_size()..]);
 
         Ok(Bytes::from(writer))
@@ -145,9 +149,7 @@ impl Cipher for CipherAesCmHmacSha1 {
             &self.inner.srtcp_session_salt,
         );
 
-        let key = GenericArray::from_slice(&self.srtcp_session_key);
-        let nonce = GenericArray::from_slice(&counter);
-        let mut stream = Aes128Ctr::new(key, nonce);
+        let mut stream = new_stream(&self.srtcp_block_cipher, &counter);
 
         stream.apply_keystream(&mut writer[HEADER_LENGTH + SSRC_LENGTH..]);
 
@@ -176,11 +178,6 @@ impl Cipher for CipherAesCmHmacSha1 {
 
         writer.extend_from_slice(&encrypted[0..tail_offset]);
 
-        let is_encrypted = encrypted[tail_offset] >> 7;
-        if is_encrypted == 0 {
-            return Ok(Bytes::from(writer));
-        }
-
         // Split the auth tag and the cipher text into two parts.
         let actual_tag = &encrypted[encrypted_len - self.auth_tag_len()..];
         if actual_tag.len() != self.auth_tag_len() {
@@ -201,6 +198,12 @@ impl Cipher for CipherAesCmHmacSha1 {
             return Err(Error::RtcpFailedToVerifyAuthTag);
         }
 
+        // Packets whose E-flag is not set are authenticated, but not encrypted.
+        let is_encrypted = encrypted[tail_offset] >> 7;
+        if is_encrypted == 0 {
+            return Ok(Bytes::from(writer));
+        }
+
         let counter = generate_counter(
             (srtcp_index & 0xFFFF) as u16,
             (srtcp_index >> 16) as u32,
@@ -208,11 +211,7 @@ impl Cipher for CipherAesCmHmacSha1 {
             &self.inner.srtcp_session_salt,
         );
 
-        let key = GenericArray::from_slice(&self.srtcp_session_key);
-        let nonce = GenericArray::from_slice(&counter);
-        let mut stream = Aes128Ctr::new(key, nonce);
-
-        stream.seek(0);
+        let mut stream = new_stream(&self.srtcp_block_cipher, &counter);
         stream.apply_keystream(&mut writer[HEADER_LENGTH + SSRC_LENGTH..]);
 
         Ok(Bytes::from(writer))
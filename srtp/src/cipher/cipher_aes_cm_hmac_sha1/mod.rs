@@ -19,6 +19,12 @@ pub(crate) use ctrcipher::CipherAesCmHmacSha1;
 #[cfg(feature = "openssl")]
 pub(crate) use opensslcipher::CipherAesCmHmacSha1;
 
+#[cfg(feature = "insecure-null-cipher")]
+mod nullcipher;
+
+#[cfg(feature = "insecure-null-cipher")]
+pub(crate) use nullcipher::CipherNullHmacSha1;
+
 type HmacSha1 = Hmac<Sha1>;
 
 pub const CIPHER_AES_CM_HMAC_SHA1AUTH_TAG_LEN: usize = 10;
@@ -31,19 +37,19 @@ pub(crate) struct CipherInner {
 }
 
 impl CipherInner {
-    pub fn new(master_key: &[u8], master_salt: &[u8]) -> Result<Self> {
+    pub fn new(master_key: &[u8], master_salt: &[u8], index_over_kdr: u64) -> Result<Self> {
         let srtp_session_salt = aes_cm_key_derivation(
             LABEL_SRTP_SALT,
             master_key,
             master_salt,
-            0,
+            index_over_kdr,
             master_salt.len(),
         )?;
         let srtcp_session_salt = aes_cm_key_derivation(
             LABEL_SRTCP_SALT,
             master_key,
             master_salt,
-            0,
+            index_over_kdr,
             master_salt.len(),
         )?;
 
@@ -53,14 +59,14 @@ impl CipherInner {
             LABEL_SRTP_AUTHENTICATION_TAG,
             master_key,
             master_salt,
-            0,
+            index_over_kdr,
             auth_key_len,
         )?;
         let srtcp_session_auth_tag = aes_cm_key_derivation(
             LABEL_SRTCP_AUTHENTICATION_TAG,
             master_key,
             master_salt,
-            0,
+            index_over_kdr,
             auth_key_len,
         )?;
 
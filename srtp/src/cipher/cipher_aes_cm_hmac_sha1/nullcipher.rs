@@ -0,0 +1,118 @@
+use bytes::{BufMut, Bytes};
+use subtle::ConstantTimeEq;
+
+use super::{Cipher, CipherInner};
+use crate::error::{Error, Result};
+use crate::key_derivation::*;
+
+/// CipherNullHmacSha1 authenticates packets with HMAC-SHA1 like
+/// CipherAesCmHmacSha1, but leaves their payload unencrypted, as the NULL
+/// cipher of https://tools.ietf.org/html/rfc3711#section-4.1.4
+pub(crate) struct CipherNullHmacSha1 {
+    inner: CipherInner,
+}
+
+impl CipherNullHmacSha1 {
+    pub fn new(master_key: &[u8], master_salt: &[u8], index_over_kdr: u64) -> Result<Self> {
+        let inner = CipherInner::new(master_key, master_salt, index_over_kdr)?;
+
+        Ok(CipherNullHmacSha1 { inner })
+    }
+}
+
+impl Cipher for CipherNullHmacSha1 {
+    fn auth_tag_len(&self) -> usize {
+        self.inner.auth_tag_len()
+    }
+
+    fn get_rtcp_index(&self, input: &[u8]) -> usize {
+        self.inner.get_rtcp_index(input)
+    }
+
+    fn encrypt_rtp(
+        &mut self,
+        plaintext: &[u8],
+        _header: &rtp::header::Header,
+        roc: u32,
+    ) -> Result<Bytes> {
+        let mut writer = Vec::with_capacity(plaintext.len() + self.auth_tag_len());
+        writer.extend_from_slice(plaintext);
+
+        // Generate the auth tag.
+        let auth_tag = &self.inner.generate_srtp_auth_tag(&writer, roc)[..self.auth_tag_len()];
+        writer.extend(auth_tag);
+
+        Ok(Bytes::from(writer))
+    }
+
+    fn decrypt_rtp(
+        &mut self,
+        encrypted: &[u8],
+        _header: &rtp::header::Header,
+        roc: u32,
+    ) -> Result<Bytes> {
+        let encrypted_len = encrypted.len();
+        if encrypted_len < self.auth_tag_len() {
+            return Err(Error::SrtpTooSmall(encrypted_len, self.auth_tag_len()));
+        }
+
+        // Split the auth tag and the plaintext into two parts.
+        let actual_tag = &encrypted[encrypted_len - self.auth_tag_len()..];
+        let plaintext = &encrypted[..encrypted_len - self.auth_tag_len()];
+
+        // Generate the auth tag we expect to see from the plaintext.
+        let expected_tag =
+            &self.inner.generate_srtp_auth_tag(plaintext, roc)[..self.auth_tag_len()];
+
+        // See if the auth tag actually matches.
+        // We use a constant time comparison to prevent timing attacks.
+        if actual_tag.ct_eq(expected_tag).unwrap_u8() != 1 {
+            return Err(Error::RtpFailedToVerifyAuthTag);
+        }
+
+        Ok(Bytes::copy_from_slice(plaintext))
+    }
+
+    fn encrypt_rtcp(&mut self, decrypted: &[u8], srtcp_index: usize, _ssrc: u32) -> Result<Bytes> {
+        let mut writer =
+            Vec::with_capacity(decrypted.len() + SRTCP_INDEX_SIZE + self.auth_tag_len());
+        writer.extend_from_slice(decrypted);
+
+        // Add SRTCP index, leaving the Encryption bit unset
+        writer.put_u32(srtcp_index as u32 & !(1u32 << 31));
+
+        // Generate the auth tag.
+        let auth_tag = &self.inner.generate_srtcp_auth_tag(&writer)[..self.auth_tag_len()];
+        writer.extend(auth_tag);
+
+        Ok(Bytes::from(writer))
+    }
+
+    fn decrypt_rtcp(&mut self, encrypted: &[u8], _srtcp_index: usize, _ssrc: u32) -> Result<Bytes> {
+        let encrypted_len = encrypted.len();
+        if encrypted_len < self.auth_tag_len() + SRTCP_INDEX_SIZE {
+            return Err(Error::SrtcpTooSmall(
+                encrypted_len,
+                self.auth_tag_len() + SRTCP_INDEX_SIZE,
+            ));
+        }
+
+        let tail_offset = encrypted_len - (self.auth_tag_len() + SRTCP_INDEX_SIZE);
+
+        // Split the auth tag and the authenticated portion into two parts.
+        let actual_tag = &encrypted[encrypted_len - self.auth_tag_len()..];
+        let authenticated = &encrypted[..encrypted_len - self.auth_tag_len()];
+
+        // Generate the auth tag we expect to see from the authenticated portion.
+        let expected_tag =
+            &self.inner.generate_srtcp_auth_tag(authenticated)[..self.auth_tag_len()];
+
+        // See if the auth tag actually matches.
+        // We use a constant time comparison to prevent timing attacks.
+        if actual_tag.ct_eq(expected_tag).unwrap_u8() != 1 {
+            return Err(Error::RtcpFailedToVerifyAuthTag);
+        }
+
+        Ok(Bytes::copy_from_slice(&encrypted[..tail_offset]))
+    }
+}
@@ -17,21 +17,21 @@ pub(crate) struct CipherAesCmHmacSha1 {
 }
 
 impl CipherAesCmHmacSha1 {
-    pub fn new(master_key: &[u8], master_salt: &[u8]) -> Result<Self> {
-        let inner = CipherInner::new(master_key, master_salt)?;
+    pub fn new(master_key: &[u8], master_salt: &[u8], index_over_kdr: u64) -> Result<Self> {
+        let inner = CipherInner::new(master_key, master_salt, index_over_kdr)?;
 
         let srtp_session_key = aes_cm_key_derivation(
             LABEL_SRTP_ENCRYPTION,
             master_key,
             master_salt,
-            0,
+            index_over_kdr,
             master_key.len(),
         )?;
         let srtcp_session_key = aes_cm_key_derivation(
             LABEL_SRTCP_ENCRYPTION,
             master_key,
             master_salt,
-            0,
+            index_over_kdr,
             master_key.len(),
         )?;
 
@@ -209,11 +209,6 @@ impl Cipher for CipherAesCmHmacSha1 {
 
         writer.extend_from_slice(&encrypted[..HEADER_LENGTH + SSRC_LENGTH]);
 
-        let is_encrypted = encrypted[tail_offset] >> 7;
-        if is_encrypted == 0 {
-            return Ok(Bytes::from(writer));
-        }
-
         // Split the auth tag and the cipher text into two parts.
         let actual_tag = &encrypted[encrypted_len - self.auth_tag_len()..];
         if actual_tag.len() != self.auth_tag_len() {
@@ -234,6 +229,12 @@ impl Cipher for CipherAesCmHmacSha1 {
             return Err(Error::RtcpFailedToVerifyAuthTag);
         }
 
+        // Packets whose E-flag is not set are authenticated, but not encrypted.
+        let is_encrypted = encrypted[tail_offset] >> 7;
+        if is_encrypted == 0 {
+            return Ok(Bytes::copy_from_slice(&encrypted[..tail_offset]));
+        }
+
         let nonce = generate_counter(
             (srtcp_index & 0xFFFF) as u16,
             (srtcp_index >> 16) as u32,
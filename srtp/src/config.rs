@@ -13,6 +13,10 @@ pub struct SessionKeys {
     pub local_master_salt: Vec<u8>,
     pub remote_master_key: Vec<u8>,
     pub remote_master_salt: Vec<u8>,
+    /// MKIs of the local and remote master keys, which are not used if empty,
+    /// as with keys extracted from DTLS.
+    pub local_mki: Vec<u8>,
+    pub remote_mki: Vec<u8>,
 }
 
 /// Config is used to configure a session.
@@ -32,6 +36,10 @@ pub struct Config {
 
     pub local_rtcp_options: Option<ContextOption>,
     pub remote_rtcp_options: Option<ContextOption>,
+
+    /// Key derivation rate of both contexts, 0 by default for the session
+    /// keys to be derived once, as with keys extracted from DTLS.
+    pub key_derivation_rate: u64,
 }
 
 impl Config {
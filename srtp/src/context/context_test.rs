@@ -212,6 +212,11 @@ lazy_static! {
     static ref MASTER_SALT: Bytes = Bytes::from_static(&[
         0xa0, 0xa1, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7, 0xa8, 0xa9, 0xaa, 0xab,
     ]);
+    static ref MASTER_KEY_256: Bytes = Bytes::from_static(&[
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d,
+        0x1e, 0x1f,
+    ]);
     static ref DECRYPTED_RTP_PACKET: Bytes = Bytes::from_static(&[
         0x80, 0x0f, 0x12, 0x34, 0xde, 0xca, 0xfb, 0xad, 0xca, 0xfe, 0xba, 0xbe, 0xab, 0xab, 0xab,
         0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab,
@@ -230,6 +235,16 @@ lazy_static! {
         0x55, 0x85, 0x2b, 0x6c, 0x21, 0xac, 0x8e, 0x70, 0x25, 0xc5, 0x2c, 0x6f, 0xbe, 0xa2, 0xb3,
         0xb4, 0x46, 0xea, 0x31, 0x12, 0x3b, 0xa8, 0x8c, 0xe6, 0x1e, 0x80, 0x00, 0x00, 0x01,
     ]);
+    static ref ENCRYPTED_RTP_PACKET_256: Bytes = Bytes::from_static(&[
+        0x80, 0x0f, 0x12, 0x34, 0xde, 0xca, 0xfb, 0xad, 0xca, 0xfe, 0xba, 0xbe, 0x0a, 0xf7, 0xf2,
+        0x1e, 0x8a, 0x90, 0xbd, 0xad, 0x7a, 0x42, 0x5c, 0x9c, 0x31, 0xed, 0x4b, 0xb1, 0xd9, 0x02,
+        0x38, 0x91, 0x7e, 0x73, 0x90, 0xa2, 0x79, 0x35, 0x00, 0xe1, 0x68, 0x1a, 0xca, 0xea,
+    ]);
+    static ref ENCRYPTED_RTCP_PACKET_256: Bytes = Bytes::from_static(&[
+        0x81, 0xc8, 0x00, 0x0b, 0xca, 0xfe, 0xba, 0xbe, 0x8a, 0xbf, 0xaf, 0xa7, 0x80, 0x07, 0x0a,
+        0x90, 0xe8, 0x0f, 0x91, 0x61, 0xcf, 0x4a, 0xac, 0x08, 0x1e, 0xd9, 0x3a, 0xaa, 0x53, 0xc1,
+        0x5b, 0x0f, 0x3e, 0xd1, 0xa3, 0xb9, 0x84, 0x16, 0x7b, 0x03, 0x80, 0x00, 0x00, 0x01,
+    ]);
 }
 
 #[test]
@@ -303,3 +318,358 @@ fn test_decrypt_rtcp() {
 
     assert_eq!(gotten_decrypted_rtcp_packet, *DECRYPTED_RTCP_PACKET)
 }
+
+#[test]
+fn test_aead_aes_256_gcm_rtp() -> Result<()> {
+    let mut encrypt_ctx = Context::new(
+        &MASTER_KEY_256,
+        &MASTER_SALT,
+        ProtectionProfile::AeadAes256Gcm,
+        None,
+        None,
+    )?;
+    let gotten_encrypted_rtp_packet = encrypt_ctx.encrypt_rtp(&DECRYPTED_RTP_PACKET)?;
+    assert_eq!(gotten_encrypted_rtp_packet, *ENCRYPTED_RTP_PACKET_256);
+
+    let mut decrypt_ctx = Context::new(
+        &MASTER_KEY_256,
+        &MASTER_SALT,
+        ProtectionProfile::AeadAes256Gcm,
+        None,
+        None,
+    )?;
+    let gotten_decrypted_rtp_packet = decrypt_ctx.decrypt_rtp(&ENCRYPTED_RTP_PACKET_256)?;
+    assert_eq!(gotten_decrypted_rtp_packet, *DECRYPTED_RTP_PACKET);
+
+    // a context of the 128-bit profile does not verify the packet
+    let result = Context::new(
+        &MASTER_KEY,
+        &MASTER_SALT,
+        ProtectionProfile::AeadAes128Gcm,
+        None,
+        None,
+    )?
+    .decrypt_rtp(&ENCRYPTED_RTP_PACKET_256);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_aead_aes_256_gcm_rtcp() -> Result<()> {
+    let mut encrypt_ctx = Context::new(
+        &MASTER_KEY_256,
+        &MASTER_SALT,
+        ProtectionProfile::AeadAes256Gcm,
+        None,
+        None,
+    )?;
+    let gotten_encrypted_rtcp_packet = encrypt_ctx.encrypt_rtcp(&DECRYPTED_RTCP_PACKET)?;
+    assert_eq!(gotten_encrypted_rtcp_packet, *ENCRYPTED_RTCP_PACKET_256);
+
+    let mut decrypt_ctx = Context::new(
+        &MASTER_KEY_256,
+        &MASTER_SALT,
+        ProtectionProfile::AeadAes256Gcm,
+        None,
+        None,
+    )?;
+    let gotten_decrypted_rtcp_packet = decrypt_ctx.decrypt_rtcp(&ENCRYPTED_RTCP_PACKET_256)?;
+    assert_eq!(gotten_decrypted_rtcp_packet, *DECRYPTED_RTCP_PACKET);
+
+    Ok(())
+}
+
+#[test]
+fn test_aead_aes_256_gcm_key_length() {
+    let result = Context::new(
+        &MASTER_KEY,
+        &MASTER_SALT,
+        ProtectionProfile::AeadAes256Gcm,
+        None,
+        None,
+    );
+    assert_eq!(result.err(), Some(Error::SrtpMasterKeyLength(32, 16)));
+}
+
+#[test]
+fn test_decrypt_rtcp_unencrypted() -> Result<()> {
+    // DECRYPTED_RTCP_PACKET, authenticated as SRTCP index 1 with the E-flag
+    // unset
+    let authenticated = Bytes::from_static(&[
+        0x81, 0xc8, 0x00, 0x0b, 0xca, 0xfe, 0xba, 0xbe, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab,
+        0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0xab, 0x0c, 0xf6, 0x35, 0x16, 0x8f, 0x82,
+        0x42, 0xa2, 0x1b, 0x12, 0xd6, 0x64, 0xec, 0xd8, 0x62, 0xe8, 0x00, 0x00, 0x00, 0x01,
+    ]);
+
+    let mut ctx = Context::new(
+        &MASTER_KEY,
+        &MASTER_SALT,
+        ProtectionProfile::AeadAes128Gcm,
+        None,
+        None,
+    )?;
+    let gotten_decrypted_rtcp_packet = ctx.decrypt_rtcp(&authenticated)?;
+    assert_eq!(gotten_decrypted_rtcp_packet, *DECRYPTED_RTCP_PACKET);
+
+    let mut tampered = authenticated.to_vec();
+    tampered[8] ^= 0x01;
+    assert!(ctx.decrypt_rtcp(&tampered).is_err());
+
+    Ok(())
+}
+
+const MKI: [u8; 4] = [0x00, 0x00, 0x00, 0x01];
+
+#[test]
+fn test_aead_mki() -> Result<()> {
+    let mut encrypt_ctx = Context::new(
+        &MASTER_KEY,
+        &MASTER_SALT,
+        ProtectionProfile::AeadAes128Gcm,
+        None,
+        None,
+    )?
+    .with_mki(&MKI)?;
+    let mut decrypt_ctx = Context::new(
+        &MASTER_KEY,
+        &MASTER_SALT,
+        ProtectionProfile::AeadAes128Gcm,
+        None,
+        None,
+    )?
+    .with_mki(&MKI)?;
+
+    // the MKI follows the ciphertext, which embeds the AEAD auth tag
+    let encrypted_rtp_packet = [&ENCRYPTED_RTP_PACKET[..], &MKI].concat();
+    assert_eq!(
+        encrypt_ctx.encrypt_rtp(&DECRYPTED_RTP_PACKET)?,
+        encrypted_rtp_packet
+    );
+    assert_eq!(
+        decrypt_ctx.decrypt_rtp(&encrypted_rtp_packet)?,
+        *DECRYPTED_RTP_PACKET
+    );
+
+    // and the ESRTCP word
+    let encrypted_rtcp_packet = [&ENCRYPTED_RTCP_PACKET[..], &MKI].concat();
+    assert_eq!(
+        encrypt_ctx.encrypt_rtcp(&DECRYPTED_RTCP_PACKET)?,
+        encrypted_rtcp_packet
+    );
+    assert_eq!(
+        decrypt_ctx.decrypt_rtcp(&encrypted_rtcp_packet)?,
+        *DECRYPTED_RTCP_PACKET
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_mki_master_key_selection() -> Result<()> {
+    let key_len = CIPHER_CONTEXT_ALGO.key_len();
+    let salt_len = CIPHER_CONTEXT_ALGO.salt_len();
+    let other_mki = [0x00, 0x00, 0x00, 0x02];
+
+    let mut encrypt_ctx = Context::new(
+        &vec![1; key_len],
+        &vec![1; salt_len],
+        CIPHER_CONTEXT_ALGO,
+        None,
+        None,
+    )?
+    .with_mki(&MKI)?;
+    encrypt_ctx.add_master_key(&other_mki, &vec![2; key_len], &vec![2; salt_len])?;
+
+    let mut decrypt_ctx = Context::new(
+        &vec![1; key_len],
+        &vec![1; salt_len],
+        CIPHER_CONTEXT_ALGO,
+        None,
+        None,
+    )?
+    .with_mki(&MKI)?;
+
+    let first = encrypt_ctx.encrypt_rtcp(&DECRYPTED_RTCP_PACKET)?;
+    encrypt_ctx.set_send_mki(&other_mki)?;
+    assert_eq!(encrypt_ctx.send_mki(), other_mki);
+    let second = encrypt_ctx.encrypt_rtcp(&DECRYPTED_RTCP_PACKET)?;
+
+    assert_eq!(decrypt_ctx.decrypt_rtcp(&first)?, *DECRYPTED_RTCP_PACKET);
+    assert_eq!(
+        decrypt_ctx.decrypt_rtcp(&second),
+        Err(Error::ErrMkiNotFound)
+    );
+
+    decrypt_ctx.add_master_key(&other_mki, &vec![2; key_len], &vec![2; salt_len])?;
+    assert_eq!(decrypt_ctx.decrypt_rtcp(&second)?, *DECRYPTED_RTCP_PACKET);
+
+    decrypt_ctx.remove_master_key(&other_mki)?;
+    let third = encrypt_ctx.encrypt_rtcp(&DECRYPTED_RTCP_PACKET)?;
+    assert_eq!(decrypt_ctx.decrypt_rtcp(&third), Err(Error::ErrMkiNotFound));
+
+    Ok(())
+}
+
+#[test]
+fn test_mki_errors() -> Result<()> {
+    let key_len = CIPHER_CONTEXT_ALGO.key_len();
+    let salt_len = CIPHER_CONTEXT_ALGO.salt_len();
+    let new_context = || {
+        Context::new(
+            &vec![0; key_len],
+            &vec![0; salt_len],
+            CIPHER_CONTEXT_ALGO,
+            None,
+            None,
+        )
+    };
+
+    let mut ctx = new_context()?;
+    assert_eq!(ctx.send_mki(), &[] as &[u8]);
+    assert_eq!(
+        ctx.add_master_key(&MKI, &vec![0; key_len], &vec![0; salt_len]),
+        Err(Error::ErrMkiNotUsed)
+    );
+    assert_eq!(ctx.set_send_mki(&MKI), Err(Error::ErrMkiNotUsed));
+    assert_eq!(
+        new_context()?.with_mki(&[]).err(),
+        Some(Error::ErrInvalidMkiLength)
+    );
+
+    let mut ctx = new_context()?.with_mki(&MKI)?;
+    assert_eq!(
+        ctx.add_master_key(&[0x01], &vec![0; key_len], &vec![0; salt_len]),
+        Err(Error::ErrInvalidMkiLength)
+    );
+    assert_eq!(
+        ctx.add_master_key(&MKI, &vec![0; key_len], &vec![0; salt_len]),
+        Err(Error::ErrMkiAlreadyUsed)
+    );
+    assert_eq!(
+        ctx.add_master_key(&[0x00, 0x00, 0x00, 0x02], &[], &vec![0; salt_len]),
+        Err(Error::SrtpMasterKeyLength(key_len, 0))
+    );
+    assert_eq!(ctx.remove_master_key(&MKI), Err(Error::ErrRemoveSendMki));
+    assert_eq!(
+        ctx.remove_master_key(&[0x00, 0x00, 0x00, 0x02]),
+        Err(Error::ErrMkiNotFound)
+    );
+    assert_eq!(
+        ctx.set_send_mki(&[0x00, 0x00, 0x00, 0x02]),
+        Err(Error::ErrMkiNotFound)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_rotate_master_key() -> Result<()> {
+    let key_len = CIPHER_CONTEXT_ALGO.key_len();
+    let salt_len = CIPHER_CONTEXT_ALGO.salt_len();
+    let new_context = || {
+        Context::new(
+            &vec![1; key_len],
+            &vec![1; salt_len],
+            CIPHER_CONTEXT_ALGO,
+            None,
+            None,
+        )
+    };
+
+    let mut encrypt_ctx = new_context()?;
+    let mut decrypt_ctx = new_context()?;
+
+    let old: Vec<Bytes> = (0..3)
+        .map(|_| encrypt_ctx.encrypt_rtcp(&DECRYPTED_RTCP_PACKET))
+        .collect::<Result<_>>()?;
+
+    encrypt_ctx.rotate_master_key(&[], &vec![2; key_len], &vec![2; salt_len], 0)?;
+    decrypt_ctx.rotate_master_key(&[], &vec![2; key_len], &vec![2; salt_len], 2)?;
+
+    // the new key protects the packets sent and decrypts those received
+    let new = encrypt_ctx.encrypt_rtcp(&DECRYPTED_RTCP_PACKET)?;
+    assert_eq!(decrypt_ctx.decrypt_rtcp(&new)?, *DECRYPTED_RTCP_PACKET);
+
+    // the old key decrypts packets until the overlap window is over
+    assert_eq!(decrypt_ctx.decrypt_rtcp(&old[0])?, *DECRYPTED_RTCP_PACKET);
+    assert_eq!(
+        decrypt_ctx.decrypt_rtcp(&old[1]),
+        Err(Error::RtcpFailedToVerifyAuthTag)
+    );
+    assert_eq!(
+        decrypt_ctx.decrypt_rtcp(&old[2]),
+        Err(Error::RtcpFailedToVerifyAuthTag)
+    );
+
+    let new = encrypt_ctx.encrypt_rtcp(&DECRYPTED_RTCP_PACKET)?;
+    assert_eq!(decrypt_ctx.decrypt_rtcp(&new)?, *DECRYPTED_RTCP_PACKET);
+
+    Ok(())
+}
+
+#[test]
+fn test_rotate_master_key_mki() -> Result<()> {
+    let key_len = CIPHER_CONTEXT_ALGO.key_len();
+    let salt_len = CIPHER_CONTEXT_ALGO.salt_len();
+    let new_context = || {
+        Context::new(
+            &vec![1; key_len],
+            &vec![1; salt_len],
+            CIPHER_CONTEXT_ALGO,
+            None,
+            None,
+        )?
+        .with_mki(&[1])
+    };
+
+    let mut encrypt_ctx = new_context()?;
+    let mut decrypt_ctx = new_context()?;
+    decrypt_ctx.add_master_key(&[3], &vec![3; key_len], &vec![3; salt_len])?;
+
+    let old: Vec<Bytes> = (0..3)
+        .map(|_| encrypt_ctx.encrypt_rtcp(&DECRYPTED_RTCP_PACKET))
+        .collect::<Result<_>>()?;
+
+    encrypt_ctx.rotate_master_key(&[2], &vec![2; key_len], &vec![2; salt_len], 0)?;
+    decrypt_ctx.rotate_master_key(&[2], &vec![2; key_len], &vec![2; salt_len], 2)?;
+    assert_eq!(encrypt_ctx.send_mki(), &[2]);
+    assert_eq!(decrypt_ctx.send_mki(), &[2]);
+
+    // the new key protects the packets sent under its MKI
+    let new = encrypt_ctx.encrypt_rtcp(&DECRYPTED_RTCP_PACKET)?;
+    assert_eq!(decrypt_ctx.decrypt_rtcp(&new)?, *DECRYPTED_RTCP_PACKET);
+
+    // the old key decrypts the packets of its MKI until the overlap window is over
+    assert_eq!(decrypt_ctx.decrypt_rtcp(&old[0])?, *DECRYPTED_RTCP_PACKET);
+    assert_eq!(
+        decrypt_ctx.decrypt_rtcp(&old[1]),
+        Err(Error::ErrMkiNotFound)
+    );
+    assert_eq!(
+        decrypt_ctx.decrypt_rtcp(&old[2]),
+        Err(Error::ErrMkiNotFound)
+    );
+
+    assert_eq!(
+        decrypt_ctx.rotate_master_key(&[3], &vec![4; key_len], &vec![4; salt_len], 0),
+        Err(Error::ErrMkiAlreadyUsed)
+    );
+    assert_eq!(
+        decrypt_ctx.rotate_master_key(&[4, 4], &vec![4; key_len], &vec![4; salt_len], 0),
+        Err(Error::ErrInvalidMkiLength)
+    );
+    let mut ctx = Context::new(
+        &vec![1; key_len],
+        &vec![1; salt_len],
+        CIPHER_CONTEXT_ALGO,
+        None,
+        None,
+    )?;
+    assert_eq!(
+        ctx.rotate_master_key(&[4], &vec![4; key_len], &vec![4; salt_len], 0),
+        Err(Error::ErrMkiNotUsed)
+    );
+
+    Ok(())
+}
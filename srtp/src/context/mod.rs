@@ -5,14 +5,17 @@ mod srtcp_test;
 #[cfg(test)]
 mod srtp_test;
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
+use bytes::{Bytes, BytesMut};
 use util::replay_detector::*;
 
 use crate::cipher::cipher_aead_aes_gcm::*;
 use crate::cipher::cipher_aes_cm_hmac_sha1::*;
 use crate::cipher::*;
 use crate::error::{Error, Result};
+use crate::key_derivation::*;
 use crate::option::*;
 use crate::protection_profile::*;
 
@@ -29,6 +32,7 @@ pub(crate) struct SrtpSsrcState {
     rollover_has_processed: bool,
     last_sequence_number: u16,
     replay_detector: Option<Box<dyn ReplayDetector + Send + 'static>>,
+    cipher: Option<DerivedCipher>,
 }
 
 /// Encrypt/Decrypt state for a single SRTCP SSRC
@@ -37,6 +41,33 @@ pub(crate) struct SrtcpSsrcState {
     srtcp_index: usize,
     ssrc: u32,
     replay_detector: Option<Box<dyn ReplayDetector + Send + 'static>>,
+    cipher: Option<DerivedCipher>,
+}
+
+/// A master key of a context, with the cipher of the session keys derived
+/// once from it.
+struct MasterKey {
+    key: Vec<u8>,
+    salt: Vec<u8>,
+    cipher: Box<dyn Cipher + Send>,
+}
+
+/// A master key replaced by rotate_master_key, which keeps decrypting the
+/// packets it protects during the overlap window of the rotation.
+struct PreviousMasterKey {
+    mki: Vec<u8>,
+    master_key: MasterKey,
+    /// The count of packets to be received before the key is dropped.
+    remaining: usize,
+    cipher: Option<DerivedCipher>,
+}
+
+/// The cipher of the session keys derived from the master key of an MKI for
+/// an "index DIV kdr", if the key derivation rate is not 0.
+struct DerivedCipher {
+    mki: Vec<u8>,
+    index_over_kdr: u64,
+    cipher: Box<dyn Cipher + Send>,
 }
 
 impl SrtpSsrcState {
@@ -102,13 +133,78 @@ impl SrtpSsrcState {
 /// Context can only be used for one-way operations
 /// it must either used ONLY for encryption or ONLY for decryption
 pub struct Context {
-    cipher: Box<dyn Cipher + Send>,
+    /// The master keys by MKI, of which there is a single one, of an empty
+    /// MKI, if MKIs are not used.
+    master_keys: HashMap<Vec<u8>, MasterKey>,
+    /// The MKI of the master key protecting the packets sent.
+    send_mki: Vec<u8>,
+    previous_master_key: Option<PreviousMasterKey>,
 
     srtp_ssrc_states: HashMap<u32, SrtpSsrcState>,
     srtcp_ssrc_states: HashMap<u32, SrtcpSsrcState>,
 
     new_srtp_replay_detector: ContextOption,
     new_srtcp_replay_detector: ContextOption,
+
+    profile: ProtectionProfile,
+    key_derivation_rate: u64,
+}
+
+/// new_cipher creates the cipher of a profile, with the session keys derived
+/// for the given "index DIV kdr".
+fn new_cipher(
+    profile: ProtectionProfile,
+    master_key: &[u8],
+    master_salt: &[u8],
+    index_over_kdr: u64,
+) -> Result<Box<dyn Cipher + Send>> {
+    Ok(match profile {
+        ProtectionProfile::Aes128CmHmacSha1_80 => Box::new(CipherAesCmHmacSha1::new(
+            master_key,
+            master_salt,
+            index_over_kdr,
+        )?),
+
+        ProtectionProfile::AeadAes128Gcm => Box::new(CipherAeadAesGcm::<aes_gcm::Aes128Gcm>::new(
+            master_key,
+            master_salt,
+            index_over_kdr,
+        )?),
+
+        ProtectionProfile::AeadAes256Gcm => Box::new(CipherAeadAesGcm::<aes_gcm::Aes256Gcm>::new(
+            master_key,
+            master_salt,
+            index_over_kdr,
+        )?),
+
+        #[cfg(feature = "insecure-null-cipher")]
+        ProtectionProfile::NullHmacSha1_80 => Box::new(CipherNullHmacSha1::new(
+            master_key,
+            master_salt,
+            index_over_kdr,
+        )?),
+    })
+}
+
+/// derive_cipher returns the cipher of a SSRC state for the given MKI and
+/// "index DIV kdr", deriving the session keys again if they changed.
+fn derive_cipher<'a>(
+    state: &'a mut Option<DerivedCipher>,
+    profile: ProtectionProfile,
+    mki: &[u8],
+    master_key: &MasterKey,
+    index_over_kdr: u64,
+) -> Result<&'a mut (dyn Cipher + Send)> {
+    let derived = match state.take() {
+        Some(derived) if derived.mki == mki && derived.index_over_kdr == index_over_kdr => derived,
+        _ => DerivedCipher {
+            mki: mki.to_vec(),
+            index_over_kdr,
+            cipher: new_cipher(profile, &master_key.key, &master_key.salt, index_over_kdr)?,
+        },
+    };
+
+    Ok(state.insert(derived).cipher.as_mut())
 }
 
 impl Context {
@@ -120,24 +216,7 @@ impl Context {
         srtp_ctx_opt: Option<ContextOption>,
         srtcp_ctx_opt: Option<ContextOption>,
     ) -> Result<Context> {
-        let key_len = profile.key_len();
-        let salt_len = profile.salt_len();
-
-        if master_key.len() != key_len {
-            return Err(Error::SrtpMasterKeyLength(key_len, master_key.len()));
-        } else if master_salt.len() != salt_len {
-            return Err(Error::SrtpSaltLength(salt_len, master_salt.len()));
-        }
-
-        let cipher: Box<dyn Cipher + Send> = match profile {
-            ProtectionProfile::Aes128CmHmacSha1_80 => {
-                Box::new(CipherAesCmHmacSha1::new(master_key, master_salt)?)
-            }
-
-            ProtectionProfile::AeadAes128Gcm => {
-                Box::new(CipherAeadAesGcm::new(master_key, master_salt)?)
-            }
-        };
+        let master_key = Self::new_master_key(profile, master_key, master_salt)?;
 
         let srtp_ctx_opt = if let Some(ctx_opt) = srtp_ctx_opt {
             ctx_opt
@@ -152,14 +231,323 @@ impl Context {
         };
 
         Ok(Context {
-            cipher,
+            master_keys: HashMap::from([(vec![], master_key)]),
+            send_mki: vec![],
+            previous_master_key: None,
             srtp_ssrc_states: HashMap::new(),
             srtcp_ssrc_states: HashMap::new(),
             new_srtp_replay_detector: srtp_ctx_opt,
             new_srtcp_replay_detector: srtcp_ctx_opt,
+            profile,
+            key_derivation_rate: 0,
+        })
+    }
+
+    fn new_master_key(
+        profile: ProtectionProfile,
+        master_key: &[u8],
+        master_salt: &[u8],
+    ) -> Result<MasterKey> {
+        let key_len = profile.key_len();
+        let salt_len = profile.salt_len();
+
+        if master_key.len() != key_len {
+            return Err(Error::SrtpMasterKeyLength(key_len, master_key.len()));
+        } else if master_salt.len() != salt_len {
+            return Err(Error::SrtpSaltLength(salt_len, master_salt.len()));
+        }
+
+        Ok(MasterKey {
+            key: master_key.to_vec(),
+            salt: master_salt.to_vec(),
+            cipher: new_cipher(profile, master_key, master_salt, 0)?,
         })
     }
 
+    /// with_key_derivation_rate sets the key derivation rate of the context,
+    /// which is 0 by default. Session keys are then derived again every `kdr`
+    /// packets of each SSRC from the master key, which must be 0 or a power
+    /// of 2 up to 2^24, as described in https://tools.ietf.org/html/rfc3711#section-4.3.1
+    pub fn with_key_derivation_rate(mut self, kdr: u64) -> Result<Context> {
+        if kdr != 0 && (!kdr.is_power_of_two() || kdr > MAX_KEY_DERIVATION_RATE) {
+            return Err(Error::ErrInvalidKeyDerivationRate(kdr));
+        }
+
+        self.key_derivation_rate = kdr;
+        Ok(self)
+    }
+
+    /// with_mki sets the MKI (Master Key Identifier) of the master key the
+    /// context was created with. The MKI is then appended to the packets
+    /// protected by the context, and packets are decrypted with the master
+    /// key of their MKI, as described in https://tools.ietf.org/html/rfc3711#section-3.1
+    pub fn with_mki(mut self, mki: &[u8]) -> Result<Context> {
+        if mki.is_empty() || (!self.send_mki.is_empty() && mki.len() != self.send_mki.len()) {
+            return Err(Error::ErrInvalidMkiLength);
+        } else if mki != self.send_mki && self.master_keys.contains_key(mki) {
+            return Err(Error::ErrMkiAlreadyUsed);
+        }
+
+        if let Some(master_key) = self.master_keys.remove(&self.send_mki) {
+            self.master_keys.insert(mki.to_vec(), master_key);
+        }
+        self.send_mki = mki.to_vec();
+        Ok(self)
+    }
+
+    /// add_master_key adds a master key of another MKI to a context using MKIs,
+    /// for the packets protected by the key to be decrypted, or to protect the
+    /// packets sent once set_send_mki is called.
+    pub fn add_master_key(
+        &mut self,
+        mki: &[u8],
+        master_key: &[u8],
+        master_salt: &[u8],
+    ) -> Result<()> {
+        if self.send_mki.is_empty() {
+            return Err(Error::ErrMkiNotUsed);
+        } else if mki.len() != self.send_mki.len() {
+            return Err(Error::ErrInvalidMkiLength);
+        } else if self.master_keys.contains_key(mki) {
+            return Err(Error::ErrMkiAlreadyUsed);
+        }
+
+        let master_key = Self::new_master_key(self.profile, master_key, master_salt)?;
+        self.master_keys.insert(mki.to_vec(), master_key);
+        Ok(())
+    }
+
+    /// remove_master_key removes the master key of an MKI, after which the
+    /// packets it protects are rejected. The master key protecting the packets
+    /// sent cannot be removed.
+    pub fn remove_master_key(&mut self, mki: &[u8]) -> Result<()> {
+        if mki == self.send_mki {
+            return Err(Error::ErrRemoveSendMki);
+        } else if self.master_keys.remove(mki).is_none() {
+            return Err(Error::ErrMkiNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// set_send_mki sets the MKI of the master key protecting the packets sent.
+    pub fn set_send_mki(&mut self, mki: &[u8]) -> Result<()> {
+        if self.send_mki.is_empty() {
+            return Err(Error::ErrMkiNotUsed);
+        } else if !self.master_keys.contains_key(mki) {
+            return Err(Error::ErrMkiNotFound);
+        }
+
+        self.send_mki = mki.to_vec();
+        Ok(())
+    }
+
+    /// rotate_master_key replaces the master key protecting the packets sent,
+    /// such as after a DTLS renegotiation, without interrupting the streams:
+    /// the replaced key keeps decrypting the packets it protects among the
+    /// next `overlap` packets received, to let in those sent before the peer
+    /// switched keys. A new rotation ends the overlap window of the previous one.
+    /// The new key takes the MKI `mki` if it is not empty, and the MKI of the
+    /// replaced key otherwise.
+    pub fn rotate_master_key(
+        &mut self,
+        mki: &[u8],
+        master_key: &[u8],
+        master_salt: &[u8],
+        overlap: usize,
+    ) -> Result<()> {
+        if !mki.is_empty() && mki != self.send_mki {
+            if self.send_mki.is_empty() {
+                return Err(Error::ErrMkiNotUsed);
+            } else if mki.len() != self.send_mki.len() {
+                return Err(Error::ErrInvalidMkiLength);
+            } else if self.master_keys.contains_key(mki) {
+                return Err(Error::ErrMkiAlreadyUsed);
+            }
+        }
+
+        let master_key = Self::new_master_key(self.profile, master_key, master_salt)?;
+        let replaced_mki = self.send_mki.clone();
+        if !mki.is_empty() {
+            self.send_mki = mki.to_vec();
+        }
+        let previous = self.master_keys.remove(&replaced_mki);
+        self.master_keys.insert(self.send_mki.clone(), master_key);
+
+        self.previous_master_key = match previous {
+            Some(master_key) if overlap > 0 => Some(PreviousMasterKey {
+                mki: replaced_mki.clone(),
+                master_key,
+                remaining: overlap,
+                cipher: None,
+            }),
+            _ => None,
+        };
+
+        // the session keys derived from the replaced key are not valid anymore
+        let is_replaced = |cipher: &Option<DerivedCipher>| matches!(cipher, Some(derived) if derived.mki == replaced_mki);
+        for state in self.srtp_ssrc_states.values_mut() {
+            if is_replaced(&state.cipher) {
+                state.cipher = None;
+            }
+        }
+        for state in self.srtcp_ssrc_states.values_mut() {
+            if is_replaced(&state.cipher) {
+                state.cipher = None;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// count_overlap counts a packet received against the overlap window of
+    /// the last rotation, dropping the replaced master key once it is over.
+    fn count_overlap(&mut self) {
+        if let Some(previous) = &mut self.previous_master_key {
+            if previous.remaining == 0 {
+                self.previous_master_key = None;
+            } else {
+                previous.remaining -= 1;
+            }
+        }
+    }
+
+    /// previous_cipher returns the cipher of the master key replaced by the last
+    /// rotation for the given MKI and index, if its overlap window is not over.
+    fn previous_cipher(
+        &mut self,
+        mki: &[u8],
+        index: u64,
+    ) -> Result<Option<&mut (dyn Cipher + Send)>> {
+        let Some(previous) = &mut self.previous_master_key else {
+            return Ok(None);
+        };
+        if previous.mki != mki {
+            return Ok(None);
+        } else if self.key_derivation_rate == 0 {
+            return Ok(Some(previous.master_key.cipher.as_mut()));
+        }
+
+        let r = index_over_kdr(index, self.key_derivation_rate);
+        let cipher = derive_cipher(
+            &mut previous.cipher,
+            self.profile,
+            mki,
+            &previous.master_key,
+            r,
+        )?;
+        Ok(Some(cipher))
+    }
+
+    /// send_mki returns the MKI of the master key protecting the packets sent,
+    /// which is empty if MKIs are not used.
+    pub fn send_mki(&self) -> &[u8] {
+        &self.send_mki
+    }
+
+    /// mki_tag_len returns the length of the authentication tag following the
+    /// MKI in protected packets. AEAD ciphers place the MKI after the
+    /// ciphertext, which embeds their tag.
+    fn mki_tag_len(&self) -> usize {
+        self.profile.auth_tag_len() - self.profile.aead_auth_tag_len()
+    }
+
+    /// remove_mki splits a protected packet into its MKI and the packet
+    /// without the MKI, if MKIs are used.
+    fn remove_mki<'a>(&self, protected: &'a [u8]) -> Result<(&'a [u8], Cow<'a, [u8]>)> {
+        let mki_len = self.send_mki.len();
+        if mki_len == 0 {
+            return Ok((&[], Cow::Borrowed(protected)));
+        }
+
+        let tag_len = self.mki_tag_len();
+        if protected.len() < mki_len + tag_len {
+            return Err(Error::ErrMkiNotFound);
+        }
+
+        let mki_offset = protected.len() - tag_len - mki_len;
+        let mut packet = Vec::with_capacity(protected.len() - mki_len);
+        packet.extend_from_slice(&protected[..mki_offset]);
+        packet.extend_from_slice(&protected[mki_offset + mki_len..]);
+
+        Ok((
+            &protected[mki_offset..mki_offset + mki_len],
+            Cow::Owned(packet),
+        ))
+    }
+
+    /// insert_mki inserts the MKI of the master key protecting the packets sent
+    /// into a protected packet, if MKIs are used.
+    fn insert_mki(&self, protected: Bytes) -> Bytes {
+        if self.send_mki.is_empty() {
+            return protected;
+        }
+
+        let mki_offset = protected.len() - self.mki_tag_len();
+        let mut packet = BytesMut::with_capacity(protected.len() + self.send_mki.len());
+        packet.extend_from_slice(&protected[..mki_offset]);
+        packet.extend_from_slice(&self.send_mki);
+        packet.extend_from_slice(&protected[mki_offset..]);
+
+        packet.freeze()
+    }
+
+    /// srtp_cipher returns the cipher protecting the SRTP packet of the given
+    /// SSRC, MKI and index. The MKI of a packet sent is the send MKI, selected
+    /// by None so that it is borrowed rather than copied for every packet.
+    fn srtp_cipher(
+        &mut self,
+        ssrc: u32,
+        mki: Option<&[u8]>,
+        index: u64,
+    ) -> Result<&mut (dyn Cipher + Send)> {
+        let mki = mki.unwrap_or(&self.send_mki);
+        let master_key = self.master_keys.get_mut(mki).ok_or(Error::ErrMkiNotFound)?;
+        if self.key_derivation_rate == 0 {
+            return Ok(master_key.cipher.as_mut());
+        }
+
+        let r = index_over_kdr(index, self.key_derivation_rate);
+        let state = self
+            .srtp_ssrc_states
+            .entry(ssrc)
+            .or_insert_with(|| SrtpSsrcState {
+                ssrc,
+                replay_detector: Some((self.new_srtp_replay_detector)()),
+                ..Default::default()
+            });
+
+        derive_cipher(&mut state.cipher, self.profile, mki, master_key, r)
+    }
+
+    /// srtcp_cipher returns the cipher protecting the SRTCP packet of the given
+    /// SSRC, MKI and index. The MKI of a packet sent is the send MKI, selected
+    /// by None so that it is borrowed rather than copied for every packet.
+    fn srtcp_cipher(
+        &mut self,
+        ssrc: u32,
+        mki: Option<&[u8]>,
+        index: usize,
+    ) -> Result<&mut (dyn Cipher + Send)> {
+        let mki = mki.unwrap_or(&self.send_mki);
+        let master_key = self.master_keys.get_mut(mki).ok_or(Error::ErrMkiNotFound)?;
+        if self.key_derivation_rate == 0 {
+            return Ok(master_key.cipher.as_mut());
+        }
+
+        let r = index_over_kdr(index as u64, self.key_derivation_rate);
+        let state = self
+            .srtcp_ssrc_states
+            .entry(ssrc)
+            .or_insert_with(|| SrtcpSsrcState {
+                ssrc,
+                replay_detector: Some((self.new_srtcp_replay_detector)()),
+                ..Default::default()
+            });
+
+        derive_cipher(&mut state.cipher, self.profile, mki, master_key, r)
+    }
+
     fn get_srtp_ssrc_state(&mut self, ssrc: u32) -> &mut SrtpSsrcState {
         let s = SrtpSsrcState {
             ssrc,
@@ -179,13 +567,17 @@ impl Context {
         self.srtcp_ssrc_states.entry(ssrc).or_insert(s)
     }
 
-    /// roc returns SRTP rollover counter value of specified SSRC.
-    fn get_roc(&self, ssrc: u32) -> Option<u32> {
+    /// get_roc returns SRTP rollover counter value of specified SSRC, if a
+    /// packet of the SSRC was processed or its counter was set.
+    pub fn get_roc(&self, ssrc: u32) -> Option<u32> {
         self.srtp_ssrc_states.get(&ssrc).map(|s| s.rollover_counter)
     }
 
-    /// set_roc sets SRTP rollover counter value of specified SSRC.
-    fn set_roc(&mut self, ssrc: u32, roc: u32) {
+    /// set_roc sets SRTP rollover counter value of specified SSRC, to keep it
+    /// in sync with the sender of a stream joined mid-session, whose sequence
+    /// numbers may have rolled over already. Set before the first packet of the
+    /// SSRC is processed, it is the counter of that packet.
+    pub fn set_roc(&mut self, ssrc: u32, roc: u32) {
         self.get_srtp_ssrc_state(ssrc).rollover_counter = roc;
     }
 
@@ -1,25 +1,54 @@
 use bytes::Bytes;
+use rtcp::header::{HEADER_LENGTH, SSRC_LENGTH};
 use util::marshal::*;
 
 use super::*;
 use crate::error::Result;
+use crate::key_derivation::SRTCP_INDEX_SIZE;
 
 impl Context {
-    /// DecryptRTCP decrypts a RTCP packet with an encrypted payload
+    /// DecryptRTCP decrypts a RTCP packet with an encrypted payload.
+    /// Packets whose E-flag is not set are only authenticated.
     pub fn decrypt_rtcp(&mut self, encrypted: &[u8]) -> Result<Bytes> {
         let mut buf = encrypted;
         rtcp::header::Header::unmarshal(&mut buf)?;
 
-        let index = self.cipher.get_rtcp_index(encrypted);
+        let min_len = HEADER_LENGTH
+            + SSRC_LENGTH
+            + SRTCP_INDEX_SIZE
+            + self.send_mki.len()
+            + self.profile.auth_tag_len();
+        if encrypted.len() < min_len {
+            return Err(Error::SrtcpTooSmall(encrypted.len(), min_len));
+        }
+
+        let (mki, encrypted) = self.remove_mki(encrypted)?;
+        self.count_overlap();
+        // the SRTCP index is at the same place for every master key
+        let index = self.master_keys[&self.send_mki]
+            .cipher
+            .get_rtcp_index(&encrypted);
         let ssrc = u32::from_be_bytes([encrypted[4], encrypted[5], encrypted[6], encrypted[7]]);
 
         if let Some(replay_detector) = &mut self.get_srtcp_ssrc_state(ssrc).replay_detector {
             if !replay_detector.check(index as u64) {
+                if replay_detector.is_too_old(index as u64) {
+                    return Err(Error::SrtcpSsrcTooOld(ssrc, index));
+                }
                 return Err(Error::SrtcpSsrcDuplicated(ssrc, index));
             }
         }
 
-        let dst = self.cipher.decrypt_rtcp(encrypted, index, ssrc)?;
+        let dst = match self
+            .srtcp_cipher(ssrc, Some(mki), index)
+            .and_then(|cipher| cipher.decrypt_rtcp(&encrypted, index, ssrc))
+        {
+            Ok(dst) => dst,
+            Err(err) => match self.previous_cipher(mki, index as u64)? {
+                Some(cipher) => cipher.decrypt_rtcp(&encrypted, index, ssrc)?,
+                None => return Err(err),
+            },
+        };
 
         if let Some(replay_detector) = &mut self.get_srtcp_ssrc_state(ssrc).replay_detector {
             replay_detector.accept();
@@ -33,6 +62,9 @@ impl Context {
     pub fn encrypt_rtcp(&mut self, decrypted: &[u8]) -> Result<Bytes> {
         let mut buf = decrypted;
         rtcp::header::Header::unmarshal(&mut buf)?;
+        if decrypted.len() < HEADER_LENGTH + SSRC_LENGTH {
+            return Err(Error::ErrTooShortRtcp);
+        }
 
         let ssrc = u32::from_be_bytes([decrypted[4], decrypted[5], decrypted[6], decrypted[7]]);
 
@@ -45,6 +77,10 @@ impl Context {
             state.srtcp_index
         };
 
-        self.cipher.encrypt_rtcp(decrypted, index, ssrc)
+        let dst = self
+            .srtcp_cipher(ssrc, None, index)?
+            .encrypt_rtcp(decrypted, index, ssrc)?;
+
+        Ok(self.insert_mki(dst))
     }
 }
@@ -1,5 +1,6 @@
 use bytes::{Buf, Bytes, BytesMut};
 use lazy_static::lazy_static;
+use rtcp::header::{HEADER_LENGTH, SSRC_LENGTH};
 
 use super::*;
 use crate::key_derivation::*;
@@ -255,3 +256,230 @@ fn test_encrypt_rtcp_separation() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_rtcp_unencrypted() -> Result<()> {
+    // RTCP_TEST_CASES[0].decrypted, authenticated as SRTCP index 2 with the
+    // E-flag unset
+    let authenticated = Bytes::from_static(&[
+        0x80, 0xc8, 0x00, 0x06, 0x66, 0xef, 0x91, 0xff, 0xdf, 0x48, 0x80, 0xdd, 0x61, 0xa6, 0x2e,
+        0xd3, 0xd8, 0xbc, 0xde, 0xbe, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x16, 0x04, 0x81, 0xca,
+        0x00, 0x06, 0x66, 0xef, 0x91, 0xff, 0x01, 0x10, 0x52, 0x6e, 0x54, 0x35, 0x43, 0x6d, 0x4a,
+        0x68, 0x7a, 0x79, 0x65, 0x74, 0x41, 0x78, 0x77, 0x2b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+        0xaf, 0x29, 0xc9, 0x59, 0x63, 0xb1, 0x7e, 0xca, 0xdb, 0x2c,
+    ]);
+
+    let mut decrypt_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        None,
+        Some(srtcp_replay_protection(10)),
+    )?;
+
+    let decrypt_result = decrypt_context.decrypt_rtcp(&authenticated)?;
+    assert_eq!(
+        decrypt_result, RTCP_TEST_CASES[0].decrypted,
+        "RTCP failed to authenticate"
+    );
+
+    // The payload of unencrypted packets is authenticated nonetheless
+    let mut rtcp_packet = BytesMut::from(&authenticated[..]);
+    rtcp_packet[HEADER_LENGTH + SSRC_LENGTH] ^= 0x01;
+    let mut decrypt_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        None,
+        None,
+    )?;
+    let result = decrypt_context.decrypt_rtcp(&rtcp_packet.freeze());
+    assert_eq!(result, Err(Error::RtcpFailedToVerifyAuthTag));
+
+    Ok(())
+}
+
+#[test]
+fn test_rtcp_too_short() -> Result<()> {
+    let mut context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        None,
+        None,
+    )?;
+
+    let encrypted = RTCP_TEST_CASES[0]
+        .encrypted
+        .slice(..HEADER_LENGTH + SSRC_LENGTH + 13);
+    let result = context.decrypt_rtcp(&encrypted);
+    assert_eq!(result, Err(Error::SrtcpTooSmall(21, 22)));
+
+    let result = context.encrypt_rtcp(&RTCP_TEST_CASES[0].decrypted[..HEADER_LENGTH]);
+    assert_eq!(result, Err(Error::ErrTooShortRtcp));
+
+    Ok(())
+}
+
+#[test]
+fn test_rtcp_replay_detector_too_old() -> Result<()> {
+    let mut encrypt_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        None,
+        None,
+    )?;
+    let mut decrypt_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        None,
+        Some(srtcp_replay_protection(10)),
+    )?;
+
+    let encrypted = (0..12)
+        .map(|_| encrypt_context.encrypt_rtcp(&RTCP_TEST_CASES[0].decrypted))
+        .collect::<Result<Vec<_>>>()?;
+
+    decrypt_context.decrypt_rtcp(&encrypted[0])?;
+    decrypt_context.decrypt_rtcp(&encrypted[11])?;
+
+    let result = decrypt_context.decrypt_rtcp(&encrypted[0]);
+    assert_eq!(result, Err(Error::SrtcpSsrcTooOld(0x66ef91ff, 1)));
+    let result = decrypt_context.decrypt_rtcp(&encrypted[11]);
+    assert_eq!(result, Err(Error::SrtcpSsrcDuplicated(0x66ef91ff, 12)));
+    decrypt_context.decrypt_rtcp(&encrypted[5])?;
+
+    Ok(())
+}
+
+#[test]
+fn test_rtcp_key_derivation_rate() -> Result<()> {
+    let mut encrypt_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        None,
+        None,
+    )?
+    .with_key_derivation_rate(4)?;
+    let mut decrypt_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        None,
+        None,
+    )?
+    .with_key_derivation_rate(4)?;
+    let mut once_derived_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        None,
+        None,
+    )?;
+
+    // SRTCP indexes start at 1
+    for index in 1..12 {
+        let encrypted = encrypt_context.encrypt_rtcp(&RTCP_TEST_CASES[0].decrypted)?;
+        assert_eq!(
+            decrypt_context.decrypt_rtcp(&encrypted)?,
+            RTCP_TEST_CASES[0].decrypted,
+            "RTCP packet with index invalid decryption: {index}"
+        );
+
+        let result = once_derived_context.decrypt_rtcp(&encrypted);
+        assert_eq!(
+            result.is_ok(),
+            index < 4,
+            "RTCP packet with index decrypted with the session keys of index 0: {index}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rtcp_mki() -> Result<()> {
+    let mki = [0xde, 0xad, 0xbe, 0xef];
+    let auth_tag_len = ProtectionProfile::Aes128CmHmacSha1_80.auth_tag_len();
+
+    let mut encrypt_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        None,
+        None,
+    )?
+    .with_mki(&mki)?;
+    let mut decrypt_context = Context::new(
+        &RTCP_TEST_MASTER_KEY,
+        &RTCP_TEST_MASTER_SALT,
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        None,
+        None,
+    )?
+    .with_mki(&mki)?;
+
+    // the MKI is placed between the ESRTCP word and the auth tag, which does
+    // not cover it
+    let test_case = &RTCP_TEST_CASES[0];
+    let tag_offset = test_case.encrypted.len() - auth_tag_len;
+    let encrypted = [
+        &test_case.encrypted[..tag_offset],
+        &mki,
+        &test_case.encrypted[tag_offset..],
+    ]
+    .concat();
+
+    assert_eq!(
+        encrypt_context.encrypt_rtcp(&test_case.decrypted)?,
+        encrypted
+    );
+    assert_eq!(
+        decrypt_context.decrypt_rtcp(&encrypted)?,
+        test_case.decrypted
+    );
+
+    // packets without the MKI are rejected
+    assert!(decrypt_context.decrypt_rtcp(&test_case.encrypted).is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "insecure-null-cipher")]
+#[test]
+fn test_rtcp_null_cipher() -> Result<()> {
+    let new_context = || {
+        Context::new(
+            &RTCP_TEST_MASTER_KEY,
+            &RTCP_TEST_MASTER_SALT,
+            ProtectionProfile::NullHmacSha1_80,
+            None,
+            None,
+        )
+    };
+    let decrypted = &RTCP_TEST_CASES[0].decrypted;
+
+    // the packet is left unencrypted, followed by the ESRTCP word with the
+    // E-flag unset, and the auth tag
+    let encrypted = new_context()?.encrypt_rtcp(decrypted)?;
+    assert_eq!(&encrypted[..decrypted.len()], &decrypted[..]);
+    assert_eq!(
+        &encrypted[decrypted.len()..decrypted.len() + SRTCP_INDEX_SIZE],
+        &[0x00, 0x00, 0x00, 0x01]
+    );
+
+    let mut decrypt_context = new_context()?;
+    assert_eq!(decrypt_context.decrypt_rtcp(&encrypted)?, decrypted);
+
+    let mut tampered = encrypted.to_vec();
+    tampered[HEADER_LENGTH + SSRC_LENGTH] ^= 0x01;
+    assert_eq!(
+        decrypt_context.decrypt_rtcp(&tampered),
+        Err(Error::RtcpFailedToVerifyAuthTag)
+    );
+
+    Ok(())
+}
@@ -10,10 +10,18 @@ impl Context {
         encrypted: &[u8],
         header: &rtp::header::Header,
     ) -> Result<Bytes> {
+        let (mki, encrypted) = self.remove_mki(encrypted)?;
+        self.count_overlap();
+
         let roc = {
             let state = self.get_srtp_ssrc_state(header.ssrc);
+            let roc = state.next_rollover_count(header.sequence_number);
             if let Some(replay_detector) = &mut state.replay_detector {
-                if !replay_detector.check(header.sequence_number as u64) {
+                let index = ((roc as u64) << 16) | header.sequence_number as u64;
+                if !replay_detector.check(index) {
+                    if replay_detector.is_too_old(index) {
+                        return Err(Error::SrtpSsrcTooOld(header.ssrc, header.sequence_number));
+                    }
                     return Err(Error::SrtpSsrcDuplicated(
                         header.ssrc,
                         header.sequence_number,
@@ -21,10 +29,20 @@ impl Context {
                 }
             }
 
-            state.next_rollover_count(header.sequence_number)
+            roc
         };
 
-        let dst = self.cipher.decrypt_rtp(encrypted, header, roc)?;
+        let index = ((roc as u64) << 16) | header.sequence_number as u64;
+        let dst = match self
+            .srtp_cipher(header.ssrc, Some(mki), index)
+            .and_then(|cipher| cipher.decrypt_rtp(&encrypted, header, roc))
+        {
+            Ok(dst) => dst,
+            Err(err) => match self.previous_cipher(mki, index)? {
+                Some(cipher) => cipher.decrypt_rtp(&encrypted, header, roc)?,
+                None => return Err(err),
+            },
+        };
         {
             let state = self.get_srtp_ssrc_state(header.ssrc);
             if let Some(replay_detector) = &mut state.replay_detector {
@@ -52,7 +70,11 @@ impl Context {
             .get_srtp_ssrc_state(header.ssrc)
             .next_rollover_count(header.sequence_number);
 
-        let dst = self.cipher.encrypt_rtp(payload, header, roc)?;
+        let index = ((roc as u64) << 16) | header.sequence_number as u64;
+        let dst = self
+            .srtp_cipher(header.ssrc, None, index)?
+            .encrypt_rtp(payload, header, roc)?;
+        let dst = self.insert_mki(dst);
 
         self.get_srtp_ssrc_state(header.ssrc)
             .update_rollover_count(header.sequence_number);
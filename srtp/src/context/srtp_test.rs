@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use bytes::Bytes;
 use lazy_static::lazy_static;
 use util::marshal::*;
@@ -58,6 +60,10 @@ lazy_static! {
 }
 
 fn build_test_context() -> Result<Context> {
+    build_test_context_with_options(None)
+}
+
+fn build_test_context_with_options(srtp_ctx_opt: Option<ContextOption>) -> Result<Context> {
     let master_key = Bytes::from_static(&[
         0x0d, 0xcd, 0x21, 0x3e, 0x4c, 0xbc, 0xf2, 0x8f, 0x01, 0x7f, 0x69, 0x94, 0x40, 0x1e, 0x28,
         0x89,
@@ -70,7 +76,7 @@ fn build_test_context() -> Result<Context> {
         &master_key,
         &master_salt,
         ProtectionProfile::Aes128CmHmacSha1_80,
-        None,
+        srtp_ctx_opt,
         None,
     )
 }
@@ -167,6 +173,333 @@ fn test_rtp_lifecycle() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_rtp_extension_with_extra_padding() -> Result<()> {
+    // a one-byte extension followed by more padding than its 32-bit alignment
+    let decrypted_raw = Bytes::from_static(&[
+        0x90, 0xe0, 0x13, 0x88, 0xd9, 0xc2, 0x93, 0xda, 0x1c, 0x64, 0x27, 0x82, 0xBE, 0xDE, 0x00,
+        0x02, 0x10, 0xAA, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05,
+    ]);
+    let header_len = 24;
+    let master_key = Bytes::from_static(&[
+        0x0d, 0xcd, 0x21, 0x3e, 0x4c, 0xbc, 0xf2, 0x8f, 0x01, 0x7f, 0x69, 0x94, 0x40, 0x1e, 0x28,
+        0x89,
+    ]);
+    let master_salt = Bytes::from_static(&[
+        0x62, 0x77, 0x60, 0x38, 0xc0, 0x6d, 0xc9, 0x41, 0x9f, 0x6d, 0xd9, 0x43, 0x3e, 0x7c,
+    ]);
+
+    for profile in [
+        ProtectionProfile::Aes128CmHmacSha1_80,
+        ProtectionProfile::AeadAes128Gcm,
+    ] {
+        let new_context = || {
+            Context::new(
+                &master_key,
+                &master_salt[..profile.salt_len()],
+                profile,
+                None,
+                None,
+            )
+        };
+
+        let encrypted_raw = new_context()?.encrypt_rtp(&decrypted_raw)?;
+        assert_eq!(
+            encrypted_raw[..header_len],
+            decrypted_raw[..header_len],
+            "{profile:?} header is left unencrypted"
+        );
+        assert_ne!(
+            encrypted_raw[header_len..decrypted_raw.len()],
+            decrypted_raw[header_len..],
+            "{profile:?} payload is encrypted"
+        );
+
+        let actual_decrypted = new_context()?.decrypt_rtp(&encrypted_raw)?;
+        assert_eq!(actual_decrypted, decrypted_raw, "{profile:?} decryption");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rtp_replay_protection() -> Result<()> {
+    let mut encrypt_context = build_test_context()?;
+    let mut decrypt_context = build_test_context_with_options(Some(srtp_replay_protection(4)))?;
+
+    let mut encrypted = HashMap::new();
+    for sequence_number in [65533, 65534, 65535, 0, 1, 2] {
+        let decrypted_pkt = rtp::packet::Packet {
+            header: rtp::header::Header {
+                sequence_number,
+                ..Default::default()
+            },
+            payload: RTP_TEST_CASE_DECRYPTED.clone(),
+        };
+        let raw = encrypt_context.encrypt_rtp(&decrypted_pkt.marshal()?)?;
+        encrypted.insert(sequence_number, raw);
+    }
+
+    let tests = vec![
+        ("first", 65534, None),
+        (
+            "duplicated",
+            65534,
+            Some(Error::SrtpSsrcDuplicated(0, 65534)),
+        ),
+        ("rollover", 1, None),
+        ("reordered across the rollover", 65535, None),
+        (
+            "duplicated across the rollover",
+            1,
+            Some(Error::SrtpSsrcDuplicated(0, 1)),
+        ),
+        (
+            "behind the window",
+            65533,
+            Some(Error::SrtpSsrcTooOld(0, 65533)),
+        ),
+        ("reordered within the window", 0, None),
+        ("next", 2, None),
+    ];
+
+    for (name, sequence_number, expected) in tests {
+        let result = decrypt_context.decrypt_rtp(&encrypted[&sequence_number]);
+        assert_eq!(result.err(), expected, "{name}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rtp_key_derivation_rate() -> Result<()> {
+    let mut encrypt_context = build_test_context()?.with_key_derivation_rate(4)?;
+    let mut decrypt_context = build_test_context()?.with_key_derivation_rate(4)?;
+    let mut once_derived_context = build_test_context()?;
+
+    for sequence_number in 0..12u16 {
+        let decrypted_pkt = rtp::packet::Packet {
+            header: rtp::header::Header {
+                sequence_number,
+                ..Default::default()
+            },
+            payload: RTP_TEST_CASE_DECRYPTED.clone(),
+        };
+        let decrypted_raw = decrypted_pkt.marshal()?;
+
+        let encrypted_raw = encrypt_context.encrypt_rtp(&decrypted_raw)?;
+        assert_eq!(
+            decrypt_context.decrypt_rtp(&encrypted_raw)?,
+            decrypted_raw,
+            "RTP packet with SeqNum invalid decryption: {sequence_number}"
+        );
+
+        // session keys are derived again every 4 packets
+        let result = once_derived_context.decrypt_rtp(&encrypted_raw);
+        assert_eq!(
+            result.is_ok(),
+            sequence_number < 4,
+            "RTP packet with SeqNum decrypted with the session keys of index 0: {sequence_number}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rtp_rotate_master_key() -> Result<()> {
+    let mut encrypt_context = build_test_context()?.with_key_derivation_rate(4)?;
+    let mut decrypt_context = build_test_context()?.with_key_derivation_rate(4)?;
+    let encrypt_rtp = |context: &mut Context, sequence_number: u16| -> Result<(Bytes, Bytes)> {
+        let decrypted_pkt = rtp::packet::Packet {
+            header: rtp::header::Header {
+                sequence_number,
+                ..Default::default()
+            },
+            payload: RTP_TEST_CASE_DECRYPTED.clone(),
+        };
+        let decrypted_raw = decrypted_pkt.marshal()?;
+        let encrypted_raw = context.encrypt_rtp(&decrypted_raw)?;
+        Ok((decrypted_raw, encrypted_raw))
+    };
+
+    let old = (0..8u16)
+        .map(|sequence_number| encrypt_rtp(&mut encrypt_context, sequence_number))
+        .collect::<Result<Vec<_>>>()?;
+
+    let (master_key, master_salt) = (vec![0x11; 16], vec![0x22; 14]);
+    encrypt_context.rotate_master_key(&[], &master_key, &master_salt, 0)?;
+    decrypt_context.rotate_master_key(&[], &master_key, &master_salt, 3)?;
+
+    // the session keys of the old key are derived again past the rotation
+    let (decrypted_raw, encrypted_raw) = &old[5];
+    assert_eq!(decrypt_context.decrypt_rtp(encrypted_raw)?, decrypted_raw);
+    let (decrypted_raw, encrypted_raw) = encrypt_rtp(&mut encrypt_context, 8)?;
+    assert_eq!(decrypt_context.decrypt_rtp(&encrypted_raw)?, decrypted_raw);
+    let (decrypted_raw, encrypted_raw) = &old[7];
+    assert_eq!(decrypt_context.decrypt_rtp(encrypted_raw)?, decrypted_raw);
+
+    let (_, encrypted_raw) = &old[6];
+    assert_eq!(
+        decrypt_context.decrypt_rtp(encrypted_raw),
+        Err(Error::RtpFailedToVerifyAuthTag)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_rtp_set_roc() -> Result<()> {
+    let mut encrypt_context = build_test_context()?;
+    let mut decrypt_context = build_test_context()?;
+    let mut late_context = build_test_context()?;
+    let ssrc = 0x12345678;
+
+    // the sender rolled over the sequence numbers before the receivers joined
+    encrypt_context.set_roc(ssrc, 1);
+    assert_eq!(encrypt_context.get_roc(ssrc), Some(1));
+    assert_eq!(decrypt_context.get_roc(ssrc), None);
+
+    let decrypted_pkt = rtp::packet::Packet {
+        header: rtp::header::Header {
+            ssrc,
+            sequence_number: 100,
+            ..Default::default()
+        },
+        payload: RTP_TEST_CASE_DECRYPTED.clone(),
+    };
+    let decrypted_raw = decrypted_pkt.marshal()?;
+    let encrypted_raw = encrypt_context.encrypt_rtp(&decrypted_raw)?;
+
+    assert_eq!(
+        late_context.decrypt_rtp(&encrypted_raw),
+        Err(Error::RtpFailedToVerifyAuthTag)
+    );
+
+    decrypt_context.set_roc(ssrc, 1);
+    assert_eq!(decrypt_context.decrypt_rtp(&encrypted_raw)?, decrypted_raw);
+    assert_eq!(decrypt_context.get_roc(ssrc), Some(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_key_derivation_rate() -> Result<()> {
+    for kdr in [0, 1, 1 << 16, 1 << 24] {
+        assert!(build_test_context()?.with_key_derivation_rate(kdr).is_ok());
+    }
+
+    for kdr in [3, 1000, 1 << 25] {
+        assert_eq!(
+            build_test_context()?.with_key_derivation_rate(kdr).err(),
+            Some(Error::ErrInvalidKeyDerivationRate(kdr)),
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_rtp_mki() -> Result<()> {
+    let mki = [0xde, 0xad, 0xbe, 0xef];
+    let auth_tag_len = ProtectionProfile::Aes128CmHmacSha1_80.auth_tag_len();
+
+    let mut encrypt_context = build_test_context()?.with_mki(&mki)?;
+    let mut decrypt_context = build_test_context()?.with_mki(&mki)?;
+
+    for test_case in RTP_TEST_CASES.iter() {
+        let decrypted_raw = rtp::packet::Packet {
+            header: rtp::header::Header {
+                sequence_number: test_case.sequence_number,
+                ..Default::default()
+            },
+            payload: RTP_TEST_CASE_DECRYPTED.clone(),
+        }
+        .marshal()?;
+
+        // the MKI is placed between the encrypted payload and the auth tag,
+        // which does not cover it
+        let encrypted_raw = rtp::packet::Packet {
+            header: rtp::header::Header {
+                sequence_number: test_case.sequence_number,
+                ..Default::default()
+            },
+            payload: test_case.encrypted.clone(),
+        }
+        .marshal()?;
+        let tag_offset = encrypted_raw.len() - auth_tag_len;
+        let encrypted_raw = [
+            &encrypted_raw[..tag_offset],
+            &mki,
+            &encrypted_raw[tag_offset..],
+        ]
+        .concat();
+
+        assert_eq!(
+            encrypt_context.encrypt_rtp(&decrypted_raw)?,
+            encrypted_raw,
+            "RTP packet with SeqNum invalid encryption: {}",
+            test_case.sequence_number
+        );
+        assert_eq!(
+            decrypt_context.decrypt_rtp(&encrypted_raw)?,
+            decrypted_raw,
+            "RTP packet with SeqNum invalid decryption: {}",
+            test_case.sequence_number
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "insecure-null-cipher")]
+#[test]
+fn test_rtp_null_cipher() -> Result<()> {
+    let master_key = Bytes::from_static(&[
+        0x0d, 0xcd, 0x21, 0x3e, 0x4c, 0xbc, 0xf2, 0x8f, 0x01, 0x7f, 0x69, 0x94, 0x40, 0x1e, 0x28,
+        0x89,
+    ]);
+    let master_salt = Bytes::from_static(&[
+        0x62, 0x77, 0x60, 0x38, 0xc0, 0x6d, 0xc9, 0x41, 0x9f, 0x6d, 0xd9, 0x43, 0x3e, 0x7c,
+    ]);
+    let new_context = || {
+        Context::new(
+            &master_key,
+            &master_salt,
+            ProtectionProfile::NullHmacSha1_80,
+            None,
+            None,
+        )
+    };
+
+    let decrypted_raw = rtp::packet::Packet {
+        header: rtp::header::Header {
+            version: 2,
+            sequence_number: 5000,
+            ..Default::default()
+        },
+        payload: RTP_TEST_CASE_DECRYPTED.clone(),
+    }
+    .marshal()?;
+
+    // the packet is left unencrypted, followed by its HMAC-SHA1 auth tag
+    let expected_tag = [0x2d, 0x02, 0x8c, 0x50, 0x80, 0xad, 0xc0, 0xa1, 0x25, 0xc9];
+    let encrypted_raw = new_context()?.encrypt_rtp(&decrypted_raw)?;
+    assert_eq!(encrypted_raw, [&decrypted_raw[..], &expected_tag].concat());
+
+    let mut decrypt_context = new_context()?;
+    assert_eq!(decrypt_context.decrypt_rtp(&encrypted_raw)?, decrypted_raw);
+
+    let mut tampered = encrypted_raw.to_vec();
+    tampered[12] ^= 0x01;
+    assert_eq!(
+        decrypt_context.decrypt_rtp(&tampered),
+        Err(Error::RtpFailedToVerifyAuthTag)
+    );
+
+    Ok(())
+}
+
 //TODO: BenchmarkEncryptRTP
 //TODO: BenchmarkEncryptRTPInPlace
 //TODO: BenchmarkDecryptRTP
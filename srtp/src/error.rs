@@ -41,8 +41,18 @@ pub enum Error {
     #[error("failed to cast child")]
     ErrFailedTypeAssertion,
 
-    #[error("index_over_kdr > 0 is not supported yet")]
-    UnsupportedIndexOverKdr,
+    #[error("key derivation rate must be 0 or a power of 2 up to 2^24, got {0}")]
+    ErrInvalidKeyDerivationRate(u64),
+    #[error("MKIs must have a same non-zero length")]
+    ErrInvalidMkiLength,
+    #[error("MKIs are not used by the context")]
+    ErrMkiNotUsed,
+    #[error("MKI is already used by another master key")]
+    ErrMkiAlreadyUsed,
+    #[error("no master key of this MKI")]
+    ErrMkiNotFound,
+    #[error("the master key protecting the packets sent cannot be removed")]
+    ErrRemoveSendMki,
     #[error("SRTP Master Key must be len {0}, got {1}")]
     SrtpMasterKeyLength(usize, usize),
     #[error("SRTP Salt must be len {0}, got {1}")]
@@ -53,6 +63,10 @@ pub enum Error {
     SrtpSsrcDuplicated(u32, u16),
     #[error("srtcp ssrc={0} index={1}: duplicated")]
     SrtcpSsrcDuplicated(u32, usize),
+    #[error("srtp ssrc={0} index={1}: too old")]
+    SrtpSsrcTooOld(u32, u16),
+    #[error("srtcp ssrc={0} index={1}: too old")]
+    SrtcpSsrcTooOld(u32, usize),
     #[error("ssrc {0} not exist in srtcp_ssrc_state")]
     SsrcMissingFromSrtcp(u32),
     #[error("Stream with ssrc {0} exists")]
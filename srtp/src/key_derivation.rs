@@ -1,6 +1,6 @@
 use aes::cipher::generic_array::GenericArray;
 use aes::cipher::BlockEncrypt;
-use aes::Aes128;
+use aes::{Aes128, Aes256};
 use aes_gcm::KeyInit;
 
 use crate::error::{Error, Result};
@@ -14,44 +14,67 @@ pub const LABEL_SRTCP_SALT: u8 = 0x05;
 
 pub(crate) const SRTCP_INDEX_SIZE: usize = 4;
 
+const AES_BLOCK_SIZE: usize = 16;
+
+/// MAX_KEY_DERIVATION_RATE is the largest key derivation rate, 2^24, as
+/// described in https://tools.ietf.org/html/rfc3711#section-4.3.1
+pub(crate) const MAX_KEY_DERIVATION_RATE: u64 = 1 << 24;
+/// The 48-bit "index DIV kdr" is xored to the PRF input.
+const MAX_INDEX_OVER_KDR: u64 = 0xFFFFFFFFFFFF;
+
+/// index_over_kdr returns the "index DIV kdr" the session keys of the packet of
+/// the given index are derived with. Session keys are derived once if the key
+/// derivation rate is 0, and every kdr packets otherwise.
+pub(crate) fn index_over_kdr(index: u64, key_derivation_rate: u64) -> u64 {
+    index.checked_div(key_derivation_rate).unwrap_or(0)
+}
+
+fn encrypt_blocks<C: BlockEncrypt>(cipher: &C, out: &mut [u8]) {
+    for block in out.chunks_exact_mut(AES_BLOCK_SIZE) {
+        cipher.encrypt_block(GenericArray::from_mut_slice(block));
+    }
+}
+
 pub(crate) fn aes_cm_key_derivation(
     label: u8,
     master_key: &[u8],
     master_salt: &[u8],
-    index_over_kdr: usize,
+    index_over_kdr: u64,
     out_len: usize,
 ) -> Result<Vec<u8>> {
-    if index_over_kdr != 0 {
-        // 24-bit "index DIV kdr" must be xored to prf input.
-        return Err(Error::UnsupportedIndexOverKdr);
-    }
-
     // https://tools.ietf.org/html/rfc3711#appendix-B.3
     // The input block for AES-CM is generated by exclusive-oring the master salt with the
     // concatenation of the encryption key label 0x00 with (index DIV kdr),
-    // - index is 'rollover count' and DIV is 'divided by'
+    // - index is the 48-bit SRTP or 31-bit SRTCP index and DIV is 'divided by'
 
-    let n_master_key = master_key.len();
     let n_master_salt = master_salt.len();
 
-    let mut prf_in = vec![0u8; n_master_key];
+    let mut prf_in = [0u8; AES_BLOCK_SIZE];
     prf_in[..n_master_salt].copy_from_slice(master_salt);
 
     prf_in[7] ^= label;
+    let r = (index_over_kdr & MAX_INDEX_OVER_KDR).to_be_bytes();
+    for (b, r) in prf_in[8..14].iter_mut().zip(&r[2..]) {
+        *b ^= r;
+    }
 
-    //The resulting value is then AES encrypted using the master key to get the cipher key.
-    let key = GenericArray::from_slice(master_key);
-    let block = Aes128::new(key);
-
-    let mut out = vec![0u8; ((out_len + n_master_key) / n_master_key) * n_master_key];
-    for (i, n) in (0..out_len).step_by(n_master_key).enumerate() {
+    let mut out = vec![0u8; out_len.div_ceil(AES_BLOCK_SIZE) * AES_BLOCK_SIZE];
+    for (i, block) in out.chunks_exact_mut(AES_BLOCK_SIZE).enumerate() {
         //BigEndian.PutUint16(prfIn[nMasterKey-2:], i)
-        prf_in[n_master_key - 2] = ((i >> 8) & 0xFF) as u8;
-        prf_in[n_master_key - 1] = (i & 0xFF) as u8;
+        prf_in[AES_BLOCK_SIZE - 2] = ((i >> 8) & 0xFF) as u8;
+        prf_in[AES_BLOCK_SIZE - 1] = (i & 0xFF) as u8;
 
-        out[n..n + n_master_key].copy_from_slice(&prf_in);
-        let out_key = GenericArray::from_mut_slice(&mut out[n..n + n_master_key]);
-        block.encrypt_block(out_key);
+        block.copy_from_slice(&prf_in);
+    }
+
+    //The resulting value is then AES encrypted using the master key to get the cipher key.
+    // AES-128 master keys are used by the AES_CM and AEAD_AES_128_GCM profiles,
+    // AES-256 ones by AEAD_AES_256_GCM, as described in
+    // https://tools.ietf.org/html/rfc6188#section-3
+    match master_key.len() {
+        16 => encrypt_blocks(&Aes128::new(GenericArray::from_slice(master_key)), &mut out),
+        32 => encrypt_blocks(&Aes256::new(GenericArray::from_slice(master_key)), &mut out),
+        _ => return Err(Error::ErrShortSrtpMasterKey),
     }
 
     Ok(out[..out_len].to_vec())
@@ -161,13 +184,107 @@ mod test {
         Ok(())
     }
 
-    // This test asserts that calling aesCmKeyDerivation with a non-zero indexOverKdr fails
-    // Currently this isn't supported, but the API makes sure we can add this in the future
+    #[test]
+    fn test_valid_session_keys_aes_256() -> Result<()> {
+        // Key Derivation Test Vectors from https://tools.ietf.org/html/rfc6188#section-7.1
+        let master_key = vec![
+            0xf0, 0xf0, 0x49, 0x14, 0xb5, 0x13, 0xf2, 0x76, 0x3a, 0x1b, 0x1f, 0xa1, 0x30, 0xf1,
+            0x0e, 0x29, 0x98, 0xf6, 0xf6, 0xe4, 0x3e, 0x43, 0x09, 0xd1, 0xe6, 0x22, 0xa0, 0xe3,
+            0x32, 0xb9, 0xf1, 0xb6,
+        ];
+        let master_salt = vec![
+            0x3b, 0x04, 0x80, 0x3d, 0xe5, 0x1e, 0xe7, 0xc9, 0x64, 0x23, 0xab, 0x5b, 0x78, 0xd2,
+        ];
+
+        let expected_session_key = vec![
+            0x5b, 0xa1, 0x06, 0x4e, 0x30, 0xec, 0x51, 0x61, 0x3c, 0xad, 0x92, 0x6c, 0x5a, 0x28,
+            0xef, 0x73, 0x1e, 0xc7, 0xfb, 0x39, 0x7f, 0x70, 0xa9, 0x60, 0x65, 0x3c, 0xaf, 0x06,
+            0x55, 0x4c, 0xd8, 0xc4,
+        ];
+        let expected_session_salt = vec![
+            0xfa, 0x31, 0x79, 0x16, 0x85, 0xca, 0x44, 0x4a, 0x9e, 0x07, 0xc6, 0xc6, 0x4e, 0x93,
+        ];
+
+        let session_key = aes_cm_key_derivation(
+            LABEL_SRTP_ENCRYPTION,
+            &master_key,
+            &master_salt,
+            0,
+            master_key.len(),
+        )?;
+        assert_eq!(session_key, expected_session_key);
+
+        let session_salt = aes_cm_key_derivation(
+            LABEL_SRTP_SALT,
+            &master_key,
+            &master_salt,
+            0,
+            master_salt.len(),
+        )?;
+        assert_eq!(session_salt, expected_session_salt);
+
+        Ok(())
+    }
+
     #[test]
     fn test_index_over_kdr() -> Result<()> {
-        let result = aes_cm_key_derivation(LABEL_SRTP_AUTHENTICATION_TAG, &[], &[], 1, 0);
-        assert!(result.is_err());
+        // The master key and salt of https://tools.ietf.org/html/rfc3711#appendix-B.3,
+        // with "index DIV kdr" xored to the input block
+        let master_key = vec![
+            0xE1, 0xF9, 0x7A, 0x0D, 0x3E, 0x01, 0x8B, 0xE0, 0xD6, 0x4F, 0xA3, 0x2C, 0x06, 0xDE,
+            0x41, 0x39,
+        ];
+        let master_salt = vec![
+            0x0E, 0xC6, 0x75, 0xAD, 0x49, 0x8A, 0xFE, 0xEB, 0xB6, 0x96, 0x0B, 0x3A, 0xAB, 0xE6,
+        ];
+        let index_over_kdr = 0x0102030405;
+
+        let expected_session_key = vec![
+            0xAA, 0x46, 0xD5, 0x2A, 0x8A, 0xAC, 0xE0, 0x9B, 0xC1, 0x08, 0x52, 0x5A, 0xBF, 0x42,
+            0x74, 0x5A,
+        ];
+        let expected_session_salt = vec![
+            0xA3, 0x2C, 0xF4, 0x8B, 0x71, 0xB7, 0xFC, 0x0E, 0x19, 0xBF, 0xE9, 0xD7, 0x2E, 0x37,
+        ];
+        let expected_session_auth_tag = vec![
+            0x24, 0x83, 0x62, 0x37, 0x3D, 0x6D, 0x84, 0xC4, 0xBE, 0x0F, 0xD6, 0x40, 0xE3, 0x17,
+            0x3D, 0xB1, 0x1A, 0x1B, 0x1F, 0xB0,
+        ];
+
+        let session_key = aes_cm_key_derivation(
+            LABEL_SRTP_ENCRYPTION,
+            &master_key,
+            &master_salt,
+            index_over_kdr,
+            master_key.len(),
+        )?;
+        assert_eq!(session_key, expected_session_key);
+
+        let session_salt = aes_cm_key_derivation(
+            LABEL_SRTP_SALT,
+            &master_key,
+            &master_salt,
+            index_over_kdr,
+            master_salt.len(),
+        )?;
+        assert_eq!(session_salt, expected_session_salt);
+
+        let session_auth_tag = aes_cm_key_derivation(
+            LABEL_SRTP_AUTHENTICATION_TAG,
+            &master_key,
+            &master_salt,
+            index_over_kdr,
+            ProtectionProfile::Aes128CmHmacSha1_80.auth_key_len(),
+        )?;
+        assert_eq!(session_auth_tag, expected_session_auth_tag);
 
         Ok(())
     }
+
+    #[test]
+    fn test_index_over_kdr_rate() {
+        assert_eq!(index_over_kdr(0x123456, 0), 0);
+        assert_eq!(index_over_kdr(0x123456, 1), 0x123456);
+        assert_eq!(index_over_kdr(0x123456, 1 << 16), 0x12);
+    }
 }
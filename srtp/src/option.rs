@@ -3,14 +3,17 @@ use util::replay_detector::*;
 pub type ContextOption = Box<dyn (Fn() -> Box<dyn ReplayDetector + Send + 'static>) + Send + Sync>;
 
 pub(crate) const MAX_SEQUENCE_NUMBER: u16 = 65535;
+pub(crate) const MAX_SRTP_INDEX: u64 = 0xFFFFFFFFFFFF;
 pub(crate) const MAX_SRTCP_INDEX: usize = 0x7FFFFFFF;
 
-/// srtp_replay_protection sets SRTP replay protection window size.
+/// srtp_replay_protection sets SRTP replay protection window size. Packets are
+/// told apart by their 48-bit index, made of the rollover counter and the
+/// sequence number, as described in https://tools.ietf.org/html/rfc3711#section-3.3.2
 pub fn srtp_replay_protection(window_size: usize) -> ContextOption {
     Box::new(move || -> Box<dyn ReplayDetector + Send> {
         Box::new(WrappedSlidingWindowDetector::new(
             window_size,
-            MAX_SEQUENCE_NUMBER as u64,
+            MAX_SRTP_INDEX,
         ))
     })
 }
@@ -5,33 +5,59 @@ pub enum ProtectionProfile {
     #[default]
     Aes128CmHmacSha1_80 = 0x0001,
     AeadAes128Gcm = 0x0007,
+    AeadAes256Gcm = 0x0008,
+    /// NullHmacSha1_80 authenticates packets without encrypting them, so that
+    /// captures stay readable while debugging. It must never be used in
+    /// production, and is only available with the `insecure-null-cipher` feature.
+    #[cfg(feature = "insecure-null-cipher")]
+    NullHmacSha1_80 = 0x0005,
 }
 
 impl ProtectionProfile {
     pub(crate) fn key_len(&self) -> usize {
         match *self {
             ProtectionProfile::Aes128CmHmacSha1_80 | ProtectionProfile::AeadAes128Gcm => 16,
+            ProtectionProfile::AeadAes256Gcm => 32,
+            #[cfg(feature = "insecure-null-cipher")]
+            ProtectionProfile::NullHmacSha1_80 => 16,
         }
     }
 
     pub(crate) fn salt_len(&self) -> usize {
         match *self {
             ProtectionProfile::Aes128CmHmacSha1_80 => 14,
-            ProtectionProfile::AeadAes128Gcm => 12,
+            ProtectionProfile::AeadAes128Gcm | ProtectionProfile::AeadAes256Gcm => 12,
+            #[cfg(feature = "insecure-null-cipher")]
+            ProtectionProfile::NullHmacSha1_80 => 14,
         }
     }
 
     pub(crate) fn auth_tag_len(&self) -> usize {
         match *self {
             ProtectionProfile::Aes128CmHmacSha1_80 => 10, //CIPHER_AES_CM_HMAC_SHA1AUTH_TAG_LEN,
-            ProtectionProfile::AeadAes128Gcm => 16,       //CIPHER_AEAD_AES_GCM_AUTH_TAG_LEN,
+            ProtectionProfile::AeadAes128Gcm | ProtectionProfile::AeadAes256Gcm => 16, //CIPHER_AEAD_AES_GCM_AUTH_TAG_LEN,
+            #[cfg(feature = "insecure-null-cipher")]
+            ProtectionProfile::NullHmacSha1_80 => 10,
+        }
+    }
+
+    /// aead_auth_tag_len returns the length of the authentication tag AEAD
+    /// ciphers embed in the ciphertext, which is part of auth_tag_len.
+    pub(crate) fn aead_auth_tag_len(&self) -> usize {
+        match *self {
+            ProtectionProfile::Aes128CmHmacSha1_80 => 0,
+            ProtectionProfile::AeadAes128Gcm | ProtectionProfile::AeadAes256Gcm => 16,
+            #[cfg(feature = "insecure-null-cipher")]
+            ProtectionProfile::NullHmacSha1_80 => 0,
         }
     }
 
     pub(crate) fn auth_key_len(&self) -> usize {
         match *self {
             ProtectionProfile::Aes128CmHmacSha1_80 => 20,
-            ProtectionProfile::AeadAes128Gcm => 0,
+            ProtectionProfile::AeadAes128Gcm | ProtectionProfile::AeadAes256Gcm => 0,
+            #[cfg(feature = "insecure-null-cipher")]
+            ProtectionProfile::NullHmacSha1_80 => 20,
         }
     }
 }
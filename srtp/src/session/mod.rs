@@ -27,6 +27,7 @@ const DEFAULT_SESSION_SRTCP_REPLAY_PROTECTION_WINDOW: usize = 64;
 /// instead of making everyone re-implement
 pub struct Session {
     local_context: Arc<Mutex<Context>>,
+    remote_context: Arc<Mutex<Context>>,
     streams_map: Arc<Mutex<HashMap<u32, Arc<Stream>>>>,
     new_stream_rx: Arc<Mutex<mpsc::Receiver<Arc<Stream>>>>,
     close_stream_tx: mpsc::Sender<u32>,
@@ -41,13 +42,17 @@ impl Session {
         config: Config,
         is_rtp: bool,
     ) -> Result<Self> {
-        let local_context = Context::new(
+        let mut local_context = Context::new(
             &config.keys.local_master_key,
             &config.keys.local_master_salt,
             config.profile,
             config.local_rtp_options,
             config.local_rtcp_options,
-        )?;
+        )?
+        .with_key_derivation_rate(config.key_derivation_rate)?;
+        if !config.keys.local_mki.is_empty() {
+            local_context = local_context.with_mki(&config.keys.local_mki)?;
+        }
 
         let mut remote_context = Context::new(
             &config.keys.remote_master_key,
@@ -67,7 +72,14 @@ impl Session {
             } else {
                 config.remote_rtcp_options
             },
-        )?;
+        )?
+        .with_key_derivation_rate(config.key_derivation_rate)?;
+        if !config.keys.remote_mki.is_empty() {
+            remote_context = remote_context.with_mki(&config.keys.remote_mki)?;
+        }
+
+        let remote_context = Arc::new(Mutex::new(remote_context));
+        let cloned_remote_context = Arc::clone(&remote_context);
 
         let streams_map = Arc::new(Mutex::new(HashMap::new()));
         let (mut new_stream_tx, new_stream_rx) = mpsc::channel(8);
@@ -88,7 +100,7 @@ impl Session {
                     &cloned_streams_map,
                     &cloned_close_stream_tx,
                     &mut new_stream_tx,
-                    &mut remote_context,
+                    &cloned_remote_context,
                     is_rtp,
                 );
                 let close_stream = close_stream_rx.recv();
@@ -109,6 +121,7 @@ impl Session {
 
         Ok(Session {
             local_context: Arc::new(Mutex::new(local_context)),
+            remote_context,
             streams_map,
             new_stream_rx: Arc::new(Mutex::new(new_stream_rx)),
             close_stream_tx,
@@ -129,7 +142,7 @@ impl Session {
         streams_map: &Arc<Mutex<HashMap<u32, Arc<Stream>>>>,
         close_stream_tx: &mpsc::Sender<u32>,
         new_stream_tx: &mut mpsc::Sender<Arc<Stream>>,
-        remote_context: &mut Context,
+        remote_context: &Arc<Mutex<Context>>,
         is_rtp: bool,
     ) -> Result<()> {
         let n = udp_rx.recv(buf).await?;
@@ -137,10 +150,14 @@ impl Session {
             return Err(Error::SessionEof);
         }
 
-        let decrypted = if is_rtp {
-            remote_context.decrypt_rtp(&buf[0..n])?
-        } else {
-            remote_context.decrypt_rtcp(&buf[0..n])?
+        let decrypted = {
+            let mut remote_context = remote_context.lock().await;
+
+            if is_rtp {
+                remote_context.decrypt_rtp(&buf[0..n])?
+            } else {
+                remote_context.decrypt_rtcp(&buf[0..n])?
+            }
         };
 
         let mut buf = &decrypted[..];
@@ -226,6 +243,33 @@ impl Session {
         Ok(())
     }
 
+    /// rotate_keys installs the master keys of new session keys, such as after
+    /// a DTLS renegotiation, without closing the streams. Packets sent from now
+    /// on are protected by the new local key, while the old remote key keeps
+    /// decrypting the packets it protects among the next `overlap` packets
+    /// received, as sent by the peer before it switched keys. The new keys take
+    /// the MKIs of the session keys, or keep those of the keys in use if they
+    /// are empty.
+    pub async fn rotate_keys(&self, keys: &SessionKeys, overlap: usize) -> Result<()> {
+        {
+            let mut local_context = self.local_context.lock().await;
+            local_context.rotate_master_key(
+                &keys.local_mki,
+                &keys.local_master_key,
+                &keys.local_master_salt,
+                0,
+            )?;
+        }
+
+        let mut remote_context = self.remote_context.lock().await;
+        remote_context.rotate_master_key(
+            &keys.remote_mki,
+            &keys.remote_master_key,
+            &keys.remote_master_salt,
+            overlap,
+        )
+    }
+
     pub async fn write(&self, buf: &Bytes, is_rtp: bool) -> Result<usize> {
         if self.is_rtp != is_rtp {
             return Err(Error::SessionRtpRtcpTypeMismatch);
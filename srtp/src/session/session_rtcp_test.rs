@@ -29,6 +29,7 @@ async fn build_session_srtcp_pair() -> Result<(Session, Session)> {
             remote_master_salt: vec![
                 0x0E, 0xC6, 0x75, 0xAD, 0x49, 0x8A, 0xFE, 0xEB, 0xB6, 0x96, 0x0B, 0x3A, 0xAB, 0xE6,
             ],
+            ..Default::default()
         },
 
         local_rtp_options: None,
@@ -36,6 +37,8 @@ async fn build_session_srtcp_pair() -> Result<(Session, Session)> {
 
         local_rtcp_options: None,
         remote_rtcp_options: None,
+
+        key_derivation_rate: 0,
     };
 
     let cb = Config {
@@ -55,6 +58,7 @@ async fn build_session_srtcp_pair() -> Result<(Session, Session)> {
             remote_master_salt: vec![
                 0x0E, 0xC6, 0x75, 0xAD, 0x49, 0x8A, 0xFE, 0xEB, 0xB6, 0x96, 0x0B, 0x3A, 0xAB, 0xE6,
             ],
+            ..Default::default()
         },
 
         local_rtp_options: None,
@@ -62,6 +66,8 @@ async fn build_session_srtcp_pair() -> Result<(Session, Session)> {
 
         local_rtcp_options: None,
         remote_rtcp_options: None,
+
+        key_derivation_rate: 0,
     };
 
     let sa = Session::new(Arc::new(ua), ca, false).await?;
@@ -33,6 +33,7 @@ async fn build_session_srtp_pair() -> Result<(Session, Session)> {
             remote_master_salt: vec![
                 0x0E, 0xC6, 0x75, 0xAD, 0x49, 0x8A, 0xFE, 0xEB, 0xB6, 0x96, 0x0B, 0x3A, 0xAB, 0xE6,
             ],
+            ..Default::default()
         },
 
         local_rtp_options: None,
@@ -40,6 +41,8 @@ async fn build_session_srtp_pair() -> Result<(Session, Session)> {
 
         local_rtcp_options: None,
         remote_rtcp_options: None,
+
+        key_derivation_rate: 0,
     };
 
     let cb = Config {
@@ -59,6 +62,7 @@ async fn build_session_srtp_pair() -> Result<(Session, Session)> {
             remote_master_salt: vec![
                 0x0E, 0xC6, 0x75, 0xAD, 0x49, 0x8A, 0xFE, 0xEB, 0xB6, 0x96, 0x0B, 0x3A, 0xAB, 0xE6,
             ],
+            ..Default::default()
         },
 
         local_rtp_options: None,
@@ -66,6 +70,8 @@ async fn build_session_srtp_pair() -> Result<(Session, Session)> {
 
         local_rtcp_options: None,
         remote_rtcp_options: None,
+
+        key_derivation_rate: 0,
     };
 
     let sa = Session::new(Arc::new(ua), ca, true).await?;
@@ -306,3 +312,60 @@ async fn test_session_srtp_replay_protection() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_session_srtp_rotate_keys() -> Result<()> {
+    let test_payload = Bytes::from_static(&[0x00, 0x01, 0x03, 0x04]);
+
+    let (sa, sb) = build_session_srtp_pair().await?;
+
+    let read_stream = sb.open(TEST_SSRC).await;
+
+    let packet = |sequence_number| rtp::packet::Packet {
+        header: rtp::header::Header {
+            ssrc: TEST_SSRC,
+            sequence_number,
+            ..Default::default()
+        },
+        payload: test_payload.clone(),
+    };
+
+    // a packet protected by the old key, delayed past the rotation
+    let delayed = {
+        let mut local_context = sa.local_context.lock().await;
+        encrypt_srtp(&mut local_context, &packet(1))?
+    };
+
+    let keys = SessionKeys {
+        local_master_key: vec![0x11; 16],
+        local_master_salt: vec![0x22; 14],
+        remote_master_key: vec![0x11; 16],
+        remote_master_salt: vec![0x22; 14],
+        ..Default::default()
+    };
+    sa.rotate_keys(&keys, 0).await?;
+    sb.rotate_keys(&keys, 8).await?;
+
+    // the MKIs of the session keys are applied, and these sessions use none
+    let mki_keys = SessionKeys {
+        local_mki: vec![1],
+        ..keys.clone()
+    };
+    assert_eq!(
+        sa.rotate_keys(&mki_keys, 0).await,
+        Err(Error::ErrMkiNotUsed)
+    );
+
+    sa.write_rtp(&packet(2)).await?;
+    let seq = payload_srtp(&read_stream, RTP_HEADER_SIZE, &test_payload).await?;
+    assert_eq!(seq, 2);
+
+    sa.udp_tx.send(&delayed).await?;
+    let seq = payload_srtp(&read_stream, RTP_HEADER_SIZE, &test_payload).await?;
+    assert_eq!(seq, 1);
+
+    sa.close().await?;
+    sb.close().await?;
+
+    Ok(())
+}
@@ -1,10 +1,7 @@
 use std::sync::Arc;
 
 use clap::{App, Arg};
-use stun::agent::*;
 use stun::client::*;
-use stun::message::*;
-use stun::xoraddr::*;
 use stun::Error;
 use tokio::net::UdpSocket;
 
@@ -37,8 +34,6 @@ async fn main() -> Result<(), Error> {
 
     let server = matches.value_of("server").unwrap();
 
-    let (handler_tx, mut handler_rx) = tokio::sync::mpsc::unbounded_channel();
-
     let conn = UdpSocket::bind("0:0").await?;
     println!("Local address: {}", conn.local_addr()?);
 
@@ -47,17 +42,8 @@ async fn main() -> Result<(), Error> {
 
     let mut client = ClientBuilder::new().with_conn(Arc::new(conn)).build()?;
 
-    let mut msg = Message::new();
-    msg.build(&[Box::<TransactionId>::default(), Box::new(BINDING_REQUEST)])?;
-
-    client.send(&msg, Some(Arc::new(handler_tx))).await?;
-
-    if let Some(event) = handler_rx.recv().await {
-        let msg = event.event_body?;
-        let mut xor_addr = XorMappedAddress::default();
-        xor_addr.get_from(&msg)?;
-        println!("Got response: {xor_addr}");
-    }
+    let addr = client.binding_request().await?;
+    println!("Got response: {addr}");
 
     client.close().await?;
 
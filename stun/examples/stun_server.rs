@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use clap::{App, Arg};
+use stun::server::*;
+use stun::Error;
+use tokio::net::UdpSocket;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let mut app = App::new("STUN Server")
+        .version("0.1.0")
+        .author("Rain Liu <yliu@webrtc.rs>")
+        .about("An example of STUN Server")
+        .arg(
+            Arg::with_name("FULLHELP")
+                .help("Prints more detailed help information")
+                .long("fullhelp"),
+        )
+        .arg(
+            Arg::with_name("listen")
+                .required_unless("FULLHELP")
+                .takes_value(true)
+                .default_value("0.0.0.0:3478")
+                .long("listen")
+                .help("Address to listen on"),
+        );
+
+    let matches = app.clone().get_matches();
+
+    if matches.is_present("FULLHELP") {
+        app.print_long_help().unwrap();
+        std::process::exit(0);
+    }
+
+    let listen = matches.value_of("listen").unwrap();
+
+    let conn = UdpSocket::bind(listen).await?;
+    let mut server = Server::new(Arc::new(conn));
+    println!("Listening on: {}", server.local_addr()?);
+
+    println!("Waiting for Ctrl-C...");
+    tokio::signal::ctrl_c().await?;
+
+    server.close().await?;
+
+    Ok(())
+}
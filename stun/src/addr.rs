@@ -2,7 +2,7 @@
 mod addr_test;
 
 use std::fmt;
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
 use crate::attributes::*;
 use crate::error::*;
@@ -47,6 +47,21 @@ impl Default for MappedAddress {
     }
 }
 
+impl From<SocketAddr> for MappedAddress {
+    fn from(addr: SocketAddr) -> Self {
+        MappedAddress {
+            ip: addr.ip(),
+            port: addr.port(),
+        }
+    }
+}
+
+impl From<&MappedAddress> for SocketAddr {
+    fn from(addr: &MappedAddress) -> Self {
+        addr.socket_addr()
+    }
+}
+
 impl Setter for MappedAddress {
     /// add_to adds MAPPED-ADDRESS to message.
     fn add_to(&self, m: &mut Message) -> Result<()> {
@@ -62,6 +77,11 @@ impl Getter for MappedAddress {
 }
 
 impl MappedAddress {
+    /// socket_addr returns the transport address of the attribute.
+    pub fn socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.ip, self.port)
+    }
+
     /// get_from_as decodes MAPPED-ADDRESS value in message m as an attribute of type t.
     pub fn get_from_as(&mut self, m: &Message, t: AttrType) -> Result<()> {
         let v = m.get(t)?;
@@ -117,12 +137,83 @@ impl MappedAddress {
 /// RFC 5389 Section 15.11
 pub type AlternateServer = MappedAddress;
 
-/// ResponseOrigin represents RESPONSE-ORIGIN attribute.
-///
-/// RFC 5780 Section 7.3
-pub type ResponseOrigin = MappedAddress;
+// address_attribute defines an attribute holding a transport address, encoded
+// in the same way as MAPPED-ADDRESS but with its own attribute type.
+macro_rules! address_attribute {
+    ($(#[$meta:meta])* $name:ident, $attr_type:ident, $attr_name:literal) => {
+        $(#[$meta])*
+        pub struct $name {
+            pub ip: IpAddr,
+            pub port: u16,
+        }
 
-/// OtherAddress represents OTHER-ADDRESS attribute.
-///
-/// RFC 5780 Section 7.4
-pub type OtherAddress = MappedAddress;
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.socket_addr())
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name::from(MappedAddress::default().socket_addr())
+            }
+        }
+
+        impl From<SocketAddr> for $name {
+            fn from(addr: SocketAddr) -> Self {
+                $name {
+                    ip: addr.ip(),
+                    port: addr.port(),
+                }
+            }
+        }
+
+        impl Setter for $name {
+            #[doc = concat!("add_to adds ", $attr_name, " to message.")]
+            fn add_to(&self, m: &mut Message) -> Result<()> {
+                MappedAddress::from(self.socket_addr()).add_to_as(m, $attr_type)
+            }
+        }
+
+        impl Getter for $name {
+            #[doc = concat!("get_from decodes ", $attr_name, " from message.")]
+            fn get_from(&mut self, m: &Message) -> Result<()> {
+                let mut a = MappedAddress::default();
+                a.get_from_as(m, $attr_type)?;
+                self.ip = a.ip;
+                self.port = a.port;
+                Ok(())
+            }
+        }
+
+        impl $name {
+            /// socket_addr returns the transport address of the attribute.
+            pub fn socket_addr(&self) -> SocketAddr {
+                SocketAddr::new(self.ip, self.port)
+            }
+        }
+    };
+}
+
+address_attribute!(
+    /// ResponseOrigin represents RESPONSE-ORIGIN attribute.
+    ///
+    /// It is the transport address the response was sent from.
+    ///
+    /// RFC 5780 Section 7.3
+    ResponseOrigin,
+    ATTR_RESPONSE_ORIGIN,
+    "RESPONSE-ORIGIN"
+);
+
+address_attribute!(
+    /// OtherAddress represents OTHER-ADDRESS attribute.
+    ///
+    /// It is the alternate transport address of the server, differing from the
+    /// address the request was received on in both IP and port.
+    ///
+    /// RFC 5780 Section 7.4
+    OtherAddress,
+    ATTR_OTHER_ADDRESS,
+    "OTHER-ADDRESS"
+);
@@ -181,3 +181,49 @@ fn test_other_address() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_mapped_address_socket_addr() -> Result<()> {
+    for addr in ["122.12.34.5:5412", "[::1]:5412"] {
+        let addr: SocketAddr = addr.parse().unwrap();
+
+        let mut m = Message::new();
+        MappedAddress::from(addr).add_to(&mut m)?;
+
+        let mut got = MappedAddress::default();
+        got.get_from(&m)?;
+        assert_eq!(got.socket_addr(), addr);
+        assert_eq!(SocketAddr::from(&got), addr);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_response_origin_other_address() -> Result<()> {
+    let origin: SocketAddr = "122.12.34.5:3478".parse().unwrap();
+    let other: SocketAddr = "[::1]:3479".parse().unwrap();
+
+    let mut m = Message::new();
+    ResponseOrigin::from(origin).add_to(&mut m)?;
+    OtherAddress::from(other).add_to(&mut m)?;
+    assert!(m.contains(ATTR_RESPONSE_ORIGIN));
+    assert!(m.contains(ATTR_OTHER_ADDRESS));
+    assert!(!m.contains(ATTR_MAPPED_ADDRESS));
+    m.write_header();
+
+    let mut decoded = Message::new();
+    decoded.write(&m.raw)?;
+
+    let mut got_origin = ResponseOrigin::default();
+    got_origin.get_from(&decoded)?;
+    assert_eq!(got_origin.socket_addr(), origin);
+    assert_eq!(got_origin.to_string(), "122.12.34.5:3478");
+
+    let mut got_other = OtherAddress::default();
+    got_other.get_from(&decoded)?;
+    assert_eq!(got_other.socket_addr(), other);
+    assert_eq!(got_other.to_string(), "[::1]:3479");
+
+    Ok(())
+}
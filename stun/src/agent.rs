@@ -8,8 +8,10 @@ use rand::Rng;
 use tokio::sync::mpsc;
 use tokio::time::Instant;
 
+use crate::attributes::ATTR_FINGERPRINT;
 use crate::client::ClientTransaction;
 use crate::error::*;
+use crate::fingerprint::FINGERPRINT;
 use crate::message::*;
 
 /// Handler handles state changes of transaction.
@@ -37,6 +39,8 @@ pub struct Agent {
     closed: bool,
     /// handles transactions
     handler: Handler,
+    /// handles the requests and indications fed by process_datagram
+    unsolicited_handler: Handler,
 }
 
 #[derive(Debug, Clone)]
@@ -120,6 +124,7 @@ impl Agent {
             transactions: HashMap::new(),
             closed: false,
             handler,
+            unsolicited_handler: noop_handler(),
         }
     }
 
@@ -164,6 +169,40 @@ impl Agent {
         Ok(())
     }
 
+    /// process_datagram processes a datagram received on a socket shared by
+    /// STUN and other protocols, such as ICE and application data. It returns
+    /// false if the datagram is not a STUN message, to be handled by the
+    /// caller: if it lacks the magic cookie, fails to decode or has a
+    /// FINGERPRINT which does not match.
+    ///
+    /// Responses are passed to handler if they match a transaction in
+    /// progress, and dropped otherwise, while requests and indications are
+    /// passed to the unsolicited handler.
+    pub fn process_datagram(&mut self, b: &[u8]) -> Result<bool> {
+        if self.closed {
+            return Err(Error::ErrAgentClosed);
+        }
+
+        let Some(message) = decode_datagram(b) else {
+            return Ok(false);
+        };
+
+        let handler = match message.typ.class {
+            CLASS_REQUEST | CLASS_INDICATION => &self.unsolicited_handler,
+            _ if self.transactions.remove(&message.transaction_id).is_some() => &self.handler,
+            _ => return Ok(true),
+        };
+
+        if let Some(handler) = handler {
+            handler.send(Event {
+                event_type: EventType::Callback(message.transaction_id),
+                event_body: Ok(message),
+            })?;
+        }
+
+        Ok(true)
+    }
+
     /// close terminates all transactions with ErrAgentClosed and renders Agent to
     /// closed state.
     pub fn close(&mut self) -> Result<()> {
@@ -183,6 +222,7 @@ impl Agent {
         self.transactions = HashMap::new();
         self.closed = true;
         self.handler = noop_handler();
+        self.unsolicited_handler = noop_handler();
 
         Ok(())
     }
@@ -263,6 +303,17 @@ impl Agent {
         Ok(())
     }
 
+    /// set_unsolicited_handler sets the handler of the requests and
+    /// indications fed by process_datagram to h.
+    pub fn set_unsolicited_handler(&mut self, h: Handler) -> Result<()> {
+        if self.closed {
+            return Err(Error::ErrAgentClosed);
+        }
+        self.unsolicited_handler = h;
+
+        Ok(())
+    }
+
     pub(crate) async fn run(mut agent: Agent, mut rx: mpsc::Receiver<ClientAgent>) {
         while let Some(client_agent) = rx.recv().await {
             let result = match client_agent {
@@ -281,3 +332,20 @@ impl Agent {
         }
     }
 }
+
+/// decode_datagram decodes a datagram as a STUN message, if it starts with
+/// the two zero bits and the magic cookie of STUN messages, as described in
+/// RFC 7983 Section 7, and its FINGERPRINT, if any, matches.
+fn decode_datagram(b: &[u8]) -> Option<Message> {
+    if !is_message(b) || b[0] & 0xc0 != 0 {
+        return None;
+    }
+
+    let mut message = Message::new();
+    message.write(b).ok()?;
+    if message.contains(ATTR_FINGERPRINT) && FINGERPRINT.check(&message).is_err() {
+        return None;
+    }
+
+    Some(message)
+}
@@ -193,3 +193,62 @@ async fn test_agent_stop() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_agent_process_datagram() -> Result<()> {
+    let (handler_tx, mut handler_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (unsolicited_tx, mut unsolicited_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut a = Agent::new(Some(Arc::new(handler_tx)));
+    a.set_unsolicited_handler(Some(Arc::new(unsolicited_tx)))?;
+
+    let build = |setters: Vec<Box<dyn Setter>>| -> Result<Message> {
+        let mut m = Message::new();
+        m.build(&setters)?;
+        Ok(m)
+    };
+
+    // a response to a transaction in progress goes to the transaction handler
+    let id = TransactionId::new();
+    a.start(id, Instant::now().add(Duration::from_secs(10)))?;
+    let response = build(vec![
+        Box::new(id),
+        Box::new(BINDING_SUCCESS),
+        Box::new(FINGERPRINT),
+    ])?;
+    assert!(a.process_datagram(&response.raw)?);
+    let e = handler_rx.try_recv().expect("response should be handled");
+    assert_eq!(e.event_body?.transaction_id, id);
+
+    // the transaction is over: a retransmitted response is dropped
+    assert!(a.process_datagram(&response.raw)?);
+    assert!(handler_rx.try_recv().is_err());
+
+    // requests and indications go to the unsolicited handler
+    let request = build(vec![
+        Box::new(TransactionId::new()),
+        Box::new(BINDING_REQUEST),
+    ])?;
+    assert!(a.process_datagram(&request.raw)?);
+    let e = unsolicited_rx
+        .try_recv()
+        .expect("request should be handled");
+    assert_eq!(e.event_body?.transaction_id, request.transaction_id);
+    assert!(handler_rx.try_recv().is_err());
+
+    // datagrams of other protocols are left to the caller
+    let mut corrupted = response.raw.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 0xff;
+    let mut rtp = vec![0x80, 0x60, 0x00, 0x01];
+    rtp.extend_from_slice(&response.raw[4..]);
+    for datagram in [&b"not stun"[..], &corrupted, &rtp] {
+        assert!(!a.process_datagram(datagram)?);
+    }
+    assert!(handler_rx.try_recv().is_err());
+    assert!(unsolicited_rx.try_recv().is_err());
+
+    a.close()?;
+    assert_eq!(a.process_datagram(&request.raw), Err(Error::ErrAgentClosed));
+
+    Ok(())
+}
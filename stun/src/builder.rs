@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod builder_test;
+
+use crate::agent::TransactionId;
+use crate::attributes::*;
+use crate::error::*;
+use crate::fingerprint::FINGERPRINT;
+use crate::integrity::*;
+use crate::message::*;
+use crate::textattrs::*;
+
+/// MessageBuilder builds a message from its attributes, laying them out in the
+/// order RFC 8489 Section 14 requires whatever the order they were given in:
+/// MESSAGE-INTEGRITY, MESSAGE-INTEGRITY-SHA256 and FINGERPRINT come last, in
+/// this order, after the other attributes.
+///
+/// ```
+/// # use stun::message::*;
+/// # use stun::integrity::MessageIntegrity;
+/// let m = Message::binding_request()
+///     .with_username("user")
+///     .with_integrity(MessageIntegrity::new_short_term_integrity("pass".to_owned()))
+///     .with_fingerprint()
+///     .build()?;
+/// # Ok::<(), stun::Error>(())
+/// ```
+pub struct MessageBuilder {
+    typ: MessageType,
+    transaction_id: TransactionId,
+    setters: Vec<Box<dyn Setter>>,
+    integrity: Option<MessageIntegrity>,
+    integrity_sha256: Option<MessageIntegritySha256>,
+    fingerprint: bool,
+}
+
+impl MessageBuilder {
+    /// new returns a builder of a message of type typ, with a new random
+    /// transaction ID.
+    pub fn new(typ: MessageType) -> Self {
+        MessageBuilder {
+            typ,
+            transaction_id: TransactionId::new(),
+            setters: vec![],
+            integrity: None,
+            integrity_sha256: None,
+            fingerprint: false,
+        }
+    }
+
+    pub fn with_transaction_id(mut self, transaction_id: TransactionId) -> Self {
+        self.transaction_id = transaction_id;
+        self
+    }
+
+    /// with_attribute adds the attribute of setter, which must not be one of
+    /// the integrity attributes or FINGERPRINT, as they are only ordered when
+    /// set with their own methods.
+    pub fn with_attribute(mut self, setter: impl Setter + 'static) -> Self {
+        self.setters.push(Box::new(setter));
+        self
+    }
+
+    pub fn with_username(self, username: &str) -> Self {
+        self.with_attribute(Username::new(ATTR_USERNAME, username.to_owned()))
+    }
+
+    pub fn with_realm(self, realm: &str) -> Self {
+        self.with_attribute(Realm::new(ATTR_REALM, realm.to_owned()))
+    }
+
+    pub fn with_nonce(self, nonce: &str) -> Self {
+        self.with_attribute(Nonce::new(ATTR_NONCE, nonce.to_owned()))
+    }
+
+    pub fn with_software(self, software: &str) -> Self {
+        self.with_attribute(Software::new(ATTR_SOFTWARE, software.to_owned()))
+    }
+
+    /// with_integrity protects the message with MESSAGE-INTEGRITY.
+    pub fn with_integrity(mut self, integrity: MessageIntegrity) -> Self {
+        self.integrity = Some(integrity);
+        self
+    }
+
+    /// with_integrity_sha256 protects the message with
+    /// MESSAGE-INTEGRITY-SHA256.
+    pub fn with_integrity_sha256(mut self, integrity: MessageIntegritySha256) -> Self {
+        self.integrity_sha256 = Some(integrity);
+        self
+    }
+
+    pub fn with_fingerprint(mut self) -> Self {
+        self.fingerprint = true;
+        self
+    }
+
+    /// build returns the encoded message. It fails with
+    /// ErrUnorderedAttribute if a setter of with_attribute added one of the
+    /// attributes which have to come last.
+    pub fn build(self) -> Result<Message> {
+        let mut m = Message::new();
+        m.typ = self.typ;
+        m.transaction_id = self.transaction_id;
+        m.write_header();
+
+        for setter in &self.setters {
+            setter.add_to(&mut m)?;
+        }
+        if [
+            ATTR_MESSAGE_INTEGRITY,
+            ATTR_MESSAGE_INTEGRITY_SHA256,
+            ATTR_FINGERPRINT,
+        ]
+        .iter()
+        .any(|t| m.contains(*t))
+        {
+            return Err(Error::ErrUnorderedAttribute);
+        }
+
+        if let Some(integrity) = &self.integrity {
+            integrity.add_to(&mut m)?;
+        }
+        if let Some(integrity) = &self.integrity_sha256 {
+            integrity.add_to(&mut m)?;
+        }
+        if self.fingerprint {
+            FINGERPRINT.add_to(&mut m)?;
+        }
+
+        Ok(m)
+    }
+}
+
+impl Message {
+    /// builder returns a builder of a message of type typ.
+    pub fn builder(typ: MessageType) -> MessageBuilder {
+        MessageBuilder::new(typ)
+    }
+
+    /// binding_request returns a builder of a Binding request.
+    pub fn binding_request() -> MessageBuilder {
+        MessageBuilder::new(BINDING_REQUEST)
+    }
+}
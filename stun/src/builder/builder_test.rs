@@ -0,0 +1,67 @@
+use super::*;
+use crate::fingerprint::FINGERPRINT;
+
+#[test]
+fn test_message_builder_order() -> Result<()> {
+    let integrity = MessageIntegrity::new_short_term_integrity("pass".to_owned());
+    let integrity_sha256 = MessageIntegritySha256::new_short_term_integrity("pass".to_owned());
+
+    // Integrity and fingerprint come last, whatever the order they are set in.
+    let m = Message::binding_request()
+        .with_fingerprint()
+        .with_integrity_sha256(integrity_sha256.clone())
+        .with_integrity(integrity.clone())
+        .with_username("user")
+        .with_software("software")
+        .build()?;
+    assert_eq!(m.typ, BINDING_REQUEST);
+    let types: Vec<AttrType> = m.attributes.0.iter().map(|a| a.typ).collect();
+    assert_eq!(
+        types,
+        vec![
+            ATTR_USERNAME,
+            ATTR_SOFTWARE,
+            ATTR_MESSAGE_INTEGRITY,
+            ATTR_MESSAGE_INTEGRITY_SHA256,
+            ATTR_FINGERPRINT,
+        ]
+    );
+
+    let mut decoded = Message::new();
+    decoded.write(&m.raw)?;
+    assert_eq!(decoded.transaction_id, m.transaction_id);
+    integrity.check(&mut decoded)?;
+    integrity_sha256.check(&mut decoded)?;
+    FINGERPRINT.check(&decoded)?;
+    let username = TextAttribute::get_from_as(&decoded, ATTR_USERNAME)?;
+    assert_eq!(username.text, "user");
+
+    Ok(())
+}
+
+#[test]
+fn test_message_builder_transaction_id() -> Result<()> {
+    let id = TransactionId([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+    let m = Message::builder(BINDING_SUCCESS)
+        .with_transaction_id(id)
+        .build()?;
+
+    let mut decoded = Message::new();
+    decoded.write(&m.raw)?;
+    assert_eq!(decoded.typ, BINDING_SUCCESS);
+    assert_eq!(decoded.transaction_id, id);
+    assert!(decoded.attributes.0.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_message_builder_unordered_attribute() -> Result<()> {
+    let result = Message::binding_request()
+        .with_attribute(FINGERPRINT)
+        .with_username("user")
+        .build();
+    assert_eq!(result.err(), Some(Error::ErrUnorderedAttribute));
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ mod client_test;
 use std::collections::HashMap;
 use std::io::BufReader;
 use std::marker::{Send, Sync};
+use std::net::SocketAddr;
 use std::ops::Add;
 use std::sync::Arc;
 
@@ -13,11 +14,15 @@ use util::Conn;
 
 use crate::agent::*;
 use crate::error::*;
+use crate::error_code::*;
 use crate::message::*;
+use crate::xoraddr::*;
 
 const DEFAULT_TIMEOUT_RATE: Duration = Duration::from_millis(5);
-const DEFAULT_RTO: Duration = Duration::from_millis(300);
+// The initial RTO, Rc and Rm recommended by RFC 5389 Section 7.2.1.
+const DEFAULT_RTO: Duration = Duration::from_millis(500);
 const DEFAULT_MAX_ATTEMPTS: u32 = 7;
+const DEFAULT_LAST_TIMEOUT_MULTIPLIER: u32 = 16;
 const DEFAULT_MAX_BUFFER_SIZE: usize = 8;
 
 /// Collector calls function f with constant rate.
@@ -85,6 +90,8 @@ pub struct ClientTransaction {
     handler: Handler,
     start: Instant,
     rto: Duration,
+    max_attempts: u32,
+    last_timeout_multiplier: u32,
     raw: Vec<u8>,
 }
 
@@ -100,7 +107,22 @@ impl ClientTransaction {
     }
 
     pub(crate) fn next_timeout(&self, now: Instant) -> Instant {
-        now.add((self.attempt + 1) * self.rto)
+        now.add(self.timeout())
+    }
+
+    /// timeout returns how long to wait for a response to the current
+    /// attempt: the RTO, doubled on each retransmission, or Rm times the RTO
+    /// after the last one, as described in RFC 5389 Section 7.2.1.
+    pub(crate) fn timeout(&self) -> Duration {
+        if self.is_last_attempt() {
+            self.rto.saturating_mul(self.last_timeout_multiplier)
+        } else {
+            self.rto.saturating_mul(2u32.saturating_pow(self.attempt))
+        }
+    }
+
+    pub(crate) fn is_last_attempt(&self) -> bool {
+        self.attempt + 1 >= self.max_attempts
     }
 }
 
@@ -109,6 +131,7 @@ struct ClientSettings {
     rto: Duration,
     rto_rate: Duration,
     max_attempts: u32,
+    last_timeout_multiplier: u32,
     closed: bool,
     //handler: Handler,
     collector: Option<Box<dyn Collector + Send>>,
@@ -122,6 +145,7 @@ impl Default for ClientSettings {
             rto: DEFAULT_RTO,
             rto_rate: DEFAULT_TIMEOUT_RATE,
             max_attempts: DEFAULT_MAX_ATTEMPTS,
+            last_timeout_multiplier: DEFAULT_LAST_TIMEOUT_MULTIPLIER,
             closed: false,
             //handler: None,
             collector: None,
@@ -150,6 +174,20 @@ impl ClientBuilder {
         self
     }
 
+    /// with_max_attempts sets Rc, the count of transmissions of a request
+    /// before its transaction fails, the first one included.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.settings.max_attempts = max_attempts;
+        self
+    }
+
+    /// with_last_timeout_multiplier sets Rm, how many RTOs to wait for a
+    /// response after the last transmission of a request.
+    pub fn with_last_timeout_multiplier(mut self, multiplier: u32) -> Self {
+        self.settings.last_timeout_multiplier = multiplier;
+        self
+    }
+
     /// with_timeout_rate sets RTO timer minimum resolution.
     pub fn with_timeout_rate(mut self, d: Duration) -> Self {
         self.settings.rto_rate = d;
@@ -175,15 +213,14 @@ impl ClientBuilder {
         self
     }
 
-    /// with_no_retransmit disables retransmissions and sets RTO to
-    /// DEFAULT_MAX_ATTEMPTS * DEFAULT_RTO which will be effectively time out
-    /// if not set.
+    /// with_no_retransmit disables retransmissions, waiting for a response as
+    /// long as the default retransmissions would last, 39.5s with the default
+    /// RTO, as described in RFC 5389 Section 7.2.2.
     /// Useful for TCP connections where transport handles RTO.
     pub fn with_no_retransmit(mut self) -> Self {
-        self.settings.max_attempts = 0;
-        if self.settings.rto == Duration::from_secs(0) {
-            self.settings.rto = DEFAULT_MAX_ATTEMPTS * DEFAULT_RTO;
-        }
+        self.settings.max_attempts = 1;
+        self.settings.last_timeout_multiplier =
+            (1 << (DEFAULT_MAX_ATTEMPTS - 1)) - 1 + DEFAULT_LAST_TIMEOUT_MULTIPLIER;
         self
     }
 
@@ -281,7 +318,6 @@ impl Client {
         mut handler_rx: mpsc::UnboundedReceiver<Event>,
         client_agent_tx: Arc<mpsc::Sender<ClientAgent>>,
         mut t: HashMap<TransactionId, ClientTransaction>,
-        max_attempts: u32,
     ) {
         tokio::spawn(async move {
             while let Some(event) = handler_rx.recv().await {
@@ -308,7 +344,7 @@ impl Client {
                             continue;
                         };
 
-                        if ct.attempt >= max_attempts || event.event_body.is_ok() {
+                        if ct.is_last_attempt() || event.event_body.is_ok() {
                             if let Some(handler) = ct.handler {
                                 let _ = handler.send(event);
                             }
@@ -405,7 +441,6 @@ impl Client {
             handler_rx,
             Arc::clone(&client_agent_tx),
             t,
-            self.settings.max_attempts,
         );
 
         let agent = Agent::new(Some(handler_tx));
@@ -441,6 +476,8 @@ impl Client {
                 handler,
                 start: Instant::now(),
                 rto: self.settings.rto,
+                max_attempts: self.settings.max_attempts,
+                last_timeout_multiplier: self.settings.last_timeout_multiplier,
                 raw: m.raw.clone(),
             };
             let d = t.next_timeout(t.start);
@@ -470,4 +507,39 @@ impl Client {
 
         Ok(())
     }
+
+    /// binding_request sends a Binding request to the STUN server the
+    /// connection is connected to, retransmitting it until a response is
+    /// received, and returns the XOR-MAPPED-ADDRESS of the response: the
+    /// address of the client as seen by the server. A server redirecting the
+    /// client with a 300 (Try Alternate) response makes it fail with
+    /// ErrTryAlternate, which holds the ALTERNATE-SERVER address to retry the
+    /// request against on a new connection.
+    pub async fn binding_request(&mut self) -> Result<SocketAddr> {
+        let msg = Message::binding_request().build()?;
+
+        let (handler_tx, mut handler_rx) = mpsc::unbounded_channel();
+        self.send(&msg, Some(Arc::new(handler_tx))).await?;
+
+        let Some(event) = handler_rx.recv().await else {
+            return Err(Error::ErrClientClosed);
+        };
+        let response = event.event_body?;
+
+        if let Some(alternate) = get_alternate_server(&response) {
+            return Err(Error::ErrTryAlternate(alternate));
+        }
+        if response.typ.class == CLASS_ERROR_RESPONSE {
+            let mut error_code = ErrorCodeAttribute::default();
+            error_code.get_from(&response)?;
+            return Err(Error::ErrErrorResponse(
+                error_code.code.0,
+                String::from_utf8_lossy(&error_code.reason).into_owned(),
+            ));
+        }
+
+        let mut addr = XorMappedAddress::default();
+        addr.get_from(&response)?;
+        Ok(addr.socket_addr())
+    }
 }
@@ -1,3 +1,5 @@
+use tokio::net::UdpSocket;
+
 use super::*;
 
 #[test]
@@ -9,4 +11,158 @@ fn ensure_client_settings_is_send() {
 
 fn ensure_send<T: Send>(_: T) {}
 
-//TODO: add more client tests
+fn client_transaction(max_attempts: u32) -> ClientTransaction {
+    ClientTransaction {
+        id: TransactionId::new(),
+        attempt: 0,
+        calls: 0,
+        handler: None,
+        start: Instant::now(),
+        rto: DEFAULT_RTO,
+        max_attempts,
+        last_timeout_multiplier: DEFAULT_LAST_TIMEOUT_MULTIPLIER,
+        raw: vec![],
+    }
+}
+
+#[test]
+fn test_client_transaction_timeout() {
+    let mut ct = client_transaction(DEFAULT_MAX_ATTEMPTS);
+
+    // RFC 5389 Section 7.2.1: with an RTO of 500ms, requests are sent at 0ms,
+    // 500ms, 1500ms, 3500ms, 7500ms, 15500ms and 31500ms, and fail at 39500ms.
+    let mut elapsed = Duration::from_secs(0);
+    let mut sent_at = vec![];
+    loop {
+        sent_at.push(elapsed.as_millis());
+        elapsed += ct.timeout();
+        if ct.is_last_attempt() {
+            break;
+        }
+        ct.attempt += 1;
+    }
+
+    assert_eq!(sent_at, vec![0, 500, 1500, 3500, 7500, 15500, 31500]);
+    assert_eq!(elapsed, Duration::from_millis(39500));
+}
+
+#[test]
+fn test_client_no_retransmit_timeout() {
+    let settings = ClientBuilder::new().with_no_retransmit().settings;
+    let ct = ClientTransaction {
+        max_attempts: settings.max_attempts,
+        last_timeout_multiplier: settings.last_timeout_multiplier,
+        ..client_transaction(0)
+    };
+
+    assert!(ct.is_last_attempt());
+    assert_eq!(ct.timeout(), Duration::from_millis(39500));
+}
+
+/// serve_binding answers the Binding requests received by conn with their
+/// source address, ignoring the first `drop` of them.
+async fn serve_binding(conn: UdpSocket, mut drop: usize) -> Result<()> {
+    let mut buf = vec![0; 1024];
+    loop {
+        let (n, src) = conn.recv_from(&mut buf).await?;
+        let mut request = Message::new();
+        request.write(&buf[..n])?;
+        if drop > 0 {
+            drop -= 1;
+            continue;
+        }
+
+        let mut response = Message::new();
+        response.build(&[
+            Box::new(request.clone()),
+            Box::new(BINDING_SUCCESS),
+            Box::new(XorMappedAddress::from(src)),
+        ])?;
+        conn.send_to(&response.raw, src).await?;
+    }
+}
+
+async fn binding_client(builder: ClientBuilder, drop: usize) -> Result<(Client, SocketAddr)> {
+    let server = UdpSocket::bind("127.0.0.1:0").await?;
+    let server_addr = server.local_addr()?;
+    tokio::spawn(serve_binding(server, drop));
+
+    let conn = UdpSocket::bind("127.0.0.1:0").await?;
+    conn.connect(server_addr).await?;
+    let local_addr = conn.local_addr()?;
+    let client = builder.with_conn(Arc::new(conn)).build()?;
+
+    Ok((client, local_addr))
+}
+
+#[tokio::test]
+async fn test_client_binding_request() -> Result<()> {
+    let (mut client, local_addr) = binding_client(ClientBuilder::new(), 0).await?;
+
+    assert_eq!(client.binding_request().await?, local_addr);
+
+    client.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_binding_request_retransmit() -> Result<()> {
+    let builder = ClientBuilder::new().with_rto(Duration::from_millis(20));
+    let (mut client, local_addr) = binding_client(builder, 2).await?;
+
+    assert_eq!(client.binding_request().await?, local_addr);
+
+    client.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_binding_request_timeout() -> Result<()> {
+    let builder = ClientBuilder::new()
+        .with_rto(Duration::from_millis(10))
+        .with_max_attempts(3)
+        .with_last_timeout_multiplier(2);
+    let (mut client, _) = binding_client(builder, 3).await?;
+
+    assert_eq!(
+        client.binding_request().await,
+        Err(Error::ErrTransactionTimeOut)
+    );
+
+    client.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_binding_request_try_alternate() -> Result<()> {
+    let alternate: SocketAddr = "203.0.113.7:3478".parse().unwrap();
+
+    let server = UdpSocket::bind("127.0.0.1:0").await?;
+    let server_addr = server.local_addr()?;
+    tokio::spawn(async move {
+        let mut buf = vec![0; 1024];
+        let (n, src) = server.recv_from(&mut buf).await?;
+        let mut request = Message::new();
+        request.write(&buf[..n])?;
+
+        let response = build_try_alternate_response(&request, alternate)?;
+        server.send_to(&response.raw, src).await?;
+        Result::<()>::Ok(())
+    });
+
+    let conn = UdpSocket::bind("127.0.0.1:0").await?;
+    conn.connect(server_addr).await?;
+    let mut client = ClientBuilder::new().with_conn(Arc::new(conn)).build()?;
+
+    assert_eq!(
+        client.binding_request().await,
+        Err(Error::ErrTryAlternate(alternate))
+    );
+
+    client.close().await?;
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use std::io;
+use std::net::SocketAddr;
 use std::string::FromUtf8Error;
 
 use thiserror::Error;
@@ -39,6 +40,8 @@ pub enum Error {
     ErrFingerprintMismatch,
     #[error("FINGERPRINT before MESSAGE-INTEGRITY attribute")]
     ErrFingerprintBeforeIntegrity,
+    #[error("integrity and fingerprint attributes must be set with the builder methods")]
+    ErrUnorderedAttribute,
     #[error("bad UNKNOWN-ATTRIBUTES size")]
     ErrBadUnknownAttrsSize,
     #[error("invalid length of IP value")]
@@ -47,6 +50,14 @@ pub enum Error {
     ErrNoConnection,
     #[error("client is closed")]
     ErrClientClosed,
+    #[error("server is closed")]
+    ErrServerClosed,
+    #[error("error response {0}: {1}")]
+    ErrErrorResponse(u16, String),
+    #[error("redirected to alternate server {0}")]
+    ErrTryAlternate(SocketAddr),
+    #[error("server does not support NAT behavior discovery")]
+    ErrNoOtherAddress,
     #[error("no agent is set")]
     ErrNoAgent,
     #[error("collector is closed")]
@@ -59,6 +70,12 @@ pub enum Error {
     ErrSchemeType,
     #[error("invalid hostname")]
     ErrHost,
+    #[error("queries not supported in stun address")]
+    ErrStunQuery,
+    #[error("invalid query")]
+    ErrInvalidQuery,
+    #[error("invalid transport protocol type")]
+    ErrProtoType,
     #[error("{0}")]
     Other(String),
     #[error("url parse: {0}")]
@@ -1,10 +1,17 @@
+#[cfg(test)]
+mod error_code_test;
+
 use std::collections::HashMap;
 use std::fmt;
+use std::net::SocketAddr;
 
+use crate::addr::*;
 use crate::attributes::*;
 use crate::checks::*;
 use crate::error::*;
 use crate::message::*;
+use crate::textattrs::*;
+use crate::uattrs::*;
 
 // ErrorCodeAttribute represents ERROR-CODE attribute.
 //
@@ -44,8 +51,8 @@ impl Setter for ErrorCodeAttribute {
 
         let mut value: Vec<u8> = Vec::with_capacity(ERROR_CODE_REASON_MAX_B);
 
-        let number = (self.code.0 % ERROR_CODE_MODULO) as u8; // error code modulo 100
-        let class = (self.code.0 / ERROR_CODE_MODULO) as u8; // hundred digit
+        let number = self.code.number(); // error code modulo 100
+        let class = self.code.class(); // hundred digit
         value.extend_from_slice(&[0, 0]);
         value.push(class); // [ERROR_CODE_CLASS_BYTE]
         value.push(number); //[ERROR_CODE_NUMBER_BYTE] =
@@ -77,7 +84,7 @@ impl Getter for ErrorCodeAttribute {
 }
 
 // ErrorCode is code for ERROR-CODE attribute.
-#[derive(PartialEq, Eq, Hash, Copy, Clone, Default)]
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Default, Debug)]
 pub struct ErrorCode(pub u16);
 
 impl Setter for ErrorCode {
@@ -96,6 +103,23 @@ impl Setter for ErrorCode {
     }
 }
 
+impl ErrorCode {
+    // class returns the class of the code, its hundreds digit.
+    pub fn class(&self) -> u8 {
+        (self.0 / ERROR_CODE_MODULO) as u8
+    }
+
+    // number returns the number of the code in its class, its code modulo 100.
+    pub fn number(&self) -> u8 {
+        (self.0 % ERROR_CODE_MODULO) as u8
+    }
+
+    // reason returns the default reason phrase of the code, if any.
+    pub fn reason(&self) -> Option<&'static [u8]> {
+        ERROR_REASONS.get(self).map(|reason| reason.as_slice())
+    }
+}
+
 // Possible error codes.
 pub const CODE_TRY_ALTERNATE: ErrorCode = ErrorCode(300);
 pub const CODE_BAD_REQUEST: ErrorCode = ErrorCode(400);
@@ -160,3 +184,138 @@ lazy_static! {
         ].iter().cloned().collect();
 
 }
+
+// build_error_response builds the response to request with the ERROR-CODE of
+// code and its default reason, followed by the attributes of setters. The
+// response has the method and transaction ID of request.
+pub fn build_error_response(
+    request: &Message,
+    code: ErrorCode,
+    mut setters: Vec<Box<dyn Setter>>,
+) -> Result<Message> {
+    let mut attrs: Vec<Box<dyn Setter>> = vec![
+        Box::new(Message {
+            transaction_id: request.transaction_id,
+            ..Default::default()
+        }),
+        Box::new(MessageType::new(request.typ.method, CLASS_ERROR_RESPONSE)),
+        Box::new(code),
+    ];
+    attrs.append(&mut setters);
+
+    let mut response = Message::new();
+    response.build(&attrs)?;
+    Ok(response)
+}
+
+// build_try_alternate_response builds the 300 (Try Alternate) response
+// redirecting the client of request to the alternate server.
+//
+// RFC 5389 Section 11
+pub fn build_try_alternate_response(request: &Message, alternate: SocketAddr) -> Result<Message> {
+    let mut response = build_error_response(request, CODE_TRY_ALTERNATE, vec![])?;
+    AlternateServer::from(alternate).add_to_as(&mut response, ATTR_ALTERNATE_SERVER)?;
+    Ok(response)
+}
+
+// get_alternate_server returns the ALTERNATE-SERVER of a 300 (Try Alternate)
+// response, which the client should retry its request against, or None if
+// response is not such a redirection.
+//
+// RFC 5389 Section 11
+pub fn get_alternate_server(response: &Message) -> Option<SocketAddr> {
+    if response.typ.class != CLASS_ERROR_RESPONSE {
+        return None;
+    }
+
+    let mut code = ErrorCodeAttribute::default();
+    code.get_from(response).ok()?;
+    if code.code != CODE_TRY_ALTERNATE {
+        return None;
+    }
+
+    let mut alternate = AlternateServer::default();
+    alternate
+        .get_from_as(response, ATTR_ALTERNATE_SERVER)
+        .ok()?;
+    Some(alternate.socket_addr())
+}
+
+// build_bad_request_response builds the 400 (Bad Request) response to a
+// malformed request.
+pub fn build_bad_request_response(request: &Message) -> Result<Message> {
+    build_error_response(request, CODE_BAD_REQUEST, vec![])
+}
+
+// build_unauthorized_response builds the 401 (Unauthorized) response
+// challenging request for long-term credentials of realm, with a new nonce.
+//
+// With short-term credentials, as in ICE, the response has no REALM and NONCE:
+// build it with build_error_response(request, CODE_UNAUTHORIZED, vec![]).
+//
+// RFC 5389 Section 10.2.1.1
+pub fn build_unauthorized_response(
+    request: &Message,
+    realm: String,
+    nonce: String,
+) -> Result<Message> {
+    build_error_response(
+        request,
+        CODE_UNAUTHORIZED,
+        vec![
+            Box::new(Realm::new(ATTR_REALM, realm)),
+            Box::new(Nonce::new(ATTR_NONCE, nonce)),
+        ],
+    )
+}
+
+// build_unknown_attribute_response builds the 420 (Unknown Attribute) response
+// to a request with comprehension-required attributes of types the server does
+// not understand.
+//
+// RFC 5389 Section 7.3.1
+pub fn build_unknown_attribute_response(
+    request: &Message,
+    types: Vec<AttrType>,
+) -> Result<Message> {
+    build_error_response(
+        request,
+        CODE_UNKNOWN_ATTRIBUTE,
+        vec![Box::new(UnknownAttributes(types))],
+    )
+}
+
+// build_stale_nonce_response builds the 438 (Stale Nonce) response to a request
+// authenticated with an expired nonce, with a new nonce to retry with.
+//
+// RFC 5389 Section 10.2.3
+pub fn build_stale_nonce_response(
+    request: &Message,
+    realm: String,
+    nonce: String,
+) -> Result<Message> {
+    build_error_response(
+        request,
+        CODE_STALE_NONCE,
+        vec![
+            Box::new(Realm::new(ATTR_REALM, realm)),
+            Box::new(Nonce::new(ATTR_NONCE, nonce)),
+        ],
+    )
+}
+
+// build_alloc_quota_reached_response builds the 486 (Allocation Quota Reached)
+// response to an Allocate request of a user who has no allocation left.
+//
+// RFC 5766 Section 6.2
+pub fn build_alloc_quota_reached_response(request: &Message) -> Result<Message> {
+    build_error_response(request, CODE_ALLOC_QUOTA_REACHED, vec![])
+}
+
+// build_role_conflict_response builds the 487 (Role Conflict) response to an
+// ICE connectivity check of an agent having the same role as the responder.
+//
+// RFC 8445 Section 7.3.1.1
+pub fn build_role_conflict_response(request: &Message) -> Result<Message> {
+    build_error_response(request, CODE_ROLE_CONFLICT, vec![])
+}
@@ -0,0 +1,120 @@
+use super::*;
+use crate::agent::TransactionId;
+
+fn binding_request() -> Result<Message> {
+    let mut m = Message::new();
+    m.build(&[Box::new(BINDING_REQUEST), Box::new(TransactionId::new())])?;
+    Ok(m)
+}
+
+fn error_code_of(m: &Message) -> Result<ErrorCodeAttribute> {
+    let mut decoded = Message::new();
+    decoded.write(&m.raw)?;
+    assert_eq!(decoded.typ, BINDING_ERROR);
+    assert_eq!(decoded.transaction_id, m.transaction_id);
+
+    let mut attr = ErrorCodeAttribute::default();
+    attr.get_from(&decoded)?;
+    Ok(attr)
+}
+
+#[test]
+fn test_error_code_class_number() -> Result<()> {
+    assert_eq!(CODE_STALE_NONCE.class(), 4);
+    assert_eq!(CODE_STALE_NONCE.number(), 38);
+    assert_eq!(CODE_STALE_NONCE.reason(), Some(&b"Stale Nonce"[..]));
+    assert_eq!(ErrorCode(499).reason(), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_build_error_response() -> Result<()> {
+    let request = binding_request()?;
+
+    for (response, code) in [
+        (build_bad_request_response(&request)?, CODE_BAD_REQUEST),
+        (
+            build_alloc_quota_reached_response(&request)?,
+            CODE_ALLOC_QUOTA_REACHED,
+        ),
+        (build_role_conflict_response(&request)?, CODE_ROLE_CONFLICT),
+    ] {
+        assert_eq!(response.transaction_id, request.transaction_id);
+        let attr = error_code_of(&response)?;
+        assert_eq!(attr.code, code);
+        assert_eq!(Some(attr.reason.as_slice()), code.reason());
+    }
+
+    assert_eq!(
+        build_error_response(&request, ErrorCode(499), vec![]).err(),
+        Some(Error::ErrNoDefaultReason)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_build_unauthorized_response() -> Result<()> {
+    let request = binding_request()?;
+
+    for (response, code) in [
+        (
+            build_unauthorized_response(&request, "realm".to_owned(), "nonce".to_owned())?,
+            CODE_UNAUTHORIZED,
+        ),
+        (
+            build_stale_nonce_response(&request, "realm".to_owned(), "nonce".to_owned())?,
+            CODE_STALE_NONCE,
+        ),
+    ] {
+        assert_eq!(error_code_of(&response)?.code, code);
+
+        let mut realm = Realm::new(ATTR_REALM, String::new());
+        let mut nonce = Nonce::new(ATTR_NONCE, String::new());
+        realm.get_from(&response)?;
+        nonce.get_from(&response)?;
+        assert_eq!(realm.text, "realm");
+        assert_eq!(nonce.text, "nonce");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_build_unknown_attribute_response() -> Result<()> {
+    let request = binding_request()?;
+    let types = vec![AttrType(0x0033), AttrType(0x0034)];
+
+    let response = build_unknown_attribute_response(&request, types.clone())?;
+    assert_eq!(error_code_of(&response)?.code, CODE_UNKNOWN_ATTRIBUTE);
+
+    let mut unknown = UnknownAttributes(vec![]);
+    unknown.get_from(&response)?;
+    assert_eq!(unknown.0, types);
+
+    Ok(())
+}
+
+#[test]
+fn test_try_alternate_response() -> Result<()> {
+    let request = binding_request()?;
+    let alternate: SocketAddr = "203.0.113.7:3478".parse().unwrap();
+
+    let response = build_try_alternate_response(&request, alternate)?;
+    assert_eq!(error_code_of(&response)?.code, CODE_TRY_ALTERNATE);
+
+    let mut decoded = Message::new();
+    decoded.write(&response.raw)?;
+    assert_eq!(get_alternate_server(&decoded), Some(alternate));
+
+    // Neither other error responses, nor a MAPPED-ADDRESS, redirect the client.
+    let response = build_bad_request_response(&request)?;
+    assert_eq!(get_alternate_server(&response), None);
+
+    let mut response = build_error_response(&request, CODE_TRY_ALTERNATE, vec![])?;
+    MappedAddress::from(alternate).add_to(&mut response)?;
+    assert_eq!(get_alternate_server(&response), None);
+
+    Ok(())
+}
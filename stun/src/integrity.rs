@@ -4,12 +4,15 @@ mod integrity_test;
 use std::fmt;
 
 use md5::{Digest, Md5};
-use ring::hmac;
+use ring::{digest, hmac};
 
+use crate::agent::TransactionId;
 use crate::attributes::*;
 use crate::checks::*;
 use crate::error::*;
+use crate::fingerprint::{FINGERPRINT, FINGERPRINT_SIZE};
 use crate::message::*;
+use crate::textattrs::*;
 
 // separator for credentials.
 pub(crate) const CREDENTIALS_SEP: &str = ":";
@@ -116,3 +119,328 @@ impl MessageIntegrity {
         check_hmac(&v, &expected)
     }
 }
+
+// MessageIntegritySha256 represents MESSAGE-INTEGRITY-SHA256 attribute, the
+// HMAC-SHA256 of the message, which may follow MESSAGE-INTEGRITY and precede
+// FINGERPRINT.
+//
+// RFC 8489 Section 14.6
+#[derive(Default, Clone)]
+pub struct MessageIntegritySha256(pub Vec<u8>);
+
+fn new_hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mac = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::sign(&mac, message).as_ref().to_vec()
+}
+
+impl fmt::Display for MessageIntegritySha256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KEY: 0x{:x?}", self.0)
+    }
+}
+
+impl Setter for MessageIntegritySha256 {
+    // add_to adds MESSAGE-INTEGRITY-SHA256 attribute to message.
+    fn add_to(&self, m: &mut Message) -> Result<()> {
+        if m.contains(ATTR_FINGERPRINT) {
+            return Err(Error::ErrFingerprintBeforeIntegrity);
+        }
+        // As for MESSAGE-INTEGRITY, the length in the header covers the
+        // attribute when computing the HMAC.
+        let length = m.length;
+        m.length += (MESSAGE_INTEGRITY_SHA256_SIZE + ATTRIBUTE_HEADER_SIZE) as u32;
+        m.write_length();
+        let v = new_hmac_sha256(&self.0, &m.raw);
+        m.length = length;
+
+        m.add(ATTR_MESSAGE_INTEGRITY_SHA256, &v);
+
+        Ok(())
+    }
+}
+
+pub(crate) const MESSAGE_INTEGRITY_SHA256_SIZE: usize = 32;
+// shortest truncation of MESSAGE-INTEGRITY-SHA256 a receiver accepts.
+const MESSAGE_INTEGRITY_SHA256_MIN_SIZE: usize = 16;
+
+impl MessageIntegritySha256 {
+    // new_long_term_integrity returns new MessageIntegritySha256 with the key
+    // of long-term credentials hashed with the SHA-256 password algorithm.
+    // Password, username, and realm must be SASL-prepared.
+    pub fn new_long_term_integrity(username: String, realm: String, password: String) -> Self {
+        let s = [username, realm, password].join(CREDENTIALS_SEP);
+
+        MessageIntegritySha256(
+            digest::digest(&digest::SHA256, s.as_bytes())
+                .as_ref()
+                .to_vec(),
+        )
+    }
+
+    // new_short_term_integrity returns new MessageIntegritySha256 with key for
+    // short-term credentials. Password must be SASL-prepared.
+    pub fn new_short_term_integrity(password: String) -> Self {
+        MessageIntegritySha256(password.as_bytes().to_vec())
+    }
+
+    // check checks MESSAGE-INTEGRITY-SHA256 attribute, which may be truncated
+    // to no less than 16 bytes.
+    pub fn check(&self, m: &mut Message) -> Result<()> {
+        let v = m.get(ATTR_MESSAGE_INTEGRITY_SHA256)?;
+        if v.len() < MESSAGE_INTEGRITY_SHA256_MIN_SIZE
+            || v.len() > MESSAGE_INTEGRITY_SHA256_SIZE
+            || v.len() % PADDING != 0
+        {
+            return Err(Error::ErrAttributeSizeInvalid);
+        }
+
+        // Only FINGERPRINT may follow the attribute, and is not covered by
+        // the HMAC.
+        let length = m.length;
+        if m.contains(ATTR_FINGERPRINT) {
+            m.length -= (ATTRIBUTE_HEADER_SIZE + FINGERPRINT_SIZE) as u32;
+        }
+        m.write_length();
+        let start_of_hmac =
+            MESSAGE_HEADER_SIZE + m.length as usize - (ATTRIBUTE_HEADER_SIZE + v.len());
+        let expected = new_hmac_sha256(&self.0, &m.raw[..start_of_hmac]);
+        m.length = length;
+        m.write_length();
+        check_hmac(&v, &expected[..v.len()])
+    }
+}
+
+pub const PASSWORD_ALGORITHM_MD5: u16 = 0x0001;
+pub const PASSWORD_ALGORITHM_SHA256: u16 = 0x0002;
+
+// PasswordAlgorithm is a password algorithm and its parameters, used to hash
+// the long-term password into the key of the message integrity.
+//
+// RFC 8489 Section 18.5
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PasswordAlgorithm {
+    pub algorithm: u16,
+    pub parameters: Vec<u8>,
+}
+
+impl fmt::Display for PasswordAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.algorithm {
+            PASSWORD_ALGORITHM_MD5 => write!(f, "MD5"),
+            PASSWORD_ALGORITHM_SHA256 => write!(f, "SHA-256"),
+            algorithm => write!(f, "0x{algorithm:x}"),
+        }
+    }
+}
+
+// password algorithm and parameters length are 16 bit.
+const PASSWORD_ALGORITHM_HEADER_SIZE: usize = 4;
+
+impl PasswordAlgorithm {
+    fn encode(&self, v: &mut Vec<u8>) {
+        v.extend_from_slice(&self.algorithm.to_be_bytes());
+        v.extend_from_slice(&(self.parameters.len() as u16).to_be_bytes());
+        v.extend_from_slice(&self.parameters);
+        v.resize(
+            v.len() + nearest_padded_value_length(self.parameters.len()) - self.parameters.len(),
+            0,
+        );
+    }
+
+    // decode decodes the algorithm at the start of v, returning its padded size.
+    fn decode(v: &[u8]) -> Result<(Self, usize)> {
+        if v.len() < PASSWORD_ALGORITHM_HEADER_SIZE {
+            return Err(Error::ErrUnexpectedEof);
+        }
+        let algorithm = u16::from_be_bytes([v[0], v[1]]);
+        let length = u16::from_be_bytes([v[2], v[3]]) as usize;
+        let size = PASSWORD_ALGORITHM_HEADER_SIZE + nearest_padded_value_length(length);
+        if v.len() < PASSWORD_ALGORITHM_HEADER_SIZE + length {
+            return Err(Error::ErrUnexpectedEof);
+        }
+
+        Ok((
+            PasswordAlgorithm {
+                algorithm,
+                parameters: v
+                    [PASSWORD_ALGORITHM_HEADER_SIZE..PASSWORD_ALGORITHM_HEADER_SIZE + length]
+                    .to_vec(),
+            },
+            size,
+        ))
+    }
+}
+
+impl Setter for PasswordAlgorithm {
+    // add_to adds PASSWORD-ALGORITHM attribute to message.
+    fn add_to(&self, m: &mut Message) -> Result<()> {
+        let mut v = Vec::with_capacity(PASSWORD_ALGORITHM_HEADER_SIZE + self.parameters.len());
+        self.encode(&mut v);
+        m.add(ATTR_PASSWORD_ALGORITHM, &v);
+        Ok(())
+    }
+}
+
+impl Getter for PasswordAlgorithm {
+    // get_from decodes PASSWORD-ALGORITHM from message.
+    fn get_from(&mut self, m: &Message) -> Result<()> {
+        let v = m.get(ATTR_PASSWORD_ALGORITHM)?;
+        let (algorithm, _) = PasswordAlgorithm::decode(&v)?;
+        *self = algorithm;
+        Ok(())
+    }
+}
+
+// PasswordAlgorithms represents PASSWORD-ALGORITHMS attribute, the password
+// algorithms a server supports, in its order of preference.
+//
+// RFC 8489 Section 14.11
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PasswordAlgorithms(pub Vec<PasswordAlgorithm>);
+
+impl Setter for PasswordAlgorithms {
+    // add_to adds PASSWORD-ALGORITHMS attribute to message.
+    fn add_to(&self, m: &mut Message) -> Result<()> {
+        let mut v = Vec::with_capacity(PASSWORD_ALGORITHM_HEADER_SIZE * self.0.len());
+        for algorithm in &self.0 {
+            algorithm.encode(&mut v);
+        }
+        m.add(ATTR_PASSWORD_ALGORITHMS, &v);
+        Ok(())
+    }
+}
+
+impl Getter for PasswordAlgorithms {
+    // get_from decodes PASSWORD-ALGORITHMS from message.
+    fn get_from(&mut self, m: &Message) -> Result<()> {
+        let v = m.get(ATTR_PASSWORD_ALGORITHMS)?;
+        self.0.clear();
+        let mut first = 0;
+        while first < v.len() {
+            let (algorithm, size) = PasswordAlgorithm::decode(&v[first..])?;
+            self.0.push(algorithm);
+            first += size;
+        }
+        Ok(())
+    }
+}
+
+// UserHash represents USERHASH attribute, the SHA-256 of the username and
+// realm, which a client sends instead of USERNAME to keep it private.
+//
+// RFC 8489 Section 14.4
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct UserHash(pub Vec<u8>);
+
+pub(crate) const USER_HASH_SIZE: usize = 32;
+
+impl UserHash {
+    // new returns the UserHash of username and realm, which must be
+    // SASL-prepared.
+    pub fn new(username: &str, realm: &str) -> Self {
+        let s = [username, realm].join(CREDENTIALS_SEP);
+
+        UserHash(
+            digest::digest(&digest::SHA256, s.as_bytes())
+                .as_ref()
+                .to_vec(),
+        )
+    }
+}
+
+impl Setter for UserHash {
+    // add_to adds USERHASH attribute to message.
+    fn add_to(&self, m: &mut Message) -> Result<()> {
+        check_size(ATTR_USER_HASH, self.0.len(), USER_HASH_SIZE)?;
+        m.add(ATTR_USER_HASH, &self.0);
+        Ok(())
+    }
+}
+
+impl Getter for UserHash {
+    // get_from decodes USERHASH from message.
+    fn get_from(&mut self, m: &Message) -> Result<()> {
+        let v = m.get(ATTR_USER_HASH)?;
+        check_size(ATTR_USER_HASH, v.len(), USER_HASH_SIZE)?;
+        self.0 = v;
+        Ok(())
+    }
+}
+
+// Challenge is the REALM and NONCE of the 401 (Unauthorized) or 438 (Stale
+// Nonce) response of a server requiring requests to be authenticated with
+// long-term credentials.
+//
+// RFC 5389 Section 10.2
+#[derive(Clone)]
+pub struct Challenge {
+    pub realm: Realm,
+    pub nonce: Nonce,
+}
+
+impl Default for Challenge {
+    fn default() -> Self {
+        Challenge {
+            realm: Realm::new(ATTR_REALM, String::new()),
+            nonce: Nonce::new(ATTR_NONCE, String::new()),
+        }
+    }
+}
+
+impl Getter for Challenge {
+    // get_from decodes the REALM and NONCE of a response.
+    fn get_from(&mut self, m: &Message) -> Result<()> {
+        self.realm = Realm::get_from_as(m, ATTR_REALM)?;
+        self.nonce = Nonce::get_from_as(m, ATTR_NONCE)?;
+        Ok(())
+    }
+}
+
+impl Challenge {
+    // integrity returns the MessageIntegrity of the long-term credentials of
+    // username and password for the realm of the challenge.
+    pub fn integrity(&self, username: &str, password: &str) -> MessageIntegrity {
+        MessageIntegrity::new_long_term_integrity(
+            username.to_owned(),
+            self.realm.text.clone(),
+            password.to_owned(),
+        )
+    }
+
+    // authenticate rebuilds the request which got the challenge, authenticated
+    // with the long-term credentials of username and their integrity. The new
+    // request has a new transaction ID, the attributes of request, then
+    // USERNAME, REALM, NONCE and MESSAGE-INTEGRITY, and FINGERPRINT if request
+    // had one. Authentication attributes of request, if it was already
+    // authenticated with a stale nonce, are replaced.
+    pub fn authenticate(
+        &self,
+        request: &Message,
+        username: &str,
+        integrity: &MessageIntegrity,
+    ) -> Result<Message> {
+        let mut setters: Vec<Box<dyn Setter>> =
+            vec![Box::new(TransactionId::new()), Box::new(request.typ)];
+        for attr in &request.attributes.0 {
+            match attr.typ {
+                ATTR_USERNAME
+                | ATTR_REALM
+                | ATTR_NONCE
+                | ATTR_MESSAGE_INTEGRITY
+                | ATTR_FINGERPRINT => {}
+                _ => setters.push(Box::new(attr.clone())),
+            }
+        }
+        setters.push(Box::new(Username::new(ATTR_USERNAME, username.to_owned())));
+        setters.push(Box::new(self.realm.clone()));
+        setters.push(Box::new(self.nonce.clone()));
+        setters.push(Box::new(integrity.clone()));
+        if request.contains(ATTR_FINGERPRINT) {
+            setters.push(Box::new(FINGERPRINT));
+        }
+
+        let mut m = Message::new();
+        m.build(&setters)?;
+        Ok(m)
+    }
+}
@@ -1,8 +1,8 @@
 use super::*;
 use crate::agent::TransactionId;
 use crate::attributes::ATTR_SOFTWARE;
+use crate::error_code::*;
 use crate::fingerprint::FINGERPRINT;
-use crate::textattrs::TextAttribute;
 
 #[test]
 fn test_message_integrity_add_to_simple() -> Result<()> {
@@ -91,3 +91,214 @@ fn test_message_integrity_before_fingerprint() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_challenge_authenticate() -> Result<()> {
+    let mut request = Message::new();
+    request.build(&[
+        Box::new(TransactionId::new()),
+        Box::new(BINDING_REQUEST),
+        Box::new(TextAttribute::new(ATTR_SOFTWARE, "software".to_owned())),
+        Box::new(FINGERPRINT),
+    ])?;
+
+    let response = build_unauthorized_response(&request, "realm".to_owned(), "nonce".to_owned())?;
+    let mut challenge = Challenge::default();
+    challenge.get_from(&response)?;
+    assert_eq!(challenge.realm.text, "realm");
+    assert_eq!(challenge.nonce.text, "nonce");
+
+    let integrity = challenge.integrity("user", "pass");
+    assert_eq!(
+        integrity.0,
+        MessageIntegrity::new_long_term_integrity(
+            "user".to_owned(),
+            "realm".to_owned(),
+            "pass".to_owned()
+        )
+        .0
+    );
+
+    let check = |m: &Message, nonce: &str| -> Result<()> {
+        let mut decoded = Message::new();
+        decoded.write(&m.raw)?;
+        assert_eq!(decoded.typ, BINDING_REQUEST);
+        assert_ne!(decoded.transaction_id, request.transaction_id);
+        integrity.check(&mut decoded)?;
+        FINGERPRINT.check(&decoded)?;
+
+        let types: Vec<AttrType> = decoded.attributes.0.iter().map(|a| a.typ).collect();
+        assert_eq!(
+            types,
+            vec![
+                ATTR_SOFTWARE,
+                ATTR_USERNAME,
+                ATTR_REALM,
+                ATTR_NONCE,
+                ATTR_MESSAGE_INTEGRITY,
+                ATTR_FINGERPRINT
+            ]
+        );
+        assert_eq!(Nonce::get_from_as(&decoded, ATTR_NONCE)?.text, nonce);
+        assert_eq!(Username::get_from_as(&decoded, ATTR_USERNAME)?.text, "user");
+        Ok(())
+    };
+
+    let authenticated = challenge.authenticate(&request, "user", &integrity)?;
+    check(&authenticated, "nonce")?;
+
+    // a stale nonce is replaced in the authenticated request
+    let response =
+        build_stale_nonce_response(&authenticated, "realm".to_owned(), "nonce2".to_owned())?;
+    challenge.get_from(&response)?;
+    let authenticated = challenge.authenticate(&authenticated, "user", &integrity)?;
+    check(&authenticated, "nonce2")?;
+
+    // a response without challenge
+    let response = build_bad_request_response(&request)?;
+    assert_eq!(
+        challenge.get_from(&response).err(),
+        Some(Error::ErrAttributeNotFound)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_message_integrity_sha256() -> Result<()> {
+    let i = MessageIntegritySha256::new_long_term_integrity(
+        "user".to_owned(),
+        "realm".to_owned(),
+        "pass".to_owned(),
+    );
+    let expected = vec![
+        0x07, 0xe9, 0x34, 0x11, 0x7a, 0xbd, 0x40, 0x83, 0x6e, 0x7c, 0x63, 0x29, 0xb5, 0x47, 0x31,
+        0xb2, 0xb2, 0xd2, 0xa5, 0xf9, 0xa7, 0x1f, 0x54, 0x49, 0x22, 0xd7, 0x5e, 0x07, 0x30, 0xd8,
+        0x25, 0x1b,
+    ];
+    assert_eq!(i.0, expected, "{}", Error::ErrIntegrityMismatch);
+
+    // Both integrities, followed by FINGERPRINT.
+    let legacy = MessageIntegrity::new_long_term_integrity(
+        "user".to_owned(),
+        "realm".to_owned(),
+        "pass".to_owned(),
+    );
+    let mut m = Message::new();
+    m.build(&[
+        Box::new(TransactionId::new()),
+        Box::new(BINDING_REQUEST),
+        Box::new(TextAttribute::new(ATTR_SOFTWARE, "software".to_owned())),
+        Box::new(legacy.clone()),
+        Box::new(i.clone()),
+        Box::new(FINGERPRINT),
+    ])?;
+    assert_eq!(m.get(ATTR_MESSAGE_INTEGRITY_SHA256)?.len(), 32);
+
+    let mut d_m = Message::new();
+    d_m.write(&m.raw)?;
+    legacy.check(&mut d_m)?;
+    i.check(&mut d_m)?;
+    FINGERPRINT.check(&d_m)?;
+
+    let wrong = MessageIntegritySha256::new_short_term_integrity("pwd".to_owned());
+    assert_eq!(wrong.check(&mut d_m), Err(Error::ErrIntegrityMismatch));
+
+    assert_eq!(
+        i.add_to(&mut d_m),
+        Err(Error::ErrFingerprintBeforeIntegrity)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_message_integrity_sha256_truncated() -> Result<()> {
+    let i = MessageIntegritySha256::new_short_term_integrity("pwd".to_owned());
+    let mut m = Message::new();
+    m.build(&[Box::new(TransactionId::new()), Box::new(BINDING_REQUEST)])?;
+
+    // The HMAC covers the length of the attribute actually sent.
+    for size in [16, 20, 12] {
+        let mut t = m.clone();
+        t.length += (ATTRIBUTE_HEADER_SIZE + size) as u32;
+        t.write_length();
+        let mac = hmac::Key::new(hmac::HMAC_SHA256, &i.0);
+        let v = hmac::sign(&mac, &t.raw);
+        t.length -= (ATTRIBUTE_HEADER_SIZE + size) as u32;
+        t.add(ATTR_MESSAGE_INTEGRITY_SHA256, &v.as_ref()[..size]);
+
+        let result = i.check(&mut t);
+        if size < 16 {
+            assert_eq!(result, Err(Error::ErrAttributeSizeInvalid));
+        } else {
+            result?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_password_algorithms() -> Result<()> {
+    let algorithms = PasswordAlgorithms(vec![
+        PasswordAlgorithm {
+            algorithm: PASSWORD_ALGORITHM_SHA256,
+            parameters: vec![],
+        },
+        PasswordAlgorithm {
+            algorithm: 0x1234,
+            parameters: vec![1, 2, 3],
+        },
+        PasswordAlgorithm {
+            algorithm: PASSWORD_ALGORITHM_MD5,
+            parameters: vec![],
+        },
+    ]);
+    let mut m = Message::new();
+    algorithms.add_to(&mut m)?;
+    algorithms.0[0].add_to(&mut m)?;
+    assert_eq!(
+        m.get(ATTR_PASSWORD_ALGORITHMS)?,
+        vec![0, 2, 0, 0, 0x12, 0x34, 0, 3, 1, 2, 3, 0, 0, 1, 0, 0]
+    );
+    assert_eq!(m.get(ATTR_PASSWORD_ALGORITHM)?, vec![0, 2, 0, 0]);
+
+    let mut got = PasswordAlgorithms::default();
+    got.get_from(&m)?;
+    assert_eq!(got, algorithms);
+    let mut got = PasswordAlgorithm::default();
+    got.get_from(&m)?;
+    assert_eq!(got, algorithms.0[0]);
+    assert_eq!(got.to_string(), "SHA-256");
+
+    let mut m = Message::new();
+    m.add(ATTR_PASSWORD_ALGORITHMS, &[0, 2, 0, 4, 1]);
+    let mut got = PasswordAlgorithms::default();
+    assert_eq!(got.get_from(&m), Err(Error::ErrUnexpectedEof));
+
+    Ok(())
+}
+
+#[test]
+fn test_user_hash() -> Result<()> {
+    let h = UserHash::new("user", "realm");
+    let expected = vec![
+        0x6a, 0x30, 0x29, 0x11, 0x6b, 0x47, 0xaa, 0x98, 0xbc, 0xaa, 0x32, 0x53, 0x99, 0x73, 0x3d,
+        0xc1, 0xa2, 0x3c, 0xd5, 0x7e, 0x26, 0xb8, 0x1b, 0xef, 0x3f, 0xf6, 0x53, 0x1c, 0xe6, 0x24,
+        0xe2, 0xda,
+    ];
+    assert_eq!(h.0, expected);
+
+    let mut m = Message::new();
+    h.add_to(&mut m)?;
+    let mut got = UserHash::default();
+    got.get_from(&m)?;
+    assert_eq!(got, h);
+
+    assert_eq!(
+        UserHash(vec![0; 16]).add_to(&mut m),
+        Err(Error::ErrAttributeSizeInvalid)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,255 @@
+#[cfg(test)]
+mod nat_test;
+
+use std::fmt;
+use std::net::SocketAddr;
+
+use tokio::time::{self, Duration, Instant};
+use util::Conn;
+
+use crate::addr::*;
+use crate::attributes::*;
+use crate::checks::*;
+use crate::error::*;
+use crate::error_code::*;
+use crate::fingerprint::*;
+use crate::message::*;
+use crate::xoraddr::*;
+
+const CHANGE_IP: u32 = 0x04;
+const CHANGE_PORT: u32 = 0x02;
+const CHANGE_REQUEST_SIZE: usize = 4;
+
+// attempts of a discovery request before it is deemed to get no response.
+const NAT_DISCOVERY_MAX_ATTEMPTS: u32 = 3;
+
+/// ChangeRequest represents CHANGE-REQUEST attribute.
+///
+/// It asks the server to send its response from its alternate IP address
+/// and/or its alternate port, as given by its OTHER-ADDRESS.
+///
+/// RFC 5780 Section 7.2
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ChangeRequest {
+    pub change_ip: bool,
+    pub change_port: bool,
+}
+
+impl fmt::Display for ChangeRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "change ip: {}, change port: {}",
+            self.change_ip, self.change_port
+        )
+    }
+}
+
+impl Setter for ChangeRequest {
+    /// add_to adds CHANGE-REQUEST to message.
+    fn add_to(&self, m: &mut Message) -> Result<()> {
+        let mut flags = 0;
+        if self.change_ip {
+            flags |= CHANGE_IP;
+        }
+        if self.change_port {
+            flags |= CHANGE_PORT;
+        }
+        m.add(ATTR_CHANGE_REQUEST, &flags.to_be_bytes());
+        Ok(())
+    }
+}
+
+impl Getter for ChangeRequest {
+    /// get_from decodes CHANGE-REQUEST from message.
+    fn get_from(&mut self, m: &Message) -> Result<()> {
+        let v = m.get(ATTR_CHANGE_REQUEST)?;
+        check_size(ATTR_CHANGE_REQUEST, v.len(), CHANGE_REQUEST_SIZE)?;
+        let flags = u32::from_be_bytes([v[0], v[1], v[2], v[3]]);
+        self.change_ip = flags & CHANGE_IP != 0;
+        self.change_port = flags & CHANGE_PORT != 0;
+        Ok(())
+    }
+}
+
+/// EndpointDependency is the mapping or filtering behavior of a NAT, telling
+/// which part of the remote endpoint its mappings or filters depend on.
+///
+/// RFC 4787 Sections 4.1 and 5
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EndpointDependency {
+    /// The same mapping or filter applies to every remote endpoint.
+    EndpointIndependent,
+    /// A mapping or filter applies to the remote IP address only.
+    AddressDependent,
+    /// A mapping or filter applies to the remote IP address and port only.
+    AddressAndPortDependent,
+}
+
+impl fmt::Display for EndpointDependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            EndpointDependency::EndpointIndependent => "endpoint-independent",
+            EndpointDependency::AddressDependent => "address-dependent",
+            EndpointDependency::AddressAndPortDependent => "address and port-dependent",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// NatBehavior is the outcome of the NAT behavior discovery.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NatBehavior {
+    /// mapped_address is the address of the client as seen by the server.
+    pub mapped_address: SocketAddr,
+    /// behind_nat is false if mapped_address is the local address of the
+    /// client, whose filtering is then that of its firewall, if any.
+    pub behind_nat: bool,
+    pub mapping: EndpointDependency,
+    pub filtering: EndpointDependency,
+}
+
+/// nat_discovery classifies the mapping and filtering behavior of the NAT
+/// conn is behind, with the tests of RFC 5780 Sections 4.3 and 4.4 against a
+/// server supporting them, which has an OTHER-ADDRESS.
+///
+/// Each request is sent up to three times, rto apart, a test getting no
+/// response once it timed out. conn must not be connected, since responses
+/// come from the alternate addresses of the server, and must not be read
+/// from concurrently. It should be bound to a specific address, else the
+/// client is always deemed behind a NAT.
+pub async fn nat_discovery(
+    conn: &(dyn Conn + Send + Sync),
+    server: SocketAddr,
+    rto: Duration,
+) -> Result<NatBehavior> {
+    // Test I, common to both behaviors: the mapped address and the alternate address of the server.
+    let response = discovery_request(conn, server, None, rto)
+        .await?
+        .ok_or(Error::ErrTransactionTimeOut)?;
+    let mapped_address = mapped_address_of(&response)?;
+    let mut other = OtherAddress::default();
+    other
+        .get_from(&response)
+        .map_err(|_| Error::ErrNoOtherAddress)?;
+    let other = other.socket_addr();
+    let behind_nat = mapped_address != conn.local_addr()?;
+
+    // The filtering tests come first, as the requests of the mapping tests to
+    // the alternate IP address of the server would let its responses through
+    // an address-dependent filtering.
+    //
+    // Filtering test II: a response from the alternate IP address and port.
+    let change_address = ChangeRequest {
+        change_ip: true,
+        change_port: true,
+    };
+    let filtering = match discovery_request(conn, server, Some(change_address), rto).await? {
+        Some(response) => {
+            mapped_address_of(&response)?;
+            EndpointDependency::EndpointIndependent
+        }
+        None => {
+            // Filtering test III: a response from the alternate port only.
+            let change_port = ChangeRequest {
+                change_ip: false,
+                change_port: true,
+            };
+            match discovery_request(conn, server, Some(change_port), rto).await? {
+                Some(response) => {
+                    mapped_address_of(&response)?;
+                    EndpointDependency::AddressDependent
+                }
+                None => EndpointDependency::AddressAndPortDependent,
+            }
+        }
+    };
+
+    let mapping = if !behind_nat {
+        EndpointDependency::EndpointIndependent
+    } else {
+        // Mapping test II: the mapping for the alternate IP address of the server.
+        let to = SocketAddr::new(other.ip(), server.port());
+        let response = discovery_request(conn, to, None, rto)
+            .await?
+            .ok_or(Error::ErrTransactionTimeOut)?;
+        let address_mapped_address = mapped_address_of(&response)?;
+
+        if address_mapped_address == mapped_address {
+            EndpointDependency::EndpointIndependent
+        } else {
+            // Mapping test III: the mapping for the alternate port too.
+            let response = discovery_request(conn, other, None, rto)
+                .await?
+                .ok_or(Error::ErrTransactionTimeOut)?;
+            if mapped_address_of(&response)? == address_mapped_address {
+                EndpointDependency::AddressDependent
+            } else {
+                EndpointDependency::AddressAndPortDependent
+            }
+        }
+    };
+
+    Ok(NatBehavior {
+        mapped_address,
+        behind_nat,
+        mapping,
+        filtering,
+    })
+}
+
+// mapped_address_of returns the XOR-MAPPED-ADDRESS of a success response, as
+// an error response, e.g. to a CHANGE-REQUEST the server does not understand,
+// fails the discovery.
+fn mapped_address_of(response: &Message) -> Result<SocketAddr> {
+    if response.typ.class == CLASS_ERROR_RESPONSE {
+        let mut error_code = ErrorCodeAttribute::default();
+        error_code.get_from(response)?;
+        return Err(Error::ErrErrorResponse(
+            error_code.code.0,
+            String::from_utf8_lossy(&error_code.reason).into_owned(),
+        ));
+    }
+
+    let mut addr = XorMappedAddress::default();
+    addr.get_from(response)?;
+    Ok(addr.socket_addr())
+}
+
+// discovery_request sends a Binding request to the server, with change if
+// any, and returns its response, or None if it got none. Responses to the
+// requests of previous tests, which were retransmitted, and datagrams with a
+// bad FINGERPRINT are ignored.
+async fn discovery_request(
+    conn: &(dyn Conn + Send + Sync),
+    to: SocketAddr,
+    change: Option<ChangeRequest>,
+    rto: Duration,
+) -> Result<Option<Message>> {
+    let mut builder = Message::binding_request().with_fingerprint();
+    if let Some(change) = change {
+        builder = builder.with_attribute(change);
+    }
+    let request = builder.build()?;
+
+    let mut buf = vec![0; 1500];
+    for _ in 0..NAT_DISCOVERY_MAX_ATTEMPTS {
+        conn.send_to(&request.raw, to).await?;
+
+        let deadline = Instant::now() + rto;
+        while let Ok(result) = time::timeout_at(deadline, conn.recv_from(&mut buf)).await {
+            let (n, _) = result?;
+            let mut response = Message::new();
+            if response.write(&buf[..n]).is_err()
+                || response.transaction_id != request.transaction_id
+                || (response.contains(ATTR_FINGERPRINT) && FINGERPRINT.check(&response).is_err())
+            {
+                continue;
+            }
+
+            return Ok(Some(response));
+        }
+    }
+
+    Ok(None)
+}
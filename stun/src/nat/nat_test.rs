@@ -0,0 +1,237 @@
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use util::vnet::nat::{EndpointDependencyType, NatType};
+use util::vnet::net::{Net, NetConfig};
+use util::vnet::router::{Nic, Router, RouterConfig};
+
+use super::*;
+
+const RTO: Duration = Duration::from_millis(50);
+
+#[test]
+fn test_change_request() -> Result<()> {
+    for (change_ip, change_port, value) in [
+        (false, false, 0x00),
+        (true, false, 0x04),
+        (false, true, 0x02),
+        (true, true, 0x06),
+    ] {
+        let change = ChangeRequest {
+            change_ip,
+            change_port,
+        };
+        let mut m = Message::new();
+        change.add_to(&mut m)?;
+        assert_eq!(m.get(ATTR_CHANGE_REQUEST)?, vec![0, 0, 0, value]);
+
+        let mut got = ChangeRequest::default();
+        got.get_from(&m)?;
+        assert_eq!(got, change);
+    }
+
+    let mut m = Message::new();
+    m.add(ATTR_CHANGE_REQUEST, &[0, 0, 6]);
+    let mut got = ChangeRequest::default();
+    assert_eq!(got.get_from(&m), Err(Error::ErrAttributeSizeInvalid));
+
+    Ok(())
+}
+
+// serve_discovery answers the Binding requests received on conns[i] from the
+// conn CHANGE-REQUEST asks for, as a server of two IP addresses with two ports
+// each: conns are ordered by IP address, then by port.
+async fn serve_discovery(conns: Arc<Vec<Arc<dyn Conn + Send + Sync>>>, i: usize) -> Result<()> {
+    let mut buf = vec![0; 1500];
+    loop {
+        let (n, src) = conns[i].recv_from(&mut buf).await?;
+        let mut request = Message::new();
+        request.write(&buf[..n])?;
+
+        let mut change = ChangeRequest::default();
+        if request.contains(ATTR_CHANGE_REQUEST) {
+            change.get_from(&request)?;
+        }
+        let mut from = i;
+        if change.change_ip {
+            from ^= 2;
+        }
+        if change.change_port {
+            from ^= 1;
+        }
+
+        let mut response = Message::new();
+        response.build(&[
+            Box::new(request.clone()),
+            Box::new(BINDING_SUCCESS),
+            Box::new(XorMappedAddress::from(src)),
+            Box::new(ResponseOrigin::from(conns[from].local_addr()?)),
+            Box::new(OtherAddress::from(conns[i ^ 3].local_addr()?)),
+        ])?;
+        conns[from].send_to(&response.raw, src).await?;
+    }
+}
+
+// discover runs the NAT behavior discovery of a client behind nat_type, or
+// with no NAT at all.
+async fn discover(nat_type: Option<NatType>) -> Result<NatBehavior> {
+    let wan = Arc::new(Mutex::new(Router::new(RouterConfig {
+        cidr: "0.0.0.0/0".to_owned(),
+        ..Default::default()
+    })?));
+
+    let server_net = Net::new(Some(NetConfig {
+        static_ips: vec!["1.2.3.4".to_owned(), "1.2.3.5".to_owned()],
+        ..Default::default()
+    }));
+    let nic = server_net.get_nic()?;
+    wan.lock().await.add_net(Arc::clone(&nic)).await?;
+    nic.lock().await.set_router(Arc::clone(&wan)).await?;
+
+    let client_net = if let Some(nat_type) = nat_type {
+        let lan = Arc::new(Mutex::new(Router::new(RouterConfig {
+            static_ip: "5.6.7.8".to_owned(),
+            cidr: "192.168.0.0/24".to_owned(),
+            nat_type: Some(nat_type),
+            ..Default::default()
+        })?));
+        let client_net = Net::new(Some(NetConfig {
+            static_ip: "192.168.0.2".to_owned(),
+            ..Default::default()
+        }));
+        let nic = client_net.get_nic()?;
+        lan.lock().await.add_net(Arc::clone(&nic)).await?;
+        nic.lock().await.set_router(Arc::clone(&lan)).await?;
+
+        wan.lock().await.add_router(Arc::clone(&lan)).await?;
+        lan.lock().await.set_router(Arc::clone(&wan)).await?;
+        client_net
+    } else {
+        let client_net = Net::new(Some(NetConfig {
+            static_ip: "1.2.3.6".to_owned(),
+            ..Default::default()
+        }));
+        let nic = client_net.get_nic()?;
+        wan.lock().await.add_net(Arc::clone(&nic)).await?;
+        nic.lock().await.set_router(Arc::clone(&wan)).await?;
+        client_net
+    };
+
+    wan.lock().await.start().await?;
+
+    let mut conns = vec![];
+    for addr in [
+        "1.2.3.4:3478",
+        "1.2.3.4:3479",
+        "1.2.3.5:3478",
+        "1.2.3.5:3479",
+    ] {
+        conns.push(server_net.bind(addr.parse().unwrap()).await?);
+    }
+    let conns = Arc::new(conns);
+    for i in 0..conns.len() {
+        tokio::spawn(serve_discovery(Arc::clone(&conns), i));
+    }
+
+    let local_addr = if nat_type.is_some() {
+        "192.168.0.2:0"
+    } else {
+        "1.2.3.6:0"
+    };
+    let conn = client_net.bind(local_addr.parse().unwrap()).await?;
+    let result = nat_discovery(conn.as_ref(), conns[0].local_addr()?, RTO).await;
+
+    wan.lock().await.stop().await?;
+
+    result
+}
+
+#[tokio::test]
+async fn test_nat_discovery_no_nat() -> Result<()> {
+    let behavior = discover(None).await?;
+
+    assert!(!behavior.behind_nat);
+    assert_eq!(behavior.mapped_address.ip().to_string(), "1.2.3.6");
+    assert_eq!(behavior.mapping, EndpointDependency::EndpointIndependent);
+    assert_eq!(behavior.filtering, EndpointDependency::EndpointIndependent);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_nat_discovery() -> Result<()> {
+    for (nat, expected) in [
+        (
+            EndpointDependencyType::EndpointIndependent,
+            EndpointDependency::EndpointIndependent,
+        ),
+        (
+            EndpointDependencyType::EndpointAddrDependent,
+            EndpointDependency::AddressDependent,
+        ),
+        (
+            EndpointDependencyType::EndpointAddrPortDependent,
+            EndpointDependency::AddressAndPortDependent,
+        ),
+    ] {
+        let behavior = discover(Some(NatType {
+            mapping_behavior: nat,
+            filtering_behavior: nat,
+            ..Default::default()
+        }))
+        .await?;
+
+        assert!(behavior.behind_nat);
+        assert_eq!(behavior.mapped_address.ip().to_string(), "5.6.7.8");
+        assert_eq!(behavior.mapping, expected, "mapping of {nat:?}");
+        assert_eq!(behavior.filtering, expected, "filtering of {nat:?}");
+    }
+
+    // Mapping and filtering are discovered independently.
+    let behavior = discover(Some(NatType {
+        mapping_behavior: EndpointDependencyType::EndpointIndependent,
+        filtering_behavior: EndpointDependencyType::EndpointAddrPortDependent,
+        ..Default::default()
+    }))
+    .await?;
+    assert_eq!(behavior.mapping, EndpointDependency::EndpointIndependent);
+    assert_eq!(
+        behavior.filtering,
+        EndpointDependency::AddressAndPortDependent
+    );
+
+    Ok(())
+}
+
+// A response with a bad FINGERPRINT is skipped, not an error
+#[tokio::test]
+async fn test_discovery_request_bad_fingerprint() -> Result<()> {
+    let server = tokio::net::UdpSocket::bind("127.0.0.1:0").await?;
+    let server_addr = server.local_addr()?;
+    tokio::spawn(async move {
+        let mut buf = vec![0; 1500];
+        let (n, src) = server.recv_from(&mut buf).await?;
+        let mut request = Message::new();
+        request.write(&buf[..n])?;
+
+        let mut response = Message::new();
+        response.build(&[
+            Box::new(request),
+            Box::new(BINDING_SUCCESS),
+            Box::new(XorMappedAddress::from(src)),
+            Box::new(FINGERPRINT),
+        ])?;
+        let mut corrupted = response.raw.clone();
+        *corrupted.last_mut().unwrap() ^= 0xff;
+        server.send_to(&corrupted, src).await?;
+        server.send_to(&response.raw, src).await?;
+        Result::<()>::Ok(())
+    });
+
+    let conn = tokio::net::UdpSocket::bind("127.0.0.1:0").await?;
+    let response = discovery_request(&conn, server_addr, None, RTO).await?;
+    let response = response.expect("the valid response should be returned");
+    assert_eq!(FINGERPRINT.check(&response), Ok(()));
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod server_test;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use util::Conn;
+
+use crate::attributes::*;
+use crate::error::*;
+use crate::error_code::*;
+use crate::fingerprint::*;
+use crate::message::*;
+use crate::uattrs::*;
+use crate::xoraddr::*;
+
+const MAX_MESSAGE_SIZE: usize = 1500;
+
+// The comprehension-required attributes a Binding request may carry without
+// being rejected, as they do not change the response of a server which does
+// not authenticate requests. CHANGE-REQUEST, which a server of a single
+// address cannot honor, is rejected with the unknown ones, as RFC 5780
+// Section 6.1 requires.
+const KNOWN_ATTRIBUTES: [AttrType; 6] = [
+    ATTR_USERNAME,
+    ATTR_MESSAGE_INTEGRITY,
+    ATTR_MESSAGE_INTEGRITY_SHA256,
+    ATTR_USER_HASH,
+    ATTR_REALM,
+    ATTR_NONCE,
+];
+
+/// Server answers the Binding requests received on a connection with the
+/// XOR-MAPPED-ADDRESS of their source, the address discovery of RFC 5389
+/// Section 7.3.1, without authentication.
+pub struct Server {
+    conn: Arc<dyn Conn + Send + Sync>,
+    close_tx: Option<mpsc::Sender<()>>,
+}
+
+impl Server {
+    /// new starts answering the requests received on conn, typically an
+    /// unconnected UDP socket, until the server is closed.
+    pub fn new(conn: Arc<dyn Conn + Send + Sync>) -> Self {
+        let (close_tx, close_rx) = mpsc::channel(1);
+        tokio::spawn(Server::serve_until_closed(close_rx, Arc::clone(&conn)));
+
+        Server {
+            conn,
+            close_tx: Some(close_tx),
+        }
+    }
+
+    /// local_addr returns the address the server listens on.
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.conn.local_addr()?)
+    }
+
+    /// close stops the server and closes its connection.
+    pub async fn close(&mut self) -> Result<()> {
+        if self.close_tx.take().is_none() {
+            return Err(Error::ErrServerClosed);
+        }
+
+        self.conn.close().await?;
+
+        Ok(())
+    }
+
+    async fn serve_until_closed(
+        mut close_rx: mpsc::Receiver<()>,
+        conn: Arc<dyn Conn + Send + Sync>,
+    ) {
+        let mut buf = vec![0; MAX_MESSAGE_SIZE];
+
+        loop {
+            let (n, src) = tokio::select! {
+                _ = close_rx.recv() => return,
+                res = conn.recv_from(&mut buf) => match res {
+                    Ok(res) => res,
+                    Err(_) => return,
+                },
+            };
+
+            if let Ok(Some(response)) = respond(&buf[..n], src) {
+                let _ = conn.send_to(&response.raw, src).await;
+            }
+        }
+    }
+}
+
+// respond returns the response to the datagram received from src, or None if
+// it is to be dropped: when it is not a STUN message, has a bad FINGERPRINT,
+// or is not a request. The response carries FINGERPRINT if the request did.
+fn respond(b: &[u8], src: SocketAddr) -> Result<Option<Message>> {
+    if !is_message(b) {
+        return Ok(None);
+    }
+    let mut request = Message::new();
+    if request.write(b).is_err() {
+        return Ok(None);
+    }
+    if request.contains(ATTR_FINGERPRINT) && FINGERPRINT.check(&request).is_err() {
+        return Ok(None);
+    }
+    if request.typ.class != CLASS_REQUEST {
+        return Ok(None);
+    }
+
+    let mut setters: Vec<Box<dyn Setter>> = vec![];
+    let unknown: Vec<AttrType> = request
+        .attributes
+        .0
+        .iter()
+        .map(|attr| attr.typ)
+        .filter(|typ| typ.required() && !KNOWN_ATTRIBUTES.contains(typ))
+        .collect();
+
+    let response = if request.typ.method != METHOD_BINDING {
+        add_fingerprint(&request, &mut setters);
+        build_error_response(&request, CODE_BAD_REQUEST, setters)?
+    } else if !unknown.is_empty() {
+        setters.push(Box::new(UnknownAttributes(unknown)));
+        add_fingerprint(&request, &mut setters);
+        build_error_response(&request, CODE_UNKNOWN_ATTRIBUTE, setters)?
+    } else {
+        setters.push(Box::new(request.clone()));
+        setters.push(Box::new(BINDING_SUCCESS));
+        setters.push(Box::new(XorMappedAddress::from(src)));
+        add_fingerprint(&request, &mut setters);
+        let mut response = Message::new();
+        response.build(&setters)?;
+        response
+    };
+
+    Ok(Some(response))
+}
+
+fn add_fingerprint(request: &Message, setters: &mut Vec<Box<dyn Setter>>) {
+    if request.contains(ATTR_FINGERPRINT) {
+        setters.push(Box::new(FINGERPRINT));
+    }
+}
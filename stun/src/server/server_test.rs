@@ -0,0 +1,106 @@
+use tokio::net::UdpSocket;
+
+use super::*;
+use crate::agent::TransactionId;
+use crate::client::ClientBuilder;
+use crate::nat::ChangeRequest;
+
+fn src() -> SocketAddr {
+    "203.0.113.7:5412".parse().unwrap()
+}
+
+fn request(setters: Vec<Box<dyn Setter>>) -> Result<Message> {
+    let mut m = Message::new();
+    let mut attrs: Vec<Box<dyn Setter>> = vec![Box::new(TransactionId::new())];
+    attrs.extend(setters);
+    m.build(&attrs)?;
+    Ok(m)
+}
+
+fn error_code_of(response: &Message) -> Result<ErrorCode> {
+    assert_eq!(response.typ.class, CLASS_ERROR_RESPONSE);
+    let mut code = ErrorCodeAttribute::default();
+    code.get_from(response)?;
+    Ok(code.code)
+}
+
+#[test]
+fn test_server_respond_binding() -> Result<()> {
+    let req = request(vec![Box::new(BINDING_REQUEST)])?;
+    let response = respond(&req.raw, src())?.expect("response");
+    assert_eq!(response.typ, BINDING_SUCCESS);
+    assert_eq!(response.transaction_id, req.transaction_id);
+    assert!(!response.contains(ATTR_FINGERPRINT));
+
+    let mut addr = XorMappedAddress::default();
+    addr.get_from(&response)?;
+    assert_eq!(addr.socket_addr(), src());
+
+    // FINGERPRINT is checked, and echoed.
+    let req = request(vec![Box::new(BINDING_REQUEST), Box::new(FINGERPRINT)])?;
+    let response = respond(&req.raw, src())?.expect("response");
+    FINGERPRINT.check(&response)?;
+
+    let mut raw = req.raw.clone();
+    let last = raw.len() - 1;
+    raw[last] ^= 0xff;
+    assert!(respond(&raw, src())?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_server_respond_invalid() -> Result<()> {
+    // Not STUN, or not a request.
+    assert!(respond(&[0x80, 0x00, 0x00, 0x00], src())?.is_none());
+    let indication = request(vec![Box::new(MessageType::new(
+        METHOD_BINDING,
+        CLASS_INDICATION,
+    ))])?;
+    assert!(respond(&indication.raw, src())?.is_none());
+
+    let allocate = request(vec![Box::new(MessageType::new(
+        METHOD_ALLOCATE,
+        CLASS_REQUEST,
+    ))])?;
+    let response = respond(&allocate.raw, src())?.expect("response");
+    assert_eq!(error_code_of(&response)?, CODE_BAD_REQUEST);
+
+    let change = request(vec![
+        Box::new(BINDING_REQUEST),
+        Box::new(ChangeRequest {
+            change_ip: true,
+            change_port: true,
+        }),
+        Box::new(RawAttribute {
+            typ: AttrType(0x8888),
+            value: vec![1, 2, 3, 4],
+            ..Default::default()
+        }),
+    ])?;
+    let response = respond(&change.raw, src())?.expect("response");
+    assert_eq!(error_code_of(&response)?, CODE_UNKNOWN_ATTRIBUTE);
+    let mut unknown = UnknownAttributes(vec![]);
+    unknown.get_from(&response)?;
+    assert_eq!(unknown.0, vec![ATTR_CHANGE_REQUEST]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_server_binding_request() -> Result<()> {
+    let mut server = Server::new(Arc::new(UdpSocket::bind("127.0.0.1:0").await?));
+
+    let conn = UdpSocket::bind("127.0.0.1:0").await?;
+    conn.connect(server.local_addr()?).await?;
+    let local_addr = conn.local_addr()?;
+    let mut client = ClientBuilder::new().with_conn(Arc::new(conn)).build()?;
+
+    assert_eq!(client.binding_request().await?, local_addr);
+
+    client.close().await?;
+    server.close().await?;
+    assert_eq!(server.close().await, Err(Error::ErrServerClosed));
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ mod uri_test;
 use std::fmt;
 
 use crate::error::*;
+use crate::url::{SchemeType, Url};
 
 // SCHEME definitions from RFC 7064 Section 3.2.
 
@@ -35,39 +36,17 @@ impl fmt::Display for Uri {
 }
 
 impl Uri {
-    // parse_uri parses URI from string.
+    // parse_uri parses URI from string. TURN URIs are parsed by Url::parse_url.
     pub fn parse_uri(raw: &str) -> Result<Self> {
-        // work around for url crate
-        if raw.contains("//") {
-            return Err(Error::ErrInvalidUrl);
-        }
-
-        let mut s = raw.to_string();
-        let pos = raw.find(':');
-        if let Some(p) = pos {
-            s.replace_range(p..p + 1, "://");
-        } else {
+        let (url, port) = Url::parse_url_and_port(raw)?;
+        if url.scheme != SchemeType::Stun && url.scheme != SchemeType::Stuns {
             return Err(Error::ErrSchemeType);
         }
 
-        let raw_parts = url::Url::parse(&s)?;
-
-        let scheme = raw_parts.scheme().into();
-        if scheme != SCHEME && scheme != SCHEME_SECURE {
-            return Err(Error::ErrSchemeType);
-        }
-
-        let host = if let Some(host) = raw_parts.host_str() {
-            host.trim()
-                .trim_start_matches('[')
-                .trim_end_matches(']')
-                .to_owned()
-        } else {
-            return Err(Error::ErrHost);
-        };
-
-        let port = raw_parts.port();
-
-        Ok(Uri { scheme, host, port })
+        Ok(Uri {
+            scheme: url.scheme.to_string(),
+            host: url.host,
+            port,
+        })
     }
 }
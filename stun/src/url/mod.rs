@@ -6,9 +6,10 @@ use std::convert::From;
 use std::fmt;
 
 use crate::error::*;
+use crate::{DEFAULT_PORT, DEFAULT_TLS_PORT};
 
-/// The type of server used in the ice.URL structure.
-#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+/// The type of server used in the `Url` structure.
+#[derive(Default, PartialEq, Eq, Debug, Copy, Clone)]
 pub enum SchemeType {
     /// The URL represents a STUN server.
     Stun,
@@ -23,15 +24,10 @@ pub enum SchemeType {
     Turns,
 
     /// Default public constant to use for "enum" like struct comparisons when no value was defined.
+    #[default]
     Unknown,
 }
 
-impl Default for SchemeType {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
 impl From<&str> for SchemeType {
     /// Defines a procedure for creating a new `SchemeType` from a raw
     /// string naming the scheme type.
@@ -59,10 +55,11 @@ impl fmt::Display for SchemeType {
     }
 }
 
-/// The transport protocol type that is used in the `ice::url::Url` structure.
-#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+/// The transport protocol type that is used in the `Url` structure.
+#[derive(Default, PartialEq, Eq, Debug, Copy, Clone)]
 pub enum ProtoType {
     /// The URL uses a UDP transport.
+    #[default]
     Udp,
 
     /// The URL uses a TCP transport.
@@ -71,12 +68,6 @@ pub enum ProtoType {
     Unknown,
 }
 
-impl Default for ProtoType {
-    fn default() -> Self {
-        Self::Udp
-    }
-}
-
 // defines a procedure for creating a new ProtoType from a raw
 // string naming the transport protocol type.
 impl From<&str> for ProtoType {
@@ -137,6 +128,12 @@ impl Url {
     /// [IETF rfc-7064](https://tools.ietf.org/html/rfc7064) and
     /// [IETF rfc-7065](https://tools.ietf.org/html/rfc7065) respectively.
     pub fn parse_url(raw: &str) -> Result<Self> {
+        Self::parse_url_and_port(raw).map(|(url, _)| url)
+    }
+
+    /// parse_url_and_port parses a URL as parse_url does, and also returns its
+    /// port if it has one rather than the default port of its scheme.
+    pub(crate) fn parse_url_and_port(raw: &str) -> Result<(Self, Option<u16>)> {
         // work around for url crate
         if raw.contains("//") {
             return Err(Error::ErrInvalidUrl);
@@ -163,12 +160,13 @@ impl Url {
             return Err(Error::ErrHost);
         };
 
-        let port = if let Some(port) = raw_parts.port() {
+        let explicit_port = raw_parts.port();
+        let port = if let Some(port) = explicit_port {
             port
         } else if scheme == SchemeType::Stun || scheme == SchemeType::Turn {
-            3478
+            DEFAULT_PORT
         } else {
-            5349
+            DEFAULT_TLS_PORT
         };
 
         let mut q_args = raw_parts.query_pairs();
@@ -226,14 +224,16 @@ impl Url {
             }
         };
 
-        Ok(Self {
+        let url = Self {
             scheme,
             host,
             port,
             username: "".to_owned(),
             password: "".to_owned(),
             proto,
-        })
+        };
+
+        Ok((url, explicit_port))
     }
 
     /*
@@ -108,12 +108,15 @@ fn test_parse_url_success() -> Result<()> {
 fn test_parse_url_failure() -> Result<()> {
     let tests = vec![
         ("", Error::ErrSchemeType),
-        (":::", Error::ErrUrlParse),
-        ("stun:[::1]:123:", Error::ErrPort),
-        ("stun:[::1]:123a", Error::ErrPort),
+        (":::", Error::Url(url::ParseError::RelativeUrlWithoutBase)),
+        ("stun:[::1]:123:", Error::Url(url::ParseError::InvalidPort)),
+        ("stun:[::1]:123a", Error::Url(url::ParseError::InvalidPort)),
         ("google.de", Error::ErrSchemeType),
         ("stun:", Error::ErrHost),
-        ("stun:google.de:abc", Error::ErrPort),
+        (
+            "stun:google.de:abc",
+            Error::Url(url::ParseError::InvalidPort),
+        ),
         ("stun:google.de?transport=udp", Error::ErrStunQuery),
         ("stuns:google.de?transport=udp", Error::ErrStunQuery),
         ("turn:google.de?trans=udp", Error::ErrInvalidQuery),
@@ -129,8 +132,7 @@ fn test_parse_url_failure() -> Result<()> {
         let result = Url::parse_url(raw_url);
         if let Err(err) = result {
             assert_eq!(
-                err.to_string(),
-                expected_err.to_string(),
+                err, expected_err,
                 "testCase: '{raw_url}', expected err '{expected_err}', but got err '{err}'"
             );
         } else {
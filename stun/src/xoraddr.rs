@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod xoraddr_test;
 
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::{fmt, mem};
 
 use crate::addr::*;
@@ -93,6 +93,21 @@ impl fmt::Display for XorMappedAddress {
     }
 }
 
+impl From<SocketAddr> for XorMappedAddress {
+    fn from(addr: SocketAddr) -> Self {
+        XorMappedAddress {
+            ip: addr.ip(),
+            port: addr.port(),
+        }
+    }
+}
+
+impl From<&XorMappedAddress> for SocketAddr {
+    fn from(addr: &XorMappedAddress) -> Self {
+        addr.socket_addr()
+    }
+}
+
 impl Setter for XorMappedAddress {
     /// add_to adds XOR-MAPPED-ADDRESS to m. Can return ErrBadIPLength
     /// if len(a.IP) is invalid.
@@ -112,6 +127,11 @@ impl Getter for XorMappedAddress {
 }
 
 impl XorMappedAddress {
+    /// socket_addr returns the transport address of the attribute.
+    pub fn socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.ip, self.port)
+    }
+
     /// add_to_as adds XOR-MAPPED-ADDRESS value to m as t attribute.
     pub fn add_to_as(&self, m: &mut Message, t: AttrType) -> Result<()> {
         let (family, ip_len, ip) = match self.ip {
@@ -248,3 +248,29 @@ fn test_xormapped_address_string() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_xormapped_address_socket_addr() -> Result<()> {
+    for addr in ["213.141.156.236:48583", "[fe80::dc2b:44ff:fe20:6009]:21254"] {
+        let addr: SocketAddr = addr.parse().unwrap();
+
+        let mut m = Message::new();
+        m.new_transaction_id()?;
+        XorMappedAddress::from(addr).add_to(&mut m)?;
+        m.write_header();
+
+        let mut decoded = Message::new();
+        decoded.write(&m.raw)?;
+        let mut got = XorMappedAddress::default();
+        got.get_from(&decoded)?;
+        assert_eq!(got.socket_addr(), addr);
+        assert_eq!(SocketAddr::from(&got), addr);
+
+        // the address is encoded xor'ed with the cookie and transaction id
+        let mut plain = MappedAddress::default();
+        plain.get_from_as(&m, ATTR_XORMAPPED_ADDRESS)?;
+        assert_ne!(plain.socket_addr(), addr);
+    }
+
+    Ok(())
+}
@@ -189,3 +189,123 @@ async fn test_client_nonce_expiration() -> Result<()> {
 
     Ok(())
 }
+
+// Answer requests with a 300 (Try Alternate) response redirecting to
+// alternate. With challenge, unauthenticated requests get a 401 (Unauthorized)
+// first, so that only the authenticated retry is redirected.
+async fn serve_try_alternate(
+    conn: UdpSocket,
+    alternate: SocketAddr,
+    challenge: bool,
+) -> Result<()> {
+    let mut buf = vec![0; 1500];
+    loop {
+        let (n, src) = conn.recv_from(&mut buf).await?;
+        let mut request = Message::new();
+        request.write(&buf[..n])?;
+
+        let response = if challenge && !request.contains(ATTR_MESSAGE_INTEGRITY) {
+            build_unauthorized_response(&request, "webrtc.rs".to_owned(), "nonce".to_owned())?
+        } else {
+            build_try_alternate_response(&request, alternate)?
+        };
+        conn.send_to(&response.raw, src).await?;
+    }
+}
+
+// Allocate through a redirector, which sends the client to the TURN server
+async fn allocate_try_alternate(challenge: bool) -> Result<()> {
+    let conn = Arc::new(UdpSocket::bind("127.0.0.1:0").await?);
+    let server_addr = conn.local_addr()?;
+
+    let server = Server::new(ServerConfig {
+        conn_configs: vec![ConnConfig {
+            conn,
+            relay_addr_generator: Box::new(RelayAddressGeneratorStatic {
+                relay_address: IpAddr::from_str("127.0.0.1")?,
+                address: "0.0.0.0".to_owned(),
+                net: Arc::new(Net::new(None)),
+            }),
+        }],
+        realm: "webrtc.rs".to_owned(),
+        auth_handler: Arc::new(TestAuthHandler {}),
+        channel_bind_timeout: Duration::from_secs(0),
+        alloc_close_notify: None,
+    })
+    .await?;
+
+    let redirector = UdpSocket::bind("127.0.0.1:0").await?;
+    let redirector_addr = redirector.local_addr()?;
+    tokio::spawn(serve_try_alternate(redirector, server_addr, challenge));
+
+    let client = Client::new(ClientConfig {
+        stun_serv_addr: String::new(),
+        turn_serv_addr: redirector_addr.to_string(),
+        username: "foo".to_owned(),
+        password: "pass".to_owned(),
+        realm: String::new(),
+        software: String::new(),
+        rto_in_ms: 0,
+        conn: Arc::new(UdpSocket::bind("127.0.0.1:0").await?),
+        vnet: None,
+    })
+    .await?;
+
+    client.listen().await?;
+
+    let allocation = client.allocate().await?;
+    assert_eq!(
+        allocation.local_addr()?.ip(),
+        IpAddr::from_str("127.0.0.1")?
+    );
+    assert_eq!(client.turn_server_addr().await, server_addr.to_string());
+
+    // Shutdown
+    client.close().await?;
+    server.close().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_client_allocate_try_alternate() -> Result<()> {
+    allocate_try_alternate(false).await
+}
+
+#[tokio::test]
+async fn test_client_allocate_try_alternate_authenticated() -> Result<()> {
+    allocate_try_alternate(true).await
+}
+
+// A server redirecting to itself must not make the client loop
+#[tokio::test]
+async fn test_client_allocate_try_alternate_loop() -> Result<()> {
+    let redirector = UdpSocket::bind("127.0.0.1:0").await?;
+    let redirector_addr = redirector.local_addr()?;
+    tokio::spawn(serve_try_alternate(redirector, redirector_addr, false));
+
+    let client = Client::new(ClientConfig {
+        stun_serv_addr: String::new(),
+        turn_serv_addr: redirector_addr.to_string(),
+        username: "foo".to_owned(),
+        password: "pass".to_owned(),
+        realm: String::new(),
+        software: String::new(),
+        rto_in_ms: 0,
+        conn: Arc::new(UdpSocket::bind("127.0.0.1:0").await?),
+        vnet: None,
+    })
+    .await?;
+
+    client.listen().await?;
+
+    let result = client.allocate().await;
+    assert_eq!(
+        result.err(),
+        Some(Error::Stun(stun::Error::ErrTryAlternate(redirector_addr)))
+    );
+
+    client.close().await?;
+
+    Ok(())
+}
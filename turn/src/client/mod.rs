@@ -44,6 +44,7 @@ use crate::proto::PROTO_UDP;
 const DEFAULT_RTO_IN_MS: u16 = 200;
 const MAX_DATA_BUFFER_SIZE: usize = u16::MAX as usize; // message size limit for Chromium
 const MAX_READ_QUEUE_SIZE: usize = 1024;
+const MAX_ALTERNATE_SERVER_REDIRECTS: usize = 3;
 
 //              interval [msec]
 // 0: 0 ms      +500
@@ -521,6 +522,27 @@ impl ClientInternal {
             }
         }
 
+        // Follow the ALTERNATE-SERVER of 300 (Try Alternate) responses, used by
+        // TURN servers to balance their load, switching to the alternate server
+        // for the whole allocation, unless it was already tried.
+        let mut servers = vec![self.turn_serv_addr.clone()];
+        loop {
+            match self.request_allocation().await {
+                Err(Error::Stun(stun::Error::ErrTryAlternate(alternate)))
+                    if servers.len() <= MAX_ALTERNATE_SERVER_REDIRECTS
+                        && !servers.contains(&alternate.to_string()) =>
+                {
+                    log::debug!("allocate redirected to {}", alternate);
+                    self.turn_serv_addr = alternate.to_string();
+                    servers.push(self.turn_serv_addr.clone());
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Performs the Allocate transactions with the TURN server.
+    async fn request_allocation(&mut self) -> Result<RelayConnConfig> {
         let mut msg = Message::new();
         msg.build(&[
             Box::new(TransactionId::new()),
@@ -536,36 +558,28 @@ impl ClientInternal {
             .perform_transaction(&msg, &self.turn_serv_addr.clone(), false)
             .await?;
         let res = tr_res.msg;
+        if let Some(alternate) = get_alternate_server(&res) {
+            return Err(stun::Error::ErrTryAlternate(alternate).into());
+        }
 
         // Anonymous allocate failed, trying to authenticate.
-        let nonce = Nonce::get_from_as(&res, ATTR_NONCE)?;
-        self.realm = Realm::get_from_as(&res, ATTR_REALM)?;
-
-        self.integrity = MessageIntegrity::new_long_term_integrity(
-            self.username.text.clone(),
-            self.realm.text.clone(),
-            self.password.clone(),
-        );
+        let mut challenge = Challenge::default();
+        challenge.get_from(&res)?;
+        self.realm = challenge.realm.clone();
+        self.integrity = challenge.integrity(&self.username.text, &self.password);
 
         // Trying to authorize.
-        msg.build(&[
-            Box::new(TransactionId::new()),
-            Box::new(MessageType::new(METHOD_ALLOCATE, CLASS_REQUEST)),
-            Box::new(RequestedTransport {
-                protocol: PROTO_UDP,
-            }),
-            Box::new(self.username.clone()),
-            Box::new(self.realm.clone()),
-            Box::new(nonce.clone()),
-            Box::new(self.integrity.clone()),
-            Box::new(FINGERPRINT),
-        ])?;
+        let msg = challenge.authenticate(&msg, &self.username.text, &self.integrity)?;
+        let nonce = challenge.nonce;
 
         log::debug!("client.Allocate call PerformTransaction 2");
         let tr_res = self
             .perform_transaction(&msg, &self.turn_serv_addr.clone(), false)
             .await?;
         let res = tr_res.msg;
+        if let Some(alternate) = get_alternate_server(&res) {
+            return Err(stun::Error::ErrTryAlternate(alternate).into());
+        }
 
         if res.typ.class == CLASS_ERROR_RESPONSE {
             let mut code = ErrorCodeAttribute::default();
@@ -638,6 +652,13 @@ impl Client {
         Ok(())
     }
 
+    /// Returns the TURN server address, which is the one of the alternate
+    /// server once `allocate` followed an ALTERNATE-SERVER redirect.
+    pub async fn turn_server_addr(&self) -> String {
+        let ci = self.client_internal.lock().await;
+        ci.turn_server_addr()
+    }
+
     /// Sends a new STUN request to the given transport address.
     pub async fn send_binding_request_to(&self, to: &str) -> Result<SocketAddr> {
         let mut ci = self.client_internal.lock().await;
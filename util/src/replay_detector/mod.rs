@@ -9,6 +9,11 @@ pub trait ReplayDetector {
     // Call accept() to mark the packet is received properly.
     fn check(&mut self, seq: u64) -> bool;
     fn accept(&mut self);
+    // is_too_old returns true if given sequence number fell behind the window,
+    // which tells why check rejected it, as opposed to being duplicated.
+    fn is_too_old(&self, _seq: u64) -> bool {
+        false
+    }
 }
 
 pub struct SlidingWindowDetector {
@@ -74,6 +79,10 @@ impl ReplayDetector for SlidingWindowDetector {
         let diff = (self.latest_seq - self.seq) % self.max_seq;
         self.mask.set_bit(diff as usize);
     }
+
+    fn is_too_old(&self, seq: u64) -> bool {
+        seq <= self.latest_seq && self.latest_seq >= self.window_size as u64 + seq
+    }
 }
 
 pub struct WrappedSlidingWindowDetector {
@@ -100,6 +109,20 @@ impl WrappedSlidingWindowDetector {
             init: false,
         }
     }
+
+    // wrapped_diff returns how far behind the latest sequence number the given
+    // one is, negative if it is ahead.
+    fn wrapped_diff(&self, seq: u64) -> i64 {
+        let mut diff = self.latest_seq as i64 - seq as i64;
+        // Wrap the number.
+        if diff > self.max_seq as i64 / 2 {
+            diff -= (self.max_seq + 1) as i64;
+        } else if diff <= -(self.max_seq as i64 / 2) {
+            diff += (self.max_seq + 1) as i64;
+        }
+
+        diff
+    }
 }
 
 impl ReplayDetector for WrappedSlidingWindowDetector {
@@ -119,14 +142,7 @@ impl ReplayDetector for WrappedSlidingWindowDetector {
             self.init = true;
         }
 
-        let mut diff = self.latest_seq as i64 - seq as i64;
-        // Wrap the number.
-        if diff > self.max_seq as i64 / 2 {
-            diff -= (self.max_seq + 1) as i64;
-        } else if diff <= -(self.max_seq as i64 / 2) {
-            diff += (self.max_seq + 1) as i64;
-        }
-
+        let diff = self.wrapped_diff(seq);
         if diff >= self.window_size as i64 {
             // Too old.
             return false;
@@ -146,14 +162,7 @@ impl ReplayDetector for WrappedSlidingWindowDetector {
             return;
         }
 
-        let mut diff = self.latest_seq as i64 - self.seq as i64;
-        // Wrap the number.
-        if diff > self.max_seq as i64 / 2 {
-            diff -= (self.max_seq + 1) as i64;
-        } else if diff <= -(self.max_seq as i64 / 2) {
-            diff += (self.max_seq + 1) as i64;
-        }
-
+        let diff = self.wrapped_diff(self.seq);
         assert!(diff < self.window_size as i64);
 
         if diff < 0 {
@@ -164,6 +173,10 @@ impl ReplayDetector for WrappedSlidingWindowDetector {
         self.mask
             .set_bit((self.latest_seq as isize - self.seq as isize) as usize);
     }
+
+    fn is_too_old(&self, seq: u64) -> bool {
+        self.init && seq <= self.max_seq && self.wrapped_diff(seq) >= self.window_size as i64
+    }
 }
 
 #[derive(Default)]
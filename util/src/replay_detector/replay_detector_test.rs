@@ -276,3 +276,36 @@ fn test_replay_detector() {
         }
     }
 }
+
+#[test]
+fn test_replay_detector_too_old() {
+    let mut detectors: Vec<(&str, Box<dyn ReplayDetector>)> = vec![
+        ("sliding", Box::new(SlidingWindowDetector::new(16, 0xFFFF))),
+        (
+            "wrapped",
+            Box::new(WrappedSlidingWindowDetector::new(16, 0xFFFF)),
+        ),
+    ];
+
+    for (name, detector) in &mut detectors {
+        for seq in [1, 2, 40] {
+            assert!(detector.check(seq), "{name}");
+            detector.accept();
+        }
+
+        // duplicated within the window
+        assert!(!detector.check(40), "{name}");
+        assert!(!detector.is_too_old(40), "{name}");
+        // not received yet, within the window
+        assert!(detector.check(30), "{name}");
+        assert!(!detector.is_too_old(30), "{name}");
+        // behind the window
+        assert!(!detector.check(2), "{name}");
+        assert!(detector.is_too_old(2), "{name}");
+        assert!(!detector.check(24), "{name}");
+        assert!(detector.is_too_old(24), "{name}");
+    }
+
+    let detector = NoOpReplayDetector;
+    assert!(!detector.is_too_old(0));
+}
@@ -413,6 +413,9 @@ impl RTCDtlsTransport {
                 dtls::extension::extension_use_srtp::SrtpProtectionProfile::Srtp_Aead_Aes_128_Gcm => {
                     srtp::protection_profile::ProtectionProfile::AeadAes128Gcm
                 }
+                dtls::extension::extension_use_srtp::SrtpProtectionProfile::Srtp_Aead_Aes_256_Gcm => {
+                    srtp::protection_profile::ProtectionProfile::AeadAes256Gcm
+                }
                 dtls::extension::extension_use_srtp::SrtpProtectionProfile::Srtp_Aes128_Cm_Hmac_Sha1_80 => {
                     srtp::protection_profile::ProtectionProfile::Aes128CmHmacSha1_80
                 }
@@ -15,7 +15,7 @@ pub struct RTCIceServer {
 
 impl RTCIceServer {
     pub(crate) fn parse_url(&self, url_str: &str) -> Result<ice::url::Url> {
-        Ok(ice::url::Url::parse_url(url_str)?)
+        Ok(ice::url::Url::parse_url(url_str).map_err(ice::Error::from)?)
     }
 
     pub(crate) fn validate(&self) -> Result<()> {
@@ -159,7 +159,7 @@ mod test {
                 credential: String::new(),
                 credential_type: RTCIceCredentialType::Oauth,
             },
-            ice::Error::ErrStunQuery,
+            ice::Error::Stun(stun::Error::ErrStunQuery),
         )];
 
         for (ice_server, expected_err) in tests {